@@ -15,7 +15,9 @@
 # which is just the original audio .wav file's first 4096 bytes.
 #
 # You can already use regular audio .wav files as input for the
-# Rust program anyway, however, this script has the benefit of
+# Rust program anyway, and it reads their headers properly, so
+# it supports more formats than this script, which only works
+# with 16-bit audio. However, this script has the benefit of
 # outputting a new WaveCAD .wav file of the correct length to be
 # able to be edited in some waveform editing software that 
 # supports WaveCAD .wav files. So if you use this first, you can 
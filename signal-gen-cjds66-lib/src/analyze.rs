@@ -0,0 +1,345 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Measuring the spectrum and quality of an arbitrary wave.
+
+The 2048 samples of a wave are one period of it, so an FFT of all of
+them gives its harmonics exactly, with no windowing needed. Levels
+are fractions of full scale, where 0 is the middle of the device's
+range, and 1 is the top of it. Phases are in degrees, with the same
+meaning as in a [FourierSeries](../fourier/struct.FourierSeries.html),
+where a phase of 0 is a sine wave which starts at 0 and rises.
+
+A [WaveAnalysis](struct.WaveAnalysis.html) can be shown as text or JSON:
+```ignore
+let analysis = analyze_wave(&wave, 10);
+print!("{}", AnalysisFormat::Json.render(&analysis));
+```
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::f64::consts::PI;
+use std::fmt;
+
+/** Harmonics weaker than this, as a fraction of full scale, are
+treated as missing, since they're well below one step of the device. */
+pub const MIN_LEVEL: f64 = 1e-9;
+
+/** How to show an analysis. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisFormat {
+	/// Readable text, one measurement per line.
+	Text,
+
+	/// A JSON object.
+	Json,
+}
+
+impl AnalysisFormat {
+	/// Parse `"text"` or `"json"`.
+	pub fn parse(format: &str) -> Result<AnalysisFormat, clap::Error> {
+		match format.to_lowercase().as_str() {
+			"text" | "txt" => Ok(AnalysisFormat::Text),
+			"json" => Ok(AnalysisFormat::Json),
+			_ => Err(Error::with_description(
+				&format!("unsupported analysis format (must be text or json): {}", format),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+
+	/// Show an analysis in this format, ending with a newline.
+	pub fn render(&self, analysis: &WaveAnalysis) -> String {
+		match self {
+			AnalysisFormat::Text => format!("{}\n", analysis),
+			AnalysisFormat::Json => format!("{}\n", analysis.to_json()),
+		}
+	}
+}
+
+/** Options for analyzing a wave. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisOptions {
+	/// How many harmonics to list, starting with the fundamental.
+	/// All of them are used for the THD either way.
+	pub harmonics: usize,
+
+	/// How to show the analysis.
+	pub format: AnalysisFormat,
+}
+
+impl Default for AnalysisOptions {
+	/// The first 10 harmonics, as text.
+	fn default() -> AnalysisOptions {
+		AnalysisOptions {
+			harmonics: 10,
+			format: AnalysisFormat::Text,
+		}
+	}
+}
+
+/** The level and phase of one harmonic. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarmonicLevel {
+	/// Which harmonic it is, where 1 is the fundamental.
+	pub number: usize,
+
+	/// The amplitude, as a fraction of full scale.
+	pub magnitude: f64,
+
+	/// The phase in degrees, from -180 to 180, or 0 if the harmonic is missing.
+	pub phase: f64,
+}
+
+/** Measurements of one period of a wave. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveAnalysis {
+	/// The number of samples.
+	pub samples: usize,
+
+	/// The average level, which is the DC offset.
+	pub dc: f64,
+
+	/// The root mean square level, including the DC offset.
+	pub rms: f64,
+
+	/// The root mean square level, without the DC offset.
+	pub ac_rms: f64,
+
+	/// The largest distance from the middle of the range.
+	pub peak: f64,
+
+	/// The largest distance from the DC level, divided by the AC RMS
+	/// level, or `None` for a flat line.
+	pub crest_factor: Option<f64>,
+
+	/// The total harmonic distortion, as a fraction of the fundamental,
+	/// or `None` if there's no fundamental.
+	pub thd: Option<f64>,
+
+	/// The lowest device value used.
+	pub min: u16,
+
+	/// The highest device value used.
+	pub max: u16,
+
+	/// How many different device values are used.
+	pub levels_used: usize,
+
+	/// How many of the device's 12 bits the wave spans, from its
+	/// lowest to its highest value.
+	pub effective_bits: f64,
+
+	/// The first few harmonics, starting with the fundamental.
+	pub harmonics: Vec<HarmonicLevel>,
+}
+
+/** The discrete Fourier transform of real samples, as (real,
+imaginary) pairs. It uses a radix-2 FFT if the number of samples is
+a power of 2, which it always is for a [WaveData](../wave/struct.WaveData.html). */
+pub fn dft(samples: &[f64]) -> Vec<(f64, f64)> {
+	let n = samples.len();
+
+	if !n.is_power_of_two() {
+		return (0..n)
+			.map(|k| {
+				samples.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, s)| {
+					let angle = -2.0 * PI * (k * i % n) as f64 / n as f64;
+
+					(re + s * angle.cos(), im + s * angle.sin())
+				})
+			})
+			.collect();
+	}
+
+	let bits = n.trailing_zeros();
+
+	// Put the samples in bit-reversed order, then combine them in
+	// larger and larger butterflies.
+	let mut out: Vec<(f64, f64)> = (0..n)
+		.map(|i| (samples[if bits == 0 { i } else { i.reverse_bits() >> (usize::BITS - bits) }], 0.0))
+		.collect();
+
+	let mut size = 2;
+
+	while size <= n {
+		let step = -2.0 * PI / size as f64;
+
+		for start in (0..n).step_by(size) {
+			for j in 0..size / 2 {
+				let (wr, wi) = ((step * j as f64).cos(), (step * j as f64).sin());
+				let (ar, ai) = out[start + j];
+				let (br, bi) = out[start + j + size / 2];
+				let (tr, ti) = (br * wr - bi * wi, br * wi + bi * wr);
+
+				out[start + j] = (ar + tr, ai + ti);
+				out[start + j + size / 2] = (ar - tr, ai - ti);
+			}
+		}
+
+		size *= 2;
+	}
+
+	out
+}
+
+/** Analyze a wave, listing the first "harmonics" harmonics. */
+pub fn analyze_wave(wave: &WaveData, harmonics: usize) -> WaveAnalysis {
+	let values = wave.samples();
+	let samples: Vec<f64> = values.iter().map(|v| (*v as f64 - HALF_SCALE) / HALF_SCALE).collect();
+	let n = samples.len() as f64;
+
+	let dc = samples.iter().sum::<f64>() / n;
+	let rms = (samples.iter().map(|s| s * s).sum::<f64>() / n).sqrt();
+	let ac_rms = (samples.iter().map(|s| (s - dc) * (s - dc)).sum::<f64>() / n).sqrt();
+	let peak = samples.iter().fold(0.0, |peak: f64, s| peak.max(s.abs()));
+	let ac_peak = samples.iter().fold(0.0, |peak: f64, s| peak.max((s - dc).abs()));
+
+	let spectrum = dft(&samples);
+	let nyquist = samples.len() / 2;
+
+	let levels: Vec<HarmonicLevel> = (1..nyquist)
+		.map(|k| {
+			let (re, im) = spectrum[k];
+			let magnitude = 2.0 * (re * re + im * im).sqrt() / n;
+
+			// A sine wave's transform is at -90 degrees, so add it back.
+			let phase = if magnitude < MIN_LEVEL {
+				0.0
+			} else {
+				let deg = im.atan2(re).to_degrees() + 90.0;
+
+				if deg > 180.0 {
+					deg - 360.0
+				} else {
+					deg
+				}
+			};
+
+			HarmonicLevel {
+				number: k,
+				magnitude,
+				phase,
+			}
+		})
+		.collect();
+
+	let thd = if levels[0].magnitude < MIN_LEVEL {
+		None
+	} else {
+		Some(levels[1..].iter().map(|h| h.magnitude * h.magnitude).sum::<f64>().sqrt() / levels[0].magnitude)
+	};
+
+	let min = values.iter().copied().min().unwrap_or_default();
+	let max = values.iter().copied().max().unwrap_or_default();
+
+	let mut used = vec![false; ARBITRARY_WAVE_MAX as usize + 1];
+	values.iter().for_each(|v| used[*v as usize] = true);
+
+	WaveAnalysis {
+		samples: values.len(),
+		dc,
+		rms,
+		ac_rms,
+		peak,
+		crest_factor: if ac_rms < MIN_LEVEL { None } else { Some(ac_peak / ac_rms) },
+		thd,
+		min,
+		max,
+		levels_used: used.iter().filter(|u| **u).count(),
+		effective_bits: ((max - min) as f64 + 1.0).log2(),
+		harmonics: levels.into_iter().take(harmonics).collect(),
+	}
+}
+
+fn to_db(level: f64) -> f64 {
+	20.0 * level.log10()
+}
+
+fn json_option(val: Option<f64>) -> String {
+	val.map_or_else(|| "null".to_string(), |val| val.to_string())
+}
+
+impl WaveAnalysis {
+	/// The analysis as a JSON object, on one line.
+	pub fn to_json(&self) -> String {
+		let harmonics: Vec<String> = self
+			.harmonics
+			.iter()
+			.map(|h| format!("{{\"number\":{},\"magnitude\":{},\"phase\":{}}}", h.number, h.magnitude, h.phase))
+			.collect();
+
+		format!(
+			"{{\"samples\":{},\"dc\":{},\"rms\":{},\"ac_rms\":{},\"peak\":{},\"crest_factor\":{},\"thd\":{},\"min\":{},\"max\":{},\"levels_used\":{},\"effective_bits\":{},\"harmonics\":[{}]}}",
+			self.samples,
+			self.dc,
+			self.rms,
+			self.ac_rms,
+			self.peak,
+			json_option(self.crest_factor),
+			json_option(self.thd),
+			self.min,
+			self.max,
+			self.levels_used,
+			self.effective_bits,
+			harmonics.join(",")
+		)
+	}
+}
+
+impl fmt::Display for WaveAnalysis {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "samples: {}", self.samples)?;
+		writeln!(f, "dc level: {:.4}", self.dc)?;
+		writeln!(f, "rms: {:.4} ({:.2} dBFS)", self.rms, to_db(self.rms))?;
+		writeln!(f, "ac rms: {:.4} ({:.2} dBFS)", self.ac_rms, to_db(self.ac_rms))?;
+		writeln!(f, "peak: {:.4} ({:.2} dBFS)", self.peak, to_db(self.peak))?;
+
+		match self.crest_factor {
+			Some(crest) => writeln!(f, "crest factor: {:.4} ({:.2} dB)", crest, to_db(crest))?,
+			None => writeln!(f, "crest factor: none (flat line)")?,
+		}
+
+		match self.thd {
+			Some(thd) => writeln!(f, "thd: {:.4}% ({:.2} dB)", thd * 100.0, to_db(thd))?,
+			None => writeln!(f, "thd: none (no fundamental)")?,
+		}
+
+		writeln!(
+			f,
+			"levels used: {} of {} ({} - {})",
+			self.levels_used,
+			ARBITRARY_WAVE_MAX as usize + 1,
+			self.min,
+			self.max
+		)?;
+		write!(f, "effective resolution: {:.2} of 12 bits", self.effective_bits)?;
+
+		if !self.harmonics.is_empty() {
+			write!(f, "\nharmonics:")?;
+		}
+
+		for h in self.harmonics.iter() {
+			write!(
+				f,
+				"\n  {:>4}: {:.6} ({:.2} dBFS), phase {:.2} degrees",
+				h.number,
+				h.magnitude,
+				to_db(h.magnitude),
+				h.phase
+			)?;
+		}
+
+		Ok(())
+	}
+}
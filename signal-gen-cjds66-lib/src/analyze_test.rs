@@ -0,0 +1,118 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::analyze::*;
+use super::convert::*;
+use super::fourier::*;
+use super::wave::*;
+
+#[test]
+pub fn parse_ok() {
+	assert_eq!(AnalysisFormat::parse("text").unwrap(), AnalysisFormat::Text);
+	assert_eq!(AnalysisFormat::parse("JSON").unwrap(), AnalysisFormat::Json);
+}
+
+#[test]
+pub fn parse_err() {
+	AnalysisFormat::parse("csv").unwrap_err();
+}
+
+#[test]
+pub fn dft_ok() {
+	// The FFT and the plain DFT agree.
+	let samples: Vec<f64> = (0..16).map(|i| ((i * 7) % 5) as f64 - 2.0).collect();
+	let fast = dft(&samples);
+
+	for (k, (re, im)) in fast.iter().enumerate() {
+		let (sr, si) = samples.iter().enumerate().fold((0.0, 0.0), |(sr, si), (i, s)| {
+			let angle = -2.0 * std::f64::consts::PI * (k * i) as f64 / 16.0;
+			(sr + s * angle.cos(), si + s * angle.sin())
+		});
+
+		assert!((re - sr).abs() < 1e-9 && (im - si).abs() < 1e-9, "{}", k);
+	}
+
+	let odd = dft(&[1.0, 2.0, 3.0]);
+	assert!((odd[0].0 - 6.0).abs() < 1e-12);
+	assert!((odd[1].0 + 1.5).abs() < 1e-12 && (odd[1].1 - 0.75f64.sqrt()).abs() < 1e-12);
+
+	let dc = dft(&[1.0; 8]);
+	assert_eq!(dc[0], (8.0, 0.0));
+	assert!(dc[1..].iter().all(|(re, im)| re.abs() < 1e-12 && im.abs() < 1e-12));
+
+	assert_eq!(dft(&[5.0]), vec![(5.0, 0.0)]);
+}
+
+#[test]
+pub fn analyze_wave_ok() {
+	let series = FourierSeries::parse("1:1.0, 3:0.1@30, 5:0.02@-90").unwrap();
+	let (wave, _) = generate_fourier_wave(&series, &ConvertOptions::default()).unwrap();
+
+	let res = analyze_wave(&wave, 5);
+
+	assert_eq!(res.samples, 2048);
+	assert_eq!(res.harmonics.len(), 5);
+	assert!(res.dc.abs() < 1e-3);
+	assert!((res.thd.unwrap() - series.thd().unwrap()).abs() < 1e-3);
+
+	let h = &res.harmonics;
+	assert_eq!(h[2].number, 3);
+	assert!((h[2].magnitude / h[0].magnitude - 0.1).abs() < 1e-3);
+	assert!((h[2].phase - 30.0).abs() < 0.5, "{}", h[2].phase);
+	assert!((h[4].phase + 90.0).abs() < 2.0, "{}", h[4].phase);
+	assert!(h[1].magnitude < 1e-3);
+
+	assert_eq!((res.min, res.max), (1, 4095));
+	assert!((res.effective_bits - 12.0).abs() < 1e-3);
+	assert!((res.rms - res.ac_rms).abs() < 1e-3);
+
+	// A pure sine wave's crest factor is the square root of 2.
+	let (sine, _) = generate_fourier_wave(&FourierSeries::parse("1:1").unwrap(), &ConvertOptions::default()).unwrap();
+	let res = analyze_wave(&sine, 1);
+
+	assert!((res.crest_factor.unwrap() - 2f64.sqrt()).abs() < 1e-3);
+	assert!(res.thd.unwrap() < 1e-3);
+	assert!(res.harmonics[0].phase.abs() < 0.1);
+}
+
+#[test]
+pub fn analyze_wave_flat_ok() {
+	let res = analyze_wave(&WaveData::default(), 3);
+
+	assert_eq!(res.dc, 0.0);
+	assert_eq!(res.crest_factor, None);
+	assert_eq!(res.thd, None);
+	assert_eq!(res.levels_used, 1);
+	assert_eq!(res.effective_bits, 0.0);
+
+	// A wave which only uses 16 levels spans 4 bits.
+	let stairs: Vec<u16> = (0..2048).map(|i| 2000 + (i / 128) as u16).collect();
+	let res = analyze_wave(&WaveData::new(&stairs).unwrap(), 0);
+
+	assert_eq!(res.levels_used, 16);
+	assert_eq!(res.effective_bits, 4.0);
+	assert!(res.harmonics.is_empty());
+}
+
+#[test]
+pub fn render_ok() {
+	let res = analyze_wave(&WaveData::default(), 2);
+
+	let text = AnalysisFormat::Text.render(&res);
+	assert!(text.contains("thd: none (no fundamental)\n"));
+	assert!(text.contains("levels used: 1 of 4096 (2048 - 2048)\n"));
+	assert!(text.ends_with("phase 0.00 degrees\n"));
+
+	let json = AnalysisFormat::Json.render(&res);
+	assert!(json.starts_with("{\"samples\":2048,\"dc\":0,"));
+	assert!(json.contains("\"crest_factor\":null,\"thd\":null,"));
+	assert!(json.ends_with("\"harmonics\":[{\"number\":1,\"magnitude\":0,\"phase\":0},{\"number\":2,\"magnitude\":0,\"phase\":0}]}\n"));
+}
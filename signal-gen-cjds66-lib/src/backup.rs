@@ -0,0 +1,161 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Backups of the device's arbitrary wave slots.
+
+A backup is a directory with one file for each slot, in the device's
+text format, named like `slot01.txt`, and a manifest named
+`manifest.sha256` with the SHA-256 hash of each file, and the serial
+number of the device it came from:
+```text
+# signal-gen-cjds66 arbitrary wave backup
+# serial: 9876500000
+3f0a...c1  slot01.txt
+```
+
+The manifest is in the same format as the output of the `sha256sum`
+program, so a backup can also be checked with `sha256sum -c manifest.sha256`.
+*/
+
+use crate::protocol::*;
+
+use clap::{Error, ErrorKind};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/** The name of a backup's manifest file. */
+pub const BACKUP_MANIFEST: &str = "manifest.sha256";
+
+/** The first line of a backup's manifest. */
+pub const BACKUP_MANIFEST_HEADER: &str = "# signal-gen-cjds66 arbitrary wave backup";
+
+const SERIAL_PREFIX: &str = "# serial:";
+
+/** The SHA-256 hash of some data. */
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+	Sha256::digest(data).into()
+}
+
+/** The SHA-256 hash of some data, in lowercase hexadecimal. */
+pub fn sha256_hex(data: &[u8]) -> String {
+	sha256(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/** The name of the file a slot is backed up to. */
+pub fn backup_file_name(slot: u8) -> String {
+	format!("slot{:02}.txt", slot)
+}
+
+/** One slot in a backup. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+	/// The slot number, from 1 to 60.
+	pub slot: u8,
+
+	/// The name of the file, in the backup's directory.
+	pub file: String,
+
+	/// The SHA-256 hash of the file, in lowercase hexadecimal.
+	pub hash: String,
+}
+
+/** The manifest of a backup. */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackupManifest {
+	/// The serial number of the device the backup came from, if known.
+	pub serial: Option<String>,
+
+	/// The slots, in order.
+	pub entries: Vec<BackupEntry>,
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid backup manifest: {}", msg), ErrorKind::InvalidValue)
+}
+
+impl BackupManifest {
+	/// Parse a manifest. Blank lines and other comments are skipped,
+	/// and the slot number of each file comes from its name.
+	pub fn parse(text: &str) -> Result<BackupManifest, clap::Error> {
+		let mut manifest = BackupManifest::default();
+
+		for (i, line) in text.lines().enumerate() {
+			let line = line.trim();
+
+			if let Some(serial) = line.strip_prefix(SERIAL_PREFIX) {
+				manifest.serial = Some(serial.trim().to_string()).filter(|serial| !serial.is_empty());
+				continue;
+			}
+
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let context = format!("line {}: {}", i + 1, line);
+
+			let (hash, file) = line
+				.split_once(char::is_whitespace)
+				.ok_or_else(|| invalid(&format!("must be a hash and a file name: {}", context)))?;
+
+			// sha256sum marks files read in binary mode with a "*".
+			let file = file.trim_start();
+			let file = file.strip_prefix('*').unwrap_or(file);
+
+			if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+				return Err(invalid(&format!("not a SHA-256 hash: {}", context)));
+			}
+
+			let slot = file
+				.strip_prefix("slot")
+				.and_then(|rest| rest.strip_suffix(".txt"))
+				.and_then(|num| num.parse::<u8>().ok())
+				.filter(|slot| {
+					(SET_ARBITRARY_WAVE_ARG_NUM_MIN..=SET_ARBITRARY_WAVE_ARG_NUM_MAX).contains(&(*slot as f64))
+				})
+				.ok_or_else(|| {
+					invalid(&format!(
+						"file names must be slot01.txt to slot{:02}.txt: {}",
+						SET_ARBITRARY_WAVE_ARG_NUM_MAX, context
+					))
+				})?;
+
+			if manifest.entries.iter().any(|entry| entry.slot == slot) {
+				return Err(invalid(&format!("slot listed more than once: {}", context)));
+			}
+
+			manifest.entries.push(BackupEntry {
+				slot,
+				file: file.to_string(),
+				hash: hash.to_lowercase(),
+			});
+		}
+
+		if manifest.entries.is_empty() {
+			return Err(invalid("there are no slots listed"));
+		}
+
+		Ok(manifest)
+	}
+}
+
+impl fmt::Display for BackupManifest {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "{}", BACKUP_MANIFEST_HEADER)?;
+
+		if let Some(serial) = &self.serial {
+			writeln!(f, "{} {}", SERIAL_PREFIX, serial)?;
+		}
+
+		for entry in self.entries.iter() {
+			writeln!(f, "{}  {}", entry.hash, entry.file)?;
+		}
+
+		Ok(())
+	}
+}
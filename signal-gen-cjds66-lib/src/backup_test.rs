@@ -0,0 +1,68 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::backup::*;
+
+#[test]
+pub fn sha256_ok() {
+	assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+	assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+	// Two blocks.
+	assert_eq!(
+		sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+		"248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+	);
+
+	assert_eq!(
+		sha256_hex(&[b'a'; 1000]),
+		"41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+	);
+}
+
+#[test]
+pub fn manifest_ok() {
+	let hash = sha256_hex(b"abc");
+
+	let manifest = BackupManifest {
+		serial: Some("9876500000".to_string()),
+		entries: vec![
+			BackupEntry { slot: 1, file: backup_file_name(1), hash: hash.clone() },
+			BackupEntry { slot: 60, file: backup_file_name(60), hash: hash.clone() },
+		],
+	};
+
+	let text = manifest.to_string();
+
+	assert!(text.starts_with(BACKUP_MANIFEST_HEADER));
+	assert!(text.contains(&format!("{}  slot60.txt\n", hash)));
+	assert_eq!(BackupManifest::parse(&text).unwrap(), manifest);
+
+	// As written by sha256sum, with no serial number.
+	let manifest = BackupManifest::parse(&format!("\r\n{} *slot07.txt\r\n", hash.to_uppercase())).unwrap();
+
+	assert_eq!(manifest.serial, None);
+	assert_eq!(manifest.entries, vec![BackupEntry { slot: 7, file: "slot07.txt".to_string(), hash }]);
+}
+
+#[test]
+pub fn manifest_err() {
+	let hash = sha256_hex(b"abc");
+
+	BackupManifest::parse("").unwrap_err();
+	BackupManifest::parse(BACKUP_MANIFEST_HEADER).unwrap_err();
+	BackupManifest::parse(&hash).unwrap_err();
+	BackupManifest::parse("abc  slot01.txt").unwrap_err();
+	BackupManifest::parse(&format!("{}  wave.txt", hash)).unwrap_err();
+	BackupManifest::parse(&format!("{}  slot00.txt", hash)).unwrap_err();
+	BackupManifest::parse(&format!("{}  slot61.txt", hash)).unwrap_err();
+	BackupManifest::parse(&format!("{}  slot01.txt\n{}  slot01.txt", hash, hash)).unwrap_err();
+}
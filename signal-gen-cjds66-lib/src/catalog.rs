@@ -0,0 +1,301 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! A catalogue of what's in a device's arbitrary wave slots, kept on
+the computer, since the device doesn't have names for them.
+
+Each device has its own catalogue, found by its serial number, with a
+name, description, source, and content hash for each slot it knows
+about. The hash is the SHA-256 hash of the slot's wave in the device's
+text format, the same as in a [backup](../backup/index.html) manifest,
+so a slot which was changed from the front panel or by another
+computer can be spotted by reading it again and comparing the hashes.
+
+A catalogue is a text file with one slot on each line, and its fields
+separated by tabs (shown as spaces here):
+```text
+# signal-gen-cjds66 arbitrary wave catalogue
+# serial: 9876500000
+# slot    state   hash        name    source                      description
+37        ok      3f0a...c1   Chirp   shape chirp start=1 end=20  For the filter tests.
+```
+
+The state is `ok`, or `changed` if the slot didn't match its hash the
+last time it was read. Tabs, newlines, and backslashes in the text
+fields are written as `\t`, `\n`, and `\\`.
+*/
+
+use crate::protocol::*;
+
+use clap::{Error, ErrorKind};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/** The first line of a catalogue. */
+pub const CATALOG_HEADER: &str = "# signal-gen-cjds66 arbitrary wave catalogue";
+
+const SERIAL_PREFIX: &str = "# serial:";
+
+const COLUMNS: &str = "# slot\tstate\thash\tname\tsource\tdescription";
+
+/** What's known about one slot. */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogEntry {
+	/// The slot number, from 1 to 60.
+	pub slot: u8,
+
+	/// A short name for the wave.
+	pub name: String,
+
+	/// Where the wave came from, such as a file name.
+	pub source: String,
+
+	/// Anything else worth knowing about it.
+	pub description: String,
+
+	/// The SHA-256 hash of the wave in the device's text format, in
+	/// lowercase hexadecimal, when it was catalogued.
+	pub hash: String,
+
+	/// Whether the slot didn't match the hash the last time it was read.
+	pub changed: bool,
+}
+
+/** The catalogue of one device's arbitrary wave slots. */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlotCatalog {
+	/// The serial number of the device.
+	pub serial: String,
+
+	/// The slots which are catalogued, in order.
+	pub entries: Vec<CatalogEntry>,
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid arbitrary wave catalogue: {}", msg), ErrorKind::InvalidValue)
+}
+
+fn escape(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+	let mut out = String::new();
+	let mut chars = text.chars();
+
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			out.push(c);
+			continue;
+		}
+
+		match chars.next() {
+			Some('t') => out.push('\t'),
+			Some('n') => out.push('\n'),
+			Some(c) => out.push(c),
+			None => out.push('\\'),
+		}
+	}
+
+	out
+}
+
+/** Where catalogues are kept if no directory is chosen: in the
+`signal-gen-cjds66/catalogs` directory inside `$XDG_CONFIG_HOME`, or
+`%APPDATA%` on Windows, or `~/.config`. Returns `None` if none of those
+are set. */
+pub fn default_catalog_dir() -> Option<String> {
+	let base = env::var_os("XDG_CONFIG_HOME")
+		.map(PathBuf::from)
+		.or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+		.or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+	Some(base.join("signal-gen-cjds66").join("catalogs").to_string_lossy().to_string())
+}
+
+/** The path of a device's catalogue in the directory "dir". Any
+characters in the serial number which don't belong in a file name are
+replaced with `_`. */
+pub fn catalog_path(dir: &str, serial: &str) -> PathBuf {
+	let name: String = serial
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+		.collect();
+
+	Path::new(dir).join(format!("{}.tsv", name))
+}
+
+impl SlotCatalog {
+	/// An empty catalogue for a device.
+	pub fn new(serial: &str) -> SlotCatalog {
+		SlotCatalog {
+			serial: serial.to_string(),
+			entries: Vec::new(),
+		}
+	}
+
+	/// Parse a catalogue.
+	pub fn parse(text: &str) -> Result<SlotCatalog, clap::Error> {
+		let mut catalog = SlotCatalog::default();
+
+		for (i, line) in text.lines().enumerate() {
+			if let Some(serial) = line.strip_prefix(SERIAL_PREFIX) {
+				catalog.serial = serial.trim().to_string();
+				continue;
+			}
+
+			if line.trim().is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let fields: Vec<&str> = line.trim_end_matches('\r').split('\t').collect();
+
+			if fields.len() != 6 {
+				return Err(invalid(&format!("line {}: must have 6 fields separated by tabs: {}", i + 1, line)));
+			}
+
+			let slot = fields[0]
+				.parse::<u8>()
+				.ok()
+				.filter(|slot| (SET_ARBITRARY_WAVE_ARG_NUM_MIN..=SET_ARBITRARY_WAVE_ARG_NUM_MAX).contains(&(*slot as f64)))
+				.ok_or_else(|| invalid(&format!("line {}: unsupported slot number: {}", i + 1, fields[0])))?;
+
+			let changed = match fields[1] {
+				"ok" => false,
+				"changed" => true,
+				_ => return Err(invalid(&format!("line {}: the state must be ok or changed: {}", i + 1, fields[1]))),
+			};
+
+			if catalog.entry(slot).is_some() {
+				return Err(invalid(&format!("line {}: slot listed more than once: {}", i + 1, slot)));
+			}
+
+			*catalog.entry_mut(slot) = CatalogEntry {
+				slot,
+				changed,
+				hash: fields[2].to_lowercase(),
+				name: unescape(fields[3]),
+				source: unescape(fields[4]),
+				description: unescape(fields[5]),
+			};
+		}
+
+		Ok(catalog)
+	}
+
+	/// Read a catalogue file, or start a new one for the device with
+	/// serial number "serial" if it doesn't exist yet.
+	pub fn load_or_new(path: &Path, serial: &str) -> Result<SlotCatalog, clap::Error> {
+		if !path.exists() {
+			return Ok(SlotCatalog::new(serial));
+		}
+
+		let text = fs::read_to_string(path).map_err(|e| {
+			Error::with_description(&format!("failed reading file: {}: {}", path.display(), e), ErrorKind::Io)
+		})?;
+
+		let mut catalog = SlotCatalog::parse(&text)?;
+
+		if catalog.serial.is_empty() {
+			catalog.serial = serial.to_string();
+		}
+
+		Ok(catalog)
+	}
+
+	/// Write the catalogue to a file, making its directory if needed.
+	pub fn save(&self, path: &Path) -> Result<(), clap::Error> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|e| {
+				Error::with_description(&format!("failed creating directory: {}: {}", dir.display(), e), ErrorKind::Io)
+			})?;
+		}
+
+		fs::write(path, self.to_string()).map_err(|e| {
+			Error::with_description(&format!("failed writing to file: {}: {}", path.display(), e), ErrorKind::Io)
+		})
+	}
+
+	/// The entry for a slot, if it's catalogued.
+	pub fn entry(&self, slot: u8) -> Option<&CatalogEntry> {
+		self.entries.iter().find(|entry| entry.slot == slot)
+	}
+
+	/// The entry for a slot, which is added if it isn't catalogued yet.
+	pub fn entry_mut(&mut self, slot: u8) -> &mut CatalogEntry {
+		let pos = match self.entries.binary_search_by_key(&slot, |entry| entry.slot) {
+			Ok(pos) => pos,
+			Err(pos) => {
+				self.entries.insert(pos, CatalogEntry { slot, ..CatalogEntry::default() });
+				pos
+			}
+		};
+
+		&mut self.entries[pos]
+	}
+
+	/// The catalogue as a table with aligned columns, for reading,
+	/// with the hashes shortened to their first 12 digits.
+	pub fn to_table(&self) -> String {
+		let mut rows = vec![vec![
+			"slot".to_string(),
+			"state".to_string(),
+			"hash".to_string(),
+			"name".to_string(),
+			"source".to_string(),
+			"description".to_string(),
+		]];
+
+		for entry in self.entries.iter() {
+			rows.push(vec![
+				entry.slot.to_string(),
+				if entry.changed { "changed" } else { "ok" }.to_string(),
+				entry.hash.chars().take(12).collect(),
+				escape(&entry.name),
+				escape(&entry.source),
+				escape(&entry.description),
+			]);
+		}
+
+		let widths: Vec<usize> = (0..6).map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap_or_default()).collect();
+
+		rows.iter()
+			.map(|row| {
+				let cells: Vec<String> = row.iter().zip(widths.iter()).map(|(cell, width)| format!("{:<1$}", cell, width)).collect();
+
+				format!("{}\n", cells.join("  ").trim_end())
+			})
+			.collect()
+	}
+}
+
+impl fmt::Display for SlotCatalog {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "{}", CATALOG_HEADER)?;
+		writeln!(f, "{} {}", SERIAL_PREFIX, self.serial)?;
+		writeln!(f, "{}", COLUMNS)?;
+
+		for entry in self.entries.iter() {
+			writeln!(
+				f,
+				"{}\t{}\t{}\t{}\t{}\t{}",
+				entry.slot,
+				if entry.changed { "changed" } else { "ok" },
+				entry.hash,
+				escape(&entry.name),
+				escape(&entry.source),
+				escape(&entry.description)
+			)?;
+		}
+
+		Ok(())
+	}
+}
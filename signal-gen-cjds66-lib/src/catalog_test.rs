@@ -0,0 +1,64 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::catalog::*;
+
+use std::path::Path;
+
+#[test]
+pub fn catalog_ok() {
+	let mut catalog = SlotCatalog::new("9876500000");
+
+	*catalog.entry_mut(37) = CatalogEntry {
+		slot: 37,
+		name: "Chirp".to_string(),
+		source: "chirp.txt".to_string(),
+		description: "For the\tfilter\ntests. C:\\waves".to_string(),
+		hash: "ab".repeat(32),
+		changed: true,
+	};
+
+	catalog.entry_mut(2).name = "Step".to_string();
+
+	// Kept in order of slot number.
+	assert_eq!(catalog.entries.iter().map(|entry| entry.slot).collect::<Vec<u8>>(), vec![2, 37]);
+	assert_eq!(catalog.entry(37).unwrap().name, "Chirp");
+	assert!(catalog.entry(1).is_none());
+
+	let text = catalog.to_string();
+
+	assert!(text.starts_with(CATALOG_HEADER));
+	assert!(text.contains("# serial: 9876500000\n"));
+	assert!(text.contains("For the\\tfilter\\ntests. C:\\\\waves"));
+	assert_eq!(SlotCatalog::parse(&text).unwrap(), catalog);
+
+	let table = catalog.to_table();
+	let lines: Vec<&str> = table.lines().collect();
+
+	assert_eq!(lines.len(), 3);
+	assert!(lines[0].starts_with("slot  state    hash"));
+	assert!(lines[2].starts_with("37    changed  abababababab  Chirp"));
+}
+
+#[test]
+pub fn catalog_err() {
+	SlotCatalog::parse("1\tok\thash\tname\tsource\n").unwrap_err();
+	SlotCatalog::parse("0\tok\thash\tname\tsource\tdescription\n").unwrap_err();
+	SlotCatalog::parse("61\tok\thash\tname\tsource\tdescription\n").unwrap_err();
+	SlotCatalog::parse("1\tstale\thash\tname\tsource\tdescription\n").unwrap_err();
+	SlotCatalog::parse("1\tok\th\tn\ts\td\n1\tok\th\tn\ts\td\n").unwrap_err();
+}
+
+#[test]
+pub fn catalog_path_ok() {
+	assert_eq!(catalog_path("catalogs", "9876500000"), Path::new("catalogs").join("9876500000.tsv"));
+	assert_eq!(catalog_path("catalogs", "../a b/c"), Path::new("catalogs").join("___a_b_c.tsv"));
+}
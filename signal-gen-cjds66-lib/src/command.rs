@@ -23,19 +23,369 @@ can just use 1 for "verbose" output, and 0 for "not verbose" output.
 extern crate byteorder;
 extern crate serial;
 
+use crate::analyze::*;
+use crate::backup::*;
+use crate::catalog::*;
+use crate::convert::*;
+use crate::export::*;
+use crate::expr::*;
+use crate::fourier::*;
+use crate::ops::*;
+use crate::plot::*;
 use crate::protocol::*;
+use crate::provision::*;
+use crate::register::*;
+use crate::render::*;
+use crate::resample::*;
+use crate::scope::*;
 use crate::serial::*;
+use crate::spice::*;
+use crate::stats::*;
+use crate::synth::*;
+use crate::transfer::*;
+use crate::units::*;
 use crate::util::*;
+use crate::wave::*;
+use crate::wav::*;
 
 use std::fs;
 use std::io::prelude::*;
-use std::io::{self, BufRead};
+use std::io;
+use std::path::{Path, PathBuf};
 use std::str;
+use std::time::Instant;
 use std::{thread, time};
 
 use byteorder::{ByteOrder, LittleEndian};
 use clap::{Error, ErrorKind};
 
+/** Read a setting back from the device after it was written,
+and check that the device reports the value which was requested.
+This is used by all the setters which have a matching getter,
+when the `verify` field of the `SerialPortType` is true.
+
+The values in `expected` are the raw numbers that were sent to
+the device (before any unit conversion is undone), in the same
+order as they appear in the device's response. A value is
+considered to match if it's within half of one step of the
+device's resolution (the smallest raw unit). `to_user` converts
+the first raw value back into the units the user requested, and
+is only used for the error message.
+
+If the device reports something different, an error of kind
+`ValueValidation` is returned, with its `info` set to:
+```ignore
+[setting name, requested value, reported value]
+```
+*/
+fn verify_setting(
+	port: &mut SerialPortType,
+	name: &str,
+	command: &str,
+	res_len: u8,
+	expected: &[f64],
+	to_user: &dyn Fn(f64) -> f64,
+	verbose: u64,
+) -> Result<(), clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	if verbose > 0 {
+		println!("\nVerifying {}:\n{}", name, command);
+	}
+
+	let expected_str = expected
+		.iter()
+		.map(|v| v.to_string())
+		.collect::<Vec<String>>()
+		.join(COMMAND_ARG_SEPARATOR);
+
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..res_len).collect();
+
+	if !port.mock {
+		let len = port.transact(&inbuf[..], &mut outbuf[..])?;
+		outbuf.truncate(len);
+
+	} else {	// Mock data for testing.
+		let command_parts: Vec<&str> = command.split(COMMAND_SEPARATOR).collect();
+
+		match port.mock_num {
+			1 => {	// The device rounded the value off by one step.
+				outbuf = Vec::from(&format!("{}{}{}{}", command_parts[0], COMMAND_SEPARATOR, expected[0] + 1.0, COMMAND_END) as &str);
+			},
+			_ => {	// Ok.
+				outbuf = Vec::from(&format!("{}{}{}{}", command_parts[0], COMMAND_SEPARATOR, expected_str, COMMAND_END) as &str);
+			},
+		}
+	}
+
+	let res = String::from_utf8_lossy(&outbuf).to_string();
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	}
+
+	let res2_parts: Vec<&str> = res.split(COMMAND_SEPARATOR).collect();
+
+	if res2_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device while verifying {}: missing equals (=): {}",
+				name, res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let res3_parts: Vec<&str> = res2_parts[1].split(COMMAND_STOP).collect();
+
+	if res3_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device while verifying {}: missing period (.): {}",
+				name, res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let actual_str = res3_parts[0];
+
+	let actual: Vec<f64> = actual_str
+		.split(COMMAND_ARG_SEPARATOR)
+		.map(|v| v.trim().parse::<f64>())
+		.collect::<Result<Vec<f64>, _>>()
+		.map_err(|e| {
+			Error::with_description(
+				&format!(
+					"unexpected response from device while verifying {}: {}: {}",
+					name, res, e
+				),
+				ErrorKind::Io,
+			)
+		})?;
+
+	let matches = actual.len() == expected.len()
+		&& actual
+			.iter()
+			.zip(expected.iter())
+			.all(|(a, e)| (a - e).abs() < 0.5);
+
+	if !matches {
+		let requested = format!("{}", to_user(expected[0]));
+		let reported = match actual.first() {
+			Some(a) => format!("{}", to_user(*a)),
+			None => String::new(),
+		};
+
+		let mut e = Error::with_description(
+			&format!(
+				"verify failed for {}: requested {} ({}) but the device reports {} ({}), tolerance is ±{}",
+				name, requested, expected_str, reported, actual_str, (to_user(0.5) - to_user(0.0)).abs()
+			),
+			ErrorKind::ValueValidation,
+		);
+
+		e.info = Some(vec![name.to_string(), requested, reported]);
+
+		return Err(e);
+	}
+
+	Ok(())
+}
+
+/** Set one of the device's numeric registers, described by an entry
+in the register table (see [`register`](../register/index.html)),
+for a particular channel. The channel is ignored for registers which
+aren't per-channel.
+
+The value is checked against the register's range and precision,
+and a unit can be written after it if the register accepts one.
+If the `verify` field of the `SerialPortType` is true, the new value
+is also verified, and registers which can't be read back aren't set,
+with an error instead.
+
+"amount" parameter, for example for `DUTY_CYCLE`:
+```ignore
+"40.1" | "0.5"
+```
+*/
+pub fn set_register(
+	port: &mut SerialPortType,
+	reg: &Register,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	let raw = if reg.amplitude {
+		reg.validate(&amplitude_to_device(port, chan, amount, verbose)?)?
+	} else {
+		reg.validate(amount)?
+	};
+
+	let command = reg.set_command(chan, raw)?;
+
+	let name = if reg.per_channel() {
+		format!("{} ch{}", reg.name, chan)
+	} else {
+		reg.name.to_string()
+	};
+
+	if port.verify && !reg.readable() {
+		return Err(Error::with_description(
+			&format!("can't verify {}: the device can't read it back, so set it without verify mode", name),
+			ErrorKind::ArgumentConflict,
+		));
+	}
+
+	if verbose > 0 {
+		println!("\nSetting {}: {}:\n{}", name, raw, command);
+	}
+
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..reg.set_res_len).collect();
+
+	if !port.mock {
+		let len = port.transact(&inbuf[..], &mut outbuf[..])?;
+		outbuf.truncate(len);
+	}
+
+	let res = String::from_utf8_lossy(&outbuf).to_string();
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	}
+
+	if port.verify {
+		verify_setting(port, &name, &reg.get_command(chan)?, reg.get_res_len, &reg.expected(raw), &|v| reg.decode(v), verbose)?;
+	}
+
+	Ok(res)
+}
+
+/** Get one of the device's numeric registers, described by an entry
+in the register table (see [`register`](../register/index.html)),
+for a particular channel. The channel is ignored for registers which
+aren't per-channel.
+
+The value is printed in the register's unit, at the channel's load
+for an amplitude, and the device's raw response is returned.
+
+Return Value (Ok Result), for example for `DUTY_CYCLE`:
+```ignore
+50%:
+":r23=500.\r\n"
+```
+*/
+pub fn get_register(
+	port: &mut SerialPortType,
+	reg: &Register,
+	chan: u64,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let (res, raw) = read_register(port, reg, chan, verbose)?;
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	} else {
+		println!("{}", register_value(port, reg, chan, raw));
+	}
+
+	Ok(res)
+}
+
+/** Convert a raw number read from a register into its value, which
+for an amplitude is the one seen by the channel's load. */
+fn register_value(port: &SerialPortType, reg: &Register, chan: u64, raw: f64) -> f64 {
+	if reg.amplitude {
+		reg.decode(raw) * channel_load(port, chan).divider()
+	} else {
+		reg.decode(raw)
+	}
+}
+
+/** Read one of the device's numeric registers without printing its
+value, and return the device's raw response and the raw number in it.
+*/
+fn read_register(
+	port: &mut SerialPortType,
+	reg: &Register,
+	chan: u64,
+	verbose: u64,
+) -> Result<(String, f64), clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	let command = reg.get_command(chan)?;
+
+	if verbose > 0 {
+		println!("\nGetting {}: ch{}:\n{}", reg.name, chan, command);
+	}
+
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..reg.get_res_len).collect();
+
+	if !port.mock {
+		let len = port.transact(&inbuf[..], &mut outbuf[..])?;
+		outbuf.truncate(len);
+
+	} else {	// Mock data for testing.
+		let command_parts: Vec<&str> = command.split(COMMAND_SEPARATOR).collect();
+
+		let raw = reg.validate(reg.example).unwrap_or_default();
+
+		outbuf = Vec::from(&format!("{}{}{}{}", command_parts[0], COMMAND_SEPARATOR, raw, COMMAND_END) as &str);
+	}
+
+	let res = String::from_utf8_lossy(&outbuf).to_string();
+
+	let res2_parts: Vec<&str> = res.split(COMMAND_SEPARATOR).collect();
+
+	if res2_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device: missing equals (=): {}",
+				res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let res3_parts: Vec<&str> = res2_parts[1].split(COMMAND_STOP).collect();
+
+	if res3_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device: missing period (.): {}",
+				res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let raw = res3_parts[0].parse::<f64>().map_err(|e| {
+		Error::with_description(
+			&format!("unexpected response from device: {}: {}", res, e),
+			ErrorKind::Io,
+		)
+	})?;
+
+	Ok((res, raw))
+}
+
 /** Get the model number of the device. */
 pub fn get_model(
 	port: &mut SerialPortType,
@@ -54,8 +404,7 @@ pub fn get_model(
 	let mut outbuf: Vec<u8> = (0..GET_MODEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
 	} else {	// Mock data for testing.
 		match port.mock_num {
@@ -147,6 +496,19 @@ pub fn get_serial(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
+	let (res, serial) = read_serial(port, verbose)?;
+
+	println!("serial:\t{}", serial);
+
+	Ok(res)
+}
+
+/** Get the serial number of the device without printing it.
+Returns the device's response, and the serial number. */
+fn read_serial(
+	port: &mut SerialPortType,
+	verbose: u64,
+) -> Result<(String, String), clap::Error> {
 	if !port.mock {
 		// Wait a bit to allow the device some time to settle.
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
@@ -163,8 +525,7 @@ pub fn get_serial(
 	let mut outbuf: Vec<u8> = (0..GET_SERIAL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
 	} else {	// Mock data for testing.
 		match port.mock_num {
@@ -246,9 +607,7 @@ pub fn get_serial(
 
 	let res3: &str = &res2[1][0..res2[1].len() - 3];
 
-	println!("serial:\t{}", res3);
-
-	Ok(res.to_string())
+	Ok((res.to_string(), res3.to_string()))
 }
 
 /** Get the model number and the serial number of the device. */
@@ -269,8 +628,7 @@ pub fn get_model_and_serial(
 	let mut outbuf: Vec<u8> = (0..GET_MODEL_AND_NUMBER_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
 	} else {	// Mock data for testing.
 		outbuf = Vec::from(":r00=60.\r\n:r01=9876500000.\r\n");
@@ -425,8 +783,7 @@ fn set_channel_output_inner(
 	let mut outbuf: Vec<u8> = (0..SET_CHANNEL_OUTPUT_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -436,6 +793,10 @@ fn set_channel_output_inner(
 		println!("{}", res);
 	}
 
+	if port.verify {
+		verify_setting(port, "channel output", GET_CHANNEL_OUTPUT, GET_CHANNEL_OUTPUT_RES_LEN, &[ch1 as u8 as f64, ch2 as u8 as f64], &|v| v, verbose)?;
+	}
+
 	Ok(res.to_string())
 }
 
@@ -469,8 +830,7 @@ pub fn get_channel_output(
 	let mut outbuf: Vec<u8> = (0..GET_CHANNEL_OUTPUT_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
 	} else {	// Mock data for tests.
 		outbuf = Vec::from(":r20=0.\r\n");
@@ -518,226 +878,249 @@ pub fn get_channel_output(
 
 /** Set the device to use a certain named or numbered waveform preset
 for a specific channel's output. You can choose one of these presets
-either by name or by the number listed next to the name below.
+either by name or by its number, and the names accepted for each
+number are listed in `register::WAVEFORM_PRESET`.
 
-"preset" parameter:
+"preset" parameter, for example:
 ```ignore
-Waveform names that are accepted, along with their corresponding numbers:
-"0":  "sine" | "sin"
-"1":  "square" | "sq"
-"2":  "pulse" | "pul"
-"3":  "triangle" | "tri"
-"4":  "partialsine" | "partial-sine" | "parsine" | "par-sine" | "parsin" | "par-sin" | "psine" | "p-sine" | "psin" | "p-sin"
-"5":  "cmos" | "cm"
-"6":  "dc"
-"7":  "halfwave" | "half-wave" | "hw" | "h-w"
-"8":  "fullwave" | "full-wave" | "fw" | "f-w"
-"9":  "pos-ladder" | "posladder" | "pos-lad" | "poslad" | "positive-ladder" | "positiveladder" | "pl"
-"10": "neg-ladder" | "negladder" | "neg-lad" | "neglad" | "negative-ladder" | "negativeladder" | "nl"
-"11": "noise" | "nois" | "noi" | "no" | "n"
-"12": "exp-rise" | "exprise" | "e-r" | "er" | "e-rise" | "erise" | "e-ris" | "eris"
-"13": "exp-decay" | "expdecay" | "e-d" | "ed" | "e-decay" | "edecay" | "e-dec" | "edec"
-"14": "multi-tone" | "multitone" | "m-t" | "mt" | "m-tone" | "mtone"
-"15": "sinc" | "sc"
-"16": "lorenz" | "loren" | "lor" | "lz"
+"0" | "sine" | "sin" | "16" | "lorenz"
 ```
 */
 pub fn set_waveform_preset(
-	mut port: &mut SerialPortType,
+	port: &mut SerialPortType,
 	chan: u64,
 	preset: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match preset.parse::<u64>() {
-		Ok(preset) => {
-			match preset {
-				0..=16 => {
-					res = set_waveform_preset_inner(&mut port, chan, preset, verbose);
-				}
+	set_register(port, &WAVEFORM_PRESET, chan, preset, verbose)
+}
 
-				_ => {
-					res = Err(Error::with_description(&format!("unsupported value passed to \"set waveform\" argument (must be 0-16): {}", preset), ErrorKind::InvalidValue));
-				}
-			}
-		}
+/** Get the current output waveform preset for a
+specific channel.
 
-		Err(_e) => {
-			match preset {
-				"sine" | "sin" => {
-					res = set_waveform_preset_inner(&mut port, chan, 0, verbose);
-				}
+Return Value (Ok Result):
+```ignore
+Waveform preset number to name mappings:
+"0":  sine
+"1":  square
+"2":  pulse
+"3":  triangle
+"4":  partial-sine
+"5":  cmos
+"6":  dc
+"7":  half-wave
+"8":  full-wave
+"9":  pos-ladder
+"10": neg-ladder
+"11": noise
+"12": exp-rise
+"13": exp-decay
+"14": multi-tone
+"15": sinc
+"16": lorenz
 
-				"square" | "sq" => {
-					res = set_waveform_preset_inner(&mut port, chan, 1, verbose);
-				}
+Arbitrary waveform presets:
+"101" - "160": arbitrary wave 1 - arbitrary wave 60
+```
+*/
+pub fn get_waveform_preset(
+	port: &mut SerialPortType,
+	chan: u64,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let (res, raw) = read_register(port, &WAVEFORM_PRESET, chan, verbose)?;
 
-				"pulse" | "pul" => {
-					res = set_waveform_preset_inner(&mut port, chan, 2, verbose);
-				}
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	} else {
+		println!("{}", raw);
+	}
 
-				"triangle" | "tri" => {
-					res = set_waveform_preset_inner(&mut port, chan, 3, verbose);
-				}
+	Ok(raw.to_string())
+}
 
-				"partialsine" | "partial-sine" | "parsine" | "par-sine" | "parsin" | "par-sin"
-				| "psine" | "p-sine" | "psin" | "p-sin" => {
-					res = set_waveform_preset_inner(&mut port, chan, 4, verbose);
-				}
+/** Set the device to use a user-defined arbitrary waveform
+preset by number, for a specific channel's output.
 
-				"cmos" | "cm" => {
-					res = set_waveform_preset_inner(&mut port, chan, 5, verbose);
-				}
+"preset" parameter:
+```ignore
+Arbitrary waveform preset 1 (a.k.a. preset 101):
+"1"
 
-				"dc" => {
-					res = set_waveform_preset_inner(&mut port, chan, 6, verbose);
-				}
-
-				"halfwave" | "half-wave" | "hw" | "h-w" => {
-					res = set_waveform_preset_inner(&mut port, chan, 7, verbose);
-				}
+Arbitrary waveform preset 60 (a.k.a. preset 160):
+"60"
+```
+*/
+pub fn set_waveform_preset_arbitrary(
+	port: &mut SerialPortType,
+	chan: u64,
+	preset: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &ARBITRARY_WAVEFORM, chan, preset, verbose)
+}
 
-				"fullwave" | "full-wave" | "fw" | "f-w" => {
-					res = set_waveform_preset_inner(&mut port, chan, 8, verbose);
-				}
+/** Set the device's output frequency for a particular channel,
+in microhertz (µHz).
 
-				"pos-ladder" | "posladder" | "pos-lad" | "poslad" | "positive-ladder"
-				| "positiveladder" | "pl" => {
-					res = set_waveform_preset_inner(&mut port, chan, 9, verbose);
-				}
+The range and precision of the "amount" parameter are listed in
+`register::FREQUENCY_MICROHERTZ`.
 
-				"neg-ladder" | "negladder" | "neg-lad" | "neglad" | "negative-ladder"
-				| "negativeladder" | "nl" => {
-					res = set_waveform_preset_inner(&mut port, chan, 10, verbose);
-				}
+A unit can also be written after the value, for example:
+```ignore
+"1.5uHz" | "0.25Hz"
+```
+*/
+pub fn set_frequency_microhertz(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &FREQUENCY_MICROHERTZ, chan, amount, verbose)
+}
 
-				"noise" | "nois" | "noi" | "no" | "n" => {
-					res = set_waveform_preset_inner(&mut port, chan, 11, verbose);
-				}
+/** Set the device's output frequency for a particular channel,
+in millihertz (mHz).
 
-				"exp-rise" | "exprise" | "e-r" | "er" | "e-rise" | "erise" | "e-ris" | "eris" => {
-					res = set_waveform_preset_inner(&mut port, chan, 12, verbose);
-				}
+The range and precision of the "amount" parameter are listed in
+`register::FREQUENCY_MILLIHERTZ`.
 
-				"exp-decay" | "expdecay" | "e-d" | "ed" | "e-decay" | "edecay" | "e-dec"
-				| "edec" => {
-					res = set_waveform_preset_inner(&mut port, chan, 13, verbose);
-				}
+A unit can also be written after the value, for example:
+```ignore
+"1.5mHz" | "20Hz"
+```
+*/
+pub fn set_frequency_millihertz(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &FREQUENCY_MILLIHERTZ, chan, amount, verbose)
+}
 
-				"multi-tone" | "multitone" | "m-t" | "mt" | "m-tone" | "mtone" => {
-					res = set_waveform_preset_inner(&mut port, chan, 14, verbose);
-				}
+/** Set the device's output frequency for a particular channel,
+in hertz (Hz).
 
-				"sinc" | "sc" => {
-					res = set_waveform_preset_inner(&mut port, chan, 15, verbose);
-				}
+The range and precision of the "amount" parameter are listed in
+`register::FREQUENCY_HERTZ`.
 
-				"lorenz" | "loren" | "lor" | "lz" => {
-					res = set_waveform_preset_inner(&mut port, chan, 16, verbose);
-				}
+A unit can also be written after the value, for example:
+```ignore
+"1.5kHz" | "500mHz"
+```
+*/
+pub fn set_frequency_hertz(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &FREQUENCY_HERTZ, chan, amount, verbose)
+}
 
-				_ => {
-					res = Err(Error::with_description(&format!("unsupported value passed to \"set waveform\" argument (must be 0-16): {}", preset), ErrorKind::InvalidValue));
-				}
-			}
-		}
-	}
+/** Set the device's output frequency for a particular channel,
+in kilohertz (kHz).
 
-	res
-}
+The range and precision of the "amount" parameter are listed in
+`register::FREQUENCY_KILOHERTZ`.
 
-fn set_waveform_preset_inner(
+A unit can also be written after the value, for example:
+```ignore
+"1.5kHz" | "250Hz"
+```
+*/
+pub fn set_frequency_kilohertz(
 	port: &mut SerialPortType,
 	chan: u64,
-	preset: u64,
+	amount: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_WAVEFORM_PRESET_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_WAVEFORM_PRESET_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
+	set_register(port, &FREQUENCY_KILOHERTZ, chan, amount, verbose)
+}
 
-	if preset > 16 {
-		return Err(Error::with_description(
-			"Unsupported waveform preset number. Must be 0-16.",
-			ErrorKind::InvalidValue,
-		));
-	}
+/** Set the device's output frequency for a particular channel,
+in megahertz (MHz).
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_SET, chan_out, COMMAND_SEPARATOR, preset, COMMAND_END,
-	);
+The range and precision of the "amount" parameter are listed in
+`register::FREQUENCY_MEGAHERTZ`.
 
-	if verbose > 0 {
-		println!(
-			"\nSetting waveform preset: ch{}={}:\n{}",
-			chan, preset, command
-		);
-	}
+A unit can also be written after the value, for example:
+```ignore
+"1.5MHz" | "250kHz"
+```
+*/
+pub fn set_frequency_megahertz(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &FREQUENCY_MEGAHERTZ, chan, amount, verbose)
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_WAVEFORM_PRESET_RES_LEN).collect();
+/** Set the device's output frequency for a particular channel,
+in whichever unit is written after the value. Values without a
+unit are in hertz (Hz).
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+If the device can't represent the value exactly in the unit it
+was written in, a finer unit is used instead.
 
-	let res = str::from_utf8(&outbuf).unwrap();
+"amount" parameter:
+```ignore
+"1.5kHz" | "250mHz" | "10MHz" | "0.5uHz" | "1000"
+```
+*/
+pub fn set_frequency(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let (unit, amount) = frequency_to_protocol(amount)?;
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+	match unit {
+		FrequencyUnit::Microhertz => set_frequency_microhertz(port, chan, &amount, verbose),
+		FrequencyUnit::Millihertz => set_frequency_millihertz(port, chan, &amount, verbose),
+		FrequencyUnit::Hertz => set_frequency_hertz(port, chan, &amount, verbose),
+		FrequencyUnit::Kilohertz => set_frequency_kilohertz(port, chan, &amount, verbose),
+		FrequencyUnit::Megahertz => set_frequency_megahertz(port, chan, &amount, verbose),
 	}
-
-	Ok(res.to_string())
 }
 
-/** Get the current output waveform preset for a
-specific channel.
+/** Get the device's output frequency for a particular channel,
+in whichever unit the channel is currently set on.
+
+Supported units are:
+```ignore
+uHz (microhertz)
+mHz (millihertz)
+Hz (hertz)
+kHz (kilohertz)
+MHz (megahertz)
+```
 
 Return Value (Ok Result):
 ```ignore
-Waveform preset number to name mappings:
-"0":  sine
-"1":  square
-"2":  pulse
-"3":  triangle
-"4":  partial-sine
-"5":  cmos
-"6":  dc
-"7":  half-wave
-"8":  full-wave
-"9":  pos-ladder
-"10": neg-ladder
-"11": noise
-"12": exp-rise
-"13": exp-decay
-"14": multi-tone
-"15": sinc
-"16": lorenz
+10,000 uHz:
+"10000 uHz"
 
-Arbitrary waveform presets:
-"101" - "160": arbitrary wave 1 - arbitrary wave 60
+10,000 mHz:
+"10000 mHz"
+
+10,000 Hz:
+"10000 Hz"
+
+10 kHz:
+"10 kHz"
+
+0.01 MHz:
+"0.01 MHz"
 ```
 */
-pub fn get_waveform_preset(
+pub fn get_frequency(
 	port: &mut SerialPortType,
 	chan: u64,
 	verbose: u64,
@@ -751,9 +1134,9 @@ pub fn get_waveform_preset(
 	let chan_out: &str;
 
 	if chan == 1 {
-		chan_out = GET_WAVEFORM_PRESET_COMMAND_CH1;
+		chan_out = GET_FREQUENCY_COMMAND_CH1;
 	} else if chan == 2 {
-		chan_out = GET_WAVEFORM_PRESET_COMMAND_CH2;
+		chan_out = GET_FREQUENCY_COMMAND_CH2;
 	} else {
 		return Err(Error::with_description(
 			"Unsupported channel number. Must be 1 or 2.",
@@ -763,27 +1146,21 @@ pub fn get_waveform_preset(
 
 	command = format!(
 		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_GET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		GET_WAVEFORM_PRESET_ARG,
-		COMMAND_END,
+		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_FREQUENCY_ARG, COMMAND_END,
 	);
 
 	if verbose > 0 {
-		println!("\nGetting waveform preset: ch{}:\n{}", chan, command);
+		println!("\nGetting frequency: ch{}:\n{}", chan, command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_WAVEFORM_PRESET_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..GET_FREQUENCY_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
 	} else {	// Mock data for testing.
-		outbuf = Vec::from(&format!(":r2{}=0.\r\n", chan) as &str);
+		outbuf = Vec::from(&format!(":r2{}=1000,0.\r\n", chan + 2) as &str);
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -816,32 +1193,68 @@ pub fn get_waveform_preset(
 
 	let res3 = res3_parts[0];
 
+	let res4_parts: Vec<&str> = res3.split(",").collect();
+
+	if res4_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device: missing comma (,): {}",
+				res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let res4_str = res4_parts[0];
+
+	let unit_num = res4_parts[1];
+
+	let mut res4 = res4_str.parse::<f64>().unwrap();
+	let mut res4_str = String::new();
+
+	if unit_num == SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ {
+		res4 /= FREQUENCY_MICROHERTZ.multiplier;
+		res4_str += &(res4.to_string() + " uHz");
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
+		res4 /= FREQUENCY_MILLIHERTZ.multiplier;
+		res4_str += &(res4.to_string() + " mHz");
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_HERTZ {
+		res4 /= FREQUENCY_HERTZ.multiplier;
+		res4_str += &(res4.to_string() + " Hz");
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
+		res4 /= FREQUENCY_KILOHERTZ.multiplier;
+		res4_str += &(res4.to_string() + " kHz");
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
+		res4 /= FREQUENCY_MEGAHERTZ.multiplier;
+		res4_str += &(res4.to_string() + " MHz");
+	}
+
 	if verbose > 0 {
 		println!("Response:");
 		println!("{}", res);
 	} else {
-		println!("{}", res3);
+		println!("{}", res4_str);
 	}
 
-	Ok(res3.to_string())
+	Ok(res4_str)
 }
 
-/** Set the device to use a user-defined arbitrary waveform
-preset by number, for a specific channel's output.
+/** Get the device's output frequency for a particular channel,
+in hertz (Hz).
 
-"preset" parameter:
+Return Value (Ok Result):
 ```ignore
-Arbitrary waveform preset 1 (a.k.a. preset 101):
-"1"
-
-Arbitrary waveform preset 60 (a.k.a. preset 160):
-"60"
+10,000 Hz:
+"10000"
 ```
 */
-pub fn set_waveform_preset_arbitrary(
-	mut port: &mut SerialPortType,
+pub fn get_frequency_hertz(
+	port: &mut SerialPortType,
 	chan: u64,
-	preset: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
 	if !port.mock {
@@ -849,1278 +1262,37 @@ pub fn set_waveform_preset_arbitrary(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let res: Result<String, clap::Error>;
+	let command: String;
+	let chan_out: &str;
 
-	match preset.parse::<u64>() {
-		Ok(preset) => match preset {
-			1..=60 => {
-				res = set_waveform_preset_arbitrary_inner(&mut port, chan, preset, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set arbitrary waveform\" argument (must be 1-60): {}", preset), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set arbitrary waveform\" argument (must be 1-60): {}: {}", preset, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_waveform_preset_arbitrary_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	preset: u64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_WAVEFORM_PRESET_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_WAVEFORM_PRESET_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if preset < 1 || preset > 60 {
-		return Err(Error::with_description(
-			"Unsupported waveform preset number. Must be 1-60.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		preset + 100,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting waveform preset: ch{}={}:\n{}",
-			chan, preset, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_WAVEFORM_PRESET_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's output frequency for a particular channel,
-in microhertz (µHz).
-
-"amount" parameter:
-```ignore
-"0.00" - "80000000.0"
-```
-*/
-pub fn set_frequency_microhertz(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set frequency uHz\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX, amount, SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN && amount <= SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX => {
-				res = set_frequency_microhertz_inner(&mut port, chan, amount * SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency uHz\" argument (must be {}-{}): {}", SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency uHz\" argument (must be {}-{}): {}: {}", SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_frequency_microhertz_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN * SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER || amount > SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX * SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of uHz. Must be {}-{}: {}", SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX, amount / SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_ARG_SEPARATOR,
-		SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting frequency in uHz: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's output frequency for a particular channel,
-in millihertz (mHz).
-
-"amount" parameter:
-```ignore
-"0.00" - "80000000.0"
-```
-*/
-pub fn set_frequency_millihertz(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set frequency mHz\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX, amount, SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN && amount <= SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX => {
-				res = set_frequency_millihertz_inner(&mut port, chan, amount * SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency mHz\" argument (must be {}-{}): {}", SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency mHz\" argument (must be {}-{}): {}: {}", SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_frequency_millihertz_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN * SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER || amount > SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX * SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of mHz. Must be {}-{}: {}", SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX, amount / SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_ARG_SEPARATOR,
-		SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting frequency in mHz: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's output frequency for a particular channel,
-in hertz (Hz).
-
-"amount" parameter:
-```ignore
-"0.00" - "60000000.0"
-```
-*/
-pub fn set_frequency_hertz(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set frequency Hz\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX, amount, SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN && amount <= SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX => {
-				res = set_frequency_hertz_inner(&mut port, chan, amount * SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency mHz\" argument (must be {}-{}): {}", SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency mHz\" argument (must be {}-{}): {}: {}", SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_frequency_hertz_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN * SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER || amount > SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX * SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of Hz. Must be {}-{}: {}", SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX, amount / SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_ARG_SEPARATOR,
-		SET_FREQUENCY_COMMAND_UNIT_HERTZ,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting frequency in Hz: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's output frequency for a particular channel,
-in kilohertz (kHz).
-
-"amount" parameter:
-```ignore
-"0.00000" - "60000.0"
-```
-*/
-pub fn set_frequency_kilohertz(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set frequency kHz\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX, amount, SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN && amount <= SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX => {
-				let amount_rounded = ((amount * SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER * SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER).round() / SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER).round();
-
-				res = set_frequency_kilohertz_inner(&mut port, chan, amount_rounded, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency kHz\" argument (must be {}-{}): {}", SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency kHz\" argument (must be {}-{}): {}: {}", SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_frequency_kilohertz_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN * SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER || amount > SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX * SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of kHz. Must be {}-{}: {}", SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX, amount / SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_ARG_SEPARATOR,
-		SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting frequency in kHz: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's output frequency for a particular channel,
-in megahertz (MHz).
-
-"amount" parameter:
-```ignore
-"0.00000000" - "60.0"
-```
-*/
-pub fn set_frequency_megahertz(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set frequency MHz\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX, amount, SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN && amount <= SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX => {
-				let amount_rounded =
-					((amount * SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER * (SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER / 10.0)).round() / (SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER / 10.0)).round();
-
-				res = set_frequency_megahertz_inner(&mut port, chan, amount_rounded, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency MHz\" argument (must be {}-{}): {}", SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set frequency MHz\" argument (must be {}-{}): {}: {}", SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_frequency_megahertz_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN * SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER || amount > SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX * SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of MHz. Must be {}-{}: {}", SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN, SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX, amount / SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_ARG_SEPARATOR,
-		SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting frequency in MHz: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Get the device's output frequency for a particular channel,
-in whichever unit the channel is currently set on.
-
-Supported units are:
-```ignore
-uHz (microhertz)
-mHz (millihertz)
-Hz (hertz)
-kHz (kilohertz)
-MHz (megahertz)
-```
-
-Return Value (Ok Result):
-```ignore
-10,000 uHz:
-"10000 uHz"
-
-10,000 mHz:
-"10000 mHz"
-
-10,000 Hz:
-"10000 Hz"
-
-10 kHz:
-"10 kHz"
-
-0.01 MHz:
-"0.01 MHz"
-```
-*/
-pub fn get_frequency(
-	port: &mut SerialPortType,
-	chan: u64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = GET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = GET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_FREQUENCY_ARG, COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting frequency: ch{}:\n{}", chan, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-
-	} else {	// Mock data for testing.
-		outbuf = Vec::from(&format!(":r2{}=1000,0.\r\n", chan + 2) as &str);
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	let res2_parts: Vec<&str> = res.split("=").collect();
-
-	if res2_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing equals (=): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res2 = res2_parts[1];
-
-	let res3_parts: Vec<&str> = res2.split(".").collect();
-
-	if res3_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing period (.): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res3 = res3_parts[0];
-
-	let res4_parts: Vec<&str> = res3.split(",").collect();
-
-	if res4_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing comma (,): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res4_str = res4_parts[0];
-
-	let unit_num = res4_parts[1];
-
-	let mut res4 = res4_str.parse::<f64>().unwrap();
-	let mut res4_str = String::new();
-
-	if unit_num == SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER;
-		res4_str += &(res4.to_string() + " uHz");
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER;
-		res4_str += &(res4.to_string() + " mHz");
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_HERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER;
-		res4_str += &(res4.to_string() + " Hz");
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER;
-		res4_str += &(res4.to_string() + " kHz");
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER;
-		res4_str += &(res4.to_string() + " MHz");
-	}
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	} else {
-		println!("{}", res4_str);
-	}
-
-	Ok(res4_str)
-}
-
-/** Get the device's output frequency for a particular channel,
-in hertz (Hz).
-
-Return Value (Ok Result):
-```ignore
-10,000 Hz:
-"10000"
-```
-*/
-pub fn get_frequency_hertz(
-	port: &mut SerialPortType,
-	chan: u64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = GET_FREQUENCY_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = GET_FREQUENCY_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_FREQUENCY_ARG, COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting frequency in hertz (Hz): ch{}:\n{}", chan, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_FREQUENCY_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-
-	} else {	// Mock data for testing.
-		outbuf = Vec::from(&format!(":r2{}=1000,0.\r\n", chan + 2) as &str);
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	let res2_parts: Vec<&str> = res.split("=").collect();
-
-	if res2_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing equals (=): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res2 = res2_parts[1];
-
-	let res3_parts: Vec<&str> = res2.split(".").collect();
-
-	if res3_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing period (.): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res3 = res3_parts[0];
-
-	let res4_parts: Vec<&str> = res3.split(",").collect();
-
-	if res4_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing comma (,): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res4_str = res4_parts[0];
-
-	let unit_num = res4_parts[1];
-
-	let mut res4 = res4_str.parse::<f64>().unwrap();
-
-	if unit_num == SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER * (10.0 as f64).powf(6.0);
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER * (10.0 as f64).powf(3.0);
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_HERTZ {
-		res4 /= SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER;
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
-		res4 = (((res4 / SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER) * (10.0 as f64).powf(3.0)) * 1000000.0).round() / 1000000.0;
-
-	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
-		res4 = (((res4 / SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER) * (10.0 as f64).powf(6.0)) * 1000.0).round() / 1000.0;
-	}
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	} else {
-		println!("{}", res4);
-	}
-
-	Ok(res4.to_string())
-}
-
-/** Set the device's output signal amplitude in volts, for a
-particular channel.
-
-"amount" parameter:
-```ignore
-"0.000" - "20.0"
-```
-*/
-pub fn set_amplitude(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set amplitude volts\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN, SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX, amount, SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN && amount <= SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX => {
-				let amount_rounded = ((amount * SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER * SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER).round() / SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER).round();
-
-				res = set_amplitude_inner(&mut port, chan, amount_rounded, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set amplitude volts\" argument (must be {}-{}): {}", SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN, SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set amplitude volts\" argument (must be {}-{}): {}: {}", SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN, SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_amplitude_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_AMPLITUDE_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_AMPLITUDE_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < (SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN * SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER) || amount > (SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX * SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER) {
-		return Err(Error::with_description(
-			&format!("Unsupported amount of volts. Must be {}-{}: {}", SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN, SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX, amount / SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_SET, chan_out, COMMAND_SEPARATOR, amount, COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!(
-			"\nSetting amplitude in volts: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_AMPLITUDE_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Get the device's output signal amplitude in volts for
-a particular channel.
-
-Return Value (Ok Result):
-```ignore
-5 volts:
-"5"
-```
-*/
-pub fn get_amplitude(
-	port: &mut SerialPortType,
-	chan: u64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = GET_AMPLITUDE_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = GET_AMPLITUDE_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_AMPLITUDE_ARG, COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting amplitude in volts: ch{}:\n{}", chan, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_AMPLITUDE_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	
-	} else {
-		outbuf = Vec::from(&format!(":r2{}=5000.\r\n", 5 + (chan - 1)) as &str);
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	let res2_parts: Vec<&str> = res.split("=").collect();
-
-	if res2_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing equals (=): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res2 = res2_parts[1];
-
-	let res3_parts: Vec<&str> = res2.split(".").collect();
-
-	if res3_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing period (.): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res3_str = res3_parts[0];
-
-	let res3 = res3_str.parse::<f64>().unwrap() / 1000.0;
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	} else {
-		println!("{}", res3);
-	}
-
-	Ok(res3.to_string())
-}
-
-/** Set the device's duty cycle in percent, for a
-particular channel.
-
-"amount" parameter:
-```ignore
-"0.0" - "99.9"
-```
-*/
-pub fn set_duty_cycle(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set duty cycle\" argument (must be {}-{}): {}: too many decimal places ({} max)", SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN, SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX, amount, SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN && amount <= SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX => {
-				let amount_rounded = ((amount * SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER * SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER).round() / SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER).round();
-
-				res = set_duty_cycle_inner(&mut port, chan, amount_rounded, verbose);
-			}
-
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set duty cycle\" argument (must be {}-{}): {}", SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN, SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
-
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set duty cycle\" argument (must be {}-{}): {}: {}", SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN, SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
-
-	res
-}
-
-fn set_duty_cycle_inner(
-	port: &mut SerialPortType,
-	chan: u64,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_DUTY_CYCLE_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_DUTY_CYCLE_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	if amount < (SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN * SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER) || amount > (SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX * SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER) {
-		return Err(Error::with_description(
-			&format!("Unsupported duty cycle. Must be {}-{}: {}", SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN, SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX, amount / SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER),
-			ErrorKind::InvalidValue,
-		));
-	}
+	if chan == 1 {
+		chan_out = GET_FREQUENCY_COMMAND_CH1;
+	} else if chan == 2 {
+		chan_out = GET_FREQUENCY_COMMAND_CH2;
+	} else {
+		return Err(Error::with_description(
+			"Unsupported channel number. Must be 1 or 2.",
+			ErrorKind::InvalidValue,
+		));
+	}
 
 	command = format!(
 		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_SET, chan_out, COMMAND_SEPARATOR, amount, COMMAND_END,
+		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_FREQUENCY_ARG, COMMAND_END,
 	);
 
 	if verbose > 0 {
-		println!(
-			"\nSetting duty cycle percent: ch{}={}:\n{}",
-			chan, amount, command
-		);
+		println!("\nGetting frequency in hertz (Hz): ch{}:\n{}", chan, command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_DUTY_CYCLE_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Get the device's duty cycle in percent, for a
-particular channel.
+	let mut outbuf: Vec<u8> = (0..GET_FREQUENCY_RES_LEN).collect();
 
-Return Value (Ok Result):
-```ignore
-1%:
-"1"
-```
-*/
-pub fn get_duty_cycle(
-	port: &mut SerialPortType,
-	chan: u64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
 	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = GET_DUTY_CYCLE_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = GET_DUTY_CYCLE_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_GET, chan_out, COMMAND_SEPARATOR, GET_DUTY_CYCLE_ARG, COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting duty cycle percent: ch{}:\n{}", chan, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_DUTY_CYCLE_RES_LEN).collect();
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+	} else {	// Mock data for testing.
+		outbuf = Vec::from(&format!(":r2{}=1000,0.\r\n", chan + 2) as &str);
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2151,307 +1323,331 @@ pub fn get_duty_cycle(
 		));
 	}
 
-	let res3_str = res3_parts[0];
+	let res3 = res3_parts[0];
 
-	let res3 = res3_str.parse::<f64>().unwrap() / 10.0;
+	let res4_parts: Vec<&str> = res3.split(",").collect();
+
+	if res4_parts.len() < 2 {
+		return Err(Error::with_description(
+			&format!(
+				"unexpected response from device: missing comma (,): {}",
+				res
+			),
+			ErrorKind::Io,
+		));
+	}
+
+	let res4_str = res4_parts[0];
+
+	let unit_num = res4_parts[1];
+
+	let mut res4 = res4_str.parse::<f64>().unwrap();
+
+	if unit_num == SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ {
+		res4 /= FREQUENCY_MICROHERTZ.multiplier * (10.0 as f64).powf(6.0);
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
+		res4 /= FREQUENCY_MILLIHERTZ.multiplier * (10.0 as f64).powf(3.0);
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_HERTZ {
+		res4 /= FREQUENCY_HERTZ.multiplier;
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
+		res4 = (((res4 / FREQUENCY_KILOHERTZ.multiplier) * (10.0 as f64).powf(3.0)) * 1000000.0).round() / 1000000.0;
+
+	} else if unit_num == SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
+		res4 = (((res4 / FREQUENCY_MEGAHERTZ.multiplier) * (10.0 as f64).powf(6.0)) * 1000.0).round() / 1000.0;
+	}
 
 	if verbose > 0 {
 		println!("Response:");
 		println!("{}", res);
 	} else {
-		println!("{}", res3);
+		println!("{}", res4);
 	}
 
-	Ok(res.to_string())
+	Ok(res4.to_string())
 }
 
-/** Set the device's voltage offset in volts, for a
-particular channel.
-
-"amount" parameter:
-```ignore
-"-9.99" - "9.99"
-```
-*/
-pub fn set_voltage_offset(
-	mut port: &mut SerialPortType,
-	chan: u64,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+/** Get the load connected to a channel's output. */
+fn channel_load(port: &SerialPortType, chan: u64) -> Load {
+	match chan {
+		1 | 2 => port.load[(chan - 1) as usize],
+		_ => Load::HighZ,
 	}
+}
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set voltage offset\" argument (must be -9.99-9.99): {}: too many decimal places (2 max)", amount), ErrorKind::InvalidValue));
-	}
+/** Read a channel's current waveform preset number without printing it. */
+fn read_waveform_preset(
+	port: &mut SerialPortType,
+	chan: u64,
+) -> Result<u64, clap::Error> {
+	let (_, raw) = read_register(port, &WAVEFORM_PRESET, chan, 0)?;
 
-	let res: Result<String, clap::Error>;
+	Ok(raw as u64)
+}
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= -9.99 && amount <= 9.99 => {
-				let amount_rounded = (((1000.0 + amount * 100.0) * 100.0).round() / 100.0).round();
+/** Get the crest factor of a channel's current waveform, for converting
+amplitudes to and from Vrms or dBm. The duty cycle is also read for the
+square and pulse waveforms.
+*/
+fn channel_crest_factor(
+	port: &mut SerialPortType,
+	chan: u64,
+) -> Result<f64, clap::Error> {
+	let preset = read_waveform_preset(port, chan)?;
 
-				res = set_voltage_offset_inner(&mut port, chan, amount_rounded, verbose);
-			}
+	// The square and pulse waveforms depend on the duty cycle.
+	let duty = match preset {
+		1 | 2 => {
+			let (_, raw) = read_register(port, &DUTY_CYCLE, chan, 0)?;
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set voltage offset\" argument (must be -9.99-9.99): {}", amount), ErrorKind::InvalidValue));
-			}
+			DUTY_CYCLE.decode(raw)
 		},
+		_ => 0.0,
+	};
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set voltage offset\" argument (must be -9.99-9.99): {}: {}", amount, e), ErrorKind::InvalidValue));
-		}
-	}
+	crest_factor_for_preset(preset, duty).ok_or_else(|| {
+		let waveform = match preset {
+			1 | 2 => format!("waveform preset {} with a {}% duty cycle", preset, duty),
+			_ => format!("waveform preset {}", preset),
+		};
 
-	res
+		Error::with_description(
+			&format!("can't convert to or from Vrms or dBm for {} on ch{}, because its crest factor isn't known: use Vpp instead", waveform, chan),
+			ErrorKind::ArgumentConflict,
+		)
+	})
 }
 
-fn set_voltage_offset_inner(
+/** Convert an amplitude argument, which may be in Vpp, Vrms, or dBm
+at the channel's load, into the unterminated Vpp the device expects.
+*/
+fn amplitude_to_device(
 	port: &mut SerialPortType,
 	chan: u64,
-	amount: f64,
+	amount: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = SET_VOLTAGE_OFFSET_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = SET_VOLTAGE_OFFSET_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
+	let (value, unit) = parse_amplitude(amount).map_err(|e| {
+		Error::with_description(&format!("unsupported value passed to \"set amplitude volts\" argument (must be {}-{}): {}: {}", AMPLITUDE_VOLTS.min, AMPLITUDE_VOLTS.max, amount, e.message), ErrorKind::InvalidValue)
+	})?;
 
-	if amount < 1.0 || amount > 1999.0 {
-		return Err(Error::with_description(
-			"Unsupported voltage offset. Must be -9.99-9.99.",
-			ErrorKind::InvalidValue,
-		));
-	}
+	let load = channel_load(port, chan);
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_SET, chan_out, COMMAND_SEPARATOR, amount, COMMAND_END,
-	);
+	let device_volts = match unit {
+		AmplitudeUnit::Vpp => {
+			if load == Load::HighZ {
+				return amount_in_unit(amount, Quantity::Voltage, 1.0);
+			}
 
-	if verbose > 0 {
-		println!(
-			"\nSetting voltage offset: ch{}={}:\n{}",
-			chan, amount, command
-		);
-	}
+			// Not rounded, so too many decimal places is still an error.
+			return Ok(format_amount(value / load.divider()));
+		}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_VOLTAGE_OFFSET_RES_LEN).collect();
+		_ => {
+			let crest_factor = channel_crest_factor(port, chan)?;
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+			amplitude_to_vpp(value, unit, crest_factor, load)? / load.divider()
+		}
+	};
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let multiplier = AMPLITUDE_VOLTS.multiplier;
+	let device_volts = format_amount((device_volts * multiplier).round() / multiplier);
 
 	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+		println!("\nConverted amplitude for ch{} ({:?} load): {} = {}Vpp unterminated", chan, load, amount, device_volts);
 	}
 
-	Ok(res.to_string())
+	Ok(device_volts)
 }
 
-/** Get the device's voltage offset in volts, for a
+/** Set the device's output signal amplitude in volts, for a
 particular channel.
 
-Return Value (Ok Result):
+The range and precision of the "amount" parameter are listed in
+`register::AMPLITUDE_VOLTS`.
+
+A unit can also be written after the value, for example:
 ```ignore
-5 volts:
-"5"
+"250mV" | "3.3Vpp" | "1Vrms" | "10dBm"
 ```
+
+The amplitude is the one seen by the channel's load (see the
+`load` field of `SerialPortType`), so with a 50Ω load the device
+is set to twice the requested voltage. Vrms and dBm are converted
+using the crest factor of the channel's current waveform, which
+is read from the device, and dBm needs a 50Ω load.
 */
-pub fn get_voltage_offset(
+pub fn set_amplitude(
 	port: &mut SerialPortType,
 	chan: u64,
+	amount: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-	let chan_out: &str;
-
-	if chan == 1 {
-		chan_out = GET_VOLTAGE_OFFSET_COMMAND_CH1;
-	} else if chan == 2 {
-		chan_out = GET_VOLTAGE_OFFSET_COMMAND_CH2;
-	} else {
-		return Err(Error::with_description(
-			"Unsupported channel number. Must be 1 or 2.",
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_GET,
-		chan_out,
-		COMMAND_SEPARATOR,
-		GET_VOLTAGE_OFFSET_ARG,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting voltage offset: ch{}:\n{}", chan, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_VOLTAGE_OFFSET_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	let res2_parts: Vec<&str> = res.split("=").collect();
-
-	if res2_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing equals (=): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res2 = res2_parts[1];
-
-	let res3_parts: Vec<&str> = res2.split(".").collect();
-
-	if res3_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing period (.): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res3_str = res3_parts[0];
+	set_register(port, &AMPLITUDE_VOLTS, chan, amount, verbose)
+}
 
-	let res3 = (res3_str.parse::<f64>().unwrap() - 1000.0) / 100.0;
+/** Get the device's output signal amplitude in volts for
+a particular channel.
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	} else {
-		println!("{}", res3);
-	}
+The amplitude is printed as the one seen by the channel's load, so
+with a 50Ω load it's half of what the device reports, and the
+device's raw response is returned, as with the other getters. Use
+[get_amplitude_as](fn.get_amplitude_as.html) for the value at the
+load.
 
-	Ok(res.to_string())
+Return Value (Ok Result):
+```ignore
+5 volts:
+":r25=5000.\r\n"
+```
+*/
+pub fn get_amplitude(
+	port: &mut SerialPortType,
+	chan: u64,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	get_register(port, &AMPLITUDE_VOLTS, chan, verbose)
 }
 
-/** Set the device's phase in degrees (°).
+/** Get the device's output signal amplitude for a particular channel,
+in volts peak-to-peak (Vpp), volts RMS (Vrms), or dBm, at the channel's
+load (see the `load` field of `SerialPortType`).
 
-"amount" parameter:
+Vrms and dBm are converted using the crest factor of the channel's
+current waveform, which is read from the device, and dBm needs a
+50Ω load.
+
+Return Value (Ok Result):
 ```ignore
-"0.0" - "360.0"
+1 volt RMS:
+"1"
 ```
 */
-pub fn set_phase(
-	mut port: &mut SerialPortType,
-	amount: &str,
+pub fn get_amplitude_as(
+	port: &mut SerialPortType,
+	chan: u64,
+	unit: AmplitudeUnit,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+	let vpp = get_amplitude_inner(port, chan, verbose)?;
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+	let res = match unit {
+		AmplitudeUnit::Vpp => vpp,
 
-	if amount_parts.len() > 1 && amount_parts[1].len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set phase\" argument (must be 0.0-360.0): {}: too many decimal places (1 max)", amount), ErrorKind::InvalidValue));
-	}
+		_ => {
+			let crest_factor = channel_crest_factor(port, chan)?;
+			let amount = vpp_to_amplitude(vpp, unit, crest_factor, channel_load(port, chan))?;
 
-	let res: Result<String, clap::Error>;
+			(amount * 1000.0).round() / 1000.0
+		}
+	};
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= 0.0 && amount <= 360.0 => {
-				let amount_rounded = ((amount * 10.0 * 10.0).round() / 10.0).round();
+	if verbose == 0 {
+		println!("{}", res);
+	} else {
+		println!("{:?}: {}", unit, res);
+	}
 
-				res = set_phase_inner(&mut port, amount_rounded, verbose);
-			}
+	Ok(res.to_string())
+}
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set phase\" argument (must be 0.0-360.0): {}", amount), ErrorKind::InvalidValue));
-			}
-		},
+fn get_amplitude_inner(
+	port: &mut SerialPortType,
+	chan: u64,
+	verbose: u64,
+) -> Result<f64, clap::Error> {
+	let (res, raw) = read_register(port, &AMPLITUDE_VOLTS, chan, verbose)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set phase\" argument (must be 0.0-360.0): {}: {}", amount, e), ErrorKind::InvalidValue));
-		}
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
 	}
 
-	res
+	Ok(register_value(port, &AMPLITUDE_VOLTS, chan, raw))
 }
 
-fn set_phase_inner(
+/** Set the device's duty cycle in percent, for a
+particular channel.
+
+The range and precision of the "amount" parameter are listed in
+`register::DUTY_CYCLE`.
+*/
+pub fn set_duty_cycle(
 	port: &mut SerialPortType,
-	amount: f64,
+	chan: u64,
+	amount: &str,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
+	set_register(port, &DUTY_CYCLE, chan, amount, verbose)
+}
 
-	if amount < 0.0 || amount > 3600.0 {
-		return Err(Error::with_description(
-			"Unsupported phase. Must be 0.0-360.0.",
-			ErrorKind::InvalidValue,
-		));
-	}
+/** Get the device's duty cycle in percent, for a
+particular channel.
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN, COMMAND_SET, SET_PHASE_COMMAND, COMMAND_SEPARATOR, amount, COMMAND_END,
-	);
+Return Value (Ok Result):
+```ignore
+1%:
+"1"
+```
+*/
+pub fn get_duty_cycle(
+	port: &mut SerialPortType,
+	chan: u64,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	get_register(port, &DUTY_CYCLE, chan, verbose)
+}
 
-	if verbose > 0 {
-		println!("\nSetting phase: {}:\n{}", amount, command);
-	}
+/** Set the device's voltage offset in volts, for a
+particular channel.
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_PHASE_RES_LEN).collect();
+The range and precision of the "amount" parameter are listed in
+`register::VOLTAGE_OFFSET`.
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+A unit can also be written after the value, for example:
+```ignore
+"-250mV" | "1.5V"
+```
+*/
+pub fn set_voltage_offset(
+	port: &mut SerialPortType,
+	chan: u64,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &VOLTAGE_OFFSET, chan, amount, verbose)
+}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+/** Get the device's voltage offset in volts, for a
+particular channel.
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+Return Value (Ok Result):
+```ignore
+5 volts:
+"5"
+```
+*/
+pub fn get_voltage_offset(
+	port: &mut SerialPortType,
+	chan: u64,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	get_register(port, &VOLTAGE_OFFSET, chan, verbose)
+}
 
-	Ok(res.to_string())
+/** Set the device's phase in degrees (°).
+
+The range and precision of the "amount" parameter are listed in
+`register::PHASE`.
+*/
+pub fn set_phase(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &PHASE, 0, amount, verbose)
 }
 
 /** Get the device's phase in degrees (°).
@@ -2463,78 +1659,10 @@ Return Value (Ok Result):
 ```
 */
 pub fn get_phase(
-	port: &mut SerialPortType, 
-	verbose: u64
+	port: &mut SerialPortType,
+	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: String;
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_GET,
-		GET_PHASE_COMMAND,
-		COMMAND_SEPARATOR,
-		GET_PHASE_ARG,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nGetting phase:\n{}", command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_PHASE_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	let res2_parts: Vec<&str> = res.split("=").collect();
-
-	if res2_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing equals (=): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res2 = res2_parts[1];
-
-	let res3_parts: Vec<&str> = res2.split(".").collect();
-
-	if res3_parts.len() < 2 {
-		return Err(Error::with_description(
-			&format!(
-				"unexpected response from device: missing period (.): {}",
-				res
-			),
-			ErrorKind::Io,
-		));
-	}
-
-	let res3_str = res3_parts[0];
-
-	let res3 = res3_str.parse::<f64>().unwrap() / 10.0;
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	} else {
-		println!("{}", res3);
-	}
-
-	Ok(res.to_string())
+	get_register(port, &PHASE, 0, verbose)
 }
 
 /** Set the device's tracking mode.
@@ -2704,8 +1832,7 @@ fn set_tracking_inner(
 	let mut outbuf: Vec<u8> = (0..SET_TRACKING_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2765,8 +1892,7 @@ pub fn switch_function_panel_main(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2803,8 +1929,7 @@ pub fn switch_function_panel_sys(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2841,8 +1966,7 @@ pub fn switch_function_panel_measurement(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2890,8 +2014,7 @@ pub fn start_measuring(
 	let mut outbuf: Vec<u8> = (0..SET_EXTENDED_FUNCTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2925,8 +2048,7 @@ pub fn switch_function_panel_counting(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -2959,8 +2081,7 @@ pub fn start_counting(
 	let mut outbuf: Vec<u8> = (0..SET_EXTENDED_FUNCTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3021,8 +2142,7 @@ pub fn switch_function_panel_sweep(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3065,8 +2185,7 @@ pub fn start_sweeping(
 	let mut outbuf: Vec<u8> = (0..SET_EXTENDED_FUNCTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3100,8 +2219,7 @@ pub fn switch_function_panel_pulse(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3134,8 +2252,7 @@ pub fn start_pulsing(
 	let mut outbuf: Vec<u8> = (0..SET_EXTENDED_FUNCTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3169,8 +2286,7 @@ pub fn switch_function_panel_bursting(
 	let mut outbuf: Vec<u8> = (0..SWITCH_FUNCTION_PANEL_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3203,8 +2319,7 @@ pub fn start_bursting(
 	let mut outbuf: Vec<u8> = (0..SET_EXTENDED_FUNCTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3238,138 +2353,43 @@ pub fn set_measurement_coupling_ac(
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
 	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_COUPLING_RES_LEN).collect();
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's measurement mode measure coupling to
-DC (Ext. IN).
-*/
-pub fn set_measurement_coupling_dc(
-	port: &mut SerialPortType,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let command: &'static str = SET_MEASUREMENT_COUPLING_DC;
-
-	if verbose > 0 {
-		println!("\nSetting measurement coupling to DC:\n{}", command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_COUPLING_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
-
-	let res = str::from_utf8(&outbuf).unwrap();
-
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
-
-	Ok(res.to_string())
-}
-
-/** Set the device's measurement mode measure gate time
-in seconds.
-
-"amount" parameter:
-```ignore
-"0.01" - "10.0"
-```
-*/
-pub fn set_measurement_gate_time(
-	mut port: &mut SerialPortType,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 && amount_parts[1].len() > 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set measurement gate time\" argument (must be 0.01-10.0): {}: too many decimal places (2 max)", amount), ErrorKind::InvalidValue));
-	}
-
-	let res: Result<String, clap::Error>;
-
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= 0.01 && amount <= 10.0 => {
-				let amount_rounded = ((amount * 100.0 * 100.0).round() / 100.0).round();
-
-				res = set_measurement_gate_time_inner(&mut port, amount_rounded, verbose);
-			}
+	if !port.mock {
+		port.transact(&inbuf[..], &mut outbuf[..])?;
+	}
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set measurement gate time\" argument (must be 0.01-10.0): {}", amount), ErrorKind::InvalidValue));
-			}
-		},
+	let res = str::from_utf8(&outbuf).unwrap();
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set measurement gate time\" argument (must be 0.01-10.0): {}: {}", amount, e), ErrorKind::InvalidValue));
-		}
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
 	}
 
-	res
+	Ok(res.to_string())
 }
 
-fn set_measurement_gate_time_inner(
+/** Set the device's measurement mode measure coupling to
+DC (Ext. IN).
+*/
+pub fn set_measurement_coupling_dc(
 	port: &mut SerialPortType,
-	amount: f64,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
-
-	if amount < 1.0 || amount > 1000.0 {
-		return Err(Error::with_description(
-			"Unsupported measurement gate time. Must be 0.01-10.0.",
-			ErrorKind::InvalidValue,
-		));
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SET_MEASUREMENT_GATE_TIME_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
+	let command: &'static str = SET_MEASUREMENT_COUPLING_DC;
 
 	if verbose > 0 {
-		println!("\nSetting measurement gate time: {}:\n{}", amount, command);
+		println!("\nSetting measurement coupling to DC:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_GATE_TIME_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_COUPLING_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3382,6 +2402,20 @@ fn set_measurement_gate_time_inner(
 	Ok(res.to_string())
 }
 
+/** Set the device's measurement mode measure gate time
+in seconds.
+
+The range and precision of the "amount" parameter are listed in
+`register::MEASUREMENT_GATE_TIME`.
+*/
+pub fn set_measurement_gate_time(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &MEASUREMENT_GATE_TIME, 0, amount, verbose)
+}
+
 /** Set the device's measurement mode to measure count
 frequency in hertz (Hz). */
 pub fn set_measurement_mode_count_frequency(
@@ -3406,8 +2440,7 @@ pub fn set_measurement_mode_count_frequency(
 	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_MODE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3444,8 +2477,7 @@ pub fn set_measurement_mode_counting_period(
 	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_MODE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3478,8 +2510,7 @@ pub fn set_measurement_count_clear(
 	let mut outbuf: Vec<u8> = (0..SET_MEASUREMENT_COUNT_CLEAR_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3515,8 +2546,7 @@ pub fn get_measurement_count(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_COUNT_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3583,8 +2613,7 @@ pub fn get_measurement_frequency(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_FREQUENCY_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3651,8 +2680,7 @@ pub fn get_measurement_frequency_period(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_FREQUENCY_PERIOD_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3721,8 +2749,7 @@ pub fn get_measurement_pulse_width_positive(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_PULSE_WIDTH_POSITIVE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3791,8 +2818,7 @@ pub fn get_measurement_pulse_width_negative(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_PULSE_WIDTH_NEGATIVE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3861,8 +2887,7 @@ pub fn get_measurement_period(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_PERIOD_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3931,8 +2956,7 @@ pub fn get_measurement_duty_cycle(
 	let mut outbuf: Vec<u8> = (0..GET_MEASUREMENT_DUTY_CYCLE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -3987,76 +3011,174 @@ pub fn get_measurement_duty_cycle(
 ```
 */
 pub fn set_burst_pulse_number(
-	mut port: &mut SerialPortType,
+	port: &mut SerialPortType,
 	amount: &str,
 	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &BURST_PULSE_NUMBER, 0, amount, verbose)
+}
+
+/** Perform a single burst pulse immediately. */
+pub fn start_burst_pulse_once(
+	port: &mut SerialPortType,
+	verbose: u64,
 ) -> Result<String, clap::Error> {
 	if !port.mock {
 		// Wait a bit to allow the device some time to settle.
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+	let command = START_BURST_PULSE_ONCE;
 
-	if amount_parts.len() > 1 && amount_parts[1] != "0" {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set burst pulse number\" argument (must be 1-1048575): {}: too many decimal places (0 max)", amount), ErrorKind::InvalidValue));
+	if verbose > 0 {
+		println!("\nBurst pulse once:\n{}", command);
 	}
 
-	let res: Result<String, clap::Error>;
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..START_BURST_PULSE_ONCE_RES_LEN).collect();
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= 1.0 && amount <= 1048575.0 => {
-				res = set_burst_pulse_number_inner(&mut port, amount, verbose);
-			}
+	if !port.mock {
+		port.transact(&inbuf[..], &mut outbuf[..])?;
+	}
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set burst pulse number\" argument (must be 1-1048575): {}", amount), ErrorKind::InvalidValue));
-			}
-		},
+	let res = str::from_utf8(&outbuf).unwrap();
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set burst pulse number\" argument (must be 1-1048575): {}: {}", amount, e), ErrorKind::InvalidValue));
-		}
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
 	}
 
-	res
+	Ok(res.to_string())
 }
 
-fn set_burst_pulse_number_inner(
+/** Set the modulation mode's Burst (CH1) mode to manual trigger
+(Manual Trig.).
+*/
+pub fn set_burst_mode_manual_trigger(
 	port: &mut SerialPortType,
-	amount: f64,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
 
-	if amount < 1.0 || amount > 1048575.0 {
-		return Err(Error::with_description(
-			"Unsupported burst pulse number. Must be 1-1048575.",
-			ErrorKind::InvalidValue,
-		));
+	let command = SET_BURST_MODE_MANUAL_TRIGGER;
+
+	if verbose > 0 {
+		println!("\nSetting burst mode to manual trigger:\n{}", command);
 	}
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SET_BURST_PULSE_NUMBER_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+
+	if !port.mock {
+		port.transact(&inbuf[..], &mut outbuf[..])?;
+	}
+
+	let res = str::from_utf8(&outbuf).unwrap();
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	}
+
+	Ok(res.to_string())
+}
+
+/** Set the modulation mode's Burst (CH1) mode to channel 2 trigger
+(CH2 Trig.).
+*/
+pub fn set_burst_mode_ch2_burst(
+	port: &mut SerialPortType,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	let command = SET_BURST_MODE_CH2_BURST;
+
+	if verbose > 0 {
+		println!("\nSetting burst mode to CH2 burst:\n{}", command);
+	}
+
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+
+	if !port.mock {
+		port.transact(&inbuf[..], &mut outbuf[..])?;
+	}
+
+	let res = str::from_utf8(&outbuf).unwrap();
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	}
+
+	Ok(res.to_string())
+}
+
+/** Set the modulation mode's Burst (CH1) mode to external
+trigger AC (Ext. Trig (AC)).
+*/
+pub fn set_burst_mode_external_burst_ac(
+	port: &mut SerialPortType,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	let command = SET_BURST_MODE_EXTERNAL_BURST_AC;
+
+	if verbose > 0 {
+		println!("\nSetting burst mode to external burst AC:\n{}", command);
+	}
+
+	let inbuf: Vec<u8> = command.as_bytes().to_vec();
+	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+
+	if !port.mock {
+		port.transact(&inbuf[..], &mut outbuf[..])?;
+	}
+
+	let res = str::from_utf8(&outbuf).unwrap();
+
+	if verbose > 0 {
+		println!("Response:");
+		println!("{}", res);
+	}
+
+	Ok(res.to_string())
+}
+
+/** Set the modulation mode's Burst (CH1) mode to external
+trigger DC (Ext. Trig (DC)).
+*/
+pub fn set_burst_mode_external_burst_dc(
+	port: &mut SerialPortType,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
+
+	let command = SET_BURST_MODE_EXTERNAL_BURST_DC;
 
 	if verbose > 0 {
-		println!("\nSetting burst pulse number: {}:\n{}", amount, command);
+		println!("\nSetting burst mode to external burst DC:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_BURST_PULSE_NUMBER_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4069,8 +3191,76 @@ fn set_burst_pulse_number_inner(
 	Ok(res.to_string())
 }
 
-/** Perform a single burst pulse immediately. */
-pub fn start_burst_pulse_once(
+/** Set the modulation mode sweep frequency's starting
+frequency in hertz (Hz).
+
+"amount" parameter:
+```ignore
+1,000 hertz:
+"1000"
+```
+
+A unit can also be written after the value, for example:
+```ignore
+"1.5kHz" | "2MHz"
+```
+*/
+pub fn set_sweep_starting_frequency(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &SWEEP_STARTING_FREQUENCY, 0, amount, verbose)
+}
+
+/** Set the modulation mode sweep frequency's end
+frequency in hertz (Hz).
+
+"amount" parameter:
+```ignore
+2,000 hertz:
+"2000"
+```
+
+A unit can also be written after the value, for example:
+```ignore
+"1.5kHz" | "2MHz"
+```
+*/
+pub fn set_sweep_end_frequency(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &SWEEP_END_FREQUENCY, 0, amount, verbose)
+}
+
+/** Set the modulation mode sweep frequency's sweep time
+in seconds.
+
+"amount" parameter:
+```ignore
+10 seconds:
+"10"
+```
+
+A unit can also be written after the value, for example:
+```ignore
+"2.5s" | "500ms"
+```
+*/
+pub fn set_sweep_time(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &SWEEP_TIME, 0, amount, verbose)
+}
+
+/** Set the modulation mode sweep frequency's sweep direction to normal:
+`Rise`
+*/
+pub fn set_sweep_direction_rise(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
@@ -4079,18 +3269,17 @@ pub fn start_burst_pulse_once(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let command = START_BURST_PULSE_ONCE;
+	let command = SET_SWEEP_DIRECTION_RISE;
 
 	if verbose > 0 {
-		println!("\nBurst pulse once:\n{}", command);
+		println!("\nSetting sweep direction to rise:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..START_BURST_PULSE_ONCE_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4103,10 +3292,10 @@ pub fn start_burst_pulse_once(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode's Burst (CH1) mode to manual trigger
-(Manual Trig.).
+/** Set the modulation mode sweep frequency's sweep direction to reverse:
+`Fall`
 */
-pub fn set_burst_mode_manual_trigger(
+pub fn set_sweep_direction_fall(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
@@ -4115,18 +3304,17 @@ pub fn set_burst_mode_manual_trigger(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let command = SET_BURST_MODE_MANUAL_TRIGGER;
+	let command = SET_SWEEP_DIRECTION_FALL;
 
 	if verbose > 0 {
-		println!("\nSetting burst mode to manual trigger:\n{}", command);
+		println!("\nSetting sweep direction to fall:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4139,10 +3327,10 @@ pub fn set_burst_mode_manual_trigger(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode's Burst (CH1) mode to channel 2 trigger
-(CH2 Trig.).
+/** Set the modulation mode sweep frequency's sweep direction to round-trip:
+`Rise & Fall`
 */
-pub fn set_burst_mode_ch2_burst(
+pub fn set_sweep_direction_rise_fall(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
@@ -4151,18 +3339,17 @@ pub fn set_burst_mode_ch2_burst(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let command = SET_BURST_MODE_CH2_BURST;
+	let command = SET_SWEEP_DIRECTION_RISE_FALL;
 
 	if verbose > 0 {
-		println!("\nSetting burst mode to CH2 burst:\n{}", command);
+		println!("\nSetting sweep direction to rise and fall:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4175,10 +3362,8 @@ pub fn set_burst_mode_ch2_burst(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode's Burst (CH1) mode to external
-trigger AC (Ext. Trig (AC)).
-*/
-pub fn set_burst_mode_external_burst_ac(
+/** Set the modulation mode sweep frequency's mode to linear. */
+pub fn set_sweep_mode_linear(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
@@ -4187,18 +3372,17 @@ pub fn set_burst_mode_external_burst_ac(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let command = SET_BURST_MODE_EXTERNAL_BURST_AC;
+	let command = SET_SWEEP_MODE_LINEAR;
 
 	if verbose > 0 {
-		println!("\nSetting burst mode to external burst AC:\n{}", command);
+		println!("\nSetting sweep mode to linear:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_SWEEP_MODE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4211,10 +3395,8 @@ pub fn set_burst_mode_external_burst_ac(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode's Burst (CH1) mode to external
-trigger DC (Ext. Trig (DC)).
-*/
-pub fn set_burst_mode_external_burst_dc(
+/** Set the modulation mode sweep frequency's mode to logarithm. */
+pub fn set_sweep_mode_logarithm(
 	port: &mut SerialPortType,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
@@ -4223,18 +3405,17 @@ pub fn set_burst_mode_external_burst_dc(
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let command = SET_BURST_MODE_EXTERNAL_BURST_DC;
+	let command = SET_SWEEP_MODE_LOGARITHM;
 
 	if verbose > 0 {
-		println!("\nSetting burst mode to external burst DC:\n{}", command);
+		println!("\nSetting sweep mode to logarithm:\n{}", command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_BURST_MODE_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_SWEEP_MODE_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4247,113 +3428,142 @@ pub fn set_burst_mode_external_burst_dc(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode sweep frequency's starting
-frequency in hertz (Hz).
+/** Set the modulation mode pulse generator's pulse width.
 
-"amount" parameter:
+It is in nanosecond units unless, the microseconds parameter is true.
+
+IMPORTANT NOTE: There seems to be no option on the device's physical
+controls to switch between nanosecond and microsecond units, but if
+you specify a value in microseconds, the device will switch to
+microsecond mode, and all future values to this command entered through
+the physical device interface will be interpreted as microsecond units
+until you turn off the device, or set a nanosecond value using this
+serial-interface-only command. If you save the device state while in
+microseconds mode, this could be a problem, because then you need to
+use this serial program to get back to the default nanoseconds mode.
+
+"amount" parameter when "microseconds" parameter is false:
 ```ignore
-1,000 hertz:
+1,000 nanoseconds:
 "1000"
 ```
+
+"amount" parameter when "microseconds" parameter is true:
+```ignore
+2,000 microseconds:
+"2000"
+```
+
+A unit can also be written after the value, which overrides the
+"microseconds" parameter, for example:
+```ignore
+"800ns" | "2.5ms"
+```
 */
-pub fn set_sweep_starting_frequency(
-	mut port: &mut SerialPortType,
+pub fn set_pulse_width(
+	port: &mut SerialPortType,
 	amount: &str,
+	microseconds: bool,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+	let (amount, microseconds) = pulse_time_to_protocol(amount, microseconds, PULSE_WIDTH_NANOSECONDS.max)?;
 
-	if amount_parts.len() > 1 && amount_parts[1].len() > 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set sweep starting frequency\" argument (must be 0.01-60000000.0): {}: too many decimal places (2 max)", amount), ErrorKind::InvalidValue));
-	}
+	let reg = if microseconds { &PULSE_WIDTH_MICROSECONDS } else { &PULSE_WIDTH_NANOSECONDS };
 
-	let res: Result<String, clap::Error>;
+	set_register(port, reg, 0, &amount, verbose)
+}
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= 0.01 && amount <= 60000000.0 => {
-				let amount_rounded = ((amount * 100.0 * 10.0).round() / 10.0).round();
+/** Set the modulation mode pulse generator's pulse period.
 
-				res = set_sweep_starting_frequency_inner(&mut port, amount_rounded, verbose);
-			}
+It is in nanosecond units unless the microseconds parameter is true.
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep starting frequency\" argument (must be 0.01-60000000.0): {}", amount), ErrorKind::InvalidValue));
-			}
-		},
+IMPORTANT NOTE: There seems to be no option on the device's physical
+controls to switch between nanosecond and microsecond units, but if
+you specify a value in microseconds, the device will switch to
+microsecond mode, and all future values to this command entered through
+the physical device interface will be interpreted as microsecond units
+until you turn off the device, or set a nanosecond value using this
+serial-interface-only command. If you save the device state while in
+microseconds mode, this could be a problem, because then you need to
+use this serial program to get back to the default nanoseconds mode.
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep starting frequency\" argument (must be 0.01-60000000.0): {}: {}", amount, e), ErrorKind::InvalidValue));
-		}
-	}
+"amount" parameter when "microseconds" parameter is false:
+```ignore
+1,000 nanoseconds:
+"1000"
+```
 
-	res
-}
+"amount" parameter when "microseconds" parameter is true:
+```ignore
+2,000 microseconds:
+"2000"
+```
 
-fn set_sweep_starting_frequency_inner(
+A unit can also be written after the value, which overrides the
+"microseconds" parameter, for example:
+```ignore
+"800ns" | "2.5ms"
+```
+*/
+pub fn set_pulse_period(
 	port: &mut SerialPortType,
-	amount: f64,
+	amount: &str,
+	microseconds: bool,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let command: String;
-
-	if amount < 1.0 || amount > 6000000000.0 {
-		return Err(Error::with_description(
-			"Unsupported sweep starting frequency. Must be 0.01-60000000.0.",
-			ErrorKind::InvalidValue,
-		));
-	}
+	let (amount, microseconds) = pulse_time_to_protocol(amount, microseconds, PULSE_PERIOD_NANOSECONDS.max)?;
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SET_SWEEP_STARTING_FREQUENCY_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
+	let reg = if microseconds { &PULSE_PERIOD_MICROSECONDS } else { &PULSE_PERIOD_NANOSECONDS };
 
-	if verbose > 0 {
-		println!(
-			"\nSetting sweep starting frequency: {}:\n{}",
-			amount, command
-		);
-	}
+	set_register(port, reg, 0, &amount, verbose)
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_STARTING_FREQUENCY_RES_LEN).collect();
+/** Set the modulation mode pulse generator's pulse offset in percent (%).
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+"amount" parameter:
+```ignore
+50 percent:
+"50"
+```
+*/
+pub fn set_pulse_offset(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &PULSE_OFFSET, 0, amount, verbose)
+}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+/** Set the modulation mode pulse generator's pulse amplitude in volts (V).
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+"amount" parameter:
+```ignore
+5 volts:
+"5"
+```
 
-	Ok(res.to_string())
+A unit can also be written after the value, for example:
+```ignore
+"250mV" | "3.3Vpp"
+```
+*/
+pub fn set_pulse_amplitude(
+	port: &mut SerialPortType,
+	amount: &str,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	set_register(port, &PULSE_AMPLITUDE, 0, amount, verbose)
 }
 
-/** Set the modulation mode sweep frequency's end
-frequency in hertz (Hz).
+/** Save all current values on the device as a numbered preset.
 
 "amount" parameter:
 ```ignore
-2,000 hertz:
-"2000"
+save as preset 0:
+"0"
 ```
 */
-pub fn set_sweep_end_frequency(
+pub fn save_preset(
 	mut port: &mut SerialPortType,
 	amount: &str,
 	verbose: u64,
@@ -4365,43 +3575,54 @@ pub fn set_sweep_end_frequency(
 
 	let amount_parts: Vec<&str> = amount.split(".").collect();
 
-	if amount_parts.len() > 1 && amount_parts[1].len() > 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set sweep termination frequency\" argument (must be 0.01-60000000.0): {}: too many decimal places (2 max)", amount), ErrorKind::InvalidValue));
+	if amount_parts.len() > 1 {
+		return Err(Error::with_description(&format!("unsupported value passed to \"save preset\" argument (must be {}-{}): {}: too many decimal places (0 max)", SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
 	let res: Result<String, clap::Error>;
 
 	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= 0.01 && amount <= 60000000.0 => {
-				let amount_rounded = ((amount * 100.0 * 10.0).round() / 10.0).round();
-
-				res = set_sweep_end_frequency_inner(&mut port, amount_rounded, verbose);
-			}
+		Ok(amount) => {
+			match amount {
+				_y if amount >= SAVE_PRESET_ARG_NUM_MIN
+					&& amount <= SAVE_PRESET_ARG_NUM_MAX =>
+				{
+					res = save_preset_inner(&mut port, amount, verbose);
+				}
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep termination frequency\" argument (must be 0.01-60000000.0): {}", amount), ErrorKind::InvalidValue));
+				_ => {
+					res = Err(Error::with_description(&format!("unsupported value passed to \"save preset\" argument (must be {}-{}): {}", SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+				}
 			}
-		},
+		}
 
 		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep termination frequency\" argument (must be 0.01-60000000.0): {}: {}", amount, e), ErrorKind::InvalidValue));
+			res = Err(Error::with_description(
+				&format!(
+					"unsupported value passed to \"save preset\" argument (must be {}-{}): {}: {}",
+					SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount, e
+				),
+				ErrorKind::InvalidValue,
+			));
 		}
 	}
 
 	res
 }
 
-fn set_sweep_end_frequency_inner(
+fn save_preset_inner(
 	port: &mut SerialPortType,
 	amount: f64,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
 	let command: String;
 
-	if amount < 1.0 || amount > 6000000000.0 {
+	if amount < SAVE_PRESET_ARG_NUM_MIN || amount > SAVE_PRESET_ARG_NUM_MAX {
 		return Err(Error::with_description(
-			"Unsupported sweep termination frequency. Must be 0.01-60000000.0.",
+			&format!(
+				"Unsupported preset number. Must be {}-{}.",
+				SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX
+			),
 			ErrorKind::InvalidValue,
 		));
 	}
@@ -4410,25 +3631,21 @@ fn set_sweep_end_frequency_inner(
 		"{}{}{}{}{}{}",
 		COMMAND_BEGIN,
 		COMMAND_SET,
-		SET_SWEEP_END_FREQUENCY_COMMAND,
+		SAVE_PRESET_COMMAND,
 		COMMAND_SEPARATOR,
 		amount,
 		COMMAND_END,
 	);
 
 	if verbose > 0 {
-		println!(
-			"\nSetting sweep termination frequency: {}:\n{}",
-			amount, command
-		);
+		println!("\nSaving values as preset number: {}:\n{}", amount, command);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_END_FREQUENCY_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SAVE_PRESET_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4441,16 +3658,15 @@ fn set_sweep_end_frequency_inner(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode sweep frequency's sweep time
-in seconds.
+/** Load all values for the device from a numbered preset.
 
 "amount" parameter:
 ```ignore
-10 seconds:
-"10"
+save as preset 0:
+"0"
 ```
 */
-pub fn set_sweep_time(
+pub fn load_preset(
 	mut port: &mut SerialPortType,
 	amount: &str,
 	verbose: u64,
@@ -4462,43 +3678,46 @@ pub fn set_sweep_time(
 
 	let amount_parts: Vec<&str> = amount.split(".").collect();
 
-	if amount_parts.len() > 1 && amount_parts[1].len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set sweep time\" argument (must be 0.1-999.9): {}: too many decimal places (1 max)", amount), ErrorKind::InvalidValue));
+	if amount_parts.len() > 1 {
+		return Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}: too many decimal places (0 max)", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
 	let res: Result<String, clap::Error>;
 
 	match amount.parse::<f64>() {
 		Ok(amount) => match amount {
-			_y if amount >= 0.1 && amount <= 999.9 => {
-				let amount_rounded = (amount * 10.0).round();
-
-				res = set_sweep_time_inner(&mut port, amount_rounded, verbose);
+			_y if amount >= LOAD_PRESET_ARG_NUM_MIN
+				&& amount <= LOAD_PRESET_ARG_NUM_MAX =>
+			{
+				res = load_preset_inner(&mut port, amount, verbose);
 			}
 
 			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep time\" argument (must be 0.1-999.9): {}", amount), ErrorKind::InvalidValue));
+				res = Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 			}
 		},
 
 		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set sweep time\" argument (must be 0.1-999.9): {}: {}", amount, e), ErrorKind::InvalidValue));
+			res = Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}: {}", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
 		}
 	}
 
 	res
 }
 
-fn set_sweep_time_inner(
+fn load_preset_inner(
 	port: &mut SerialPortType,
 	amount: f64,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
 	let command: String;
 
-	if amount < 1.0 || amount > 6000000000.0 {
+	if amount < LOAD_PRESET_ARG_NUM_MIN || amount > LOAD_PRESET_ARG_NUM_MAX {
 		return Err(Error::with_description(
-			"Unsupported sweep time. Must be 0.1-999.9.",
+			&format!(
+				"Unsupported preset number. Must be {}-{}.",
+				LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX
+			),
 			ErrorKind::InvalidValue,
 		));
 	}
@@ -4507,22 +3726,24 @@ fn set_sweep_time_inner(
 		"{}{}{}{}{}{}",
 		COMMAND_BEGIN,
 		COMMAND_SET,
-		SET_SWEEP_TIME_COMMAND,
+		LOAD_PRESET_COMMAND,
 		COMMAND_SEPARATOR,
 		amount,
 		COMMAND_END,
 	);
 
 	if verbose > 0 {
-		println!("\nSetting sweep time: {}:\n{}", amount, command);
+		println!(
+			"\nRecalling values from preset number: {}:\n{}",
+			amount, command
+		);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_TIME_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..LOAD_PRESET_RES_LEN).collect();
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		port.transact(&inbuf[..], &mut outbuf[..])?;
 	}
 
 	let res = str::from_utf8(&outbuf).unwrap();
@@ -4535,811 +3756,1335 @@ fn set_sweep_time_inner(
 	Ok(res.to_string())
 }
 
-/** Set the modulation mode sweep frequency's sweep direction to normal:
-`Rise`
+/** Convert the contents of a WaveCAD (.wav) file, a regular .wav
+audio file, an oscilloscope capture, or a circuit simulator's output
+to a wave for the device. See [wav](../wav/index.html),
+[scope](../scope/index.html), and [spice](../spice/index.html) for
+the formats which are read. Anything else is read as a WaveCAD file.
+
+Audio is scaled so its full range fills the device's, and captures
+and simulations so their lowest and highest voltages do. Pick out
+part of a longer recording with the "resample" parameter.
+
+"data" parameter, the bytes of the file.
+
+"channel" parameter, which channel to use from a multichannel
+audio file or oscilloscope capture, or which trace to use from a
+circuit simulator's output, counting from 1, or 0 to mix all the
+channels together. It's ignored for WaveCAD files:
+```ignore
+first channel:
+1
+```
+
+"resample" parameter, which part of the file to use, and how to
+resample it:
+```ignore
+use one detected period, resampled with a cubic spline:
+&ResampleOptions { interpolation: Some(Interpolation::Cubic), window: None, single_period: true }
+```
+
+"convert" parameter, how to scale the wave to the device's range:
+```ignore
+remove any DC offset and normalize to 90% of full scale, with dither:
+&ConvertOptions { normalize: Some(0.9), remove_dc: true, gain_db: 0.0, dither: true, fail_on_clip: false }
+```
+
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
 */
-pub fn set_sweep_direction_rise(
-	port: &mut SerialPortType,
+pub fn wav_to_wave_data(
+	data: &[u8],
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let mut samples = if is_riff_wave(data) {
+		let wav = parse_wav(data)?;
+
+		if verbose > 0 {
+			println!(
+				"\nReading .wav audio: {} channels, {} Hz, {}-bit {}, {} samples per channel",
+				wav.channels,
+				wav.sample_rate,
+				wav.bits_per_sample,
+				if wav.format == SampleFormat::Float { "float" } else { "PCM" },
+				wav.frames()
+			);
+		}
+
+		prepare_wave(&wav.channel(channel)?, Some(wav.sample_rate as f64), resample, ARBITRARY_WAVE_LEN)?
+	} else if SpiceFormat::detect(data).is_some() {
+		let output = parse_spice(data)?;
+
+		if verbose > 0 {
+			println!(
+				"\nReading {} simulator output: traces {}, {} points over {} s",
+				output.format,
+				output.traces.iter().enumerate().map(|(i, name)| format!("{} {}", i + 1, name)).collect::<Vec<String>>().join(", "),
+				output.len(),
+				format_amount(output.duration())
+			);
+		}
+
+		let (samples, low, high) = fit_volts(&spice_samples(&output, channel, resample, ARBITRARY_WAVE_LEN)?);
+
+		if verbose > 0 {
+			println!(
+				"Simulated from {:.3} to {:.3}: play it back with an amplitude of {:.3} V and an offset of {:.3} V for the same levels",
+				low,
+				high,
+				high - low,
+				(high + low) / 2.0
+			);
+		}
+
+		samples
+	} else if let Some(vendor) = str::from_utf8(data).ok().and_then(ScopeVendor::detect) {
+		let capture = parse_scope_csv(str::from_utf8(data).unwrap_or_default(), Some(vendor))?;
+
+		if verbose > 0 {
+			println!(
+				"\nReading {} oscilloscope capture: channels {}, {} samples per channel, {} samples per second",
+				capture.vendor,
+				capture.channels.join(", "),
+				capture.len(),
+				capture.sample_rate().map_or("unknown".to_string(), format_amount)
+			);
+		}
+
+		let (samples, low, high) = fit_volts(&capture_samples(&capture, channel, resample, ARBITRARY_WAVE_LEN)?);
+
+		if verbose > 0 {
+			println!(
+				"Captured from {:.3} V to {:.3} V: play it back with an amplitude of {:.3} V and an offset of {:.3} V for the same levels",
+				low,
+				high,
+				high - low,
+				(high + low) / 2.0
+			);
+		}
+
+		samples
+	} else {
+		let mut buf = data.to_vec();
+		buf.resize(WAVECAD_LEN, 0);
+
+		let mut wavecad = [0i16; ARBITRARY_WAVE_LEN];
+
+		LittleEndian::read_i16_into(&buf[..WAVECAD_LEN], &mut wavecad);
+
+		let samples: Vec<f64> = wavecad.iter().map(|val| *val as f64 / HALF_SCALE).collect();
+
+		prepare_wave(&samples, None, resample, ARBITRARY_WAVE_LEN)?
+	};
+
+	samples.resize(ARBITRARY_WAVE_LEN, 0.0);
+
+	let (values, report) = convert_to_device(&samples, convert)?;
+
+	Ok((WaveData::new(&values)?, report))
+}
+
+/** Convert a WaveCAD (.wav) file, or a regular .wav audio file,
+to the device's arbitrary waveform text (.txt) file format. See
+[wav_to_wave_data](fn.wav_to_wave_data.html) for the supported
+formats, and the "channel", "resample", and "convert" parameters.
+
+"path" parameter:
+```ignore
+some-wav-file-to-convert.wav:
+"some-wav-file-to-convert.wav"
+```
+
+"out_path" parameter, where to save the text file, or `None` to save
+it in the same directory as the input file, with a .txt extension:
+```ignore
+None | Some("some-other-name.txt")
+```
+
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn wav_to_txt(
+	path: &str,
+	out_path: Option<&str>,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	if path == "" {
+		return Err(Error::with_description(
+			&format!(
+				"unsupported path passed as \"wav_to_txt\" argument (must not be blank): {}",
+				path
+			),
+			ErrorKind::InvalidValue,
+		));
 	}
 
-	let command = SET_SWEEP_DIRECTION_RISE;
+	let new_path = out_path.map_or_else(|| change_file_extension(path, ".txt"), |out| out.to_string());
+
+	let data = fs::read(path).map_err(|e| {
+		Error::with_description(
+			&format!("failed reading file: {}: {}", path, e),
+			ErrorKind::Io,
+		)
+	})?;
+
+	check_capture(path, &data)?;
+
+	let (wave, report) = wav_to_wave_data(&data, channel, resample, convert, verbose)?;
+
+	fs::write(&new_path, wave.to_txt_string()).map_err(|e| {
+		Error::with_description(
+			&format!("failed writing to file: {}: {}", new_path, e),
+			ErrorKind::Io,
+		)
+	})?;
 
 	if verbose > 0 {
-		println!("\nSetting sweep direction to rise:\n{}", command);
+		println!(
+			"\nWaveCAD file converted to text and saved: {} -> {}",
+			path, new_path
+		);
 	}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
+	Ok((wave, report))
+}
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+/** Convert the device's arbitrary waveform text (.txt) file format
+to a WaveCAD (.wav) file.
+
+Outputs the resulting binary file to stdout if the "output_binary"
+parameter is set to true.
+
+"path" parameter:
+```ignore
+some-txt-file-to-convert.txt:
+"some-txt-file-to-convert.txt"
+```
+
+"out_path" parameter, where to save the WaveCAD file, or `None` to
+save it in the same directory as the input file, with a .wav extension:
+```ignore
+None | Some("some-other-name.wav")
+```
+
+Returns the wave, which can give the WaveCAD bytes with
+[to_wavecad_bytes](../wave/struct.WaveData.html#method.to_wavecad_bytes).
+*/
+pub fn txt_to_wav(path: &str, out_path: Option<&str>, output_binary: bool, verbose: u64) -> Result<WaveData, clap::Error> {
+	if path == "" {
+		return Err(Error::with_description(
+			&format!(
+				"unsupported path passed as \"txt_to_wav\" argument (must not be blank): {}",
+				path
+			),
+			ErrorKind::InvalidValue,
+		));
 	}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	if output_binary && verbose > 0 {
+		return Err(Error::with_description("failed outputting binary to stdout: you can't do this when the verbosity level is greater than 0", ErrorKind::InvalidValue));
+	}
+
+	let new_path = out_path.map_or_else(|| change_file_extension(path, ".wav"), |out| out.to_string());
+
+	let file = fs::File::open(path).map_err(|e| {
+		Error::with_description(
+			&format!("failed opening file: {}: {}", path, e),
+			ErrorKind::Io,
+		)
+	})?;
+
+	let wave = WaveData::from_txt(file)?;
+
+	// Save the new WaveCAD file.
+	let outfile = fs::File::create(&new_path).map_err(|e| {
+		Error::with_description(
+			&format!("failed creating file: {}: {}", new_path, e),
+			ErrorKind::Io,
+		)
+	})?;
+
+	wave.to_wavecad(outfile)?;
+
+	if output_binary {
+		let mut out = std::io::stdout();
+		wave.to_wavecad(&mut out)?;
+		out.flush()?;
+	}
 
 	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+		println!(
+			"\nText file converted to WaveCAD and saved: {} -> {}",
+			path, new_path
+		);
 	}
 
-	Ok(res.to_string())
+	Ok(wave)
 }
 
-/** Set the modulation mode sweep frequency's sweep direction to reverse:
-`Fall`
-*/
-pub fn set_sweep_direction_fall(
-	port: &mut SerialPortType,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+/** Check that a file ending in .csv is an oscilloscope capture, and
+one ending in .raw is an ngspice raw file, in a layout
+[wav_to_wave_data](fn.wav_to_wave_data.html) knows, so it isn't read
+as a WaveCAD file instead. */
+fn check_capture(path: &str, data: &[u8]) -> Result<(), clap::Error> {
+	if path.to_lowercase().ends_with(".raw") && SpiceFormat::detect(data).is_none() {
+		return Err(Error::with_description(
+			&format!("invalid circuit simulator output: {}: it isn't an ngspice raw file (export LTspice's waves as text instead)", path),
+			ErrorKind::InvalidValue,
+		));
 	}
 
-	let command = SET_SWEEP_DIRECTION_FALL;
+	if !path.to_lowercase().ends_with(".csv") {
+		return Ok(());
+	}
 
-	if verbose > 0 {
-		println!("\nSetting sweep direction to fall:\n{}", command);
+	match str::from_utf8(data).ok().and_then(ScopeVendor::detect) {
+		Some(_vendor) => Ok(()),
+		None => Err(Error::with_description(
+			&format!("invalid oscilloscope capture: {}: the layout isn't one of Rigol's, Siglent's, or Keysight's", path),
+			ErrorKind::InvalidValue,
+		)),
 	}
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
+/** Read a wave from a file, as it would be uploaded to the device.
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+Files ending in .wav are read as WaveCAD files or regular .wav audio
+files, files ending in .csv as oscilloscope captures, and ngspice raw
+files and LTspice text exports as circuit simulator output, as with
+[wav_to_wave_data](fn.wav_to_wave_data.html), which explains the
+"channel", "resample", and "convert" parameters. Other
+files are read in the device's text format, and resampled if
+"resample" is active.
+*/
+pub fn load_wave_file(
+	path: &str,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<WaveData, clap::Error> {
+	let data = fs::read(path).map_err(|e| {
+		Error::with_description(
+			&format!("failed reading file: {}: {}", path, e),
+			ErrorKind::Io,
+		)
+	})?;
+
+	if path.to_lowercase().ends_with(".wav")
+		|| path.to_lowercase().ends_with(".csv")
+		|| path.to_lowercase().ends_with(".raw")
+		|| SpiceFormat::detect(&data).is_some()
+	{
+		check_capture(path, &data)?;
+
+		return Ok(wav_to_wave_data(&data, channel, resample, convert, verbose)?.0);
 	}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let text = str::from_utf8(&data).map_err(|e| {
+		Error::with_description(
+			&format!("failed reading file: {}: {}", path, e),
+			ErrorKind::InvalidValue,
+		)
+	})?;
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+	if resample.is_active() {
+		Ok(resample_wave_data(text, resample, convert.fail_on_clip)?.0)
+	} else {
+		WaveData::from_txt_str(text)
 	}
+}
 
-	Ok(res.to_string())
+/** Read a wave from a file, and print an analysis of its spectrum
+and quality, as chosen in "analysis". See
+[load_wave_file](fn.load_wave_file.html) for the other parameters,
+and [analyze_arbitrary_wave](fn.analyze_arbitrary_wave.html) for the
+"analysis" parameter.
+
+Returns the analysis.
+*/
+pub fn analyze_wave_file(
+	path: &str,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	analysis: &AnalysisOptions,
+	verbose: u64,
+) -> Result<WaveAnalysis, clap::Error> {
+	let wave = load_wave_file(path, channel, resample, convert, verbose)?;
+	let res = analyze_wave(&wave, analysis.harmonics);
+
+	print!("{}", analysis.format.render(&res));
+
+	Ok(res)
 }
 
-/** Set the modulation mode sweep frequency's sweep direction to round-trip:
-`Rise & Fall`
+/** Print a plot of a wave, and optionally of a second wave over it
+to compare them, in the terminal. See [plot](../plot/index.html) for
+how it's drawn.
+
+"port" parameter, the device to read the wave to compare with from,
+if it's in one of the slots, or `None`:
+```ignore
+None | Some(&mut port)
+```
+
+"compare" parameter, a file or slot to compare with, read as in
+[wave_op](fn.wave_op.html), which explains the "channel" and
+"resample" parameters:
+```ignore
+Some(&Source::parse("slot:5")?) | Some(&Source::parse("expected.txt")?) | None
+```
+
+"plot" parameter, the plot's size and style:
+```ignore
+&PlotOptions { width: 80, height: 16, ..PlotOptions::default() }
+```
+
+Returns the plot.
 */
-pub fn set_sweep_direction_rise_fall(
-	port: &mut SerialPortType,
+pub fn plot_wave(
+	port: Option<&mut SerialPortType>,
+	wave: &WaveData,
+	compare: Option<&Source>,
+	plot: &PlotOptions,
+	channel: u16,
+	resample: &ResampleOptions,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+	let compare = match compare {
+		Some(source) => Some(load_source(port, source, channel, resample, verbose)?),
+		None => None,
+	};
 
-	let command = SET_SWEEP_DIRECTION_RISE_FALL;
+	let res = crate::plot::plot(wave, compare.as_ref(), plot);
+
+	print!("{}", res);
+
+	Ok(res)
+}
+
+/** Read a wave from a file, as it would be uploaded to the device, and
+print a plot of it. See [load_wave_file](fn.load_wave_file.html) and
+[plot_wave](fn.plot_wave.html) for the parameters. The wave to compare
+with can't be in one of the slots, since there's no device; use
+[plot_wave](fn.plot_wave.html) for that.
+
+Returns the plot.
+*/
+pub fn plot_wave_file(
+	path: &str,
+	compare: Option<&Source>,
+	plot: &PlotOptions,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let wave = load_wave_file(path, channel, resample, convert, verbose)?;
+
+	plot_wave(None, &wave, compare, plot, channel, resample, verbose)
+}
+
+/** Draw a wave as an image, and save it as an SVG or PNG file. See
+[render](../render/index.html) for what's drawn.
+
+"out_path" parameter, where to save the image, in the format given by
+the end of its name:
+```ignore
+"wave.svg" | "wave.png"
+```
+
+"render" parameter, the image's size, and the output's amplitude,
+offset, and frequency to label the axes in volts and time with:
+```ignore
+&RenderOptions { amplitude: Some(5.0), frequency: Some(1000.0), spectrum: true, ..RenderOptions::default() }
+```
+
+Returns the image file's contents.
+*/
+pub fn render_wave(
+	wave: &WaveData,
+	out_path: &str,
+	render: &RenderOptions,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	let format = RenderFormat::from_path(out_path)?;
+	let res = crate::render::render(wave, render, format)?;
+
+	fs::write(out_path, &res).map_err(|e| {
+		Error::with_description(
+			&format!("failed writing to file: {}: {}", out_path, e),
+			ErrorKind::Io,
+		)
+	})?;
 
 	if verbose > 0 {
-		println!("\nSetting sweep direction to rise and fall:\n{}", command);
+		println!("Wave image saved: {} ({}x{})", out_path, render.width, render.height);
 	}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_DIRECTION_RES_LEN).collect();
+	Ok(res)
+}
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+/** Read a wave from a file, as it would be uploaded to the device, and
+save an image of it. See [load_wave_file](fn.load_wave_file.html) and
+[render_wave](fn.render_wave.html) for the parameters.
+
+Returns the image file's contents.
+*/
+pub fn render_wave_file(
+	path: &str,
+	out_path: &str,
+	render: &RenderOptions,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	// Check the image's file name before doing any work.
+	RenderFormat::from_path(out_path)?;
+
+	let wave = load_wave_file(path, channel, resample, convert, verbose)?;
+
+	render_wave(&wave, out_path, render, verbose)
+}
+
+/** Save a wave in another generator's file format. See
+[export](../export/index.html) for the formats.
+
+"out_path" parameter, where to save the file, in the format given by
+the end of its name unless "export" chooses one:
+```ignore
+"wave.csv" | "wave.arb" | "wave.raf"
+```
+
+"export" parameter, the format, and the output's frequency, amplitude,
+and offset, which set the times, voltages, and levels in the file:
+```ignore
+&ExportOptions { format: Some(ExportFormat::Siglent), frequency: 1000.0, amplitude: 5.0, offset: 0.0 }
+```
+
+Returns the file's contents.
+*/
+pub fn export_wave(
+	wave: &WaveData,
+	out_path: &str,
+	export: &ExportOptions,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	export.validate()?;
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let format = export.format_for(out_path)?;
+	let res = crate::export::export(wave, format, export);
+
+	fs::write(out_path, &res).map_err(|e| {
+		Error::with_description(
+			&format!("failed writing to file: {}: {}", out_path, e),
+			ErrorKind::Io,
+		)
+	})?;
 
 	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+		println!("Wave exported as a {} file: {}", format, out_path);
 	}
 
-	Ok(res.to_string())
+	Ok(res)
 }
 
-/** Set the modulation mode sweep frequency's mode to linear. */
-pub fn set_sweep_mode_linear(
-	port: &mut SerialPortType,
+/** Read a wave from a file, as it would be uploaded to the device, and
+save it in another generator's file format. See
+[load_wave_file](fn.load_wave_file.html) and
+[export_wave](fn.export_wave.html) for the parameters.
+
+Returns the exported file's contents.
+*/
+pub fn export_wave_file(
+	path: &str,
+	out_path: &str,
+	export: &ExportOptions,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+) -> Result<Vec<u8>, clap::Error> {
+	// Check the options before doing any work.
+	export.validate()?;
+	export.format_for(out_path)?;
 
-	let command = SET_SWEEP_MODE_LINEAR;
+	let wave = load_wave_file(path, channel, resample, convert, verbose)?;
 
-	if verbose > 0 {
-		println!("\nSetting sweep mode to linear:\n{}", command);
-	}
+	export_wave(&wave, out_path, export, verbose)
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_MODE_RES_LEN).collect();
+/** Generate a common arbitrary wave from a spec, such as a sine with
+harmonics or a Gaussian pulse, in the device's text format. See
+[synth](../synth/index.html) for the shapes and their parameters.
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+"spec" parameter:
+```ignore
+A Gaussian pulse which is 5% of the period wide:
+"gauss sigma=0.05"
+```
 
-	let res = str::from_utf8(&outbuf).unwrap();
+"out_path" parameter, where to save the wave, or `None` to print it
+to stdout in the text format. It's saved as a WaveCAD file if the
+name ends in .wav, or as text otherwise:
+```ignore
+None | Some("gauss.txt") | Some("gauss.wav")
+```
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn gen_wave(
+	spec: &str,
+	out_path: Option<&str>,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let (wave, report) = generate_wave(&WaveSpec::parse(spec)?, convert)?;
 
-	Ok(res.to_string())
+	save_generated_wave(&wave, spec, out_path, verbose)?;
+
+	Ok((wave, report))
 }
 
-/** Set the modulation mode sweep frequency's mode to logarithm. */
-pub fn set_sweep_mode_logarithm(
-	port: &mut SerialPortType,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+/** Generate an arbitrary wave from a math formula over `t`, which
+goes from 0 up to 1, in the device's text format. See
+[expr](../expr/index.html) for what the formula can use, and
+[generate_expr_wave](../expr/fn.generate_expr_wave.html) for the
+"auto_scale" parameter.
 
-	let command = SET_SWEEP_MODE_LOGARITHM;
+"formula" parameter:
+```ignore
+"0.7*sin(2*pi*t) + 0.3*sin(6*pi*t + pi/4)"
+```
 
-	if verbose > 0 {
-		println!("\nSetting sweep mode to logarithm:\n{}", command);
-	}
+"out_path" parameter, where to save the wave, or `None` to print it
+to stdout in the text format. It's saved as a WaveCAD file if the
+name ends in .wav, or as text otherwise:
+```ignore
+None | Some("wave.txt") | Some("wave.wav")
+```
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_SWEEP_MODE_RES_LEN).collect();
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn expr_wave(
+	formula: &str,
+	out_path: Option<&str>,
+	auto_scale: bool,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let (wave, report) = generate_expr_wave(&Expr::parse(formula)?, auto_scale, convert)?;
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+	save_generated_wave(&wave, formula, out_path, verbose)?;
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	Ok((wave, report))
+}
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+/** Generate an arbitrary wave from a Fourier series, a recipe of
+harmonics, in the device's text format. See
+[generate_fourier_wave](../fourier/fn.generate_fourier_wave.html)
+for how it's normalized, and [expr_wave](fn.expr_wave.html) for the
+"out_path" parameter.
 
-	Ok(res.to_string())
-}
+"series" parameter:
+```ignore
+The fundamental, the 3rd harmonic at 10% and 30 degrees, and the 5th at 2%:
+&FourierSeries::parse("1:1.0, 3:0.1@30, 5:0.02")?
+```
 
-/** Set the modulation mode pulse generator's pulse width.
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn fourier_wave(
+	series: &FourierSeries,
+	out_path: Option<&str>,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let (wave, report) = generate_fourier_wave(series, convert)?;
 
-It is in nanosecond units unless, the microseconds parameter is true.
+	save_generated_wave(&wave, "Fourier series", out_path, verbose)?;
 
-IMPORTANT NOTE: There seems to be no option on the device's physical
-controls to switch between nanosecond and microsecond units, but if
-you specify a value in microseconds, the device will switch to
-microsecond mode, and all future values to this command entered through
-the physical device interface will be interpreted as microsecond units
-until you turn off the device, or set a nanosecond value using this
-serial-interface-only command. If you save the device state while in
-microseconds mode, this could be a problem, because then you need to
-use this serial program to get back to the default nanoseconds mode.
+	Ok((wave, report))
+}
 
-"amount" parameter when "microseconds" parameter is false:
+/** Build an arbitrary wave from other waves with a pipeline of
+operations, such as mixing two of them and fading the result in and
+out, in the device's text format. See [ops](../ops/index.html) for the
+operations, and [expr_wave](fn.expr_wave.html) for the "out_path"
+parameter.
+
+"port" parameter, the device to read any `slot:N` waves from, or
+`None` if the pipeline doesn't use any:
 ```ignore
-1,000 nanoseconds:
-"1000"
+None | Some(&mut port)
 ```
 
-"amount" parameter when "microseconds" parameter is true:
+"pipeline" parameter:
 ```ignore
-2,000 microseconds:
-"2000"
+&WavePipeline::parse("mix a.txt:0.8 slot:3:0.2 | window hann")?
 ```
+
+Files are read with [load_wave_file](fn.load_wave_file.html), which
+explains the "channel" and "resample" parameters. The result is
+converted to the device's range as chosen in "convert".
+
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
 */
-pub fn set_pulse_width(
-	mut port: &mut SerialPortType,
-	amount: &str,
-	microseconds: bool,
+pub fn wave_op(
+	port: Option<&mut SerialPortType>,
+	pipeline: &WavePipeline,
+	out_path: Option<&str>,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let (wave, report) = run_wave_op(port, pipeline, channel, resample, convert, verbose)?;
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-	let units: &'static str;
-	let arg_min: f64;
-	let arg_max: f64;
-
-	if microseconds {
-		units = "us";
-		arg_min = SET_PULSE_WIDTH_ARG_MICROSECONDS_MIN;
-		arg_max = SET_PULSE_WIDTH_ARG_MICROSECONDS_MAX;
-	} else {
-		units = "ns";
-		arg_min = SET_PULSE_WIDTH_ARG_NANOSECONDS_MIN;
-		arg_max = SET_PULSE_WIDTH_ARG_NANOSECONDS_MAX;
-	}
+	save_generated_wave(&wave, "wave operations", out_path, verbose)?;
 
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set pulse width ({})\" argument (must be {}-{}): {}: too many decimal places (0 max)", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
-	}
+	Ok((wave, report))
+}
 
-	let res: Result<String, clap::Error>;
+/** Run a pipeline of wave operations, reading its files and slots. */
+fn run_wave_op(
+	mut port: Option<&mut SerialPortType>,
+	pipeline: &WavePipeline,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let levels = pipeline.run(|source| load_source(port.as_deref_mut(), source, channel, resample, verbose))?;
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= arg_min && amount <= arg_max => {
-				if units == "ns" && amount as i64 % 5 != 0 {
-					return Err(Error::with_description(&format!("unsupported value passed to \"set pulse width ({})\" argument (must be {}-{}): {}: if nanoseconds, it must be a multiple of 5", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
-				}
+	let (samples, report) = convert_to_device(&levels, convert)?;
 
-				res = set_pulse_width_inner(&mut port, amount, microseconds, verbose);
-			}
+	Ok((WaveData::new(&samples)?, report))
+}
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse width ({})\" argument (must be {}-{}): {}", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
-			}
+/** Read a wave from a file, as in [load_wave_file](fn.load_wave_file.html),
+or from one of the device's slots. */
+fn load_source(
+	port: Option<&mut SerialPortType>,
+	source: &Source,
+	channel: u16,
+	resample: &ResampleOptions,
+	verbose: u64,
+) -> Result<WaveData, clap::Error> {
+	match source {
+		Source::File(path) => load_wave_file(path, channel, resample, &ConvertOptions::default(), verbose),
+
+		Source::Slot(amount) => match port {
+			Some(port) => read_arbitrary_wave(port, *amount, verbose),
+			None => Err(Error::with_description(
+				&format!("can't read arbitrary wave slot {} without a device", amount),
+				ErrorKind::InvalidValue,
+			)),
 		},
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse width ({})\" argument (must be {}-{}): {}: {}", units, arg_min, arg_max, amount, e), ErrorKind::InvalidValue));
-		}
+		Source::Current => Err(Error::with_description("the current wave can't be loaded", ErrorKind::InvalidValue)),
 	}
-
-	res
 }
 
-fn set_pulse_width_inner(
-	port: &mut SerialPortType,
-	amount: f64,
-	microseconds: bool,
+/** Save a generated wave as a WaveCAD file if "out_path" ends in
+.wav, or in the text format otherwise, or print it to stdout in the
+text format if there's no "out_path". */
+fn save_generated_wave(
+	wave: &WaveData,
+	source: &str,
+	out_path: Option<&str>,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	let units: &'static str;
-	let arg_min: f64;
-	let arg_max: f64;
-	let command: String;
-
-	if microseconds {
-		units = "us";
-		arg_min = SET_PULSE_WIDTH_ARG_MICROSECONDS_MIN;
-		arg_max = SET_PULSE_WIDTH_ARG_MICROSECONDS_MAX;
-
-		command = format!(
-			"{}{}{}{}{}{}{}{}",
-			COMMAND_BEGIN,
-			COMMAND_SET,
-			SET_PULSE_WIDTH_COMMAND,
-			COMMAND_SEPARATOR,
-			amount,
-			COMMAND_ARG_SEPARATOR,
-			SET_PULSE_WIDTH_ARG_MICROSECONDS,
-			COMMAND_END,
-		);
-	} else {
-		units = "ns";
-		arg_min = SET_PULSE_WIDTH_ARG_NANOSECONDS_MIN;
-		arg_max = SET_PULSE_WIDTH_ARG_NANOSECONDS_MAX;
-
-		command = format!(
-			"{}{}{}{}{}{}{}{}",
-			COMMAND_BEGIN,
-			COMMAND_SET,
-			SET_PULSE_WIDTH_COMMAND,
-			COMMAND_SEPARATOR,
-			amount,
-			COMMAND_ARG_SEPARATOR,
-			SET_PULSE_WIDTH_ARG_NANOSECONDS,
-			COMMAND_END,
-		);
-	}
-
-	if amount < arg_min || amount > arg_max {
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported pulse width ({}). Must be {}-{}.",
-				units, arg_min, arg_max
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
+) -> Result<(), clap::Error> {
+	let out_path = match out_path {
+		Some(out_path) => out_path,
 
-	if verbose > 0 {
-		println!("\nSetting pulse width: {} {}:\n{}", amount, units, command);
-	}
+		None => {
+			print!("{}", wave.to_txt_string());
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_PULSE_WIDTH_RES_LEN).collect();
+			return Ok(());
+		}
+	};
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+	let data = if out_path.to_lowercase().ends_with(".wav") {
+		wave.to_wavecad_bytes()
+	} else {
+		wave.to_txt_string().into_bytes()
+	};
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	fs::write(out_path, data).map_err(|e| {
+		Error::with_description(
+			&format!("failed writing to file: {}: {}", out_path, e),
+			ErrorKind::Io,
+		)
+	})?;
 
 	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+		println!("\nWave generated and saved: {} -> {}", source, out_path);
 	}
 
-	Ok(res.to_string())
+	Ok(())
 }
 
-/** Set the modulation mode pulse generator's pulse period.
+/** Write a user-defined arbitrary waveform to the device.
 
-It is in nanosecond units unless the microseconds parameter is true.
+Use the helper function
+[set_arbitrary_wave_stdin](fn.set_arbitrary_wave_stdin.html)
+instead to accept the text input for the wave from stdin.
 
-IMPORTANT NOTE: There seems to be no option on the device's physical
-controls to switch between nanosecond and microsecond units, but if
-you specify a value in microseconds, the device will switch to
-microsecond mode, and all future values to this command entered through
-the physical device interface will be interpreted as microsecond units
-until you turn off the device, or set a nanosecond value using this
-serial-interface-only command. If you save the device state while in
-microseconds mode, this could be a problem, because then you need to
-use this serial program to get back to the default nanoseconds mode.
+Specify which arbitrary wave preset to save it in, as the
+"amount" parameter.
 
-"amount" parameter when "microseconds" parameter is false:
+"data" parameter, some user-defined wave of 2048 samples, each in
+the range of 0 - 4095. See [WaveData](../wave/struct.WaveData.html)
+for the ways to make one, for example from the device's text format:
 ```ignore
-1,000 nanoseconds:
-"1000"
+&WaveData::from_txt(&b"2456\n3016\n4054\n...\n1012\n"[..])?
 ```
 
-"amount" parameter when "microseconds" parameter is true:
-```ignore
-2,000 microseconds:
-"2000"
-```
+If the port's `verify` flag is set, the slot is read back afterwards
+and compared sample by sample, and the wave is uploaded again if it
+doesn't match, up to `wave_retries` more times. Each retry is counted
+in the port's `stats`, and the samples which differ are printed before
+it if "verbose" is more than 0. If it still doesn't match, the error
+lists the indices of the samples which differ.
 */
-pub fn set_pulse_period(
-	mut port: &mut SerialPortType,
-	amount: &str,
-	microseconds: bool,
+pub fn set_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: f64,
+	data: &WaveData,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+	let verify = port.verify;
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-	let units: &'static str;
-	let arg_min: f64;
-	let arg_max: f64;
-
-	if microseconds {
-		units = "us";
-		arg_min = SET_PULSE_PERIOD_ARG_MICROSECONDS_MIN;
-		arg_max = SET_PULSE_PERIOD_ARG_MICROSECONDS_MAX;
-	} else {
-		units = "ns";
-		arg_min = SET_PULSE_PERIOD_ARG_NANOSECONDS_MIN;
-		arg_max = SET_PULSE_PERIOD_ARG_NANOSECONDS_MAX;
-	}
+	upload_arbitrary_wave(port, amount, data, verify, verbose)
+}
 
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set pulse period ({})\" argument (must be {}-{}): {}: too many decimal places (0 max)", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
+/** Write a wave to a slot, and if "verify" is true, read it back and
+write it again until it matches, or the port's retries run out. */
+fn upload_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: f64,
+	data: &WaveData,
+	verify: bool,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let mut res = write_arbitrary_wave(port, amount, data, verbose)?;
+
+	if !verify {
+		return Ok(res);
 	}
 
-	let res: Result<String, clap::Error>;
+	let mut attempts = 1;
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= arg_min && amount <= arg_max => {
-				if units == "ns" && amount as i64 % 5 != 0 {
-					return Err(Error::with_description(&format!("unsupported value passed to \"set pulse period ({})\" argument (must be {}-{}): {}: if nanoseconds, it must be a multiple of 5", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
-				}
+	loop {
+		if !port.mock {
+			// Wait a bit to allow the device some time to settle.
+			thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+		}
 
-				res = set_pulse_period_inner(&mut port, amount, microseconds, verbose);
-			}
+		let diff = read_arbitrary_wave(port, amount, verbose)?.diff(data);
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse period ({})\" argument (must be {}-{}): {}", units, arg_min, arg_max, amount), ErrorKind::InvalidValue));
-			}
-		},
+		if diff.is_empty() {
+			return Ok(res);
+		}
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse period ({})\" argument (must be {}-{}): {}: {}", units, arg_min, arg_max, amount, e), ErrorKind::InvalidValue));
+		if attempts > port.wave_retries {
+			return Err(Error::with_description(
+				&format!(
+					"arbitrary wave slot {} doesn't match what was written to it after {} attempts: {} samples differ, at indices: {}",
+					amount,
+					attempts,
+					diff.len(),
+					index_ranges(&diff)
+				),
+				ErrorKind::ValueValidation,
+			));
 		}
+
+		if verbose > 0 {
+			println!(
+				"warning: arbitrary wave slot {} doesn't match what was written to it: {} samples differ, at indices: {}: retrying",
+				amount,
+				diff.len(),
+				index_ranges(&diff)
+			);
+		}
+
+		port.stats.record_retry(&format!("{}{:02}", SET_ARBITRARY_WAVE_COMMAND, amount));
+
+		res = write_arbitrary_wave(port, amount, data, verbose)?;
+		attempts += 1;
 	}
+}
 
-	res
+/** A list of indices as ranges, such as "3, 1024-2047", with at most
+10 ranges shown. */
+fn index_ranges(indices: &[usize]) -> String {
+	let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+	for &i in indices {
+		match ranges.last_mut() {
+			Some(last) if last.1 + 1 == i => last.1 = i,
+			_ => ranges.push((i, i)),
+		}
+	}
+
+	let mut out: Vec<String> = ranges
+		.iter()
+		.take(10)
+		.map(|(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+		.collect();
+
+	if ranges.len() > 10 {
+		out.push(format!("and {} more", ranges.len() - 10));
+	}
+
+	out.join(", ")
 }
 
-fn set_pulse_period_inner(
+/** Send a wave to a slot once. */
+fn write_arbitrary_wave(
 	port: &mut SerialPortType,
 	amount: f64,
-	microseconds: bool,
+	data: &WaveData,
 	verbose: u64,
 ) -> Result<String, clap::Error> {
-	let units: &'static str;
-	let arg_min: f64;
-	let arg_max: f64;
-	let command: String;
-
-	if microseconds {
-		units = "us";
-		arg_min = SET_PULSE_PERIOD_ARG_MICROSECONDS_MIN;
-		arg_max = SET_PULSE_PERIOD_ARG_MICROSECONDS_MAX;
-
-		command = format!(
-			"{}{}{}{}{}{}{}{}",
-			COMMAND_BEGIN,
-			COMMAND_SET,
-			SET_PULSE_PERIOD_COMMAND,
-			COMMAND_SEPARATOR,
-			amount,
-			COMMAND_ARG_SEPARATOR,
-			SET_PULSE_PERIOD_ARG_MICROSECONDS,
-			COMMAND_END,
-		);
-	} else {
-		units = "ns";
-		arg_min = SET_PULSE_PERIOD_ARG_NANOSECONDS_MIN;
-		arg_max = SET_PULSE_PERIOD_ARG_NANOSECONDS_MAX;
-
-		command = format!(
-			"{}{}{}{}{}{}{}{}",
-			COMMAND_BEGIN,
-			COMMAND_SET,
-			SET_PULSE_PERIOD_COMMAND,
-			COMMAND_SEPARATOR,
-			amount,
-			COMMAND_ARG_SEPARATOR,
-			SET_PULSE_PERIOD_ARG_NANOSECONDS,
-			COMMAND_END,
-		);
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	if amount < arg_min || amount > arg_max {
+	let command: String;
+
+	if amount < SET_ARBITRARY_WAVE_ARG_NUM_MIN || amount > SET_ARBITRARY_WAVE_ARG_NUM_MAX {
 		return Err(Error::with_description(
 			&format!(
-				"Unsupported pulse period ({}). Must be {}-{}.",
-				units, arg_min, arg_max
+				"Unsupported slot number. Must be {}-{}.",
+				SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX
 			),
 			ErrorKind::InvalidValue,
 		));
 	}
 
+	let amount_str = format!("{:02}", amount);
+
+	command = format!(
+		"{}{}{}{}{}{}",
+		COMMAND_BEGIN,
+		SET_ARBITRARY_WAVE_COMMAND,
+		amount_str,
+		COMMAND_SEPARATOR,
+		data.to_command_arg(),
+		COMMAND_END,
+	);
+
 	if verbose > 0 {
-		println!("\nSetting pulse period: {} {}:\n{}", amount, units, command);
+		println!(
+			"\nWriting arbitrary wave to slot {}:\n\n{}",
+			amount, command
+		);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_PULSE_PERIOD_RES_LEN).collect();
+	let mut outbuf: Vec<u8> = (0..SET_ARBITRARY_WAVE_RES_LEN).collect();
+
+	report_progress(port, Direction::Upload, amount, 0);
 
 	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+		let start = Instant::now();
+		let key = command_key(&inbuf);
+		let mut sent = 0;
+
+		for (end, samples) in upload_chunks(&inbuf, 128) {
+			if let Err(e) = port.port.as_mut().unwrap().write_all(&inbuf[sent..end]) {
+				port.stats.record(&key, start.elapsed(), sent, 0, Outcome::from_error(&e));
+				return Err(e.into());
+			}
+
+			sent = end;
+
+			report_progress(port, Direction::Upload, amount, samples);
+		}
+
+		let res = port.port.as_mut().unwrap().read(&mut outbuf[..]);
+		let received = *res.as_ref().unwrap_or(&0);
+
+		port.stats.record(&key, start.elapsed(), sent, received, Outcome::from_result(&res, &outbuf));
+
+		outbuf.truncate(res?);
+	} else {	// Mock data for testing.
+		for (_end, samples) in upload_chunks(&inbuf, 128) {
+			report_progress(port, Direction::Upload, amount, samples);
+		}
+
+		let uploads = port.mock_uploads.entry(amount as u8).or_insert(0);
+		*uploads += 1;
+
+		let cut_short = match port.mock_num {
+			1 => true,	// Every upload is cut short.
+			2 => *uploads == 1,	// Only the first upload is cut short.
+			_ => false,	// Ok.
+		};
+
+		let written = if cut_short {	// Only the first half was written.
+			let mut samples = port.mock_waves.get(&(amount as u8)).cloned().unwrap_or_default().samples().to_vec();
+
+			samples[..ARBITRARY_WAVE_LEN / 2].copy_from_slice(&data.samples()[..ARBITRARY_WAVE_LEN / 2]);
+
+			WaveData::new(&samples)?
+		} else {
+			data.clone()
+		};
+
+		port.mock_waves.insert(amount as u8, written);
 	}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let res = String::from_utf8_lossy(&outbuf).to_string();
 
 	if verbose > 0 {
 		println!("Response:");
 		println!("{}", res);
 	}
 
-	Ok(res.to_string())
+	Ok(res)
 }
 
-/** Set the modulation mode pulse generator's pulse offset in percent (%).
+/** Write a user-defined arbitrary waveform to the device from a WaveCAD (.wav)
+file, or a regular .wav audio file, saving it into one of the device's
+arbitrary wave preset slots. See [wav_to_txt](fn.wav_to_txt.html) for
+the supported .wav formats.
 
-"amount" parameter:
+"arg" parameter:
 ```ignore
-50 percent:
-"50"
+Save the wav file into preset 1:
+"1,some-wav-file-to-upload.wav"
 ```
+
+"channel" parameter, which channel to use from a multichannel
+audio file, counting from 1, or 0 to mix all the channels together:
+```ignore
+first channel:
+1
+```
+
+"resample" parameter, which part of the file to use, and how to
+resample it to 2048 samples.
+
+"convert" parameter, how to scale the wave to the device's range.
+
+Returns the device's response along with a report of the conversion,
+which says how many values had to be clipped.
 */
-pub fn set_pulse_offset(
+pub fn set_arbitrary_wavecad(
 	mut port: &mut SerialPortType,
-	amount: &str,
+	arg: &str,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+) -> Result<(String, ConversionReport), clap::Error> {
+	let arg_parts: Vec<&str> = arg.split(",").collect();
+
+	if arg_parts.len() < 2 {
+		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{},<file_path>): slot number and file path must be present and separated with a comma but no space: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, arg), ErrorKind::InvalidValue));
 	}
 
+	let amount = arg_parts[0];
+
 	let amount_parts: Vec<&str> = amount.split(".").collect();
 
 	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set pulse offset\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_PULSE_OFFSET_ARG_PERCENT_MIN, SET_PULSE_OFFSET_ARG_PERCENT_MAX, amount), ErrorKind::InvalidValue));
+		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
-	let res: Result<String, clap::Error>;
+	let path = arg_parts[1];
+
+	let res: Result<(String, ConversionReport), clap::Error>;
 
 	match amount.parse::<f64>() {
 		Ok(amount) => match amount {
-			_y if amount >= SET_PULSE_OFFSET_ARG_PERCENT_MIN
-				&& amount <= SET_PULSE_OFFSET_ARG_PERCENT_MAX =>
+			_y if amount >= SET_ARBITRARY_WAVE_ARG_NUM_MIN
+				&& amount <= SET_ARBITRARY_WAVE_ARG_NUM_MAX =>
 			{
-				res = set_pulse_offset_inner(&mut port, amount, verbose);
+				let data = fs::read(path).map_err(|e| {
+					Error::with_description(
+						&format!("failed reading file: {}: {}", path, e),
+						ErrorKind::Io,
+					)
+				})?;
+
+				check_capture(path, &data)?;
+
+				let (data, report) = wav_to_wave_data(&data, channel, resample, convert, verbose)?;
+
+				res = set_arbitrary_wave(&mut port, amount, &data, verbose).map(|res| (res, report));
 			}
 
 			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse offset\" argument (must be {}-{}): {}", SET_PULSE_OFFSET_ARG_PERCENT_MIN, SET_PULSE_OFFSET_ARG_PERCENT_MAX, amount), ErrorKind::InvalidValue));
+				res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 			}
 		},
 
 		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse offset\" argument (must be {}-{}): {}: {}", SET_PULSE_OFFSET_ARG_PERCENT_MIN, SET_PULSE_OFFSET_ARG_PERCENT_MAX, amount, e), ErrorKind::InvalidValue));
+			res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
 		}
 	}
 
 	res
 }
 
-fn set_pulse_offset_inner(
-	port: &mut SerialPortType,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
-
-	if amount < SET_PULSE_OFFSET_ARG_PERCENT_MIN || amount > SET_PULSE_OFFSET_ARG_PERCENT_MAX {
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported pulse offset. Must be {}-{}.",
-				SET_PULSE_OFFSET_ARG_PERCENT_MIN, SET_PULSE_OFFSET_ARG_PERCENT_MAX
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
-
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SET_PULSE_OFFSET_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
-
-	if verbose > 0 {
-		println!("\nSetting pulse offset: {}:\n{}", amount, command);
-	}
-
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_PULSE_OFFSET_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+/** Write a user-defined arbitrary waveform to the device from stdin.
 
-	let res = str::from_utf8(&outbuf).unwrap();
+Specify which arbitrary wave preset to save it in, as the
+"amount" parameter.
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+The stdin values define some user-defined wave. Must be 2048
+ASCII whole numbers, each in the range of 0 - 4095, usually one
+per line. Commas, blank lines, Windows line endings, and `#`
+comments are also fine, and so are decimal numbers from -1.0 to
+1.0, as described in [parse_txt](../wave/fn.parse_txt.html).
+For example:
+```ignore
+"2456"
+"3016"
+"4054"
+"4001"
+...
+"3244"
+"2010"
+"1012"
 
-	Ok(res.to_string())
-}
+```
 
-/** Set the modulation mode pulse generator's pulse amplitude in volts (V).
+If the "resample" parameter picks a window, a single period, or
+an interpolation method, any number of values are accepted, and
+they're resampled to 2048 values. The window must be given in value
+numbers, since there's no sample rate for stdin. Resampling can
+overshoot the device's range, so values outside it are clipped, or
+it fails if "fail_on_clip" is set.
 
-"amount" parameter:
-```ignore
-5 volts:
-"5"
-```
+Returns the device's response, and a report of the wave's levels
+and how many values had to be clipped to fit.
 */
-pub fn set_pulse_amplitude(
+pub fn set_arbitrary_wave_stdin(
 	mut port: &mut SerialPortType,
 	amount: &str,
+	resample: &ResampleOptions,
+	fail_on_clip: bool,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
-
+) -> Result<(String, ConversionReport), clap::Error> {
 	let amount_parts: Vec<&str> = amount.split(".").collect();
 
-	if amount_parts.len() > 1 && amount_parts[1].len() > 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"set pulse amplitude\" argument (must be {}-{}): {}: too many decimal places (2 max)", SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN, SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX, amount), ErrorKind::InvalidValue));
+	if amount_parts.len() > 1 {
+		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
-	let res: Result<String, clap::Error>;
+	let res: Result<(String, ConversionReport), clap::Error>;
 
 	match amount.parse::<f64>() {
 		Ok(amount) => match amount {
-			_y if amount >= SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN
-				&& amount <= SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX =>
+			_y if amount >= SET_ARBITRARY_WAVE_ARG_NUM_MIN
+				&& amount <= SET_ARBITRARY_WAVE_ARG_NUM_MAX =>
 			{
-				let amount_rounded = ((amount * 100.0 * 100.0).round() / 100.0).round();
-
-				res = set_pulse_amplitude_inner(&mut port, amount_rounded, verbose);
+				res = set_arbitrary_wave_stdin_inner(&mut port, amount, resample, fail_on_clip, verbose);
 			}
 
 			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse amplitude\" argument (must be {}-{}): {}", SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN, SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX, amount), ErrorKind::InvalidValue));
+				res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 			}
 		},
 
 		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"set pulse amplitude\" argument (must be {}-{}): {}: {}", SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN, SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX, amount, e), ErrorKind::InvalidValue));
+			res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
 		}
 	}
 
 	res
 }
 
-fn set_pulse_amplitude_inner(
+fn set_arbitrary_wave_stdin_inner(
 	port: &mut SerialPortType,
 	amount: f64,
+	resample: &ResampleOptions,
+	fail_on_clip: bool,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
+) -> Result<(String, ConversionReport), clap::Error> {
+	let mut text = String::new();
 
-	if amount < SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN * 100.0
-		|| amount > SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX * 100.0
-	{
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported pulse amplitude. Must be {}-{}.",
-				SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN, SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
+	io::stdin().lock().read_to_string(&mut text).map_err(|e| {
+		Error::with_description(&format!("failed reading wave data from stdin: {}", e), ErrorKind::Io)
+	})?;
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SET_PULSE_AMPLITUDE_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
+	let (data, report) = if resample.is_active() {
+		resample_wave_data(&text, resample, fail_on_clip)?
+	} else {
+		let data = WaveData::from_txt_str(&text)?;
+		let (_, report) = convert_to_device(&levels(&data), &ConvertOptions::default())?;
 
-	if verbose > 0 {
-		println!("\nSetting pulse amplitude: {}:\n{}", amount, command);
-	}
+		(data, report)
+	};
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_PULSE_AMPLITUDE_RES_LEN).collect();
+	Ok((set_arbitrary_wave(port, amount, &data, verbose)?, report))
+}
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+/** Generate a common arbitrary wave from a spec, and write it to
+one of the device's arbitrary wave preset slots in one step. See
+[gen_wave](fn.gen_wave.html) for the "spec" parameter, and
+[set_arbitrary_wave](fn.set_arbitrary_wave.html) for the rest.
 
-	let res = str::from_utf8(&outbuf).unwrap();
+"amount" parameter:
+```ignore
+Save it in preset 12:
+"12"
+```
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn set_arbitrary_wave_gen(
+	port: &mut SerialPortType,
+	amount: &str,
+	spec: &str,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-	Ok(res.to_string())
+	let (wave, report) = generate_wave(&WaveSpec::parse(spec)?, convert)?;
+
+	set_arbitrary_wave(port, amount, &wave, verbose)?;
+
+	Ok((wave, report))
 }
 
-/** Save all current values on the device as a numbered preset.
+/** Generate an arbitrary wave from a math formula, and write it to
+one of the device's arbitrary wave preset slots in one step. See
+[expr_wave](fn.expr_wave.html) for the "formula" and "auto_scale"
+parameters, and [set_arbitrary_wave_gen](fn.set_arbitrary_wave_gen.html)
+for the rest.
 
-"amount" parameter:
-```ignore
-save as preset 0:
-"0"
-```
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
 */
-pub fn save_preset(
-	mut port: &mut SerialPortType,
+pub fn set_arbitrary_wave_expr(
+	port: &mut SerialPortType,
 	amount: &str,
+	formula: &str,
+	auto_scale: bool,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+	let (wave, report) = generate_expr_wave(&Expr::parse(formula)?, auto_scale, convert)?;
 
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"save preset\" argument (must be {}-{}): {}: too many decimal places (0 max)", SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
-	}
+	set_arbitrary_wave(port, amount, &wave, verbose)?;
 
-	let res: Result<String, clap::Error>;
+	Ok((wave, report))
+}
 
-	match amount.parse::<f64>() {
-		Ok(amount) => {
-			match amount {
-				_y if amount >= SAVE_PRESET_ARG_NUM_MIN
-					&& amount <= SAVE_PRESET_ARG_NUM_MAX =>
-				{
-					res = save_preset_inner(&mut port, amount, verbose);
-				}
+/** Generate an arbitrary wave from a Fourier series, and write it to
+one of the device's arbitrary wave preset slots in one step. See
+[fourier_wave](fn.fourier_wave.html) for the "series" parameter, and
+[set_arbitrary_wave_gen](fn.set_arbitrary_wave_gen.html) for the rest.
 
-				_ => {
-					res = Err(Error::with_description(&format!("unsupported value passed to \"save preset\" argument (must be {}-{}): {}", SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
-				}
-			}
-		}
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn set_arbitrary_wave_fourier(
+	port: &mut SerialPortType,
+	amount: &str,
+	series: &FourierSeries,
+	convert: &ConvertOptions,
+	verbose: u64,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(
-				&format!(
-					"unsupported value passed to \"save preset\" argument (must be {}-{}): {}: {}",
-					SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX, amount, e
-				),
-				ErrorKind::InvalidValue,
-			));
-		}
-	}
+	let (wave, report) = generate_fourier_wave(series, convert)?;
 
-	res
+	set_arbitrary_wave(port, amount, &wave, verbose)?;
+
+	Ok((wave, report))
 }
 
-fn save_preset_inner(
+/** Build an arbitrary wave with a pipeline of operations, and write
+it to one of the device's arbitrary wave preset slots in one step. The
+pipeline can read from the slots too, including the one it writes to.
+See [wave_op](fn.wave_op.html) for the "pipeline", "channel", and
+"resample" parameters, and
+[set_arbitrary_wave_gen](fn.set_arbitrary_wave_gen.html) for the rest.
+
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit.
+*/
+pub fn set_arbitrary_wave_op(
 	port: &mut SerialPortType,
-	amount: f64,
+	amount: &str,
+	pipeline: &WavePipeline,
+	channel: u16,
+	resample: &ResampleOptions,
+	convert: &ConvertOptions,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-	if amount < SAVE_PRESET_ARG_NUM_MIN || amount > SAVE_PRESET_ARG_NUM_MAX {
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported preset number. Must be {}-{}.",
-				SAVE_PRESET_ARG_NUM_MIN, SAVE_PRESET_ARG_NUM_MAX
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
+	let (wave, report) = run_wave_op(Some(port), pipeline, channel, resample, convert, verbose)?;
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		COMMAND_SET,
-		SAVE_PRESET_COMMAND,
-		COMMAND_SEPARATOR,
-		amount,
-		COMMAND_END,
-	);
+	set_arbitrary_wave(port, amount, &wave, verbose)?;
 
-	if verbose > 0 {
-		println!("\nSaving values as preset number: {}:\n{}", amount, command);
-	}
+	Ok((wave, report))
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SAVE_PRESET_RES_LEN).collect();
+/** Parse the slot number for a generated wave. */
+fn parse_wave_slot(amount: &str) -> Result<f64, clap::Error> {
+	let amount_parts: Vec<&str> = amount.split(".").collect();
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+	if amount_parts.len() > 1 {
+		return Err(Error::with_description(&format!("unsupported value passed to \"wave slot\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let amount = amount.parse::<f64>().map_err(|e| {
+		Error::with_description(&format!("unsupported value passed to \"wave slot\" argument (must be {}-{}): {}: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue)
+	})?;
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+	if !(SET_ARBITRARY_WAVE_ARG_NUM_MIN..=SET_ARBITRARY_WAVE_ARG_NUM_MAX).contains(&amount) {
+		return Err(Error::with_description(&format!("unsupported value passed to \"wave slot\" argument (must be {}-{}): {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
-	Ok(res.to_string())
+	Ok(amount)
 }
 
-/** Load all values for the device from a numbered preset.
+/** Read a user-defined arbitrary waveform from one of the device's
+numbered arbitrary wave preset slots.
 
 "amount" parameter:
 ```ignore
-save as preset 0:
-"0"
+Get the waveform data which is stored in preset 1:
+"1"
 ```
+
+Returns the wave, which is also printed in the device's text format,
+unless the verbosity level is greater than 0. In mock mode, it's the
+last wave written to the slot, or a flat line in the middle of the
+range if there isn't one.
 */
-pub fn load_preset(
+pub fn get_arbitrary_wave(
 	mut port: &mut SerialPortType,
 	amount: &str,
 	verbose: u64,
-) -> Result<String, clap::Error> {
+) -> Result<WaveData, clap::Error> {
 	if !port.mock {
 		// Wait a bit to allow the device some time to settle.
 		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
@@ -5348,778 +5093,732 @@ pub fn load_preset(
 	let amount_parts: Vec<&str> = amount.split(".").collect();
 
 	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}: too many decimal places (0 max)", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+		return Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 	}
 
-	let res: Result<String, clap::Error>;
+	let res: Result<WaveData, clap::Error>;
 
 	match amount.parse::<f64>() {
 		Ok(amount) => match amount {
-			_y if amount >= LOAD_PRESET_ARG_NUM_MIN
-				&& amount <= LOAD_PRESET_ARG_NUM_MAX =>
+			_y if amount >= GET_ARBITRARY_WAVE_ARG_NUM_MIN
+				&& amount <= GET_ARBITRARY_WAVE_ARG_NUM_MAX =>
 			{
-				res = load_preset_inner(&mut port, amount, verbose);
+				res = get_arbitrary_wave_inner(&mut port, amount, verbose);
 			}
 
 			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+				res = Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}", GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
 			}
 		},
 
 		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"recall preset\" argument (must be {}-{}): {}: {}", LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
+			res = Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}: {}", GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
 		}
 	}
 
 	res
 }
 
-fn load_preset_inner(
+fn get_arbitrary_wave_inner(
 	port: &mut SerialPortType,
 	amount: f64,
 	verbose: u64,
-) -> Result<String, clap::Error> {
+) -> Result<WaveData, clap::Error> {
+	let wave = read_arbitrary_wave(port, amount, verbose)?;
+
+	if verbose == 0 {
+		print!("{}", wave.to_txt_string());
+	}
+
+	Ok(wave)
+}
+
+/** Read a wave from a slot, without printing it. */
+fn read_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: f64,
+	verbose: u64,
+) -> Result<WaveData, clap::Error> {
 	let command: String;
 
-	if amount < LOAD_PRESET_ARG_NUM_MIN || amount > LOAD_PRESET_ARG_NUM_MAX {
+	if amount < GET_ARBITRARY_WAVE_ARG_NUM_MIN || amount > GET_ARBITRARY_WAVE_ARG_NUM_MAX {
 		return Err(Error::with_description(
 			&format!(
-				"Unsupported preset number. Must be {}-{}.",
-				LOAD_PRESET_ARG_NUM_MIN, LOAD_PRESET_ARG_NUM_MAX
+				"Unsupported slot number. Must be {}-{}.",
+				GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX
 			),
 			ErrorKind::InvalidValue,
 		));
 	}
 
+	let amount_str = format!("{:02}", amount);
+
 	command = format!(
 		"{}{}{}{}{}{}",
 		COMMAND_BEGIN,
-		COMMAND_SET,
-		LOAD_PRESET_COMMAND,
+		GET_ARBITRARY_WAVE_COMMAND,
+		amount_str,
 		COMMAND_SEPARATOR,
-		amount,
+		GET_ARBITRARY_WAVE_ARG2,
 		COMMAND_END,
 	);
 
 	if verbose > 0 {
 		println!(
-			"\nRecalling values from preset number: {}:\n{}",
+			"\nReading arbitrary wave from slot: {}:\n{}",
 			amount, command
 		);
 	}
 
 	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..LOAD_PRESET_RES_LEN).collect();
-
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
-	}
+	let mut outbuf = [0u8; 1024];
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let mut response: Vec<u8> = Vec::with_capacity(GET_ARBITRARY_WAVE_RES_LEN as usize);
+	let mut parser = WaveParser::new();
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
-	}
+	report_progress(port, Direction::Download, amount, 0);
 
-	Ok(res.to_string())
-}
+	if !port.mock {
+		let start = Instant::now();
+		let key = command_key(&inbuf);
 
-/** Convert a WaveCAD (.wav) file to the device's arbitrary
-waveform text (.txt) file format.
+		if let Err(e) = port.port.as_mut().unwrap().write_all(&inbuf[..]) {
+			port.stats.record(&key, start.elapsed(), inbuf.len(), 0, Outcome::from_error(&e));
+			return Err(e.into());
+		}
 
-"path" parameter:
-```ignore
-some-wav-file-to-convert.wav:
-"some-wav-file-to-convert.wav"
-```
-*/
-pub fn wav_to_txt(path: &str, verbose: u64) -> Result<String, clap::Error> {
-	let mut res: Result<String, clap::Error>;
+		// Read until the terminator after the last sample, so none of the
+		// response is left for the next command to read.
+		while !parser.is_done() {
+			let n = match port.port.as_mut().unwrap().read(&mut outbuf[..]) {
+				Ok(0) => break,
+				Ok(n) => n,
 
-	if path == "" {
-		res = Err(Error::with_description(
-			&format!(
-				"unsupported path passed as \"wav_to_txt\" argument (must not be blank): {}",
-				path
-			),
-			ErrorKind::InvalidValue,
-		));
-		return res;
-	}
+				Err(e) => {
+					port.stats.record(&key, start.elapsed(), inbuf.len(), response.len(), Outcome::from_error(&e));
 
-	let new_path: &str = &change_file_extension(path, ".txt");
+					return Err(Error::with_description(
+						&format!("Reached end of buffer unexpectedly: {}.", e),
+						ErrorKind::InvalidValue,
+					));
+				}
+			};
 
-	match fs::File::open(path) {
-		Ok(mut file) => {
-			let mut buf = [0u8; 4096];
-			res = file.read(&mut buf).map_or_else(
-				|e| {
-					Err(Error::with_description(
-						&format!("failed reading file: {}: {}", path, e),
-						ErrorKind::Io,
-					))
-				},
-				|_res| Ok("".to_string()),
-			);
+			response.extend_from_slice(&outbuf[..n]);
 
-			if res.is_err() {
-				return res;
+			if let Err(e) = parser.feed(&outbuf[..n]) {
+				port.stats.record(&key, start.elapsed(), inbuf.len(), response.len(), Outcome::Error);
+				return Err(e);
 			}
 
-			let mut out = String::new();
+			report_progress(port, Direction::Download, amount, parser.samples_done());
+		}
 
-			let mut outbuf = [0i16; 2048];
+		let outcome = if parser.is_done() { Outcome::Ok } else { Outcome::ShortRead };
 
-			LittleEndian::read_i16_into(&buf, &mut outbuf);
+		port.stats.record(&key, start.elapsed(), inbuf.len(), response.len(), outcome);
 
-			let clamp_min = 0;
-			let clamp_max = 4095;
+		if verbose > 0 && parser.is_done() {
+			println!("\nReached end of buffer: Decoded all expected samples and the terminator\n");
+		}
 
-			let mut clamp_min_count = 0u64;
-			let mut clamp_max_count = 0u64;
+	} else {	// Mock data for testing, sent through the parser in pieces.
+		let wave = port.mock_waves.get(&(amount as u8)).cloned().unwrap_or_default();
 
-			let mut clamp_min_adjustment_total = 0i64;
-			let mut clamp_max_adjustment_total = 0i64;
+		response = format!("{}{}{}{}{}", COMMAND_BEGIN, GET_ARBITRARY_WAVE_COMMAND, amount_str, COMMAND_SEPARATOR, wave.to_command_arg())
+			.into_bytes();
+		response.extend_from_slice(format!("{}{}", COMMAND_ARG_SEPARATOR, COMMAND_END).as_bytes());
 
-			for (i, val) in outbuf.iter().enumerate() {
-				let mut new_val = *val as i64 + 2048;
+		for piece in response.chunks(outbuf.len()) {
+			parser.feed(piece)?;
+			report_progress(port, Direction::Download, amount, parser.samples_done());
+		}
+	}
 
-				if new_val < clamp_min {
-					clamp_min_count += 1;
-					let clamp_min_adjustment = clamp_min - new_val;
-					clamp_min_adjustment_total += clamp_min_adjustment;
+	if verbose > 0 {
+		println!("Response size: {} bytes\n", response.len());
+		println!("Response:");
+		println!("{}\n", String::from_utf8_lossy(&response));
+	}
 
-					println!("warning: wave data below min supported value, clamping to {}:\tline: {}:\tvalue lost: {}:\tadjustment: +{}", clamp_min, i + 1, new_val, clamp_min_adjustment);
+	parser.finish()
+}
 
-					new_val = clamp_min;
-				} else if new_val > clamp_max {
-					clamp_max_count += 1;
-					let clamp_max_adjustment = clamp_max - new_val;
-					clamp_max_adjustment_total += clamp_max_adjustment;
+/** Call the port's progress callback, if it has one. */
+fn report_progress(port: &mut SerialPortType, direction: Direction, amount: f64, done: usize) {
+	if let Some(progress) = port.progress.as_mut() {
+		progress(&Progress {
+			direction,
+			slot: amount as u8,
+			done,
+			total: ARBITRARY_WAVE_LEN,
+		});
+	}
+}
 
-					println!("warning: wave data above max supported value, clamping to {}:\tline: {}:\tvalue lost: {}:\tadjustment: {}", clamp_max, i + 1, new_val, clamp_max_adjustment);
+/** Read one of the device's arbitrary wave preset slots, and print
+an analysis of its spectrum and quality, as chosen in "analysis". See
+[analyze](../analyze/index.html) for what's measured.
 
-					new_val = clamp_max;
-				}
+"amount" parameter:
+```ignore
+Analyze the waveform which is stored in preset 12:
+"12"
+```
 
-				out += &new_val.to_string();
+"analysis" parameter, how many harmonics to list, and whether to
+print text or JSON:
+```ignore
+&AnalysisOptions { harmonics: 10, format: AnalysisFormat::Json }
+```
 
-				if i < buf.len() - 1 {
-					out += "\n";
-				}
-			}
+Returns the analysis. In mock mode, it's of the last wave written to
+the slot, or a flat line in the middle of the range if there isn't one.
+*/
+pub fn analyze_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: &str,
+	analysis: &AnalysisOptions,
+	verbose: u64,
+) -> Result<WaveAnalysis, clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-			if clamp_min_count > 0 || clamp_max_count > 0 {
-				println!("");
-			}
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
 
-			if clamp_min_count > 0 {
-				println!("warning: wave data was min clamped to {}:\ttimes min clamped: {}:\tsummed min clamp adjustments: +{}", clamp_min, clamp_min_count, clamp_min_adjustment_total);
-			}
+	let wave = read_arbitrary_wave(port, amount, verbose)?;
+	let res = analyze_wave(&wave, analysis.harmonics);
 
-			if clamp_max_count > 0 {
-				println!("warning: wave data was max clamped to {}:\ttimes max clamped: {}:\tsummed max clamp adjustments: {}", clamp_max, clamp_max_count, clamp_max_adjustment_total);
-			}
+	print!("{}", analysis.format.render(&res));
 
-			if clamp_min_count > 0 || clamp_max_count > 0 {
-				println!("warning: some of the wave data was clamped:\ttotal times clamped: {}:\ttotal summed clamp adjustments: {}\n", clamp_min_count + clamp_max_count, clamp_min_adjustment_total - clamp_max_adjustment_total);
-			}
+	Ok(res)
+}
 
-			match fs::File::create(&format!("{}", new_path)) {
-				Ok(mut outfile) => {
-					res = outfile.write_all(out.as_bytes()).map_or_else(
-						|e| {
-							Err(Error::with_description(
-								&format!("failed writing to file: {}: {}", new_path, e),
-								ErrorKind::Io,
-							))
-						},
-						|_res| Ok(fs::read_to_string(&format!("{}", new_path)).unwrap()),
-					);
-
-					if res.is_err() {
-						return res;
-					}
+/** Read one of the device's arbitrary wave preset slots, and print a
+plot of it instead of its data, for a quick check that the right wave
+is in it. See [plot_wave](fn.plot_wave.html) for the other parameters.
 
-					if verbose > 0 {
-						println!(
-							"\nWaveCAD file converted to text and saved: {} -> {}",
-							path, new_path
-						);
-					}
-				}
+"amount" parameter:
+```ignore
+Plot the waveform which is stored in preset 12:
+"12"
+```
 
-				Err(e) => {
-					res = Err(Error::with_description(
-						&format!("failed creating file: {}: {}", new_path, e),
-						ErrorKind::Io,
-					));
-					return res;
-				}
-			}
-		}
+Returns the plot.
+*/
+pub fn plot_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: &str,
+	compare: Option<&Source>,
+	plot: &PlotOptions,
+	channel: u16,
+	resample: &ResampleOptions,
+	verbose: u64,
+) -> Result<String, clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(
-				&format!("failed opening file: {}: {}", path, e),
-				ErrorKind::Io,
-			));
-			return res;
-		}
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	res
-}
+	let wave = read_arbitrary_wave(port, amount, verbose)?;
 
-/** Convert the device's arbitrary waveform text (.txt) file format
-to a WaveCAD (.wav) file.
+	plot_wave(Some(port), &wave, compare, plot, channel, resample, verbose)
+}
 
-Outputs the resulting binary file to stdout if the "output_binary"
-parameter is set to true.
+/** Read one of the device's arbitrary wave preset slots, and save an
+image of it. See [render_wave](fn.render_wave.html) for the other
+parameters.
 
-"path" parameter:
+"amount" parameter:
 ```ignore
-some-txt-file-to-convert.txt:
-"some-txt-file-to-convert.txt"
+Draw the waveform which is stored in preset 12:
+"12"
 ```
-*/
-pub fn txt_to_wav(path: &str, output_binary: bool, verbose: u64) -> Result<String, clap::Error> {
-	let mut res: Result<String, clap::Error>;
-
-	if path == "" {
-		return Err(Error::with_description(
-			&format!(
-				"unsupported path passed as \"txt_to_wav\" argument (must not be blank): {}",
-				path
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
 
-	let new_path: &str = &change_file_extension(path, ".wav");
+Returns the image file's contents.
+*/
+pub fn render_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: &str,
+	out_path: &str,
+	render: &RenderOptions,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-	match fs::File::open(path) {
-		Ok(mut file) => {
-			// Buffer to hold the input file contents.
-			let mut stringbuf = String::new();
-			res = file.read_to_string(&mut stringbuf).map_or_else(
-				|e| {
-					Err(Error::with_description(
-						&format!("failed reading file: {}: {}", path, e),
-						ErrorKind::Io,
-					))
-				},
-				|_res| Ok("".to_string()),
-			);
+	// Check the image's file name before doing any work.
+	RenderFormat::from_path(out_path)?;
 
-			if res.is_err() {
-				return res;
-			}
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
 
-			// Split the input file buffer on newlines.
-			let stringbuf: Vec<&str> = stringbuf.split("\n").collect();
-
-			// Convert the input file to WaveCAD format.
-			let buf: Vec<i16> = stringbuf
-				.iter()
-				.map(|val| {
-					val.chars()
-						.map(|c| {
-							// Convert number characters to integers.
-							c.to_digit(10).unwrap() as i16
-						})
-						.fold(
-							// Join individual digits into full numbers.
-							0,
-							|acc, elem| acc * 10 + elem,
-						)
-				})
-				.map(
-					// Adjust the numbers so they're in the correct
-					// range for the WaveCAD format.
-					|n| n - 2048,
-				)
-				.collect();
-
-			// Remove trailing 0 because of newline.
-			let buf = &buf[..buf.len() - 1];
-
-			// Buffer to hold the final WaveCAD format bytes.
-			let mut outbuf = [0u8; 4096];
-
-			// Write to the WaveCAD buffer.
-			LittleEndian::write_i16_into(&buf, &mut outbuf);
-
-			// Save the new WaveCAD file.
-			match fs::File::create(&format!("{}", new_path)) {
-				Ok(mut outfile) => {
-					res = outfile.write_all(&outbuf).map_or_else(
-						|e| {
-							Err(Error::with_description(&format!("failed writing to file: {}: {}", new_path, e), ErrorKind::Io))
-						},
-						|_res| {
-							if output_binary {
-								if verbose > 0 {
-									return Err(Error::with_description(&format!("failed outputting binary to stdout: you can't do this when the verbosity level is greater than 0"), ErrorKind::InvalidValue));
-								}
+	let wave = read_arbitrary_wave(port, amount, verbose)?;
 
-								let mut out = std::io::stdout();
-								out.write_all(&outbuf).unwrap();
-								out.flush().unwrap();
-							}
+	render_wave(&wave, out_path, render, verbose)
+}
 
-							Ok("".to_string())
-						}
-					);
+/** Read one of the device's arbitrary wave preset slots, and save it
+in another generator's file format. See
+[export_wave](fn.export_wave.html) for the other parameters.
 
-					if res.is_err() {
-						return res;
-					}
+"amount" parameter:
+```ignore
+Export the waveform which is stored in preset 12:
+"12"
+```
 
-					if verbose > 0 {
-						println!(
-							"\nText file converted to WaveCAD and saved: {} -> {}",
-							path, new_path
-						);
-					}
-				}
+Returns the exported file's contents.
+*/
+pub fn export_arbitrary_wave(
+	port: &mut SerialPortType,
+	amount: &str,
+	out_path: &str,
+	export: &ExportOptions,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	let amount = parse_wave_slot(amount)?;
 
-				Err(e) => {
-					res = Err(Error::with_description(
-						&format!("failed creating file: {}: {}", new_path, e),
-						ErrorKind::Io,
-					));
-					return res;
-				}
-			}
-		}
+	// Check the options before doing any work.
+	export.validate()?;
+	export.format_for(out_path)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(
-				&format!("failed opening file: {}: {}", path, e),
-				ErrorKind::Io,
-			));
-			return res;
-		}
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	res
-}
-
-/** Write a user-defined arbitrary waveform to the device.
+	let wave = read_arbitrary_wave(port, amount, verbose)?;
 
-Use the helper function
-[set_arbitrary_wave_stdin](fn.set_arbitrary_wave_stdin.html)
-instead to accept the text input for the "data" parameter
-from stdin.
+	export_wave(&wave, out_path, export, verbose)
+}
 
-Specify which arbitrary wave preset to save it in, as the
-"amount" parameter.
+/** Read all of the device's arbitrary wave slots, and save each one
+to a file in the device's text format, in the directory "dir", which
+is created if it doesn't exist. A manifest with the SHA-256 hash of
+each file and the device's serial number is saved with them, after
+all the slots are read. See [backup](../backup/index.html) for the
+file names and the manifest's format.
 
-"data" parameter, some user-defined wave. Must be 2048
-lines of ASCII whole numbers, each in the range of 0 - 4095,
-followed by one extra blank line. For example:
+"dir" parameter:
 ```ignore
-"2456"
-"3016"
-"4054"
-"4001"
-...
-"3244"
-"2010"
-"1012"
-
+"backups/unit-1"
 ```
+
+Returns the manifest.
 */
-pub fn set_arbitrary_wave(
+pub fn backup_arbitrary_waves(
 	port: &mut SerialPortType,
-	amount: f64,
-	data: &[String],
+	dir: &str,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+) -> Result<BackupManifest, clap::Error> {
+	fs::create_dir_all(dir).map_err(|e| {
+		Error::with_description(
+			&format!("failed creating directory: {}: {}", dir, e),
+			ErrorKind::Io,
+		)
+	})?;
 
-	let command: String;
+	let mut manifest = BackupManifest {
+		serial: Some(read_serial(port, verbose)?.1),
+		entries: Vec::new(),
+	};
 
-	if amount < SET_ARBITRARY_WAVE_ARG_NUM_MIN || amount > SET_ARBITRARY_WAVE_ARG_NUM_MAX {
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported slot number. Must be {}-{}.",
-				SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX
-			),
-			ErrorKind::InvalidValue,
-		));
-	}
+	for slot in SET_ARBITRARY_WAVE_ARG_NUM_MIN as u8..=SET_ARBITRARY_WAVE_ARG_NUM_MAX as u8 {
+		if !port.mock {
+			// Wait a bit to allow the device some time to settle.
+			thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+		}
+
+		let data = read_arbitrary_wave(port, slot as f64, verbose)?.to_txt_string().into_bytes();
+		let file = backup_file_name(slot);
+		let path = Path::new(dir).join(&file);
+
+		fs::write(&path, &data).map_err(|e| {
+			Error::with_description(
+				&format!("failed writing to file: {}: {}", path.display(), e),
+				ErrorKind::Io,
+			)
+		})?;
 
-	if verbose > 0 {
-		println!("\nInput arbitrary waveform data (one integer from 0-4095 per line, and 2048 lines total):\n");
+		if verbose > 0 {
+			println!("\nArbitrary wave slot {} saved: {}", slot, path.display());
+		}
+
+		manifest.entries.push(BackupEntry {
+			slot,
+			file,
+			hash: sha256_hex(&data),
+		});
 	}
 
-	let amount_str = format!("{:02}", amount);
+	let path = Path::new(dir).join(BACKUP_MANIFEST);
 
-	let mut arg = String::new();
+	fs::write(&path, manifest.to_string()).map_err(|e| {
+		Error::with_description(
+			&format!("failed writing to file: {}: {}", path.display(), e),
+			ErrorKind::Io,
+		)
+	})?;
 
-	let mut i = 0;
-	for line in data {
-		let line_len = line.chars().collect::<Vec<char>>().len();
-		i += 1;
+	println!("backed up {} arbitrary wave slots: {}", manifest.entries.len(), dir);
 
-		if line_len < 1 {
-			break;
-		}
+	Ok(manifest)
+}
 
-		match line.parse::<u32>() {
-			Ok(num) => {
-				if num > 4095 {
-					return Err(Error::with_description(&format!("Invalid arbitrary wave data. Must be 2048 lines of integers in the range of 0 - 4095: Number out of range: {}: on line: {}", line, i), ErrorKind::InvalidValue));
-				}
+/** Write the arbitrary wave slots saved by
+[backup_arbitrary_waves](fn.backup_arbitrary_waves.html) in the
+directory "dir" back to the device, skipping the slots which already
+match. Every file is checked against its hash in the manifest before
+any slots are written, so a damaged backup doesn't get partly restored.
+A note is printed if the backup came from a different device.
 
-				arg += &(line.chars().collect::<String>() + ",");
-			}
+Returns the slots which were written.
+*/
+pub fn restore_arbitrary_waves(
+	port: &mut SerialPortType,
+	dir: &str,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	let read = |path: &Path| {
+		fs::read(path).map_err(|e| {
+			Error::with_description(
+				&format!("failed reading file: {}: {}", path.display(), e),
+				ErrorKind::Io,
+			)
+		})
+	};
 
-			Err(e) => {
-				return Err(Error::with_description(&format!("Invalid arbitrary wave data. Must be 2048 lines of integers in the range of 0 - 4095: Invalid number: {}: on line: {}: {}", line, i, e), ErrorKind::InvalidValue));
-			}
-		}
-	}
+	let path = Path::new(dir).join(BACKUP_MANIFEST);
 
-	// 2048 for stdin, 2049 for wavecad
-	if i != 2048 && i != 2049 {
-		return Err(Error::with_description(&format!("Invalid arbitrary wave data. Must be 2048 lines of integers in the range of 0 - 4095: Incorrect number of lines: {}", i), ErrorKind::InvalidValue));
-	}
+	let manifest = BackupManifest::parse(&String::from_utf8_lossy(&read(&path)?))?;
 
-	arg = arg
-		.chars()
-		.take(arg.chars().collect::<Vec<char>>().len() - 1)
-		.collect::<String>()
-		.to_string();
+	let mut waves = Vec::with_capacity(manifest.entries.len());
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		SET_ARBITRARY_WAVE_COMMAND,
-		amount_str,
-		COMMAND_SEPARATOR,
-		arg,
-		COMMAND_END,
-	);
+	for entry in manifest.entries.iter() {
+		let path = Path::new(dir).join(&entry.file);
+		let data = read(&path)?;
 
-	if verbose > 0 {
-		println!(
-			"\nWriting arbitrary wave to slot {}:\n\n{}",
-			amount, command
-		);
+		if sha256_hex(&data) != entry.hash {
+			return Err(Error::with_description(
+				&format!("backup file doesn't match its SHA-256 hash in the manifest: {}", path.display()),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		let text = str::from_utf8(&data).map_err(|e| {
+			Error::with_description(
+				&format!("failed reading file: {}: {}", path.display(), e),
+				ErrorKind::InvalidValue,
+			)
+		})?;
+
+		waves.push((entry.slot, WaveData::from_txt_str(text)?));
 	}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..SET_ARBITRARY_WAVE_RES_LEN).collect();
+	let serial = read_serial(port, verbose)?.1;
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		port.port.as_mut().unwrap().read(&mut outbuf[..])?;
+	if let Some(backup_serial) = manifest.serial.as_ref().filter(|backup_serial| **backup_serial != serial) {
+		println!("note: the backup is from a different device: serial {}, restoring to serial {}", backup_serial, serial);
 	}
 
-	let res = str::from_utf8(&outbuf).unwrap();
+	let mut written = Vec::new();
+	let verify = port.verify;
 
-	if verbose > 0 {
-		println!("Response:");
-		println!("{}", res);
+	for (slot, wave) in waves.iter() {
+		if sync_arbitrary_wave(port, *slot, wave, verify, verbose)? {
+			written.push(*slot);
+		} else if verbose > 0 {
+			println!("\nArbitrary wave slot {} already matches the backup, skipping it.", slot);
+		}
 	}
 
-	Ok(res.to_string())
+	println!(
+		"restored {} arbitrary wave slots, skipped {} which already matched: {}",
+		written.len(),
+		waves.len() - written.len(),
+		dir
+	);
+
+	Ok(written)
 }
 
-/** Write a user-defined arbitrary waveform to the device from a WaveCAD (.wav)
-file, saving it into one of the device's arbitrary wave preset slots.
+/** Write a wave to one of the device's arbitrary wave slots, unless
+the slot already has it. If "verify" is true, the slot is read back
+afterwards, and written again if it doesn't match, as in
+[set_arbitrary_wave](fn.set_arbitrary_wave.html).
 
-"arg" parameter:
-```ignore
-Save the wav file into preset 1:
-"1,some-wav-file-to-upload.wav"
-```
+Returns true if the wave was written.
 */
-pub fn set_arbitrary_wavecad(
-	mut port: &mut SerialPortType,
-	arg: &str,
+fn sync_arbitrary_wave(
+	port: &mut SerialPortType,
+	slot: u8,
+	wave: &WaveData,
+	verify: bool,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	let arg_parts: Vec<&str> = arg.split(",").collect();
-
-	if arg_parts.len() < 2 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{},<file_path>): slot number and file path must be present and separated with a comma but no space: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, arg), ErrorKind::InvalidValue));
+) -> Result<bool, clap::Error> {
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 	}
 
-	let amount = arg_parts[0];
-
-	let amount_parts: Vec<&str> = amount.split(".").collect();
-
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+	if read_arbitrary_wave(port, slot as f64, verbose)? == *wave {
+		return Ok(false);
 	}
 
-	let path = arg_parts[1];
-
-	let res: Result<String, clap::Error>;
+	upload_arbitrary_wave(port, slot as f64, wave, verify, verbose)?;
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_ARBITRARY_WAVE_ARG_NUM_MIN
-				&& amount <= SET_ARBITRARY_WAVE_ARG_NUM_MAX =>
-			{
-				let data = wav_to_txt(path, verbose);
+	Ok(true)
+}
 
-				if data.is_err() {
-					return data;
-				}
+/** Make the device's arbitrary wave slots match a provisioning
+manifest, uploading only the slots which differ, and reading each one
+back afterwards to check it. See [provision](../provision/index.html)
+for the manifest's format. All the waves are prepared before any slots
+are written, so a mistake in the manifest doesn't leave the device
+partly provisioned. A line of progress is printed for each slot.
 
-				let data: Vec<String> = data
-					.unwrap()
-					.split("\n")
-					.map(|res| res.to_string())
-					.collect();
+"path" parameter, a YAML or TOML manifest:
+```ignore
+"slots.yaml" | "slots.toml"
+```
 
-				res = set_arbitrary_wave(&mut port, amount, &data, verbose);
-			}
+"catalog_dir" parameter, where the device's slot catalogue is kept,
+to record the source and hash of each slot in, or `None` to leave it.
+See [sync_arbitrary_wave_catalog](fn.sync_arbitrary_wave_catalog.html).
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
+Returns the slots which were written.
+*/
+pub fn provision_arbitrary_waves(
+	port: &mut SerialPortType,
+	path: &str,
+	catalog_dir: Option<&str>,
+	verbose: u64,
+) -> Result<Vec<u8>, clap::Error> {
+	let manifest = ProvisionManifest::load(path)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wavecad\" argument (must be {}-{}): {}: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
-		}
-	}
+	let mut waves = Vec::with_capacity(manifest.slots.len());
 
-	res
-}
+	for slot in manifest.slots.iter() {
+		let wave = load_wave_source(&slot.source, verbose).map_err(|e| {
+			Error::with_description(&format!("slot {}: {}: {}", slot.slot, slot.description, e.message.trim_start_matches("error: ")), e.kind)
+		})?;
 
-/** Write a user-defined arbitrary waveform to the device from stdin.
+		waves.push(wave);
+	}
 
-Specify which arbitrary wave preset to save it in, as the
-"amount" parameter.
+	let mut written = Vec::new();
 
-The stdin values define some user-defined wave. Must be 2048
-lines of ASCII whole numbers, each in the range of 0 - 4095,
-followed by one extra blank line. For example:
-```ignore
-"2456"
-"3016"
-"4054"
-"4001"
-...
-"3244"
-"2010"
-"1012"
+	for (i, (slot, wave)) in manifest.slots.iter().zip(waves.iter()).enumerate() {
+		let res = if sync_arbitrary_wave(port, slot.slot, wave, true, verbose)? {
+			written.push(slot.slot);
 
-```
-*/
-pub fn set_arbitrary_wave_stdin(
-	mut port: &mut SerialPortType,
-	amount: &str,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+			"uploaded and verified"
+		} else {
+			"already matches"
+		};
 
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+		println!("[{}/{}] slot {}: {}: {}", i + 1, manifest.slots.len(), slot.slot, slot.description, res);
 	}
 
-	let res: Result<String, clap::Error>;
+	println!(
+		"provisioned {} arbitrary wave slots, skipped {} which already matched: {}",
+		written.len(),
+		manifest.slots.len() - written.len(),
+		path
+	);
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= SET_ARBITRARY_WAVE_ARG_NUM_MIN
-				&& amount <= SET_ARBITRARY_WAVE_ARG_NUM_MAX =>
-			{
-				res = set_arbitrary_wave_stdin_inner(&mut port, amount, verbose);
-			}
+	if let Some(catalog_dir) = catalog_dir {
+		let serial = read_serial(port, verbose)?.1;
+		let catalog_path = catalog_path(catalog_dir, &serial);
+		let mut catalog = SlotCatalog::load_or_new(&catalog_path, &serial)?;
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
+		for (slot, wave) in manifest.slots.iter().zip(waves.iter()) {
+			let entry = catalog.entry_mut(slot.slot);
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"write arbitrary wave\" argument (must be {}-{}): {}: {}", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
+			entry.source = slot.description.clone();
+			entry.hash = sha256_hex(wave.to_txt_string().as_bytes());
+			entry.changed = false;
 		}
+
+		catalog.save(&catalog_path)?;
 	}
 
-	res
+	Ok(written)
 }
 
-fn set_arbitrary_wave_stdin_inner(
+/** Read or generate the wave for a slot in a provisioning manifest. */
+fn load_wave_source(source: &WaveSource, verbose: u64) -> Result<WaveData, clap::Error> {
+	match source {
+		WaveSource::File { path, channel, resample, convert } => load_wave_file(path, *channel, resample, convert, verbose),
+		WaveSource::Shape { spec, convert } => Ok(generate_wave(spec, convert)?.0),
+		WaveSource::Expr { expr, auto_scale, convert } => Ok(generate_expr_wave(expr, *auto_scale, convert)?.0),
+		WaveSource::Fourier { series, convert } => Ok(generate_fourier_wave(series, convert)?.0),
+	}
+}
+
+/** Load the slot catalogue of the device on "port" from "catalog_dir".
+Returns the catalogue, and its path. */
+fn load_device_catalog(
 	port: &mut SerialPortType,
-	amount: f64,
+	catalog_dir: &str,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	let data: Vec<String> = io::stdin()
-		.lock()
-		.lines()
-		.collect::<Result<_, _>>()
-		.map_or_else(|_e| Vec::new(), |res| res);
-
-	let data_len = data.len();
-
-	if data_len != 2048 {
-		return Err(Error::with_description(&format!("Invalid arbitrary wave data from stdin. Must be 2048 lines of integers in the range of 0 - 4095: Incorrect number of lines: {}", data_len), ErrorKind::InvalidValue));
-	}
+) -> Result<(SlotCatalog, PathBuf), clap::Error> {
+	let serial = read_serial(port, verbose)?.1;
+	let path = catalog_path(catalog_dir, &serial);
 
-	return set_arbitrary_wave(port, amount, &data[0..2048], verbose);
+	Ok((SlotCatalog::load_or_new(&path, &serial)?, path))
 }
 
-/** Read a user-defined arbitrary waveform from one of the device's
-numbered arbitrary wave preset slots.
+/** Print the catalogue of what's in the device's arbitrary wave
+slots, which is kept in "catalog_dir" on the computer, with a name,
+source, description, and content hash for each slot. The device's
+serial number is read to find its catalogue, but the slots aren't, so
+use [sync_arbitrary_wave_catalog](fn.sync_arbitrary_wave_catalog.html)
+to check that they still match. See [catalog](../catalog/index.html)
+for more details.
 
-"amount" parameter:
+"catalog_dir" parameter:
 ```ignore
-Get the waveform data which is stored in preset 1:
-"1"
+&default_catalog_dir().unwrap()
 ```
+
+Returns the catalogue.
 */
-pub fn get_arbitrary_wave(
-	mut port: &mut SerialPortType,
-	amount: &str,
+pub fn list_arbitrary_waves(
+	port: &mut SerialPortType,
+	catalog_dir: &str,
 	verbose: u64,
-) -> Result<String, clap::Error> {
-	if !port.mock {
-		// Wait a bit to allow the device some time to settle.
-		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
-	}
+) -> Result<SlotCatalog, clap::Error> {
+	let (catalog, path) = load_device_catalog(port, catalog_dir, verbose)?;
 
-	let amount_parts: Vec<&str> = amount.split(".").collect();
+	println!("arbitrary wave catalogue for device {}: {}", catalog.serial, path.display());
 
-	if amount_parts.len() > 1 {
-		return Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}: too many decimal places (0 max)", SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
+	if catalog.entries.is_empty() {
+		println!("no slots are catalogued yet");
+	} else {
+		print!("{}", catalog.to_table());
 	}
 
-	let res: Result<String, clap::Error>;
+	Ok(catalog)
+}
 
-	match amount.parse::<f64>() {
-		Ok(amount) => match amount {
-			_y if amount >= GET_ARBITRARY_WAVE_ARG_NUM_MIN
-				&& amount <= GET_ARBITRARY_WAVE_ARG_NUM_MAX =>
-			{
-				res = get_arbitrary_wave_inner(&mut port, amount, verbose);
-			}
+/** Read all of the device's arbitrary wave slots, and compare them
+with the hashes in its catalogue in "catalog_dir", flagging the slots
+which were changed from the front panel or by another computer since
+they were catalogued. Slots which weren't catalogued yet are added,
+without names. A changed slot stays flagged until it's named again
+with [name_arbitrary_wave](fn.name_arbitrary_wave.html), or
+provisioned, or changed back.
 
-			_ => {
-				res = Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}", GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX, amount), ErrorKind::InvalidValue));
-			}
-		},
+Returns the updated catalogue.
+*/
+pub fn sync_arbitrary_wave_catalog(
+	port: &mut SerialPortType,
+	catalog_dir: &str,
+	verbose: u64,
+) -> Result<SlotCatalog, clap::Error> {
+	let (mut catalog, path) = load_device_catalog(port, catalog_dir, verbose)?;
 
-		Err(e) => {
-			res = Err(Error::with_description(&format!("unsupported value passed to \"read arbitrary wave\" argument (must be {}-{}): {}: {}", GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX, amount, e), ErrorKind::InvalidValue));
+	let (mut added, mut changed) = (0, 0);
+
+	for slot in SET_ARBITRARY_WAVE_ARG_NUM_MIN as u8..=SET_ARBITRARY_WAVE_ARG_NUM_MAX as u8 {
+		if !port.mock {
+			// Wait a bit to allow the device some time to settle.
+			thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
 		}
-	}
 
-	res
-}
+		let hash = sha256_hex(read_arbitrary_wave(port, slot as f64, verbose)?.to_txt_string().as_bytes());
+		let is_new = catalog.entry(slot).is_none();
+		let entry = catalog.entry_mut(slot);
 
-fn get_arbitrary_wave_inner(
-	port: &mut SerialPortType,
-	amount: f64,
-	verbose: u64,
-) -> Result<String, clap::Error> {
-	let command: String;
+		if is_new {
+			entry.hash = hash;
+			added += 1;
+		} else {
+			entry.changed = entry.hash != hash;
 
-	if amount < GET_ARBITRARY_WAVE_ARG_NUM_MIN || amount > GET_ARBITRARY_WAVE_ARG_NUM_MAX {
-		return Err(Error::with_description(
-			&format!(
-				"Unsupported slot number. Must be {}-{}.",
-				GET_ARBITRARY_WAVE_ARG_NUM_MIN, GET_ARBITRARY_WAVE_ARG_NUM_MAX
-			),
-			ErrorKind::InvalidValue,
-		));
+			if entry.changed {
+				println!("slot {}: changed since it was catalogued: {}", slot, if entry.name.is_empty() { "(no name)" } else { &entry.name });
+				changed += 1;
+			}
+		}
 	}
 
-	let amount_str = format!("{:02}", amount);
+	catalog.save(&path)?;
 
-	command = format!(
-		"{}{}{}{}{}{}",
-		COMMAND_BEGIN,
-		GET_ARBITRARY_WAVE_COMMAND,
-		amount_str,
-		COMMAND_SEPARATOR,
-		GET_ARBITRARY_WAVE_ARG2,
-		COMMAND_END,
+	println!(
+		"synced arbitrary wave catalogue for device {}: {} changed, {} added, {} unchanged: {}",
+		catalog.serial,
+		changed,
+		added,
+		catalog.entries.len() - changed - added,
+		path.display()
 	);
 
-	if verbose > 0 {
-		println!(
-			"\nReading arbitrary wave from slot: {}:\n{}",
-			amount, command
-		);
-	}
+	Ok(catalog)
+}
 
-	let inbuf: Vec<u8> = command.as_bytes().to_vec();
-	let mut outbuf: Vec<u8> = (0..GET_ARBITRARY_WAVE_RES_LEN).map(|_val| 0u8).collect();
+/** Give one of the device's arbitrary wave slots a name in its
+catalogue in "catalog_dir", and optionally a source and description.
+The slot is read, and its current contents are recorded as the
+catalogued ones, clearing any flag that it changed.
 
-	let mut res: &str = "";
-	let mut res_str: String = "".to_string();
-	let mut n = 0;
+"amount" parameter, the slot:
+```ignore
+"37"
+```
 
-	if !port.mock {
-		port.port.as_mut().unwrap().write(&inbuf[..])?;
-		n = 0;
-		let mut chunk: &str;
-
-		while n < GET_ARBITRARY_WAVE_RES_LEN as usize {
-			match port.port.as_mut().unwrap().read(&mut outbuf[n..]) {
-				Ok(val) => {
-					// Track buffer position.
-					n += val;
-
-					// Count number of commas to detect end of buffer.
-					chunk = str::from_utf8(&outbuf[..]).unwrap();
-					let num_commas = chunk.matches(',').count();
-					if num_commas == 2048 {
-						if verbose > 0 {
-							println!("\nReached end of buffer: Detected all expected commas\n");
-						}
-
-						break;
-					}
-				}
+"name", "source", and "description" parameters:
+```ignore
+"Chirp", Some("shape chirp start=1 end=20"), Some("For the filter tests.")
+```
 
-				Err(e) => {
-					return Err(Error::with_description(
-						&format!("Reached end of buffer unexpectedly: {}.", e),
-						ErrorKind::InvalidValue,
-					));
-				}
-			}
-		}
+Returns the slot's entry in the catalogue.
+*/
+pub fn name_arbitrary_wave(
+	port: &mut SerialPortType,
+	catalog_dir: &str,
+	amount: &str,
+	name: &str,
+	source: Option<&str>,
+	description: Option<&str>,
+	verbose: u64,
+) -> Result<CatalogEntry, clap::Error> {
+	let slot = parse_wave_slot(amount)? as u8;
 
-		res = str::from_utf8(&outbuf[..n]).unwrap();
+	let (mut catalog, path) = load_device_catalog(port, catalog_dir, verbose)?;
 
-		let res_parts: Vec<&str> = res.split("=").collect();
+	if !port.mock {
+		// Wait a bit to allow the device some time to settle.
+		thread::sleep(time::Duration::from_millis(COMMAND_DELAY_MS));
+	}
 
-		if res_parts.len() < 2 {
-			return Err(Error::with_description(
-				&format!("Invalid response from device: {}", res),
-				ErrorKind::Io,
-			));
-		}
+	let hash = sha256_hex(read_arbitrary_wave(port, slot as f64, verbose)?.to_txt_string().as_bytes());
+	let entry = catalog.entry_mut(slot);
 
-		let res_data: Vec<&str> = res_parts[1].split(",").collect();
-		let res_data = &res_data[..res_data.len() - 1];
+	entry.name = name.to_string();
+	entry.hash = hash;
+	entry.changed = false;
 
-		res_str = res_data.join("\n");
+	if let Some(source) = source {
+		entry.source = source.to_string();
 	}
 
-	if verbose > 0 {
-		println!("Response size: {} bytes\n", n);
-		println!("Response:");
-		println!("{}\n", res);
-	} else {
-		println!("{}", res_str);
+	if let Some(description) = description {
+		entry.description = description.to_string();
 	}
 
-	Ok(res_str)
+	let entry = entry.clone();
+
+	catalog.save(&path)?;
+
+	println!("slot {} catalogued as: {}", slot, entry.name);
+
+	Ok(entry)
 }
@@ -9,9 +9,25 @@ software.
 
 /*! Unit Tests */
 
+use super::analyze::*;
+use super::backup::*;
 use super::serial::*;
 use super::command::*;
-use super::protocol::*;
+use super::convert::*;
+use super::export::*;
+use super::fourier::*;
+use super::ops::*;
+use super::plot::*;
+use super::register::*;
+use super::render::*;
+use super::resample::*;
+use super::transfer::*;
+use super::units::*;
+use super::wave::*;
+
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
 	
 #[test]
 pub fn get_model_ok() {
@@ -365,29 +381,29 @@ pub fn set_frequency_microhertz_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_microhertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN.to_string(), verbose).unwrap();
+			set_frequency_microhertz(&mut port, chan, &FREQUENCY_MICROHERTZ.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_microhertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX.to_string(), verbose).unwrap();
+			set_frequency_microhertz(&mut port, chan, &FREQUENCY_MICROHERTZ.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_microhertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_frequency_microhertz(&mut port, chan, &(FREQUENCY_MICROHERTZ.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_frequency_microhertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(FREQUENCY_MICROHERTZ.decimal_places + 1) {
+				set_frequency_microhertz(&mut port, chan, &(FREQUENCY_MICROHERTZ.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -401,22 +417,22 @@ pub fn set_frequency_microhertz_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_microhertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_microhertz(&mut port, chans, &(FREQUENCY_MICROHERTZ.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_microhertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_microhertz(&mut port, chans, &(FREQUENCY_MICROHERTZ.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_microhertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_frequency_microhertz(&mut port, chans, &(FREQUENCY_MICROHERTZ.min + (1.0 / ((10.0 as f64).powf((FREQUENCY_MICROHERTZ.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_microhertz(&mut port, chans + 1, &(SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_frequency_microhertz(&mut port, chans + 1, &(FREQUENCY_MICROHERTZ.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -430,29 +446,29 @@ pub fn set_frequency_millihertz_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_millihertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN.to_string(), verbose).unwrap();
+			set_frequency_millihertz(&mut port, chan, &FREQUENCY_MILLIHERTZ.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_millihertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX.to_string(), verbose).unwrap();
+			set_frequency_millihertz(&mut port, chan, &FREQUENCY_MILLIHERTZ.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_millihertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_frequency_millihertz(&mut port, chan, &(FREQUENCY_MILLIHERTZ.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_frequency_millihertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(FREQUENCY_MILLIHERTZ.decimal_places + 1) {
+				set_frequency_millihertz(&mut port, chan, &(FREQUENCY_MILLIHERTZ.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -466,22 +482,22 @@ pub fn set_frequency_millihertz_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_millihertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_millihertz(&mut port, chans, &(FREQUENCY_MILLIHERTZ.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_millihertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_millihertz(&mut port, chans, &(FREQUENCY_MILLIHERTZ.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_millihertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_frequency_millihertz(&mut port, chans, &(FREQUENCY_MILLIHERTZ.min + (1.0 / ((10.0 as f64).powf((FREQUENCY_MILLIHERTZ.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_millihertz(&mut port, chans + 1, &(SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_frequency_millihertz(&mut port, chans + 1, &(FREQUENCY_MILLIHERTZ.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -495,29 +511,29 @@ pub fn set_frequency_hertz_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_hertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN.to_string(), verbose).unwrap();
+			set_frequency_hertz(&mut port, chan, &FREQUENCY_HERTZ.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_hertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX.to_string(), verbose).unwrap();
+			set_frequency_hertz(&mut port, chan, &FREQUENCY_HERTZ.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_hertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_frequency_hertz(&mut port, chan, &(FREQUENCY_HERTZ.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_frequency_hertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(FREQUENCY_HERTZ.decimal_places + 1) {
+				set_frequency_hertz(&mut port, chan, &(FREQUENCY_HERTZ.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -531,22 +547,22 @@ pub fn set_frequency_hertz_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_hertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_hertz(&mut port, chans, &(FREQUENCY_HERTZ.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_hertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_hertz(&mut port, chans, &(FREQUENCY_HERTZ.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_hertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_frequency_hertz(&mut port, chans, &(FREQUENCY_HERTZ.min + (1.0 / ((10.0 as f64).powf((FREQUENCY_HERTZ.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_hertz(&mut port, chans + 1, &(SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_frequency_hertz(&mut port, chans + 1, &(FREQUENCY_HERTZ.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -560,29 +576,29 @@ pub fn set_frequency_kilohertz_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_kilohertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN.to_string(), verbose).unwrap();
+			set_frequency_kilohertz(&mut port, chan, &FREQUENCY_KILOHERTZ.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_kilohertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX.to_string(), verbose).unwrap();
+			set_frequency_kilohertz(&mut port, chan, &FREQUENCY_KILOHERTZ.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_kilohertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_frequency_kilohertz(&mut port, chan, &(FREQUENCY_KILOHERTZ.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_frequency_kilohertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(FREQUENCY_KILOHERTZ.decimal_places + 1) {
+				set_frequency_kilohertz(&mut port, chan, &(FREQUENCY_KILOHERTZ.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -596,22 +612,22 @@ pub fn set_frequency_kilohertz_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_kilohertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_kilohertz(&mut port, chans, &(FREQUENCY_KILOHERTZ.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_kilohertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_kilohertz(&mut port, chans, &(FREQUENCY_KILOHERTZ.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_kilohertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_frequency_kilohertz(&mut port, chans, &(FREQUENCY_KILOHERTZ.min + (1.0 / ((10.0 as f64).powf((FREQUENCY_KILOHERTZ.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_kilohertz(&mut port, chans + 1, &(SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_frequency_kilohertz(&mut port, chans + 1, &(FREQUENCY_KILOHERTZ.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -625,29 +641,29 @@ pub fn set_frequency_megahertz_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_megahertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN.to_string(), verbose).unwrap();
+			set_frequency_megahertz(&mut port, chan, &FREQUENCY_MEGAHERTZ.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_megahertz(&mut port, chan, &SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX.to_string(), verbose).unwrap();
+			set_frequency_megahertz(&mut port, chan, &FREQUENCY_MEGAHERTZ.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_frequency_megahertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_frequency_megahertz(&mut port, chan, &(FREQUENCY_MEGAHERTZ.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_frequency_megahertz(&mut port, chan, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(FREQUENCY_MEGAHERTZ.decimal_places + 1) {
+				set_frequency_megahertz(&mut port, chan, &(FREQUENCY_MEGAHERTZ.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -661,22 +677,22 @@ pub fn set_frequency_megahertz_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_megahertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_megahertz(&mut port, chans, &(FREQUENCY_MEGAHERTZ.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_megahertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_frequency_megahertz(&mut port, chans, &(FREQUENCY_MEGAHERTZ.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_megahertz(&mut port, chans, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_frequency_megahertz(&mut port, chans, &(FREQUENCY_MEGAHERTZ.min + (1.0 / ((10.0 as f64).powf((FREQUENCY_MEGAHERTZ.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_frequency_megahertz(&mut port, chans + 1, &(SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_frequency_megahertz(&mut port, chans + 1, &(FREQUENCY_MEGAHERTZ.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -742,29 +758,29 @@ pub fn set_amplitude_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_amplitude(&mut port, chan, &SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN.to_string(), verbose).unwrap();
+			set_amplitude(&mut port, chan, &AMPLITUDE_VOLTS.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_amplitude(&mut port, chan, &SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX.to_string(), verbose).unwrap();
+			set_amplitude(&mut port, chan, &AMPLITUDE_VOLTS.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_amplitude(&mut port, chan, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX / 2.0).to_string(), verbose).unwrap();
+			set_amplitude(&mut port, chan, &(AMPLITUDE_VOLTS.max / 2.0).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_amplitude(&mut port, chan, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(AMPLITUDE_VOLTS.decimal_places + 1) {
+				set_amplitude(&mut port, chan, &(AMPLITUDE_VOLTS.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -778,22 +794,22 @@ pub fn set_amplitude_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_amplitude(&mut port, chans, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_amplitude(&mut port, chans, &(AMPLITUDE_VOLTS.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_amplitude(&mut port, chans, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_amplitude(&mut port, chans, &(AMPLITUDE_VOLTS.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_amplitude(&mut port, chans, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_amplitude(&mut port, chans, &(AMPLITUDE_VOLTS.min + (1.0 / ((10.0 as f64).powf((AMPLITUDE_VOLTS.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_amplitude(&mut port, chans + 1, &(SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_amplitude(&mut port, chans + 1, &(AMPLITUDE_VOLTS.min).to_string(), verbose).unwrap_err();
 	}
 }
 
@@ -833,29 +849,29 @@ pub fn set_duty_cycle_ok() {
 	// Test arg min.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_duty_cycle(&mut port, chan, &SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN.to_string(), verbose).unwrap();
+			set_duty_cycle(&mut port, chan, &DUTY_CYCLE.min.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg max.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_duty_cycle(&mut port, chan, &SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX.to_string(), verbose).unwrap();
+			set_duty_cycle(&mut port, chan, &DUTY_CYCLE.max.to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test arg middle.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			set_duty_cycle(&mut port, chan, &(((SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX / 2.0) * SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER).round() / SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER).to_string(), verbose).unwrap();
+			set_duty_cycle(&mut port, chan, &(((DUTY_CYCLE.max / 2.0) * DUTY_CYCLE.multiplier).round() / DUTY_CYCLE.multiplier).to_string(), verbose).unwrap();
 		}
 	}
 
 	// Test decimal places.
 	for verbose in 0..(verbose_max + 1) {
 		for chan in 1..(chans + 1) {
-			for decimal in 1..(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES + 1) {
-				set_duty_cycle(&mut port, chan, &(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
+			for decimal in 1..(DUTY_CYCLE.decimal_places + 1) {
+				set_duty_cycle(&mut port, chan, &(DUTY_CYCLE.min + (1.0 / ((10.0 as f64).powf(decimal as f64)))).to_string(), verbose).unwrap();
 			}
 		}
 	}
@@ -869,21 +885,706 @@ pub fn set_duty_cycle_err() {
 
 	// Test greater than arg max.
 	for verbose in 0..(verbose_max + 1) {
-		set_duty_cycle(&mut port, chans, &(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX + 1.0).to_string(), verbose).unwrap_err();
+		set_duty_cycle(&mut port, chans, &(DUTY_CYCLE.max + 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test less than arg min.
 	for verbose in 0..(verbose_max + 1) {
-		set_duty_cycle(&mut port, chans, &(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN - 1.0).to_string(), verbose).unwrap_err();
+		set_duty_cycle(&mut port, chans, &(DUTY_CYCLE.min - 1.0).to_string(), verbose).unwrap_err();
 	}
 
 	// Test too many decimal places.
 	for verbose in 0..(verbose_max + 1) {
-		set_duty_cycle(&mut port, chans, &(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN + (1.0 / ((10.0 as f64).powf((SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES + 1) as f64)))).to_string(), verbose).unwrap_err();
+		set_duty_cycle(&mut port, chans, &(DUTY_CYCLE.min + (1.0 / ((10.0 as f64).powf((DUTY_CYCLE.decimal_places + 1) as f64)))).to_string(), verbose).unwrap_err();
 	}
 
 	// Test invalid channel.
 	for verbose in 0..(verbose_max + 1) {
-		set_duty_cycle(&mut port, chans + 1, &(SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN).to_string(), verbose).unwrap_err();
+		set_duty_cycle(&mut port, chans + 1, &(DUTY_CYCLE.min).to_string(), verbose).unwrap_err();
 	}
 }
+
+
+#[test]
+pub fn verify_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	port.verify = true;
+	let verbose_max = 1;
+	let chans = 2;
+
+	for verbose in 0..(verbose_max + 1) {
+		set_channel_output(&mut port, "1,0", verbose).unwrap();
+		set_phase(&mut port, "180.5", verbose).unwrap();
+
+		for chan in 1..(chans + 1) {
+			set_waveform_preset(&mut port, chan, "sine", verbose).unwrap();
+			set_waveform_preset_arbitrary(&mut port, chan, "12", verbose).unwrap();
+			set_frequency_hertz(&mut port, chan, "1000.25", verbose).unwrap();
+			set_frequency_megahertz(&mut port, chan, "1.5", verbose).unwrap();
+			set_amplitude(&mut port, chan, "1.234", verbose).unwrap();
+			set_duty_cycle(&mut port, chan, "40.1", verbose).unwrap();
+			set_voltage_offset(&mut port, chan, "-1.25", verbose).unwrap();
+		}
+	}
+}
+
+#[test]
+pub fn verify_err() {
+	let mut port = SerialPortType::new("", true, 1).unwrap();
+	port.verify = true;
+	let chans = 2;
+
+	set_channel_output(&mut port, "1,0", 0).unwrap_err();
+
+	let e = set_phase(&mut port, "180.5", 0).unwrap_err();
+	assert_eq!(e.kind, clap::ErrorKind::ValueValidation);
+
+	for chan in 1..(chans + 1) {
+		set_waveform_preset(&mut port, chan, "sine", 0).unwrap_err();
+		set_frequency_hertz(&mut port, chan, "1000.25", 0).unwrap_err();
+		set_duty_cycle(&mut port, chan, "40.1", 0).unwrap_err();
+		set_voltage_offset(&mut port, chan, "-1.25", 0).unwrap_err();
+
+		let e = set_amplitude(&mut port, chan, "1.234", 0).unwrap_err();
+		assert_eq!(e.kind, clap::ErrorKind::ValueValidation);
+		assert_eq!(e.info, Some(vec![format!("amplitude volts ch{}", chan), "1.234".to_string(), "1.235".to_string()]));
+	}
+
+	// Settings which can't be read back can't be verified.
+	let e = set_sweep_time(&mut port, "500ms", 0).unwrap_err();
+	assert_eq!(e.kind, clap::ErrorKind::ArgumentConflict);
+
+	set_pulse_width(&mut port, "800ns", false, 0).unwrap_err();
+
+	// Not verifying, so the mismatch isn't noticed.
+	port.verify = false;
+	set_amplitude(&mut port, 1, "1.234", 0).unwrap();
+}
+
+
+#[test]
+pub fn set_frequency_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let verbose_max = 1;
+	let chans = 2;
+
+	let args = ["1.5kHz", "1000", "10MHz", "250mHz", "1.234567kHz", "0.5uHz"];
+
+	for verbose in 0..(verbose_max + 1) {
+		for chan in 1..(chans + 1) {
+			for arg in args.iter() {
+				set_frequency(&mut port, chan, arg, verbose).unwrap();
+			}
+		}
+	}
+}
+
+#[test]
+pub fn set_frequency_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let chans = 2;
+
+	let args = ["61MHz", "1.5kV", "kHz", "1.001uHz"];
+
+	for chan in 1..(chans + 1) {
+		for arg in args.iter() {
+			set_frequency(&mut port, chan, arg, 0).unwrap_err();
+		}
+	}
+
+	set_frequency(&mut port, chans + 1, "1kHz", 0).unwrap_err();
+}
+
+#[test]
+pub fn set_with_units_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	port.verify = true;
+
+	set_amplitude(&mut port, 1, "250mV", 0).unwrap();
+	set_amplitude(&mut port, 2, "3.3Vpp", 0).unwrap();
+	set_voltage_offset(&mut port, 1, "-250mV", 0).unwrap();
+	set_frequency_hertz(&mut port, 1, "1.5kHz", 0).unwrap();
+
+	// The sweep and pulse settings can't be read back to verify them.
+	port.verify = false;
+
+	set_sweep_time(&mut port, "500ms", 0).unwrap();
+	set_sweep_starting_frequency(&mut port, "1.5kHz", 0).unwrap();
+	set_pulse_width(&mut port, "800ns", false, 0).unwrap();
+	set_pulse_period(&mut port, "2.5ms", false, 0).unwrap();
+	set_pulse_amplitude(&mut port, "250mV", 0).unwrap();
+}
+
+#[test]
+pub fn set_with_units_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	set_amplitude(&mut port, 1, "21V", 0).unwrap_err();
+	set_amplitude(&mut port, 1, "1.2345V", 0).unwrap_err();
+	set_amplitude(&mut port, 1, "250mHz", 0).unwrap_err();
+	set_voltage_offset(&mut port, 1, "10V", 0).unwrap_err();
+	set_sweep_time(&mut port, "50ms", 0).unwrap_err();
+	set_pulse_width(&mut port, "802ns", false, 0).unwrap_err();
+}
+
+#[test]
+pub fn set_amplitude_load_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	port.verify = true;
+	port.load = [Load::Ohms50, Load::HighZ];
+
+	set_amplitude(&mut port, 1, "1.2345", 0).unwrap();
+	set_amplitude(&mut port, 1, "10dBm", 1).unwrap();
+	set_amplitude(&mut port, 1, "1Vrms", 0).unwrap();
+	set_amplitude(&mut port, 2, "1Vrms", 0).unwrap();
+
+	// The mock device reports 5V unterminated, and the raw response
+	// doesn't depend on the load.
+	assert_eq!(get_amplitude(&mut port, 1, 0).unwrap(), ":r25=5000.\r\n");
+	assert_eq!(get_amplitude(&mut port, 2, 0).unwrap(), ":r26=5000.\r\n");
+	assert_eq!(get_amplitude_as(&mut port, 1, AmplitudeUnit::Vpp, 0).unwrap(), "2.5");
+	assert_eq!(get_amplitude_as(&mut port, 2, AmplitudeUnit::Vrms, 1).unwrap(), "1.768");
+	assert_eq!(get_amplitude_as(&mut port, 1, AmplitudeUnit::Dbm, 0).unwrap(), "11.938");
+}
+
+#[test]
+pub fn set_amplitude_load_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	port.load = [Load::Ohms50, Load::HighZ];
+
+	// Over 20V unterminated.
+	set_amplitude(&mut port, 1, "10.5", 0).unwrap_err();
+	set_amplitude(&mut port, 1, "1.23456", 0).unwrap_err();
+
+	// dBm needs a load resistance.
+	set_amplitude(&mut port, 2, "10dBm", 0).unwrap_err();
+	get_amplitude_as(&mut port, 2, AmplitudeUnit::Dbm, 0).unwrap_err();
+}
+
+#[test]
+pub fn set_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	set_arbitrary_wave(&mut port, 1.0, &WaveData::default(), 0).unwrap();
+	set_arbitrary_wave(&mut port, 60.0, &WaveData::default(), 1).unwrap();
+}
+
+#[test]
+pub fn set_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	set_arbitrary_wave(&mut port, 0.0, &WaveData::default(), 0).unwrap_err();
+	set_arbitrary_wave(&mut port, 61.0, &WaveData::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn set_arbitrary_wave_verify_ok() {
+	let mut port = SerialPortType::new("", true, 2).unwrap();
+	let high = WaveData::new(&[4095; 2048]).unwrap();
+
+	// The first upload is cut short, and retried.
+	port.verify = true;
+
+	set_arbitrary_wave(&mut port, 7.0, &high, 0).unwrap();
+
+	assert_eq!(get_arbitrary_wave(&mut port, "7", 1).unwrap(), high);
+	assert_eq!(port.stats.commands["a07"].retries, 1);
+
+	// Without verifying, a cut short upload isn't noticed.
+	port.mock_num = 1;
+	port.verify = false;
+
+	set_arbitrary_wave(&mut port, 8.0, &high, 0).unwrap();
+
+	assert_eq!(get_arbitrary_wave(&mut port, "8", 1).unwrap().diff(&high), (1024..2048).collect::<Vec<usize>>());
+}
+
+#[test]
+pub fn set_arbitrary_wave_verify_err() {
+	let mut port = SerialPortType::new("", true, 1).unwrap();
+
+	// Every upload is cut short, so the retries run out.
+	port.verify = true;
+	port.wave_retries = 3;
+
+	let e = set_arbitrary_wave(&mut port, 9.0, &WaveData::new(&[4095; 2048]).unwrap(), 0).unwrap_err();
+
+	assert_eq!(e.kind, clap::ErrorKind::ValueValidation);
+	assert!(e.message.contains("after 4 attempts: 1024 samples differ, at indices: 1024-2047"));
+	assert_eq!(port.stats.commands["a09"].retries, 3);
+}
+
+#[test]
+pub fn arbitrary_wave_progress_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let seen = Rc::new(RefCell::new(Vec::new()));
+	let seen2 = seen.clone();
+
+	port.progress = Some(Box::new(move |progress: &Progress| seen2.borrow_mut().push(*progress)));
+
+	set_arbitrary_wave(&mut port, 3.0, &WaveData::new(&[4095; 2048]).unwrap(), 0).unwrap();
+	get_arbitrary_wave(&mut port, "3", 1).unwrap();
+
+	let seen = seen.borrow();
+	let uploads: Vec<&Progress> = seen.iter().filter(|progress| progress.direction == Direction::Upload).collect();
+	let downloads: Vec<&Progress> = seen.iter().filter(|progress| progress.direction == Direction::Download).collect();
+
+	for events in [&uploads, &downloads] {
+		assert_eq!(events[0].done, 0);
+		assert_eq!(events.last().unwrap().done, 2048);
+		assert!(events.windows(2).all(|pair| pair[0].done <= pair[1].done));
+		assert!(events.iter().all(|progress| progress.slot == 3 && progress.total == 2048));
+	}
+}
+
+#[test]
+pub fn set_arbitrary_wave_gen_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	let (wave, report) = set_arbitrary_wave_gen(&mut port, "12", "gauss sigma=0.05", &ConvertOptions::default(), 0).unwrap();
+
+	assert_eq!(wave.samples()[1024], 4095);
+	assert_eq!(report.clipped(), 0);
+}
+
+#[test]
+pub fn set_arbitrary_wave_gen_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	set_arbitrary_wave_gen(&mut port, "0", "sine", &ConvertOptions::default(), 0).unwrap_err();
+	set_arbitrary_wave_gen(&mut port, "1.5", "sine", &ConvertOptions::default(), 0).unwrap_err();
+	set_arbitrary_wave_gen(&mut port, "1", "triangle", &ConvertOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn set_arbitrary_wave_expr_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	let (wave, _) = set_arbitrary_wave_expr(&mut port, "60", "sin(2*pi*t)", true, &ConvertOptions::default(), 0).unwrap();
+
+	assert_eq!(wave.samples()[512], 4095);
+}
+
+#[test]
+pub fn set_arbitrary_wave_expr_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	set_arbitrary_wave_expr(&mut port, "61", "t", true, &ConvertOptions::default(), 0).unwrap_err();
+	set_arbitrary_wave_expr(&mut port, "1", "sin(", true, &ConvertOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn set_arbitrary_wave_fourier_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let series = FourierSeries::parse("1:1.0, 3:0.1@30, 5:0.02").unwrap();
+
+	let (_, report) = set_arbitrary_wave_fourier(&mut port, "12", &series, &ConvertOptions::default(), 1).unwrap();
+
+	assert_eq!(report.clipped(), 0);
+}
+
+#[test]
+pub fn set_arbitrary_wave_fourier_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let series = FourierSeries::parse("1:1").unwrap();
+
+	set_arbitrary_wave_fourier(&mut port, "0", &series, &ConvertOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn set_arbitrary_wave_op_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let pipeline = WavePipeline::parse("load slot:1 | mix _:0.5 slot:2:0.5 | window hann").unwrap();
+
+	let (wave, report) = set_arbitrary_wave_op(&mut port, "1", &pipeline, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap();
+
+	assert_eq!(wave, WaveData::default());
+	assert_eq!(report.clipped(), 0);
+}
+
+#[test]
+pub fn set_arbitrary_wave_op_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let pipeline = WavePipeline::parse("load slot:1").unwrap();
+
+	set_arbitrary_wave_op(&mut port, "61", &pipeline, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	let pipeline = WavePipeline::parse("load /nonexistent/wave.txt").unwrap();
+
+	set_arbitrary_wave_op(&mut port, "1", &pipeline, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn wave_op_err() {
+	// Slots can't be read without a device.
+	let pipeline = WavePipeline::parse("load slot:1").unwrap();
+
+	wave_op(None, &pipeline, None, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn analyze_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	let res = analyze_arbitrary_wave(&mut port, "1", &AnalysisOptions::default(), 0).unwrap();
+
+	assert_eq!(res.levels_used, 1);
+	assert_eq!(res.harmonics.len(), 10);
+}
+
+#[test]
+pub fn analyze_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	analyze_arbitrary_wave(&mut port, "61", &AnalysisOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn analyze_wave_file_err() {
+	analyze_wave_file(
+		"/nonexistent/wave.txt",
+		1,
+		&ResampleOptions::default(),
+		&ConvertOptions::default(),
+		&AnalysisOptions::default(),
+		0,
+	)
+	.unwrap_err();
+}
+
+#[test]
+pub fn backup_restore_arbitrary_waves_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-backup-test-{}", std::process::id()));
+	let dir_str = dir.to_str().unwrap();
+
+	let manifest = backup_arbitrary_waves(&mut port, dir_str, 0).unwrap();
+
+	assert_eq!(manifest.serial.as_deref(), Some("9876500000"));
+	assert_eq!(manifest.entries.len(), 60);
+	assert_eq!(manifest.entries[59].file, "slot60.txt");
+
+	// Mock mode reads back the same waves, so nothing is written.
+	assert_eq!(restore_arbitrary_waves(&mut port, dir_str, 0).unwrap(), Vec::<u8>::new());
+
+	// Change one slot, and update its hash.
+	let text = WaveData::new(&[4095; 2048]).unwrap().to_txt_string();
+
+	fs::write(dir.join("slot05.txt"), &text).unwrap();
+	let old = format!("{}  slot05.txt", manifest.entries[4].hash);
+	let new = format!("{}  slot05.txt", sha256_hex(text.as_bytes()));
+
+	fs::write(dir.join(BACKUP_MANIFEST), fs::read_to_string(dir.join(BACKUP_MANIFEST)).unwrap().replace(&old, &new)).unwrap();
+
+	assert_eq!(restore_arbitrary_waves(&mut port, dir_str, 1).unwrap(), vec![5]);
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn restore_arbitrary_waves_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-restore-test-{}", std::process::id()));
+	let dir_str = dir.to_str().unwrap();
+
+	// No manifest.
+	restore_arbitrary_waves(&mut port, "/nonexistent/backup", 0).unwrap_err();
+
+	backup_arbitrary_waves(&mut port, dir_str, 0).unwrap();
+
+	// A file which doesn't match its hash.
+	fs::write(dir.join("slot01.txt"), "0\n").unwrap();
+
+	restore_arbitrary_waves(&mut port, dir_str, 0).unwrap_err();
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn provision_arbitrary_waves_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-provision-test-{}", std::process::id()));
+
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("high.txt"), WaveData::new(&[4095; 2048]).unwrap().to_txt_string()).unwrap();
+	fs::write(dir.join("flat.txt"), WaveData::default().to_txt_string()).unwrap();
+	fs::write(dir.join("slots.toml"), "[slots]\n1 = \"high.txt\"\n2 = \"flat.txt\"\n3 = { shape = \"sine\" }\n").unwrap();
+
+	let manifest = dir.join("slots.toml");
+	let manifest = manifest.to_str().unwrap();
+
+	// Mock slots start out flat, so that one is skipped.
+	assert_eq!(provision_arbitrary_waves(&mut port, manifest, None, 0).unwrap(), vec![1, 3]);
+	assert_eq!(get_arbitrary_wave(&mut port, "1", 1).unwrap(), WaveData::new(&[4095; 2048]).unwrap());
+
+	// They all match now, and are catalogued.
+	let catalog_dir = dir.join("catalogs");
+
+	assert_eq!(provision_arbitrary_waves(&mut port, manifest, catalog_dir.to_str(), 0).unwrap(), Vec::<u8>::new());
+
+	let catalog = list_arbitrary_waves(&mut port, catalog_dir.to_str().unwrap(), 0).unwrap();
+
+	assert_eq!(catalog.entries.iter().map(|entry| entry.slot).collect::<Vec<u8>>(), vec![1, 2, 3]);
+	assert_eq!(catalog.entry(1).unwrap().hash, sha256_hex(WaveData::new(&[4095; 2048]).unwrap().to_txt_string().as_bytes()));
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn provision_arbitrary_waves_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-provision-err-test-{}", std::process::id()));
+
+	provision_arbitrary_waves(&mut port, "/nonexistent/slots.yaml", None, 0).unwrap_err();
+
+	// A missing file stops it before anything is written.
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("slots.yaml"), "slots:\n  1:\n    shape: sine\n  2: missing.txt\n").unwrap();
+
+	provision_arbitrary_waves(&mut port, dir.join("slots.yaml").to_str().unwrap(), None, 0).unwrap_err();
+
+	assert_eq!(get_arbitrary_wave(&mut port, "1", 1).unwrap(), WaveData::default());
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn arbitrary_wave_catalog_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-catalog-test-{}", std::process::id()));
+	let dir_str = dir.to_str().unwrap();
+
+	assert!(list_arbitrary_waves(&mut port, dir_str, 0).unwrap().entries.is_empty());
+
+	let entry = name_arbitrary_wave(&mut port, dir_str, "37", "Flat", Some("flat.txt"), None, 0).unwrap();
+
+	assert_eq!(entry.slot, 37);
+	assert_eq!(entry.source, "flat.txt");
+	assert_eq!(entry.hash, sha256_hex(WaveData::default().to_txt_string().as_bytes()));
+
+	// All the other slots are added, and none have changed.
+	let catalog = sync_arbitrary_wave_catalog(&mut port, dir_str, 0).unwrap();
+
+	assert_eq!(catalog.entries.len(), 60);
+	assert!(catalog.entries.iter().all(|entry| !entry.changed));
+	assert_eq!(catalog.entry(37).unwrap().name, "Flat");
+
+	// Change the slot behind the catalogue's back.
+	set_arbitrary_wave(&mut port, 37.0, &WaveData::new(&[4095; 2048]).unwrap(), 0).unwrap();
+
+	let catalog = sync_arbitrary_wave_catalog(&mut port, dir_str, 0).unwrap();
+
+	assert!(catalog.entry(37).unwrap().changed);
+	assert_eq!(catalog.entries.iter().filter(|entry| entry.changed).count(), 1);
+	assert_eq!(list_arbitrary_waves(&mut port, dir_str, 0).unwrap(), catalog);
+
+	// Naming it again accepts the new contents.
+	assert!(!name_arbitrary_wave(&mut port, dir_str, "37", "High", None, None, 0).unwrap().changed);
+	assert_eq!(list_arbitrary_waves(&mut port, dir_str, 0).unwrap().entry(37).unwrap().source, "flat.txt");
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn arbitrary_wave_catalog_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-catalog-err-test-{}", std::process::id()));
+	let dir_str = dir.to_str().unwrap();
+
+	name_arbitrary_wave(&mut port, dir_str, "0", "Nothing", None, None, 0).unwrap_err();
+	name_arbitrary_wave(&mut port, dir_str, "61", "Nothing", None, None, 0).unwrap_err();
+
+	// A broken catalogue.
+	fs::create_dir_all(&dir).unwrap();
+	fs::write(dir.join("9876500000.tsv"), "1\tok\n").unwrap();
+
+	list_arbitrary_waves(&mut port, dir_str, 0).unwrap_err();
+	sync_arbitrary_wave_catalog(&mut port, dir_str, 0).unwrap_err();
+
+	fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn plot_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let plot = PlotOptions::default();
+
+	let res = plot_arbitrary_wave(&mut port, "4", None, &plot, 1, &ResampleOptions::default(), 0).unwrap();
+
+	assert_eq!(res.lines().count(), plot.height + 2);
+	assert!(res.contains("◂ max 2048, mean 2048, min 2048"));
+
+	// Compared with another slot.
+	set_arbitrary_wave(&mut port, 5.0, &WaveData::new(&[4095; 2048]).unwrap(), 0).unwrap();
+
+	let res = plot_arbitrary_wave(&mut port, "4", Some(&Source::Slot(5.0)), &plot, 1, &ResampleOptions::default(), 0).unwrap();
+
+	assert!(res.contains("compare: min 4095, max 4095"));
+}
+
+#[test]
+pub fn plot_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let plot = PlotOptions::default();
+
+	plot_arbitrary_wave(&mut port, "0", None, &plot, 1, &ResampleOptions::default(), 0).unwrap_err();
+	plot_arbitrary_wave(&mut port, "4", Some(&Source::Current), &plot, 1, &ResampleOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn plot_wave_file_ok() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-plot-test-{}.txt", std::process::id()));
+
+	fs::write(&path, WaveData::default().to_txt_string()).unwrap();
+
+	let res = plot_wave_file(
+		path.to_str().unwrap(),
+		Some(&Source::File(path.to_str().unwrap().to_string())),
+		&PlotOptions::default(),
+		1,
+		&ResampleOptions::default(),
+		&ConvertOptions::default(),
+		0,
+	)
+	.unwrap();
+
+	assert!(res.contains("compare: min 2048, max 2048"));
+
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+pub fn plot_wave_file_err() {
+	let opts = PlotOptions::default();
+
+	plot_wave_file("/nonexistent/wave.txt", None, &opts, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	// There's no device to read a slot from.
+	plot_wave(None, &WaveData::default(), Some(&Source::Slot(1.0)), &opts, 1, &ResampleOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn render_wave_file_ok() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-render-test-{}.txt", std::process::id()));
+	let out_path = path.with_extension("svg");
+
+	fs::write(&path, WaveData::default().to_txt_string()).unwrap();
+
+	let res = render_wave_file(
+		path.to_str().unwrap(),
+		out_path.to_str().unwrap(),
+		&RenderOptions::default(),
+		1,
+		&ResampleOptions::default(),
+		&ConvertOptions::default(),
+		0,
+	)
+	.unwrap();
+
+	assert_eq!(fs::read(&out_path).unwrap(), res);
+	assert!(res.starts_with(b"<svg"));
+
+	fs::remove_file(&path).unwrap();
+	fs::remove_file(&out_path).unwrap();
+}
+
+#[test]
+pub fn render_wave_file_err() {
+	let opts = RenderOptions::default();
+
+	render_wave_file("/nonexistent/wave.txt", "wave.png", &opts, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+	render_wave(&WaveData::default(), "wave.jpg", &opts, 0).unwrap_err();
+	render_wave(&WaveData::default(), "/nonexistent/wave.png", &opts, 0).unwrap_err();
+}
+
+#[test]
+pub fn render_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let out_path = std::env::temp_dir().join(format!("signal-gen-cjds66-render-test-{}.png", std::process::id()));
+
+	let res = render_arbitrary_wave(&mut port, "4", out_path.to_str().unwrap(), &RenderOptions::default(), 0).unwrap();
+
+	assert_eq!(fs::read(&out_path).unwrap(), res);
+	assert_eq!(&res[1..4], b"PNG");
+
+	fs::remove_file(&out_path).unwrap();
+}
+
+#[test]
+pub fn render_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	render_arbitrary_wave(&mut port, "0", "wave.png", &RenderOptions::default(), 0).unwrap_err();
+	render_arbitrary_wave(&mut port, "4", "wave.gif", &RenderOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn export_wave_file_ok() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-export-test-{}.txt", std::process::id()));
+	let out_path = path.with_extension("arb");
+
+	fs::write(&path, WaveData::default().to_txt_string()).unwrap();
+
+	let res = export_wave_file(
+		path.to_str().unwrap(),
+		out_path.to_str().unwrap(),
+		&ExportOptions::default(),
+		1,
+		&ResampleOptions::default(),
+		&ConvertOptions::default(),
+		0,
+	)
+	.unwrap();
+
+	assert_eq!(fs::read(&out_path).unwrap(), res);
+	assert!(res.starts_with(b"File Format:1.10\r\n"));
+
+	fs::remove_file(&path).unwrap();
+	fs::remove_file(&out_path).unwrap();
+}
+
+#[test]
+pub fn export_wave_file_err() {
+	let opts = ExportOptions::default();
+
+	export_wave_file("/nonexistent/wave.txt", "wave.csv", &opts, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+	export_wave(&WaveData::default(), "wave.wfm", &opts, 0).unwrap_err();
+	export_wave(&WaveData::default(), "/nonexistent/wave.csv", &opts, 0).unwrap_err();
+	export_wave(&WaveData::default(), "wave.csv", &ExportOptions { frequency: -1.0, ..opts }, 0).unwrap_err();
+}
+
+#[test]
+pub fn export_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let out_path = std::env::temp_dir().join(format!("signal-gen-cjds66-export-test-{}.raf", std::process::id()));
+
+	let res = export_arbitrary_wave(&mut port, "4", out_path.to_str().unwrap(), &ExportOptions::default(), 0).unwrap();
+
+	assert_eq!(fs::read(&out_path).unwrap(), res);
+	assert_eq!(res, to_rigol_raf(&WaveData::default()));
+
+	fs::remove_file(&out_path).unwrap();
+}
+
+#[test]
+pub fn export_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	export_arbitrary_wave(&mut port, "0", "wave.csv", &ExportOptions::default(), 0).unwrap_err();
+	export_arbitrary_wave(&mut port, "4", "wave.wfm", &ExportOptions::default(), 0).unwrap_err();
+}
+
+#[test]
+pub fn get_arbitrary_wave_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	assert_eq!(get_arbitrary_wave(&mut port, "1", 1).unwrap(), WaveData::default());
+}
+
+#[test]
+pub fn get_arbitrary_wave_err() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+
+	get_arbitrary_wave(&mut port, "0", 0).unwrap_err();
+	get_arbitrary_wave(&mut port, "1.5", 0).unwrap_err();
+}
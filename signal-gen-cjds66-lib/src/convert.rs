@@ -0,0 +1,242 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Converting samples to the device's 12-bit arbitrary wave values.
+
+Samples go in as floating point numbers where `-1.0` to `1.0` is the
+full range of the device, and come out as whole numbers from 0 to 4095.
+On the way, the DC offset can be removed, the wave can be normalized
+so its peak reaches a chosen fraction of full scale, a gain in dB can
+be applied, and TPDF dither can be added to hide the distortion of
+reducing the resolution to 12 bits. Anything still out of range is
+clipped, or refused if "fail on clip" is chosen.
+
+A [ConversionReport](struct.ConversionReport.html) describes what happened.
+*/
+
+use crate::protocol::*;
+
+use clap::{Error, ErrorKind};
+use std::fmt;
+
+/** The number of device steps between the middle of the range and full scale. */
+pub const HALF_SCALE: f64 = 2048.0;
+
+/** The largest peak, as a fraction of full scale, which fits in the
+device's range both above and below the middle, since there's one
+step less above it than below it. Normalizing to 1 uses this, or one
+step less with dither, to leave room for it. */
+pub const MAX_PEAK: f64 = (HALF_SCALE - 1.0) / HALF_SCALE;
+
+/** Options for converting samples to device values. The defaults
+convert the samples as they are. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConvertOptions {
+	/// Scale the wave so its peak is this fraction (0-1) of the largest
+	/// peak which fits without clipping, even once dither is added, or
+	/// `None` to leave it.
+	pub normalize: Option<f64>,
+
+	/// Whether to subtract the average of the wave, centering it.
+	pub remove_dc: bool,
+
+	/// A gain to apply in dB, after normalizing.
+	pub gain_db: f64,
+
+	/// Whether to add triangular (TPDF) dither before rounding to 12 bits.
+	pub dither: bool,
+
+	/// Whether to fail instead of clipping values which are out of range.
+	pub fail_on_clip: bool,
+}
+
+impl ConvertOptions {
+	/// Check that the options are in range.
+	pub fn validate(&self) -> Result<(), clap::Error> {
+		if let Some(target) = self.normalize {
+			if !(target > 0.0 && target <= 1.0) {
+				return Err(Error::with_description(
+					&format!("unsupported normalization target (must be more than 0 and at most 1, as a fraction of full scale): {}", target),
+					ErrorKind::InvalidValue,
+				));
+			}
+		}
+
+		if !self.gain_db.is_finite() {
+			return Err(Error::with_description(
+				&format!("unsupported gain (must be a number of dB): {}", self.gain_db),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		Ok(())
+	}
+}
+
+/** What happened while converting a wave. Levels are fractions of
+full scale, measured after the DC removal, normalization, and gain,
+but before dithering and clipping. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversionReport {
+	/// The number of samples converted.
+	pub samples: usize,
+
+	/// The number of values which were raised to 0.
+	pub clipped_low: usize,
+
+	/// The number of values which were lowered to 4095.
+	pub clipped_high: usize,
+
+	/// The first sample which was clipped, counting from 0.
+	pub first_clipped: Option<usize>,
+
+	/// The largest distance from the middle of the range.
+	pub peak: f64,
+
+	/// The root mean square level.
+	pub rms: f64,
+
+	/// The average level, which is the DC offset.
+	pub dc: f64,
+
+	/// The DC offset which was removed.
+	pub dc_removed: f64,
+
+	/// The total gain applied by normalizing and the gain option, in dB.
+	pub gain_db: f64,
+}
+
+impl ConversionReport {
+	/// The number of values which were clipped.
+	pub fn clipped(&self) -> usize {
+		self.clipped_low + self.clipped_high
+	}
+}
+
+impl fmt::Display for ConversionReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "samples: {}", self.samples)?;
+		writeln!(f, "peak: {:.4} ({:.2} dBFS)", self.peak, to_db(self.peak))?;
+		writeln!(f, "rms: {:.4} ({:.2} dBFS)", self.rms, to_db(self.rms))?;
+		writeln!(f, "dc offset: {:.4}", self.dc)?;
+		writeln!(f, "dc offset removed: {:.4}", self.dc_removed)?;
+		writeln!(f, "gain applied: {:.2} dB", self.gain_db)?;
+		write!(f, "clipped: {} ({} low, {} high)", self.clipped(), self.clipped_low, self.clipped_high)?;
+
+		if let Some(i) = self.first_clipped {
+			write!(f, ", first at sample {}", i)?;
+		}
+
+		Ok(())
+	}
+}
+
+fn to_db(level: f64) -> f64 {
+	20.0 * level.log10()
+}
+
+/** A small deterministic random number generator for dither, so the
+same input always converts to the same output. */
+struct XorShift(u64);
+
+impl XorShift {
+	/// A random number from -0.5 to 0.5.
+	fn next(&mut self) -> f64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+
+		(self.0 >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+	}
+}
+
+/** Convert samples to device values from 0 to 4095, as chosen in "opts". */
+pub fn convert_to_device(samples: &[f64], opts: &ConvertOptions) -> Result<(Vec<u16>, ConversionReport), clap::Error> {
+	opts.validate()?;
+
+	let mut report = ConversionReport {
+		samples: samples.len(),
+		..ConversionReport::default()
+	};
+
+	let mean = if samples.is_empty() {
+		0.0
+	} else {
+		samples.iter().sum::<f64>() / samples.len() as f64
+	};
+
+	if opts.remove_dc {
+		report.dc_removed = mean;
+	}
+
+	let centered: Vec<f64> = samples.iter().map(|s| s - report.dc_removed).collect();
+
+	let mut gain = 10f64.powf(opts.gain_db / 20.0);
+
+	if let Some(target) = opts.normalize {
+		let peak = centered.iter().fold(0.0, |peak: f64, s| peak.max(s.abs()));
+
+		// Dither moves values by up to a step either way.
+		let max_peak = if opts.dither { MAX_PEAK - 1.0 / HALF_SCALE } else { MAX_PEAK };
+
+		if peak > 0.0 {
+			gain *= target * max_peak / peak;
+		}
+	}
+
+	report.gain_db = to_db(gain);
+
+	let scaled: Vec<f64> = centered.iter().map(|s| s * gain).collect();
+
+	if !scaled.is_empty() {
+		report.peak = scaled.iter().fold(0.0, |peak: f64, s| peak.max(s.abs()));
+		report.rms = (scaled.iter().map(|s| s * s).sum::<f64>() / scaled.len() as f64).sqrt();
+		report.dc = scaled.iter().sum::<f64>() / scaled.len() as f64;
+	}
+
+	let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+	let max = ARBITRARY_WAVE_MAX as f64;
+	let mut out = Vec::with_capacity(scaled.len());
+
+	for (i, s) in scaled.iter().enumerate() {
+		let mut val = s * HALF_SCALE + HALF_SCALE;
+
+		if opts.dither {
+			val += rng.next() + rng.next();
+		}
+
+		let val = val.round();
+
+		if val < 0.0 || val > max {
+			if val < 0.0 {
+				report.clipped_low += 1;
+			} else {
+				report.clipped_high += 1;
+			}
+
+			report.first_clipped = report.first_clipped.or(Some(i));
+		}
+
+		out.push(val.max(0.0).min(max) as u16);
+	}
+
+	if opts.fail_on_clip && report.clipped() > 0 {
+		return Err(Error::with_description(
+			&format!(
+				"wave data is out of the device's range of 0 - {} and would be clipped: {} values clipped: first at sample {}: try normalizing it, or lowering the gain",
+				ARBITRARY_WAVE_MAX,
+				report.clipped(),
+				report.first_clipped.unwrap_or_default()
+			),
+			ErrorKind::InvalidValue,
+		));
+	}
+
+	Ok((out, report))
+}
@@ -0,0 +1,124 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::convert::*;
+
+#[test]
+pub fn convert_to_device_ok() {
+	let (out, report) = convert_to_device(&[0.0, 0.5, -0.5, -1.0, 2047.0 / 2048.0], &ConvertOptions::default()).unwrap();
+
+	assert_eq!(out, vec![2048, 3072, 1024, 0, 4095]);
+	assert_eq!(report.samples, 5);
+	assert_eq!(report.clipped(), 0);
+	assert_eq!(report.peak, 1.0);
+	assert_eq!(report.gain_db, 0.0);
+
+	// A wave with an offset, centered and normalized.
+	let opts = ConvertOptions {
+		remove_dc: true,
+		normalize: Some(1.0),
+		..ConvertOptions::default()
+	};
+
+	let (out, report) = convert_to_device(&[0.25, 0.35, 0.15, 0.25], &opts).unwrap();
+
+	assert_eq!(out, vec![2048, 4095, 1, 2048]);
+	assert!((report.dc_removed - 0.25).abs() < 1e-12);
+	assert!(report.dc.abs() < 1e-12);
+	assert!((report.peak - MAX_PEAK).abs() < 1e-12);
+	assert!((report.rms - MAX_PEAK / 2f64.sqrt()).abs() < 1e-12);
+
+	// A gain, with the levels reported after it.
+	let opts = ConvertOptions {
+		gain_db: -6.0206,
+		..ConvertOptions::default()
+	};
+
+	let (out, report) = convert_to_device(&[0.5, -0.5], &opts).unwrap();
+
+	assert_eq!(out, vec![2560, 1536]);
+	assert!((report.peak - 0.25).abs() < 1e-4);
+	assert!((report.gain_db + 6.0206).abs() < 1e-9);
+}
+
+#[test]
+pub fn convert_to_device_clip_ok() {
+	let (out, report) = convert_to_device(&[1.5, 0.0, -1.5, 1.0], &ConvertOptions::default()).unwrap();
+
+	assert_eq!(out, vec![4095, 2048, 0, 4095]);
+	assert_eq!(report.clipped_high, 2);
+	assert_eq!(report.clipped_low, 1);
+	assert_eq!(report.first_clipped, Some(0));
+	assert!(report.to_string().contains("clipped: 3 (1 low, 2 high), first at sample 0"));
+}
+
+#[test]
+pub fn convert_to_device_dither_ok() {
+	let opts = ConvertOptions {
+		dither: true,
+		..ConvertOptions::default()
+	};
+
+	// A level between two steps should come out as a mix of nearby
+	// steps, which average out close to the level.
+	let samples = vec![0.1 + 0.3 / 2048.0; 2048];
+	let (out, _) = convert_to_device(&samples, &opts).unwrap();
+
+	let mean = out.iter().map(|v| *v as f64).sum::<f64>() / out.len() as f64;
+
+	assert!(out.iter().all(|v| (2251..=2254).contains(v)));
+	assert!(out.iter().any(|v| *v != out[0]));
+	assert!((mean - (2048.0 + 204.8 + 0.3)).abs() < 0.1, "{}", mean);
+
+	// The same input always gives the same output.
+	assert_eq!(convert_to_device(&samples, &opts).unwrap().0, out);
+
+	// Normalizing to full scale leaves room for the dither.
+	let opts = ConvertOptions {
+		normalize: Some(1.0),
+		dither: true,
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	let samples: Vec<f64> = (0..2048).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+	let (out, report) = convert_to_device(&samples, &opts).unwrap();
+
+	assert_eq!(report.clipped(), 0);
+	assert!(out.iter().all(|v| (1..=4095).contains(v)));
+}
+
+#[test]
+pub fn convert_to_device_err() {
+	let opts = ConvertOptions {
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	convert_to_device(&[0.0, 1.0], &opts).unwrap_err();
+	convert_to_device(&[0.0, 0.9], &opts).unwrap();
+
+	for normalize in [0.0, -1.0, 1.5].iter() {
+		let opts = ConvertOptions {
+			normalize: Some(*normalize),
+			..ConvertOptions::default()
+		};
+
+		convert_to_device(&[0.5], &opts).unwrap_err();
+	}
+
+	let opts = ConvertOptions {
+		gain_db: f64::NAN,
+		..ConvertOptions::default()
+	};
+
+	convert_to_device(&[0.5], &opts).unwrap_err();
+}
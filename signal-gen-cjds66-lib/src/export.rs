@@ -0,0 +1,310 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Arbitrary waves in other generators' file formats, so the same
+waves can be used on the rest of the bench.
+
+The other formats hold voltages, or values with levels given in
+volts, and some hold a time for each sample or a sample rate, so the
+output's amplitude (peak-to-peak), offset, and frequency are given in
+[ExportOptions](struct.ExportOptions.html). The device's values of
+0 - 4095 go from the offset minus half the amplitude to the offset
+plus half the amplitude.
+
+A plain CSV file, with a heading for each column:
+```text
+sample,time (s),voltage (V),value
+0,0.000000000e0,0.000000,2048
+```
+
+A Siglent CSV file, for Siglent's EasyWave and SDG generators:
+```text
+data length,2048
+frequency,1000.000000
+amp,2.000000
+offset,0.000000
+phase,0.000000
+
+xpos,value
+0.000000000e0,0.000000
+```
+
+A Keysight .arb file, for the 33500 and 33600 series, with each value
+from -32767 to 32767:
+```text
+File Format:1.10
+Checksum:0
+Channel Count:1
+Sample Rate:2048000.000000
+High Level:1.000000
+Low Level:-1.000000
+Data Type:"short"
+Filter:"normal"
+Data Points:2048
+Data:
+0
+```
+
+A Rigol .RAF file, for the DG1000Z, DG800, and DG4000 series, is each
+value as a 14-bit number from 0 to 16383, in 2 bytes, least
+significant first, without a header.
+
+A SPICE PWL file, for a piecewise linear source in ngspice or LTspice,
+is the time and voltage of each sample, and then the first voltage
+again at the end of the period, so it joins up with itself when it's
+repeated, such as with LTspice's
+`PWL REPEAT FOREVER (FILE=wave.pwl) ENDREPEAT`:
+```text
+0.000000000e0 0.000000
+4.882812500e-7 0.002930
+```
+*/
+
+use crate::convert::HALF_SCALE;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::fmt;
+
+/** Which file format to export a wave in. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+	/// A plain CSV file, with a heading for each column.
+	Csv,
+
+	/// A Siglent CSV file.
+	Siglent,
+
+	/// A Keysight .arb file.
+	Keysight,
+
+	/// A Rigol .RAF file.
+	Rigol,
+
+	/// A SPICE PWL file.
+	Pwl,
+}
+
+impl fmt::Display for ExportFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ExportFormat::Csv => write!(f, "CSV"),
+			ExportFormat::Siglent => write!(f, "Siglent CSV"),
+			ExportFormat::Keysight => write!(f, "Keysight .arb"),
+			ExportFormat::Rigol => write!(f, "Rigol .RAF"),
+			ExportFormat::Pwl => write!(f, "SPICE PWL"),
+		}
+	}
+}
+
+impl ExportFormat {
+	/// Parse `csv`, `siglent`, `keysight`, `rigol`, or `pwl`.
+	pub fn parse(name: &str) -> Result<ExportFormat, clap::Error> {
+		match name.trim().to_lowercase().as_str() {
+			"csv" => Ok(ExportFormat::Csv),
+			"siglent" => Ok(ExportFormat::Siglent),
+			"keysight" | "arb" => Ok(ExportFormat::Keysight),
+			"rigol" | "raf" => Ok(ExportFormat::Rigol),
+			"pwl" | "spice" => Ok(ExportFormat::Pwl),
+			_ => Err(Error::with_description(
+				&format!("unsupported export format (must be csv, siglent, keysight, rigol, or pwl): {}", name),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+
+	/// The format for a file name ending in .csv, .arb, .raf, or .pwl. Siglent
+	/// CSV files have to be chosen by name, since they end in .csv too.
+	pub fn from_path(path: &str) -> Result<ExportFormat, clap::Error> {
+		let lower = path.to_lowercase();
+
+		if lower.ends_with(".csv") {
+			Ok(ExportFormat::Csv)
+		} else if lower.ends_with(".arb") {
+			Ok(ExportFormat::Keysight)
+		} else if lower.ends_with(".raf") {
+			Ok(ExportFormat::Rigol)
+		} else if lower.ends_with(".pwl") {
+			Ok(ExportFormat::Pwl)
+		} else {
+			Err(Error::with_description(
+				&format!("unsupported export file name (must end in .csv, .arb, .raf, or .pwl, or choose the format): {}", path),
+				ErrorKind::InvalidValue,
+			))
+		}
+	}
+}
+
+/** How to export a wave. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+	/// Which format to export in, or `None` to choose it from the end
+	/// of the file's name.
+	pub format: Option<ExportFormat>,
+
+	/// The frequency the wave is played at, in hertz.
+	pub frequency: f64,
+
+	/// The output's peak-to-peak amplitude, in volts.
+	pub amplitude: f64,
+
+	/// The output's offset, in volts.
+	pub offset: f64,
+}
+
+impl Default for ExportOptions {
+	fn default() -> ExportOptions {
+		ExportOptions {
+			format: None,
+			frequency: 1000.0,
+			amplitude: 2.0,
+			offset: 0.0,
+		}
+	}
+}
+
+impl ExportOptions {
+	/// Check that the options are in range.
+	pub fn validate(&self) -> Result<(), clap::Error> {
+		if !(self.frequency > 0.0 && self.frequency.is_finite()) {
+			return Err(Error::with_description(
+				&format!("unsupported export frequency (must be more than 0 Hz): {}", self.frequency),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		if !(self.amplitude >= 0.0 && self.amplitude.is_finite() && self.offset.is_finite()) {
+			return Err(Error::with_description(
+				&format!(
+					"unsupported export amplitude or offset (must be at least 0 Vpp, and a number of volts): {} Vpp, {} V",
+					self.amplitude, self.offset
+				),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		Ok(())
+	}
+
+	/// The format chosen, or the one for the end of "path".
+	pub fn format_for(&self, path: &str) -> Result<ExportFormat, clap::Error> {
+		match self.format {
+			Some(format) => Ok(format),
+			None => ExportFormat::from_path(path),
+		}
+	}
+
+	/// The voltage of a device value.
+	pub fn volts(&self, value: u16) -> f64 {
+		self.offset + (value as f64 - HALF_SCALE) / HALF_SCALE * self.amplitude / 2.0
+	}
+
+	/// The time of a sample, in seconds from the start of the wave.
+	pub fn time(&self, sample: usize, len: usize) -> f64 {
+		sample as f64 / len as f64 / self.frequency
+	}
+}
+
+/** A plain CSV file, with the sample number, time, voltage, and
+device value of each sample. */
+pub fn to_csv(wave: &WaveData, opts: &ExportOptions) -> String {
+	let samples = wave.samples();
+	let mut out = String::from("sample,time (s),voltage (V),value\n");
+
+	for (i, value) in samples.iter().enumerate() {
+		out.push_str(&format!("{},{:.9e},{:.6},{}\n", i, opts.time(i, samples.len()), opts.volts(*value), value));
+	}
+
+	out
+}
+
+/** A Siglent CSV file, with the settings, and then the time and
+voltage of each sample. */
+pub fn to_siglent_csv(wave: &WaveData, opts: &ExportOptions) -> String {
+	let samples = wave.samples();
+
+	let mut out = format!(
+		"data length,{}\nfrequency,{:.6}\namp,{:.6}\noffset,{:.6}\nphase,{:.6}\n\nxpos,value\n",
+		samples.len(),
+		opts.frequency,
+		opts.amplitude,
+		opts.offset,
+		0.0
+	);
+
+	for (i, value) in samples.iter().enumerate() {
+		out.push_str(&format!("{:.9e},{:.6}\n", opts.time(i, samples.len()), opts.volts(*value)));
+	}
+
+	out
+}
+
+/** A Keysight .arb file, with the settings, and then each value from
+-32767 to 32767. The sample rate plays the wave at the frequency. */
+pub fn to_keysight_arb(wave: &WaveData, opts: &ExportOptions) -> String {
+	let samples = wave.samples();
+
+	let mut out = format!(
+		"File Format:1.10\r\nChecksum:0\r\nChannel Count:1\r\nSample Rate:{:.6}\r\nHigh Level:{:.6}\r\nLow Level:{:.6}\r\nData Type:\"short\"\r\nFilter:\"normal\"\r\nData Points:{}\r\nData:\r\n",
+		opts.frequency * samples.len() as f64,
+		opts.offset + opts.amplitude / 2.0,
+		opts.offset - opts.amplitude / 2.0,
+		samples.len()
+	);
+
+	for value in samples.iter() {
+		let short = ((*value as f64 - HALF_SCALE) / HALF_SCALE * 32767.0).round().clamp(-32767.0, 32767.0) as i16;
+
+		out.push_str(&format!("{}\r\n", short));
+	}
+
+	out
+}
+
+/** A Rigol .RAF file, with each value as a 14-bit number from 0 to
+16383, in 2 bytes, least significant first. */
+pub fn to_rigol_raf(wave: &WaveData) -> Vec<u8> {
+	wave.samples()
+		.iter()
+		.flat_map(|value| {
+			let raf = (*value as u32 * 16383 + ARBITRARY_WAVE_MAX as u32 / 2) / ARBITRARY_WAVE_MAX as u32;
+
+			(raf as u16).to_le_bytes()
+		})
+		.collect()
+}
+
+/** A SPICE PWL file, with the time and voltage of each sample, and
+the first voltage again at the end of the period. */
+pub fn to_pwl(wave: &WaveData, opts: &ExportOptions) -> String {
+	let samples = wave.samples();
+	let mut out = String::new();
+
+	for (i, value) in samples.iter().enumerate() {
+		out.push_str(&format!("{:.9e} {:.6}\n", opts.time(i, samples.len()), opts.volts(*value)));
+	}
+
+	if let Some(first) = samples.first() {
+		out.push_str(&format!("{:.9e} {:.6}\n", 1.0 / opts.frequency, opts.volts(*first)));
+	}
+
+	out
+}
+
+/** A wave in one of the formats. */
+pub fn export(wave: &WaveData, format: ExportFormat, opts: &ExportOptions) -> Vec<u8> {
+	match format {
+		ExportFormat::Csv => to_csv(wave, opts).into_bytes(),
+		ExportFormat::Siglent => to_siglent_csv(wave, opts).into_bytes(),
+		ExportFormat::Keysight => to_keysight_arb(wave, opts).into_bytes(),
+		ExportFormat::Rigol => to_rigol_raf(wave),
+		ExportFormat::Pwl => to_pwl(wave, opts).into_bytes(),
+	}
+}
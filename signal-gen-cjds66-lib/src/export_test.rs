@@ -0,0 +1,148 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::export::*;
+use super::wave::*;
+
+fn ramp() -> WaveData {
+	WaveData::new(&(0..2048).map(|i| (i * 2) as u16).collect::<Vec<u16>>()).unwrap()
+}
+
+#[test]
+pub fn export_format_ok() {
+	assert_eq!(ExportFormat::parse("Siglent").unwrap(), ExportFormat::Siglent);
+	assert_eq!(ExportFormat::parse("arb").unwrap(), ExportFormat::Keysight);
+	assert_eq!(ExportFormat::parse("spice").unwrap(), ExportFormat::Pwl);
+
+	assert_eq!(ExportFormat::from_path("wave.csv").unwrap(), ExportFormat::Csv);
+	assert_eq!(ExportFormat::from_path("WAVE.ARB").unwrap(), ExportFormat::Keysight);
+	assert_eq!(ExportFormat::from_path("wave.RAF").unwrap(), ExportFormat::Rigol);
+	assert_eq!(ExportFormat::from_path("stimulus.pwl").unwrap(), ExportFormat::Pwl);
+
+	let opts = ExportOptions {
+		format: Some(ExportFormat::Siglent),
+		..ExportOptions::default()
+	};
+
+	assert_eq!(opts.format_for("wave.csv").unwrap(), ExportFormat::Siglent);
+	assert_eq!(ExportOptions::default().format_for("wave.csv").unwrap(), ExportFormat::Csv);
+}
+
+#[test]
+pub fn export_format_err() {
+	assert!(ExportFormat::parse("tektronix").is_err());
+	assert!(ExportFormat::from_path("wave.txt").is_err());
+	assert!(ExportOptions::default().format_for("wave").is_err());
+}
+
+#[test]
+pub fn export_options_ok() {
+	let opts = ExportOptions {
+		amplitude: 5.0,
+		offset: 1.0,
+		frequency: 1000.0,
+		..ExportOptions::default()
+	};
+
+	opts.validate().unwrap();
+
+	assert_eq!(opts.volts(0), -1.5);
+	assert_eq!(opts.volts(2048), 1.0);
+	assert_eq!(opts.time(1024, 2048), 0.0005);
+}
+
+#[test]
+pub fn export_options_err() {
+	let opts = ExportOptions::default();
+
+	ExportOptions { frequency: 0.0, ..opts.clone() }.validate().unwrap_err();
+	ExportOptions { amplitude: -1.0, ..opts.clone() }.validate().unwrap_err();
+	ExportOptions { offset: f64::NAN, ..opts }.validate().unwrap_err();
+}
+
+#[test]
+pub fn to_csv_ok() {
+	let res = to_csv(&ramp(), &ExportOptions::default());
+	let lines: Vec<&str> = res.lines().collect();
+
+	assert_eq!(lines.len(), 2049);
+	assert_eq!(lines[0], "sample,time (s),voltage (V),value");
+	assert_eq!(lines[1], "0,0.000000000e0,-1.000000,0");
+	assert_eq!(lines[1025], "1024,5.000000000e-4,0.000000,2048");
+
+	let res = to_siglent_csv(&ramp(), &ExportOptions::default());
+	let lines: Vec<&str> = res.lines().collect();
+
+	assert_eq!(lines.len(), 2055);
+	assert_eq!(lines[0], "data length,2048");
+	assert_eq!(lines[1], "frequency,1000.000000");
+	assert_eq!(lines[2], "amp,2.000000");
+	assert_eq!(lines[6], "xpos,value");
+	assert_eq!(lines[7], "0.000000000e0,-1.000000");
+	assert_eq!(lines[1031], "5.000000000e-4,0.000000");
+}
+
+#[test]
+pub fn to_keysight_arb_ok() {
+	let opts = ExportOptions {
+		amplitude: 3.3,
+		offset: 1.65,
+		..ExportOptions::default()
+	};
+
+	let res = to_keysight_arb(&ramp(), &opts);
+	let lines: Vec<&str> = res.split("\r\n").collect();
+
+	assert_eq!(lines.len(), 2048 + 11);
+	assert_eq!(lines[0], "File Format:1.10");
+	assert_eq!(lines[3], "Sample Rate:2048000.000000");
+	assert_eq!(lines[4], "High Level:3.300000");
+	assert_eq!(lines[5], "Low Level:0.000000");
+	assert_eq!(lines[8], "Data Points:2048");
+	assert_eq!(lines[9], "Data:");
+	assert_eq!(lines[10], "-32767");
+	assert_eq!(lines[10 + 1024], "0");
+	assert_eq!(lines[10 + 2047], "32735");
+	assert_eq!(lines[2058], "");
+}
+
+#[test]
+pub fn to_rigol_raf_ok() {
+	let res = to_rigol_raf(&ramp());
+
+	assert_eq!(res.len(), 4096);
+	assert_eq!(&res[..4], &[0, 0, 8, 0]);
+	assert_eq!(&res[2048..2050], &(8194u16).to_le_bytes());
+
+	let full = WaveData::new(&[4095; 2048]).unwrap();
+
+	assert_eq!(&to_rigol_raf(&full)[..2], &(16383u16).to_le_bytes());
+	assert_eq!(export(&full, ExportFormat::Rigol, &ExportOptions::default()), to_rigol_raf(&full));
+}
+
+#[test]
+pub fn to_pwl_ok() {
+	let opts = ExportOptions {
+		frequency: 10_000.0,
+		amplitude: 5.0,
+		offset: 2.5,
+		..ExportOptions::default()
+	};
+
+	let res = to_pwl(&ramp(), &opts);
+	let lines: Vec<&str> = res.lines().collect();
+
+	assert_eq!(lines.len(), 2049);
+	assert_eq!(lines[0], "0.000000000e0 0.000000");
+	assert_eq!(lines[1024], "5.000000000e-5 2.500000");
+	assert_eq!(lines[2048], "1.000000000e-4 0.000000");
+	assert_eq!(export(&ramp(), ExportFormat::Pwl, &opts), res.into_bytes());
+}
@@ -0,0 +1,617 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Arbitrary waves defined by a math formula over `t`, which goes
+from 0 up to 1 across the wave.
+
+```ignore
+let expr = Expr::parse("0.7*sin(2*pi*t) + 0.3*sin(6*pi*t + pi/4)")?;
+let (wave, report) = generate_expr_wave(&expr, true, &ConvertOptions::default())?;
+```
+
+The operators are `+`, `-`, `*`, `/`, `%` (which always gives a
+result with the sign of the divisor), and `^` for powers, along with
+the comparisons `<`, `<=`, `>`, `>=`, `==`, and `!=`, and `&&`, `||`,
+and `!`, where anything other than 0 is true, and true is 1. They
+have the usual precedence, and `^` groups from the right, so
+`-2^2` is -4 and `2^3^2` is 512.
+
+The constants are `t`, `pi`, `tau` (2 pi), and `e`. The functions are:
+
+* `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `atan2(y, x)`, `sinh`,
+  `cosh`, and `tanh`
+* `exp`, `ln`, `log10`, `log2`, `sqrt`, `pow(x, y)`
+* `abs`, `sign`, `floor`, `ceil`, `round`, `frac`, `mod(x, y)`
+* `min(...)` and `max(...)` of any number of values, `clamp(x, lo, hi)`
+* `if(condition, then, else)`, for piecewise waves, such as
+  `if(t < 0.5, 1, -1)`
+
+Formulas can be nested up to [EXPR_DEPTH_MAX](constant.EXPR_DEPTH_MAX.html)
+levels deep, counting brackets, function calls, signs, and each operator
+in a row, such as each `+` in `t + t + t`.
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::f64::consts::{E, PI};
+
+/** How many levels deep a formula can be nested. */
+pub const EXPR_DEPTH_MAX: usize = 256;
+
+/** A parsed math formula. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+	/// A number.
+	Num(f64),
+
+	/// The time, from 0 up to 1.
+	T,
+
+	/// Negation.
+	Neg(Box<Expr>),
+
+	/// Logical not.
+	Not(Box<Expr>),
+
+	/// A binary operator.
+	Op(Op, Box<Expr>, Box<Expr>),
+
+	/// A function call, with its arguments.
+	Call(Func, Vec<Expr>),
+}
+
+/** A binary operator. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	Mod,
+	Pow,
+	Lt,
+	Le,
+	Gt,
+	Ge,
+	Eq,
+	Ne,
+	And,
+	Or,
+}
+
+/** A built-in function. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Func {
+	Sin,
+	Cos,
+	Tan,
+	Asin,
+	Acos,
+	Atan,
+	Atan2,
+	Sinh,
+	Cosh,
+	Tanh,
+	Exp,
+	Ln,
+	Log10,
+	Log2,
+	Sqrt,
+	Pow,
+	Abs,
+	Sign,
+	Floor,
+	Ceil,
+	Round,
+	Frac,
+	Mod,
+	Min,
+	Max,
+	Clamp,
+	If,
+}
+
+impl Func {
+	/// The function with this name, and how many arguments it takes,
+	/// where `None` is any number from 1 up.
+	fn lookup(name: &str) -> Option<(Func, Option<usize>)> {
+		let found = match name {
+			"sin" => (Func::Sin, Some(1)),
+			"cos" => (Func::Cos, Some(1)),
+			"tan" => (Func::Tan, Some(1)),
+			"asin" => (Func::Asin, Some(1)),
+			"acos" => (Func::Acos, Some(1)),
+			"atan" => (Func::Atan, Some(1)),
+			"atan2" => (Func::Atan2, Some(2)),
+			"sinh" => (Func::Sinh, Some(1)),
+			"cosh" => (Func::Cosh, Some(1)),
+			"tanh" => (Func::Tanh, Some(1)),
+			"exp" => (Func::Exp, Some(1)),
+			"ln" => (Func::Ln, Some(1)),
+			"log10" => (Func::Log10, Some(1)),
+			"log2" => (Func::Log2, Some(1)),
+			"sqrt" => (Func::Sqrt, Some(1)),
+			"pow" => (Func::Pow, Some(2)),
+			"abs" => (Func::Abs, Some(1)),
+			"sign" => (Func::Sign, Some(1)),
+			"floor" => (Func::Floor, Some(1)),
+			"ceil" => (Func::Ceil, Some(1)),
+			"round" => (Func::Round, Some(1)),
+			"frac" => (Func::Frac, Some(1)),
+			"mod" => (Func::Mod, Some(2)),
+			"min" => (Func::Min, None),
+			"max" => (Func::Max, None),
+			"clamp" => (Func::Clamp, Some(3)),
+			"if" => (Func::If, Some(3)),
+			_ => return None,
+		};
+
+		Some(found)
+	}
+}
+
+fn invalid(expr: &str, pos: usize, msg: &str) -> clap::Error {
+	Error::with_description(
+		&format!("invalid wave expression: {}: at column {}: {}", msg, pos + 1, expr),
+		ErrorKind::InvalidValue,
+	)
+}
+
+/** A token, and the column it starts at, counting from 0. */
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Num(f64),
+	Name(String),
+	Sym(&'static str),
+}
+
+fn tokenize(expr: &str) -> Result<Vec<(Token, usize)>, clap::Error> {
+	const SYMBOLS: [&str; 17] = [
+		"<=", ">=", "==", "!=", "&&", "||", "**", "+", "-", "*", "/", "%", "^", "(", ")", ",", "!",
+	];
+
+	const SINGLES: [&str; 2] = ["<", ">"];
+
+	let chars: Vec<char> = expr.chars().collect();
+	let mut tokens = Vec::new();
+	let mut i = 0;
+
+	while i < chars.len() {
+		let c = chars[i];
+
+		if c.is_whitespace() {
+			i += 1;
+		} else if c.is_ascii_digit() || c == '.' {
+			let start = i;
+
+			while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+				i += 1;
+			}
+
+			// An exponent, like 1e-3.
+			if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+				let mut j = i + 1;
+
+				if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+					j += 1;
+				}
+
+				if j < chars.len() && chars[j].is_ascii_digit() {
+					i = j;
+
+					while i < chars.len() && chars[i].is_ascii_digit() {
+						i += 1;
+					}
+				}
+			}
+
+			let text: String = chars[start..i].iter().collect();
+
+			let num = text
+				.parse::<f64>()
+				.map_err(|_e| invalid(expr, start, &format!("invalid number: {}", text)))?;
+
+			tokens.push((Token::Num(num), start));
+		} else if c.is_alphabetic() || c == '_' {
+			let start = i;
+
+			while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+				i += 1;
+			}
+
+			tokens.push((Token::Name(chars[start..i].iter().collect::<String>().to_lowercase()), start));
+		} else {
+			let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+
+			let sym = SYMBOLS
+				.iter()
+				.chain(SINGLES.iter())
+				.find(|sym| rest.starts_with(**sym))
+				.ok_or_else(|| invalid(expr, i, &format!("unexpected character: {}", c)))?;
+
+			// "**" is another way to write "^".
+			tokens.push((Token::Sym(if *sym == "**" { "^" } else { sym }), i));
+			i += sym.chars().count();
+		}
+	}
+
+	Ok(tokens)
+}
+
+/** A recursive descent parser, with one function for each level of
+precedence, from the lowest to the highest. */
+struct Parser<'a> {
+	expr: &'a str,
+	tokens: Vec<(Token, usize)>,
+	pos: usize,
+	depth: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn peek(&self) -> Option<&Token> {
+		self.tokens.get(self.pos).map(|(token, _)| token)
+	}
+
+	/// The column of the current token, or the end of the expression.
+	fn column(&self) -> usize {
+		self.tokens
+			.get(self.pos)
+			.map_or_else(|| self.expr.chars().count(), |(_, col)| *col)
+	}
+
+	fn error(&self, msg: &str) -> clap::Error {
+		invalid(self.expr, self.column(), msg)
+	}
+
+	/// Go one level deeper into the formula, if it isn't too deep.
+	fn enter(&mut self) -> Result<(), clap::Error> {
+		self.depth += 1;
+
+		if self.depth > EXPR_DEPTH_MAX {
+			return Err(self.error(&format!("it's nested too deeply (more than {} levels)", EXPR_DEPTH_MAX)));
+		}
+
+		Ok(())
+	}
+
+	/// Parse one level deeper into the formula.
+	fn nested(&mut self, next: fn(&mut Parser<'a>) -> Result<Expr, clap::Error>) -> Result<Expr, clap::Error> {
+		self.enter()?;
+
+		let res = next(self);
+		self.depth -= 1;
+
+		res
+	}
+
+	/// Take the next token if it's one of these symbols.
+	fn eat(&mut self, syms: &[&'static str]) -> Option<&'static str> {
+		match self.peek() {
+			Some(Token::Sym(sym)) if syms.contains(sym) => {
+				let sym = *sym;
+				self.pos += 1;
+				Some(sym)
+			}
+			_ => None,
+		}
+	}
+
+	fn expect(&mut self, sym: &'static str) -> Result<(), clap::Error> {
+		self.eat(&[sym])
+			.map(|_| ())
+			.ok_or_else(|| self.error(&format!("expected \"{}\"", sym)))
+	}
+
+	/// The binary operator of the next token, and how tightly it binds,
+	/// from 0 for `||` up to 4 for `*`, `/`, and `%`.
+	fn peek_binary(&self) -> Option<(usize, Op)> {
+		let op = match self.peek() {
+			Some(Token::Sym(sym)) => *sym,
+			_ => return None,
+		};
+
+		match op {
+			"||" => Some((0, Op::Or)),
+			"&&" => Some((1, Op::And)),
+			"<=" => Some((2, Op::Le)),
+			">=" => Some((2, Op::Ge)),
+			"==" => Some((2, Op::Eq)),
+			"!=" => Some((2, Op::Ne)),
+			"<" => Some((2, Op::Lt)),
+			">" => Some((2, Op::Gt)),
+			"+" => Some((3, Op::Add)),
+			"-" => Some((3, Op::Sub)),
+			"*" => Some((4, Op::Mul)),
+			"/" => Some((4, Op::Div)),
+			"%" => Some((4, Op::Mod)),
+			_ => None,
+		}
+	}
+
+	/// Parse operators which bind at least as tightly as "level",
+	/// grouping from the left.
+	fn binary(&mut self, level: usize) -> Result<Expr, clap::Error> {
+		let depth = self.depth;
+		let mut left = self.unary()?;
+
+		// Each operator in a row nests the ones before it a level deeper.
+		while let Some((op_level, op)) = self.peek_binary().filter(|(op_level, _)| *op_level >= level) {
+			self.pos += 1;
+			self.enter()?;
+
+			let right = self.binary(op_level + 1)?;
+
+			left = Expr::Op(op, Box::new(left), Box::new(right));
+		}
+
+		self.depth = depth;
+
+		Ok(left)
+	}
+
+	/// Parse a whole formula, or one inside brackets.
+	fn or(&mut self) -> Result<Expr, clap::Error> {
+		self.binary(0)
+	}
+
+	fn unary(&mut self) -> Result<Expr, clap::Error> {
+		match self.eat(&["-", "+", "!"]) {
+			Some("-") => Ok(Expr::Neg(Box::new(self.nested(Parser::unary)?))),
+			Some("!") => Ok(Expr::Not(Box::new(self.nested(Parser::unary)?))),
+			Some(_) => self.nested(Parser::unary),
+			None => self.power(),
+		}
+	}
+
+	fn power(&mut self) -> Result<Expr, clap::Error> {
+		let base = self.primary()?;
+
+		if self.eat(&["^"]).is_some() {
+			// The exponent can be negated, and groups from the right.
+			let exponent = self.nested(Parser::unary)?;
+
+			return Ok(Expr::Op(Op::Pow, Box::new(base), Box::new(exponent)));
+		}
+
+		Ok(base)
+	}
+
+	fn primary(&mut self) -> Result<Expr, clap::Error> {
+		let token = self.peek().cloned();
+
+		match token {
+			Some(Token::Num(num)) => {
+				self.pos += 1;
+				Ok(Expr::Num(num))
+			}
+
+			Some(Token::Sym("(")) => {
+				self.pos += 1;
+				let inner = self.nested(Parser::or)?;
+				self.expect(")")?;
+				Ok(inner)
+			}
+
+			Some(Token::Name(name)) => {
+				let col = self.column();
+				self.pos += 1;
+
+				match name.as_str() {
+					"t" => return Ok(Expr::T),
+					"pi" => return Ok(Expr::Num(PI)),
+					"tau" => return Ok(Expr::Num(2.0 * PI)),
+					"e" => return Ok(Expr::Num(E)),
+					_ => {}
+				}
+
+				let (func, arity) = Func::lookup(&name)
+					.ok_or_else(|| invalid(self.expr, col, &format!("unknown name: {}", name)))?;
+
+				self.expect("(")?;
+
+				let mut args = vec![self.nested(Parser::or)?];
+
+				while self.eat(&[","]).is_some() {
+					args.push(self.nested(Parser::or)?);
+				}
+
+				self.expect(")")?;
+
+				if let Some(arity) = arity {
+					if args.len() != arity {
+						return Err(invalid(
+							self.expr,
+							col,
+							&format!("{} takes {} argument(s), not {}", name, arity, args.len()),
+						));
+					}
+				}
+
+				Ok(Expr::Call(func, args))
+			}
+
+			_ => Err(self.error("expected a number, t, a constant, a function, or \"(\"")),
+		}
+	}
+}
+
+fn truth(val: bool) -> f64 {
+	if val {
+		1.0
+	} else {
+		0.0
+	}
+}
+
+impl Expr {
+	/// Parse a formula.
+	pub fn parse(expr: &str) -> Result<Expr, clap::Error> {
+		let mut parser = Parser {
+			expr,
+			tokens: tokenize(expr)?,
+			pos: 0,
+			depth: 0,
+		};
+
+		if parser.tokens.is_empty() {
+			return Err(invalid(expr, 0, "it's empty"));
+		}
+
+		let parsed = parser.or()?;
+
+		if parser.pos < parser.tokens.len() {
+			return Err(parser.error("unexpected text after the end of the expression"));
+		}
+
+		Ok(parsed)
+	}
+
+	/// The value of the formula at time "t".
+	pub fn eval(&self, t: f64) -> f64 {
+		match self {
+			Expr::Num(num) => *num,
+			Expr::T => t,
+			Expr::Neg(inner) => -inner.eval(t),
+			Expr::Not(inner) => truth(inner.eval(t) == 0.0),
+
+			Expr::Op(op, left, right) => {
+				let a = left.eval(t);
+
+				// Only evaluate what's needed, like "if".
+				match op {
+					Op::And => return truth(a != 0.0 && right.eval(t) != 0.0),
+					Op::Or => return truth(a != 0.0 || right.eval(t) != 0.0),
+					_ => {}
+				}
+
+				let b = right.eval(t);
+
+				match op {
+					Op::Add => a + b,
+					Op::Sub => a - b,
+					Op::Mul => a * b,
+					Op::Div => a / b,
+					Op::Mod => a - b * (a / b).floor(),
+					Op::Pow => a.powf(b),
+					Op::Lt => truth(a < b),
+					Op::Le => truth(a <= b),
+					Op::Gt => truth(a > b),
+					Op::Ge => truth(a >= b),
+					Op::Eq => truth(a == b),
+					Op::Ne => truth(a != b),
+					Op::And | Op::Or => unreachable!(),
+				}
+			}
+
+			Expr::Call(Func::If, args) => {
+				if args[0].eval(t) != 0.0 {
+					args[1].eval(t)
+				} else {
+					args[2].eval(t)
+				}
+			}
+
+			Expr::Call(func, args) => {
+				let vals: Vec<f64> = args.iter().map(|arg| arg.eval(t)).collect();
+				let x = vals[0];
+
+				match func {
+					Func::Sin => x.sin(),
+					Func::Cos => x.cos(),
+					Func::Tan => x.tan(),
+					Func::Asin => x.asin(),
+					Func::Acos => x.acos(),
+					Func::Atan => x.atan(),
+					Func::Atan2 => x.atan2(vals[1]),
+					Func::Sinh => x.sinh(),
+					Func::Cosh => x.cosh(),
+					Func::Tanh => x.tanh(),
+					Func::Exp => x.exp(),
+					Func::Ln => x.ln(),
+					Func::Log10 => x.log10(),
+					Func::Log2 => x.log2(),
+					Func::Sqrt => x.sqrt(),
+					Func::Pow => x.powf(vals[1]),
+					Func::Abs => x.abs(),
+					Func::Sign => {
+						if x == 0.0 {
+							0.0
+						} else {
+							x.signum()
+						}
+					}
+					Func::Floor => x.floor(),
+					Func::Ceil => x.ceil(),
+					Func::Round => x.round(),
+					Func::Frac => x - x.floor(),
+					Func::Mod => x - vals[1] * (x / vals[1]).floor(),
+					Func::Min => vals.iter().copied().fold(f64::INFINITY, f64::min),
+					Func::Max => vals.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+					Func::Clamp => x.max(vals[1]).min(vals[2]),
+					Func::If => unreachable!(),
+				}
+			}
+		}
+	}
+
+	/// The values of the formula at "len" evenly spaced times, from 0 up to 1.
+	pub fn samples(&self, len: usize) -> Result<Vec<f64>, clap::Error> {
+		let mut samples = Vec::with_capacity(len);
+
+		for i in 0..len {
+			let t = i as f64 / len as f64;
+			let val = self.eval(t);
+
+			if !val.is_finite() {
+				return Err(Error::with_description(
+					&format!("wave expression isn't a finite number at t = {} (sample {}): {}", t, i, val),
+					ErrorKind::InvalidValue,
+				));
+			}
+
+			samples.push(val);
+		}
+
+		Ok(samples)
+	}
+}
+
+/** Generate a wave for an arbitrary wave slot from a formula.
+
+If "auto_scale" is true, the wave is stretched or squeezed so that
+its lowest value is 0 and its highest is 4095. Otherwise, -1 to 1 is
+the full range of the device, as with the
+[synth](../synth/index.html) shapes. Then it's converted to the
+device's range as chosen in "convert".
+*/
+pub fn generate_expr_wave(
+	expr: &Expr,
+	auto_scale: bool,
+	convert: &ConvertOptions,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let mut samples = expr.samples(ARBITRARY_WAVE_LEN)?;
+
+	let min = samples.iter().copied().fold(f64::INFINITY, f64::min);
+	let max = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+	if auto_scale && max > min {
+		samples
+			.iter_mut()
+			.for_each(|s| *s = -1.0 + (*s - min) / (max - min) * (1.0 + MAX_PEAK));
+	} else {
+		samples.iter_mut().for_each(|s| *s *= MAX_PEAK);
+	}
+
+	let (samples, report) = convert_to_device(&samples, convert)?;
+
+	Ok((WaveData::new(&samples)?, report))
+}
@@ -0,0 +1,134 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::convert::*;
+use super::expr::*;
+
+use std::f64::consts::PI;
+
+fn eval(expr: &str, t: f64) -> f64 {
+	Expr::parse(expr).unwrap().eval(t)
+}
+
+#[test]
+pub fn parse_ok() {
+	assert_eq!(eval("1 + 2 * 3", 0.0), 7.0);
+	assert_eq!(eval("(1 + 2) * 3", 0.0), 9.0);
+	assert_eq!(eval("-2^2", 0.0), -4.0);
+	assert_eq!(eval("2^3^2", 0.0), 512.0);
+	assert_eq!(eval("2**-1", 0.0), 0.5);
+	assert_eq!(eval("1.5e1 + .5 + 2E-1", 0.0), 15.7);
+	assert_eq!(eval("-7 % 3", 0.0), 2.0);
+	assert_eq!(eval("10 - 2 - 3", 0.0), 5.0);
+	assert_eq!(eval("t * 2", 0.25), 0.5);
+	assert_eq!(eval("TAU / Pi", 0.0), 2.0);
+	assert_eq!(eval("ln(e)", 0.0), 1.0);
+
+	assert!((eval("0.7*sin(2*pi*t) + 0.3*sin(6*pi*t+pi/4)", 0.25) - (0.7 - 0.3 * (PI / 4.0).sin())).abs() < 1e-12);
+
+	assert_eq!(eval("1 < 2 && 2 <= 2 && !(1 > 2) && 3 >= 4 || 1 == 1", 0.0), 1.0);
+	assert_eq!(eval("1 != 1", 0.0), 0.0);
+
+	assert_eq!(eval("if(t < 0.5, 1, -1)", 0.25), 1.0);
+	assert_eq!(eval("if(t < 0.5, 1, -1)", 0.75), -1.0);
+
+	assert_eq!(eval("min(3, t, 2)", 0.5), 0.5);
+	assert_eq!(eval("max(3)", 0.0), 3.0);
+	assert_eq!(eval("clamp(5, -1, 1)", 0.0), 1.0);
+	assert_eq!(eval("sign(-3) + sign(0) + abs(-2)", 0.0), 1.0);
+	assert_eq!(eval("frac(-0.25) + floor(1.5) + ceil(1.5) + round(2.5)", 0.0), 6.75);
+	assert_eq!(eval("mod(7, -3) + pow(2, 3) + sqrt(4) + log10(100) + log2(8)", 0.0), 13.0);
+	assert!((eval("atan2(1, 1)", 0.0) - PI / 4.0).abs() < 1e-12);
+
+	// Only the chosen side of an "if" matters.
+	assert_eq!(eval("if(1, 1, ln(-1))", 0.0), 1.0);
+
+	// Nested as deeply as it can be.
+	assert_eq!(eval(&format!("{}t{}", "(".repeat(200), ")".repeat(200)), 0.5), 0.5);
+	assert_eq!(eval(&format!("{}t", "-".repeat(200)), 0.5), 0.5);
+	assert_eq!(eval(&vec!["t"; 200].join(" + "), 0.5), 100.0);
+}
+
+#[test]
+pub fn parse_err() {
+	let err = Expr::parse("sin(2*pi*t").unwrap_err();
+	assert!(err.message.contains("column 11"), "{}", err.message);
+
+	let err = Expr::parse("1 + foo(t)").unwrap_err();
+	assert!(err.message.contains("unknown name: foo") && err.message.contains("column 5"), "{}", err.message);
+
+	let err = Expr::parse("1 $ 2").unwrap_err();
+	assert!(err.message.contains("column 3"), "{}", err.message);
+
+	Expr::parse("").unwrap_err();
+	Expr::parse("1 +").unwrap_err();
+	Expr::parse("1 2").unwrap_err();
+	Expr::parse("2pi").unwrap_err();
+	Expr::parse("sin(1, 2)").unwrap_err();
+	Expr::parse("if(1, 2)").unwrap_err();
+	Expr::parse("min()").unwrap_err();
+	Expr::parse("x").unwrap_err();
+	Expr::parse("1.2.3").unwrap_err();
+
+	// Nested too deeply.
+	let err = Expr::parse(&format!("{}t", "-".repeat(100_000))).unwrap_err();
+	assert!(err.message.contains("nested too deeply"), "{}", err.message);
+
+	let err = Expr::parse(&format!("{}t{}", "(".repeat(5000), ")".repeat(5000))).unwrap_err();
+	assert!(err.message.contains("nested too deeply"), "{}", err.message);
+
+	Expr::parse(&vec!["t"; 100_000].join("+")).unwrap_err();
+	Expr::parse(&format!("{}t", "sin(".repeat(1000))).unwrap_err();
+	Expr::parse(&format!("2{}", "^2".repeat(1000))).unwrap_err();
+}
+
+#[test]
+pub fn samples_ok() {
+	assert_eq!(Expr::parse("t").unwrap().samples(4).unwrap(), vec![0.0, 0.25, 0.5, 0.75]);
+}
+
+#[test]
+pub fn samples_err() {
+	Expr::parse("1 / t").unwrap().samples(4).unwrap_err();
+	Expr::parse("sqrt(t - 0.5)").unwrap().samples(4).unwrap_err();
+}
+
+#[test]
+pub fn generate_expr_wave_ok() {
+	let opts = ConvertOptions::default();
+
+	// Auto scaling fills the whole range.
+	let (wave, report) = generate_expr_wave(&Expr::parse("0.1 * t + 3").unwrap(), true, &opts).unwrap();
+	assert_eq!(wave.samples()[0], 0);
+	assert_eq!(wave.samples()[2047], 4095);
+	assert_eq!(report.clipped(), 0);
+
+	// Without it, -1 to 1 is the whole range.
+	let (wave, _) = generate_expr_wave(&Expr::parse("if(t < 0.5, 0.5, -0.5)").unwrap(), false, &opts).unwrap();
+	assert_eq!(wave.samples()[0], 3072);
+	assert_eq!(wave.samples()[2047], 1025);
+
+	// A flat line can't be stretched, so it's left as it is.
+	let (wave, _) = generate_expr_wave(&Expr::parse("0").unwrap(), true, &opts).unwrap();
+	assert!(wave.samples().iter().all(|s| *s == 2048));
+}
+
+#[test]
+pub fn generate_expr_wave_err() {
+	let opts = ConvertOptions {
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	generate_expr_wave(&Expr::parse("2 * t").unwrap(), false, &opts).unwrap_err();
+	generate_expr_wave(&Expr::parse("2 * t").unwrap(), true, &opts).unwrap();
+	generate_expr_wave(&Expr::parse("ln(t)").unwrap(), true, &opts).unwrap_err();
+}
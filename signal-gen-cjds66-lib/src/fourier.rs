@@ -0,0 +1,238 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Arbitrary waves built from a recipe of harmonics, such as for
+testing distortion (THD) measurements and filters.
+
+Each harmonic is a sine wave at a whole number of cycles per period,
+with an amplitude and a phase in degrees, where a phase of 0 starts
+at 0 and rises. A list of them can be parsed from text, as
+`harmonic:amplitude` or `harmonic:amplitude@phase`, separated by
+commas or spaces:
+```ignore
+let series = FourierSeries::parse("1:1.0, 3:0.1@30, 5:0.02")?;
+```
+
+Or from CSV, with one harmonic per line, as `harmonic,amplitude` or
+`harmonic,amplitude,phase`. A header line, blank lines, and `#`
+comments are skipped:
+```ignore
+let series = FourierSeries::from_csv("harmonic,amplitude,phase\n1,1.0,0\n3,0.1,30\n")?;
+```
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::f64::consts::PI;
+use std::fs;
+
+/** One harmonic of a Fourier series. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Harmonic {
+	/// Which harmonic it is, where 1 is the fundamental.
+	pub number: usize,
+
+	/// The amplitude, relative to the others.
+	pub amplitude: f64,
+
+	/// The phase, in degrees.
+	pub phase: f64,
+}
+
+/** A list of harmonics, each different. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FourierSeries {
+	harmonics: Vec<Harmonic>,
+}
+
+/** The highest harmonic which fits in an arbitrary wave slot, which
+is just below half the number of samples. */
+pub const MAX_HARMONIC: usize = ARBITRARY_WAVE_LEN / 2 - 1;
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid harmonics: {}", msg), ErrorKind::InvalidValue)
+}
+
+fn parse_num<T: std::str::FromStr>(what: &str, text: &str, context: &str) -> Result<T, clap::Error>
+where
+	T::Err: std::fmt::Display,
+{
+	text.trim()
+		.parse::<T>()
+		.map_err(|e| invalid(&format!("invalid {}: {}: {}: {}", what, text.trim(), e, context)))
+}
+
+impl FourierSeries {
+	/// Check that there's at least one harmonic, and that they're
+	/// all different, in range, and finite.
+	pub fn new(harmonics: &[Harmonic]) -> Result<FourierSeries, clap::Error> {
+		if harmonics.is_empty() {
+			return Err(invalid("there must be at least one"));
+		}
+
+		for (i, h) in harmonics.iter().enumerate() {
+			if h.number < 1 || h.number > MAX_HARMONIC {
+				return Err(invalid(&format!("harmonic number out of range (must be 1 - {}): {}", MAX_HARMONIC, h.number)));
+			}
+
+			if !h.amplitude.is_finite() || !h.phase.is_finite() {
+				return Err(invalid(&format!("amplitude and phase must be finite numbers: harmonic {}", h.number)));
+			}
+
+			if harmonics[..i].iter().any(|other| other.number == h.number) {
+				return Err(invalid(&format!("harmonic given more than once: {}", h.number)));
+			}
+		}
+
+		Ok(FourierSeries {
+			harmonics: harmonics.to_vec(),
+		})
+	}
+
+	/// The harmonics.
+	pub fn harmonics(&self) -> &[Harmonic] {
+		&self.harmonics
+	}
+
+	/// Parse a list like `"1:1.0, 3:0.1@30, 5:0.02"`.
+	pub fn parse(list: &str) -> Result<FourierSeries, clap::Error> {
+		let mut harmonics = Vec::new();
+
+		for item in list.split(|c: char| c == ',' || c.is_whitespace()).filter(|item| !item.is_empty()) {
+			let mut parts = item.splitn(2, ':');
+			let number = parts.next().unwrap_or_default();
+
+			let rest = parts
+				.next()
+				.ok_or_else(|| invalid(&format!("must be harmonic:amplitude or harmonic:amplitude@phase: {}", item)))?;
+
+			let mut parts = rest.splitn(2, '@');
+			let amplitude = parts.next().unwrap_or_default();
+
+			harmonics.push(Harmonic {
+				number: parse_num("harmonic number", number, item)?,
+				amplitude: parse_num("amplitude", amplitude, item)?,
+				phase: match parts.next() {
+					Some(phase) => parse_num("phase", phase, item)?,
+					None => 0.0,
+				},
+			});
+		}
+
+		FourierSeries::new(&harmonics)
+	}
+
+	/// Parse CSV, with one `harmonic,amplitude[,phase]` on each line.
+	pub fn from_csv(text: &str) -> Result<FourierSeries, clap::Error> {
+		let mut harmonics = Vec::new();
+		let mut first = true;
+
+		for (i, line) in text.lines().enumerate() {
+			let line = line.split('#').next().unwrap_or_default().trim();
+
+			if line.is_empty() {
+				continue;
+			}
+
+			let fields: Vec<&str> = line.split([',', ';', '\t']).collect();
+			let context = format!("on line {}", i + 1);
+
+			// The first line is a header if it doesn't start with a number.
+			if first {
+				first = false;
+
+				if fields[0].trim().parse::<f64>().is_err() {
+					continue;
+				}
+			}
+
+			if fields.len() < 2 || fields.len() > 3 {
+				return Err(invalid(&format!("must be harmonic,amplitude or harmonic,amplitude,phase: {}", context)));
+			}
+
+			harmonics.push(Harmonic {
+				number: parse_num("harmonic number", fields[0], &context)?,
+				amplitude: parse_num("amplitude", fields[1], &context)?,
+				phase: match fields.get(2) {
+					Some(phase) if !phase.trim().is_empty() => parse_num("phase", phase, &context)?,
+					_ => 0.0,
+				},
+			});
+		}
+
+		FourierSeries::new(&harmonics)
+	}
+
+	/// Read a CSV file, as with [from_csv](#method.from_csv).
+	pub fn load_csv(path: &str) -> Result<FourierSeries, clap::Error> {
+		let text = fs::read_to_string(path).map_err(|e| {
+			Error::with_description(&format!("failed reading file: {}: {}", path, e), ErrorKind::Io)
+		})?;
+
+		FourierSeries::from_csv(&text)
+	}
+
+	/// The total harmonic distortion, as a fraction of the fundamental,
+	/// or `None` if there's no fundamental.
+	pub fn thd(&self) -> Option<f64> {
+		let fundamental = self.harmonics.iter().find(|h| h.number == 1)?.amplitude.abs();
+
+		if fundamental == 0.0 {
+			return None;
+		}
+
+		let rest = self
+			.harmonics
+			.iter()
+			.filter(|h| h.number > 1)
+			.map(|h| h.amplitude * h.amplitude)
+			.sum::<f64>();
+
+		Some(rest.sqrt() / fundamental)
+	}
+
+	/// The sum of the harmonics at "len" evenly spaced times over one
+	/// period, not normalized.
+	pub fn samples(&self, len: usize) -> Vec<f64> {
+		(0..len)
+			.map(|i| {
+				let t = i as f64 / len as f64;
+
+				self.harmonics
+					.iter()
+					.map(|h| h.amplitude * (2.0 * PI * h.number as f64 * t + h.phase.to_radians()).sin())
+					.sum()
+			})
+			.collect()
+	}
+}
+
+/** Generate a wave for an arbitrary wave slot from a Fourier series.
+
+It's normalized so its peak is the largest one which fits in the
+device's range, keeping the harmonics in proportion, unless "convert"
+chooses a different normalization. Then it's converted to the
+device's range as chosen in "convert".
+*/
+pub fn generate_fourier_wave(
+	series: &FourierSeries,
+	convert: &ConvertOptions,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let convert = ConvertOptions {
+		normalize: convert.normalize.or(Some(1.0)),
+		..convert.clone()
+	};
+
+	let (samples, report) = convert_to_device(&series.samples(ARBITRARY_WAVE_LEN), &convert)?;
+
+	Ok((WaveData::new(&samples)?, report))
+}
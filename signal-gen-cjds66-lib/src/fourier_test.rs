@@ -0,0 +1,113 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::convert::*;
+use super::fourier::*;
+
+#[test]
+pub fn parse_ok() {
+	let series = FourierSeries::parse("1:1.0, 3:0.1@30 5:-0.02@-90").unwrap();
+
+	assert_eq!(
+		series.harmonics(),
+		&[
+			Harmonic { number: 1, amplitude: 1.0, phase: 0.0 },
+			Harmonic { number: 3, amplitude: 0.1, phase: 30.0 },
+			Harmonic { number: 5, amplitude: -0.02, phase: -90.0 },
+		][..]
+	);
+}
+
+#[test]
+pub fn parse_err() {
+	FourierSeries::parse("").unwrap_err();
+	FourierSeries::parse("1").unwrap_err();
+	FourierSeries::parse("1:x").unwrap_err();
+	FourierSeries::parse("1:1@x").unwrap_err();
+	FourierSeries::parse("0:1").unwrap_err();
+	FourierSeries::parse("1.5:1").unwrap_err();
+	FourierSeries::parse("1024:1").unwrap_err();
+	FourierSeries::parse("1:1, 1:0.5").unwrap_err();
+	FourierSeries::parse("1:inf").unwrap_err();
+}
+
+#[test]
+pub fn from_csv_ok() {
+	let csv = "Harmonic, Amplitude, Phase\r\n\r\n1, 1.0, 0 # the fundamental\r\n3;0.1;30\n5\t0.02\n";
+	let series = FourierSeries::from_csv(csv).unwrap();
+
+	assert_eq!(series, FourierSeries::parse("1:1 3:0.1@30 5:0.02").unwrap());
+
+	// No header.
+	assert_eq!(FourierSeries::from_csv("2,0.5,\n").unwrap(), FourierSeries::parse("2:0.5").unwrap());
+}
+
+#[test]
+pub fn from_csv_err() {
+	let err = FourierSeries::from_csv("harmonic,amplitude\n1,1\n3,x\n").unwrap_err();
+	assert!(err.message.contains("on line 3"), "{}", err.message);
+
+	FourierSeries::from_csv("harmonic,amplitude\n").unwrap_err();
+	FourierSeries::from_csv("1,1\nharmonic,amplitude\n").unwrap_err();
+	FourierSeries::from_csv("1\n").unwrap_err();
+	FourierSeries::from_csv("1,1,0,0\n").unwrap_err();
+	FourierSeries::load_csv("/nonexistent/harmonics.csv").unwrap_err();
+}
+
+#[test]
+pub fn thd_ok() {
+	let thd = FourierSeries::parse("1:1.0, 3:0.1@30, 5:0.02").unwrap().thd().unwrap();
+	assert!((thd - (0.01f64 + 0.0004).sqrt()).abs() < 1e-12);
+
+	assert_eq!(FourierSeries::parse("1:2").unwrap().thd(), Some(0.0));
+	assert_eq!(FourierSeries::parse("2:1").unwrap().thd(), None);
+	assert_eq!(FourierSeries::parse("1:0 2:1").unwrap().thd(), None);
+}
+
+#[test]
+pub fn samples_ok() {
+	let samples = FourierSeries::parse("1:1 2:0.5@90").unwrap().samples(4);
+	let expected = [0.5, 1.0 - 0.5, -0.0 + 0.5, -1.0 - 0.5];
+
+	for (s, e) in samples.iter().zip(expected.iter()) {
+		assert!((s - e).abs() < 1e-12, "{:?}", samples);
+	}
+}
+
+#[test]
+pub fn generate_fourier_wave_ok() {
+	// The peak fills the range, whatever the amplitudes are.
+	let (wave, report) = generate_fourier_wave(&FourierSeries::parse("1:0.1").unwrap(), &ConvertOptions::default()).unwrap();
+
+	assert_eq!(wave.samples()[512], 4095);
+	assert_eq!(wave.samples()[1536], 1);
+	assert_eq!(report.clipped(), 0);
+
+	let opts = ConvertOptions {
+		normalize: Some(0.5),
+		..ConvertOptions::default()
+	};
+
+	let (wave, _) = generate_fourier_wave(&FourierSeries::parse("1:3").unwrap(), &opts).unwrap();
+
+	assert_eq!(wave.samples()[512], 3072);
+}
+
+#[test]
+pub fn generate_fourier_wave_err() {
+	let opts = ConvertOptions {
+		gain_db: 1.0,
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	generate_fourier_wave(&FourierSeries::parse("1:1").unwrap(), &opts).unwrap_err();
+}
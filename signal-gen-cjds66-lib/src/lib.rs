@@ -42,25 +42,109 @@ Manufacturer's webpage for the device:
 extern crate bitflags;
 extern crate clap;
 
+pub mod analyze;
+pub mod backup;
+pub mod catalog;
 pub mod command;
+pub mod convert;
 pub mod error;
+pub mod export;
+pub mod expr;
+pub mod fourier;
+pub mod ops;
+pub mod plot;
 pub mod protocol;
+pub mod provision;
+pub mod register;
+pub mod render;
+pub mod resample;
+pub mod scope;
 pub mod serial;
+pub mod spice;
+pub mod stats;
+pub mod synth;
+pub mod transfer;
+pub mod units;
 pub mod util;
+pub mod wav;
+pub mod wave;
 
 
 /* Unit Test Modules */
 
+#[cfg(test)]
+mod analyze_test;
+
+#[cfg(test)]
+mod backup_test;
+
+#[cfg(test)]
+mod catalog_test;
+
 #[cfg(test)]
 mod command_test;
 
+#[cfg(test)]
+mod convert_test;
+
 #[cfg(test)]
 mod error_test;
 
+#[cfg(test)]
+mod export_test;
+
+#[cfg(test)]
+mod expr_test;
+
+#[cfg(test)]
+mod fourier_test;
+
+#[cfg(test)]
+mod ops_test;
+
+#[cfg(test)]
+mod plot_test;
+
+#[cfg(test)]
+mod provision_test;
+
+#[cfg(test)]
+mod register_test;
+
+#[cfg(test)]
+mod render_test;
+
+#[cfg(test)]
+mod resample_test;
+
+#[cfg(test)]
+mod scope_test;
+
 #[cfg(test)]
 mod serial_test;
 
+#[cfg(test)]
+mod spice_test;
+
+#[cfg(test)]
+mod stats_test;
+
+#[cfg(test)]
+mod synth_test;
+
+#[cfg(test)]
+mod transfer_test;
+
+#[cfg(test)]
+mod units_test;
+
 #[cfg(test)]
 mod util_test;
 
+#[cfg(test)]
+mod wav_test;
+
+#[cfg(test)]
+mod wave_test;
+
 /* END Unit Test Modules */
@@ -0,0 +1,628 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Arithmetic and transforms on arbitrary waves.
+
+The operations work on levels which are fractions of full scale,
+where -1 to 1 is the full range of the device, so adding two waves
+adds their distances from the middle of the range, and multiplying
+them is ring modulation. They're available as methods on
+[WaveData](../wave/struct.WaveData.html), which clip the result to
+the device's range:
+```ignore
+let am = carrier.mul(&modulator)?.window(Window::Hann)?;
+```
+
+They can also be chained in a [WavePipeline](struct.WavePipeline.html),
+which keeps the levels unclipped until the end. Its stages are
+separated by `|`, and each one is an operation, followed by its
+arguments, separated by spaces:
+```ignore
+let pipeline = WavePipeline::parse("mix a.txt:0.8 b.txt:0.2 | window hann")?;
+```
+
+Waves in the arguments are the name of a file, `slot:N` for one of
+the device's arbitrary wave slots, or `_` for the result of the
+stages before. The operations are:
+
+* `load WAVE`: Start with a wave.
+* `add WAVE...`, `sub WAVE...`, `mul WAVE...`: Add, subtract, or
+  multiply the waves. If there's already a result, it's the first
+  wave, otherwise the first argument is.
+* `mix WAVE:WEIGHT...`: Add the waves, each multiplied by its weight.
+  Only the waves listed are used, so list `_` to include the result
+  of the stages before.
+* `concat WAVE...`: Squeeze the waves into equal parts of the 2048
+  samples, one after another, starting with the result so far, if
+  there is one.
+* `invert`: Flip the wave upside down.
+* `reverse`: Play the wave backwards.
+* `rotate N` or `rotate Ndeg`: Delay the wave by N samples, or N
+  degrees of its period, wrapping around. Negative values move it
+  earlier.
+* `window NAME`: Fade the wave in and out with a `hann`, `hamming`,
+  `blackman`, `triangle`, or `tukey` window. The Tukey window takes
+  the fraction of the wave which is faded, from 0 to 1, which is 0.5
+  if it's left out, as in `window tukey 0.25`.
+* `repeat K`: Fit K cycles of the wave into the 2048 samples, by
+  keeping every Kth sample.
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::resample::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::f64::consts::PI;
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid wave operation: {}", msg), ErrorKind::InvalidValue)
+}
+
+/** A window which fades a wave in and out. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Window {
+	/// A raised cosine, which fades all the way to zero at the ends.
+	Hann,
+
+	/// A raised cosine which stops a little above zero at the ends.
+	Hamming,
+
+	/// A sum of two cosines, with a narrower middle than Hann.
+	Blackman,
+
+	/// A straight fade up to the middle and back down.
+	Triangle,
+
+	/// Flat in the middle, with cosine fades over this fraction of the wave.
+	Tukey(f64),
+}
+
+impl Window {
+	/// The window's level at "i" of "n" samples.
+	fn at(&self, i: usize, n: usize) -> f64 {
+		if n < 2 {
+			return 1.0;
+		}
+
+		let x = i as f64 / (n - 1) as f64;
+
+		match self {
+			Window::Hann => 0.5 - 0.5 * (2.0 * PI * x).cos(),
+			Window::Hamming => 0.54 - 0.46 * (2.0 * PI * x).cos(),
+			Window::Blackman => 0.42 - 0.5 * (2.0 * PI * x).cos() + 0.08 * (4.0 * PI * x).cos(),
+			Window::Triangle => 1.0 - (2.0 * x - 1.0).abs(),
+			Window::Tukey(alpha) => {
+				let edge = alpha / 2.0;
+
+				if *alpha <= 0.0 {
+					1.0
+				} else if x < edge {
+					0.5 - 0.5 * (PI * x / edge).cos()
+				} else if x > 1.0 - edge {
+					0.5 - 0.5 * (PI * (1.0 - x) / edge).cos()
+				} else {
+					1.0
+				}
+			}
+		}
+	}
+}
+
+/** How far to rotate a wave. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rotation {
+	/// A number of samples.
+	Samples(i64),
+
+	/// Degrees of the wave's period.
+	Degrees(f64),
+}
+
+impl Rotation {
+	/// The rotation in samples, for a wave of "len" samples.
+	fn samples(&self, len: usize) -> i64 {
+		match self {
+			Rotation::Samples(n) => *n,
+			Rotation::Degrees(deg) => (deg / 360.0 * len as f64).round() as i64,
+		}
+	}
+}
+
+/** Add the waves, sample by sample. The result is as long as the shortest. */
+pub fn add(a: &[f64], b: &[f64]) -> Vec<f64> {
+	a.iter().zip(b.iter()).map(|(a, b)| a + b).collect()
+}
+
+/** Subtract "b" from "a", sample by sample. */
+pub fn sub(a: &[f64], b: &[f64]) -> Vec<f64> {
+	a.iter().zip(b.iter()).map(|(a, b)| a - b).collect()
+}
+
+/** Multiply the waves, sample by sample, which is ring modulation. */
+pub fn mul(a: &[f64], b: &[f64]) -> Vec<f64> {
+	a.iter().zip(b.iter()).map(|(a, b)| a * b).collect()
+}
+
+/** Add the waves, each multiplied by its weight. */
+pub fn mix(waves: &[(&[f64], f64)]) -> Vec<f64> {
+	let len = waves.iter().map(|(wave, _)| wave.len()).min().unwrap_or_default();
+
+	(0..len)
+		.map(|i| waves.iter().map(|(wave, weight)| wave[i] * weight).sum())
+		.collect()
+}
+
+/** Flip the wave upside down. */
+pub fn invert(wave: &[f64]) -> Vec<f64> {
+	wave.iter().map(|s| -s).collect()
+}
+
+/** Play the wave backwards. */
+pub fn reverse(wave: &[f64]) -> Vec<f64> {
+	wave.iter().rev().copied().collect()
+}
+
+/** Delay the wave, wrapping around. */
+pub fn rotate(wave: &[f64], rotation: Rotation) -> Vec<f64> {
+	let len = wave.len() as i64;
+
+	if len == 0 {
+		return Vec::new();
+	}
+
+	let shift = rotation.samples(wave.len()).rem_euclid(len);
+
+	(0..len).map(|i| wave[(i - shift).rem_euclid(len) as usize]).collect()
+}
+
+/** Fade the wave in and out. */
+pub fn window(wave: &[f64], window: Window) -> Vec<f64> {
+	wave.iter()
+		.enumerate()
+		.map(|(i, s)| s * window.at(i, wave.len()))
+		.collect()
+}
+
+/** Fit "k" cycles of the wave into the same number of samples, by
+keeping every "k"th sample. Harmonics above 1/"k" of the highest one
+which fits will alias. */
+pub fn repeat(wave: &[f64], k: usize) -> Vec<f64> {
+	(0..wave.len()).map(|i| wave[i * k % wave.len()]).collect()
+}
+
+/** Squeeze the waves into "len" samples, one after another, each
+taking an equal part, resampled with band-limited sinc interpolation. */
+pub fn concat(waves: &[&[f64]], len: usize) -> Vec<f64> {
+	let mut out = Vec::with_capacity(len);
+
+	for (i, wave) in waves.iter().enumerate() {
+		let start = len * i / waves.len();
+		let end = len * (i + 1) / waves.len();
+
+		out.extend(resample(wave, end - start, Interpolation::Sinc));
+	}
+
+	out
+}
+
+/** The levels of a wave, as fractions of full scale. */
+pub fn levels(wave: &WaveData) -> Vec<f64> {
+	wave.samples().iter().map(|s| (*s as f64 - HALF_SCALE) / HALF_SCALE).collect()
+}
+
+/** A wave from levels which are fractions of full scale, clipped to
+the device's range. There must be 2048 of them. */
+pub fn from_levels(levels: &[f64]) -> Result<WaveData, clap::Error> {
+	let (samples, _) = convert_to_device(levels, &ConvertOptions::default())?;
+
+	WaveData::new(&samples)
+}
+
+impl WaveData {
+	fn map_levels(&self, f: impl FnOnce(&[f64]) -> Vec<f64>) -> Result<WaveData, clap::Error> {
+		from_levels(&f(&levels(self)))
+	}
+
+	/// Add another wave, clipping the result.
+	pub fn add(&self, other: &WaveData) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| add(a, &levels(other)))
+	}
+
+	/// Subtract another wave, clipping the result.
+	pub fn sub(&self, other: &WaveData) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| sub(a, &levels(other)))
+	}
+
+	/// Multiply by another wave, which is ring modulation.
+	pub fn mul(&self, other: &WaveData) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| mul(a, &levels(other)))
+	}
+
+	/// Add the waves, each multiplied by its weight, clipping the result.
+	pub fn mix(waves: &[(&WaveData, f64)]) -> Result<WaveData, clap::Error> {
+		if waves.is_empty() {
+			return Err(invalid("there are no waves to mix"));
+		}
+
+		let all: Vec<(Vec<f64>, f64)> = waves.iter().map(|(wave, weight)| (levels(wave), *weight)).collect();
+		let refs: Vec<(&[f64], f64)> = all.iter().map(|(wave, weight)| (&wave[..], *weight)).collect();
+
+		from_levels(&mix(&refs))
+	}
+
+	/// Flip the wave upside down, clipping the bottom value, since
+	/// there's one step less above the middle than below it.
+	pub fn invert(&self) -> Result<WaveData, clap::Error> {
+		self.map_levels(invert)
+	}
+
+	/// Play the wave backwards.
+	pub fn reverse(&self) -> Result<WaveData, clap::Error> {
+		self.map_levels(reverse)
+	}
+
+	/// Delay the wave, wrapping around.
+	pub fn rotate(&self, rotation: Rotation) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| rotate(a, rotation))
+	}
+
+	/// Fade the wave in and out.
+	pub fn window(&self, window: Window) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| crate::ops::window(a, window))
+	}
+
+	/// Fit "k" cycles of the wave into it.
+	pub fn repeat(&self, k: usize) -> Result<WaveData, clap::Error> {
+		self.map_levels(|a| repeat(a, k))
+	}
+
+	/// Squeeze the waves into one, one after another.
+	pub fn concat(waves: &[&WaveData]) -> Result<WaveData, clap::Error> {
+		if waves.is_empty() {
+			return Err(invalid("there are no waves to concatenate"));
+		}
+
+		let all: Vec<Vec<f64>> = waves.iter().map(|wave| levels(wave)).collect();
+		let refs: Vec<&[f64]> = all.iter().map(|wave| &wave[..]).collect();
+
+		from_levels(&concat(&refs, ARBITRARY_WAVE_LEN))
+	}
+}
+
+/** Where a wave in a pipeline comes from. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+	/// A file, in the device's text format, or a .wav file.
+	File(String),
+
+	/// One of the device's arbitrary wave slots.
+	Slot(f64),
+
+	/// The result of the stages before.
+	Current,
+}
+
+impl Source {
+	/// Parse a file name, `slot:N`, or `_`.
+	pub fn parse(source: &str) -> Result<Source, clap::Error> {
+		if source == "_" {
+			return Ok(Source::Current);
+		}
+
+		if let Some(slot) = source.strip_prefix("slot:") {
+			let num = slot
+				.parse::<f64>()
+				.ok()
+				.filter(|n| n.fract() == 0.0 && (SET_ARBITRARY_WAVE_ARG_NUM_MIN..=SET_ARBITRARY_WAVE_ARG_NUM_MAX).contains(n))
+				.ok_or_else(|| {
+					invalid(&format!(
+						"unsupported slot number (must be {}-{}): {}",
+						SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, source
+					))
+				})?;
+
+			return Ok(Source::Slot(num));
+		}
+
+		Ok(Source::File(source.to_string()))
+	}
+}
+
+/** One stage of a pipeline. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaveOp {
+	/// Start with a wave.
+	Load(Source),
+
+	/// Add the waves to the result so far, or to the first of them.
+	Add(Vec<Source>),
+
+	/// Subtract the waves from the result so far, or from the first of them.
+	Sub(Vec<Source>),
+
+	/// Multiply the result so far, or the first wave, by the waves.
+	Mul(Vec<Source>),
+
+	/// Add the waves, each multiplied by its weight.
+	Mix(Vec<(Source, f64)>),
+
+	/// Squeeze the waves into equal parts, after the result so far.
+	Concat(Vec<Source>),
+
+	/// Flip the wave upside down.
+	Invert,
+
+	/// Play the wave backwards.
+	Reverse,
+
+	/// Delay the wave, wrapping around.
+	Rotate(Rotation),
+
+	/// Fade the wave in and out.
+	Window(Window),
+
+	/// Fit this many cycles of the wave into it.
+	Repeat(usize),
+}
+
+/** A chain of operations on waves. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavePipeline {
+	/// The stages, in order.
+	pub ops: Vec<WaveOp>,
+}
+
+fn parse_stage(stage: &str) -> Result<WaveOp, clap::Error> {
+	let words: Vec<&str> = stage.split_whitespace().collect();
+	let name = words.first().map(|w| w.to_lowercase()).unwrap_or_default();
+	let args = &words[words.len().min(1)..];
+
+	let sources = || -> Result<Vec<Source>, clap::Error> {
+		if args.is_empty() {
+			return Err(invalid(&format!("{}: needs at least one wave", name)));
+		}
+
+		args.iter().map(|arg| Source::parse(arg)).collect()
+	};
+
+	let no_args = |op: WaveOp| {
+		if args.is_empty() {
+			Ok(op)
+		} else {
+			Err(invalid(&format!("{}: doesn't take any arguments: {}", name, args.join(" "))))
+		}
+	};
+
+	let one_arg = || {
+		if args.len() == 1 {
+			Ok(args[0])
+		} else {
+			Err(invalid(&format!("{}: takes one argument", name)))
+		}
+	};
+
+	match name.as_str() {
+		"load" => Ok(WaveOp::Load(Source::parse(one_arg()?)?)),
+		"add" => Ok(WaveOp::Add(sources()?)),
+		"sub" | "subtract" => Ok(WaveOp::Sub(sources()?)),
+		"mul" | "multiply" | "am" => Ok(WaveOp::Mul(sources()?)),
+		"concat" => Ok(WaveOp::Concat(sources()?)),
+
+		"mix" => {
+			if args.is_empty() {
+				return Err(invalid("mix: needs at least one wave"));
+			}
+
+			let mut waves = Vec::new();
+
+			for arg in args.iter() {
+				let (source, weight) = arg
+					.rsplit_once(':')
+					.and_then(|(source, weight)| weight.parse::<f64>().ok().filter(|w| w.is_finite()).map(|w| (source, w)))
+					.ok_or_else(|| invalid(&format!("mix: each wave needs a weight, as WAVE:WEIGHT: {}", arg)))?;
+
+				waves.push((Source::parse(source)?, weight));
+			}
+
+			Ok(WaveOp::Mix(waves))
+		}
+
+		"invert" => no_args(WaveOp::Invert),
+		"reverse" => no_args(WaveOp::Reverse),
+
+		"rotate" => {
+			let arg = one_arg()?;
+
+			let rotation = match arg.strip_suffix("deg") {
+				Some(deg) => deg.parse::<f64>().ok().filter(|d| d.is_finite()).map(Rotation::Degrees),
+				None => arg.parse::<i64>().ok().map(Rotation::Samples),
+			};
+
+			Ok(WaveOp::Rotate(rotation.ok_or_else(|| {
+				invalid(&format!("rotate: must be a whole number of samples, or a number of degrees, such as 90deg: {}", arg))
+			})?))
+		}
+
+		"window" => {
+			let kind = args.first().map(|w| w.to_lowercase()).unwrap_or_default();
+
+			let window = match (kind.as_str(), args.len()) {
+				("hann" | "hanning", 1) => Window::Hann,
+				("hamming", 1) => Window::Hamming,
+				("blackman", 1) => Window::Blackman,
+				("triangle" | "bartlett", 1) => Window::Triangle,
+				("tukey", 1) => Window::Tukey(0.5),
+				("tukey", 2) => Window::Tukey(
+					args[1]
+						.parse::<f64>()
+						.ok()
+						.filter(|a| (0.0..=1.0).contains(a))
+						.ok_or_else(|| invalid(&format!("window tukey: the faded fraction must be 0 - 1: {}", args[1])))?,
+				),
+				_ => {
+					return Err(invalid(&format!(
+						"window: must be hann, hamming, blackman, triangle, or tukey with an optional fraction: {}",
+						args.join(" ")
+					)))
+				}
+			};
+
+			Ok(WaveOp::Window(window))
+		}
+
+		"repeat" => {
+			let arg = one_arg()?;
+
+			let k = arg
+				.parse::<usize>()
+				.ok()
+				.filter(|k| (1..=ARBITRARY_WAVE_LEN / 2).contains(k))
+				.ok_or_else(|| invalid(&format!("repeat: must be a whole number of cycles from 1 - {}: {}", ARBITRARY_WAVE_LEN / 2, arg)))?;
+
+			Ok(WaveOp::Repeat(k))
+		}
+
+		"" => Err(invalid("empty stage")),
+
+		_ => Err(invalid(&format!(
+			"unknown operation: {} (must be load, add, sub, mul, mix, concat, invert, reverse, rotate, window, or repeat)",
+			name
+		))),
+	}
+}
+
+impl WavePipeline {
+	/// Parse stages separated by `|`.
+	pub fn parse(pipeline: &str) -> Result<WavePipeline, clap::Error> {
+		let ops = pipeline
+			.split('|')
+			.map(parse_stage)
+			.collect::<Result<Vec<WaveOp>, clap::Error>>()?;
+
+		Ok(WavePipeline { ops })
+	}
+
+	/// All the waves the pipeline reads, in order.
+	pub fn sources(&self) -> Vec<&Source> {
+		self.ops
+			.iter()
+			.flat_map(|op| match op {
+				WaveOp::Load(source) => vec![source],
+				WaveOp::Add(sources) | WaveOp::Sub(sources) | WaveOp::Mul(sources) | WaveOp::Concat(sources) => {
+					sources.iter().collect()
+				}
+				WaveOp::Mix(waves) => waves.iter().map(|(source, _)| source).collect(),
+				_ => Vec::new(),
+			})
+			.collect()
+	}
+
+	/// Whether the pipeline reads any of the device's slots.
+	pub fn uses_slots(&self) -> bool {
+		self.sources().iter().any(|source| matches!(source, Source::Slot(_)))
+	}
+
+	/// Run the pipeline, getting each file and slot wave with "load".
+	/// Returns the levels, as fractions of full scale, unclipped.
+	pub fn run<F>(&self, mut load: F) -> Result<Vec<f64>, clap::Error>
+	where
+		F: FnMut(&Source) -> Result<WaveData, clap::Error>,
+	{
+		let mut current: Option<Vec<f64>> = None;
+
+		for op in self.ops.iter() {
+			let mut get = |source: &Source, current: &Option<Vec<f64>>| -> Result<Vec<f64>, clap::Error> {
+				match source {
+					Source::Current => current
+						.clone()
+						.ok_or_else(|| invalid("_ is the result of the stages before, but there aren't any")),
+					_ => Ok(levels(&load(source)?)),
+				}
+			};
+
+			// The result so far, followed by the waves listed.
+			let mut operands = |sources: &[Source], current: &Option<Vec<f64>>| -> Result<Vec<Vec<f64>>, clap::Error> {
+				let mut waves: Vec<Vec<f64>> = current.iter().cloned().collect();
+
+				for source in sources.iter() {
+					waves.push(get(source, current)?);
+				}
+
+				Ok(waves)
+			};
+
+			let next = match op {
+				WaveOp::Load(source) => get(source, &current)?,
+
+				WaveOp::Add(sources) | WaveOp::Sub(sources) | WaveOp::Mul(sources) => {
+					let waves = operands(sources, &current)?;
+					let mut acc = waves[0].clone();
+
+					for wave in waves[1..].iter() {
+						acc = match op {
+							WaveOp::Add(_) => add(&acc, wave),
+							WaveOp::Sub(_) => sub(&acc, wave),
+							_ => mul(&acc, wave),
+						};
+					}
+
+					acc
+				}
+
+				WaveOp::Concat(sources) => {
+					let waves = operands(sources, &current)?;
+					let refs: Vec<&[f64]> = waves.iter().map(|wave| &wave[..]).collect();
+
+					concat(&refs, ARBITRARY_WAVE_LEN)
+				}
+
+				WaveOp::Mix(sources) => {
+					let mut waves = Vec::with_capacity(sources.len());
+
+					for (source, weight) in sources.iter() {
+						waves.push((get(source, &current)?, *weight));
+					}
+
+					let refs: Vec<(&[f64], f64)> = waves.iter().map(|(wave, weight)| (&wave[..], *weight)).collect();
+
+					mix(&refs)
+				}
+
+				_ => {
+					let name = match op {
+						WaveOp::Invert => "invert",
+						WaveOp::Reverse => "reverse",
+						WaveOp::Rotate(_) => "rotate",
+						WaveOp::Window(_) => "window",
+						_ => "repeat",
+					};
+
+					let wave = current
+						.as_ref()
+						.ok_or_else(|| invalid(&format!("{}: there's no wave yet, so start with load, or another operation which reads waves", name)))?;
+
+					match op {
+						WaveOp::Invert => invert(wave),
+						WaveOp::Reverse => reverse(wave),
+						WaveOp::Rotate(rotation) => rotate(wave, *rotation),
+						WaveOp::Window(kind) => window(wave, *kind),
+						WaveOp::Repeat(k) => repeat(wave, *k),
+						_ => wave.clone(),
+					}
+				}
+			};
+
+			current = Some(next);
+		}
+
+		current.ok_or_else(|| invalid("there are no stages"))
+	}
+}
@@ -0,0 +1,174 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::ops::*;
+use super::protocol::*;
+use super::wave::*;
+
+fn ramp() -> WaveData {
+	let samples: Vec<u16> = (0..ARBITRARY_WAVE_LEN as u16).map(|i| i * 2).collect();
+
+	WaveData::new(&samples).unwrap()
+}
+
+fn flat(val: u16) -> WaveData {
+	WaveData::new(&[val; ARBITRARY_WAVE_LEN]).unwrap()
+}
+
+#[test]
+pub fn arithmetic_ok() {
+	assert_eq!(add(&[0.25, -0.5], &[0.5, 0.25]), vec![0.75, -0.25]);
+	assert_eq!(sub(&[0.25, -0.5], &[0.5, 0.25]), vec![-0.25, -0.75]);
+	assert_eq!(mul(&[0.5, -0.5], &[0.5, 1.0]), vec![0.25, -0.5]);
+	assert_eq!(mix(&[(&[1.0, 0.5][..], 0.8), (&[0.5, 0.5][..], 0.2)]), vec![0.9, 0.5]);
+	assert_eq!(invert(&[0.5, -0.25]), vec![-0.5, 0.25]);
+}
+
+#[test]
+pub fn reorder_ok() {
+	let wave = [0.0, 1.0, 2.0, 3.0];
+
+	assert_eq!(reverse(&wave), vec![3.0, 2.0, 1.0, 0.0]);
+	assert_eq!(rotate(&wave, Rotation::Samples(1)), vec![3.0, 0.0, 1.0, 2.0]);
+	assert_eq!(rotate(&wave, Rotation::Samples(-1)), vec![1.0, 2.0, 3.0, 0.0]);
+	assert_eq!(rotate(&wave, Rotation::Degrees(180.0)), vec![2.0, 3.0, 0.0, 1.0]);
+	assert_eq!(repeat(&wave, 2), vec![0.0, 2.0, 0.0, 2.0]);
+}
+
+#[test]
+pub fn window_ok() {
+	let faded = window(&[1.0; 101], Window::Hann);
+
+	assert!(faded[0].abs() < 1e-12);
+	assert!((faded[50] - 1.0).abs() < 1e-12);
+	assert!(faded[100].abs() < 1e-12);
+
+	let faded = window(&[1.0; 101], Window::Tukey(0.5));
+
+	assert!(faded[0].abs() < 1e-12);
+	assert_eq!(faded[25..=75], [1.0; 51][..]);
+
+	assert_eq!(window(&[1.0; 11], Window::Tukey(0.0)), vec![1.0; 11]);
+	assert!((window(&[1.0; 11], Window::Triangle)[5] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+pub fn concat_ok() {
+	let out = concat(&[&[0.5; 16][..], &[-0.5; 8][..]], 20);
+
+	assert_eq!(out.len(), 20);
+	assert!(out[..10].iter().all(|s| (s - 0.5).abs() < 1e-9));
+	assert!(out[10..].iter().all(|s| (s + 0.5).abs() < 1e-9));
+}
+
+#[test]
+pub fn wave_data_ok() {
+	let wave = ramp();
+
+	assert_eq!(wave.reverse().unwrap().samples()[0], 4094);
+	assert_eq!(wave.rotate(Rotation::Samples(1)).unwrap().samples()[1], 0);
+	assert_eq!(wave.repeat(2).unwrap().samples()[1024], 0);
+	assert_eq!(wave.invert().unwrap().samples()[1], 4094);
+
+	// The bottom of the range can't be flipped exactly, so it's clipped.
+	assert_eq!(wave.invert().unwrap().samples()[0], 4095);
+
+	assert_eq!(flat(3072).add(&flat(3072)).unwrap(), flat(4095));
+	assert_eq!(flat(3072).sub(&flat(3072)).unwrap(), flat(2048));
+	assert_eq!(flat(3072).mul(&flat(1024)).unwrap(), flat(1536));
+	assert_eq!(WaveData::mix(&[(&flat(4095), 0.5), (&flat(0), 0.5)]).unwrap(), flat(2048));
+	assert_eq!(flat(4000).window(Window::Hann).unwrap().samples()[0], 2048);
+	assert_eq!(WaveData::concat(&[&flat(3072), &flat(1024)]).unwrap().samples()[1500], 1024);
+}
+
+#[test]
+pub fn wave_data_err() {
+	WaveData::mix(&[]).unwrap_err();
+	WaveData::concat(&[]).unwrap_err();
+}
+
+#[test]
+pub fn pipeline_parse_ok() {
+	let pipeline = WavePipeline::parse("mix a.txt:0.8 slot:3:0.2 | window hann | rotate 90deg | repeat 2").unwrap();
+
+	assert_eq!(
+		pipeline.ops,
+		vec![
+			WaveOp::Mix(vec![(Source::File("a.txt".to_string()), 0.8), (Source::Slot(3.0), 0.2)]),
+			WaveOp::Window(Window::Hann),
+			WaveOp::Rotate(Rotation::Degrees(90.0)),
+			WaveOp::Repeat(2),
+		]
+	);
+	assert!(pipeline.uses_slots());
+
+	let pipeline = WavePipeline::parse("load a.txt|add _ b.txt|window tukey 0.25|rotate -10|invert").unwrap();
+
+	assert_eq!(pipeline.ops[1], WaveOp::Add(vec![Source::Current, Source::File("b.txt".to_string())]));
+	assert_eq!(pipeline.ops[2], WaveOp::Window(Window::Tukey(0.25)));
+	assert_eq!(pipeline.ops[3], WaveOp::Rotate(Rotation::Samples(-10)));
+	assert!(!pipeline.uses_slots());
+}
+
+#[test]
+pub fn pipeline_parse_err() {
+	WavePipeline::parse("").unwrap_err();
+	WavePipeline::parse("load a.txt |").unwrap_err();
+	WavePipeline::parse("shuffle").unwrap_err();
+	WavePipeline::parse("load").unwrap_err();
+	WavePipeline::parse("load a.txt b.txt").unwrap_err();
+	WavePipeline::parse("add").unwrap_err();
+	WavePipeline::parse("mix a.txt").unwrap_err();
+	WavePipeline::parse("mix a.txt:x").unwrap_err();
+	WavePipeline::parse("load slot:0").unwrap_err();
+	WavePipeline::parse("load slot:1.5").unwrap_err();
+	WavePipeline::parse("invert now").unwrap_err();
+	WavePipeline::parse("rotate 1.5").unwrap_err();
+	WavePipeline::parse("rotate xdeg").unwrap_err();
+	WavePipeline::parse("window kaiser").unwrap_err();
+	WavePipeline::parse("window tukey 2").unwrap_err();
+	WavePipeline::parse("repeat 0").unwrap_err();
+	WavePipeline::parse("repeat 1025").unwrap_err();
+}
+
+#[test]
+pub fn pipeline_run_ok() {
+	let load = |source: &Source| match source {
+		Source::File(name) if name == "high" => Ok(flat(3072)),
+		_ => Ok(flat(1024)),
+	};
+
+	let levels = WavePipeline::parse("mix high:0.8 low:0.2").unwrap().run(load).unwrap();
+	assert!((levels[0] - 0.3).abs() < 1e-12);
+
+	// The result so far is the first operand.
+	let levels = WavePipeline::parse("load high | add high | invert").unwrap().run(load).unwrap();
+	assert_eq!(levels[0], -1.0);
+
+	let levels = WavePipeline::parse("load high | mix _:0.5 low:0.5").unwrap().run(load).unwrap();
+	assert_eq!(levels[0], 0.0);
+
+	let levels = WavePipeline::parse("mul high high").unwrap().run(load).unwrap();
+	assert_eq!(levels[0], 0.25);
+
+	let levels = WavePipeline::parse("load high | concat low").unwrap().run(load).unwrap();
+	assert_eq!(levels.len(), ARBITRARY_WAVE_LEN);
+	assert!((levels[2000] + 0.5).abs() < 1e-9);
+}
+
+#[test]
+pub fn pipeline_run_err() {
+	let load = |_: &Source| Ok(WaveData::default());
+
+	WavePipeline::parse("invert").unwrap().run(load).unwrap_err();
+	WavePipeline::parse("load _").unwrap().run(load).unwrap_err();
+	WavePipeline::parse("load a.txt").unwrap().run(|_| Err(clap::Error::with_description("no file", clap::ErrorKind::Io))).unwrap_err();
+}
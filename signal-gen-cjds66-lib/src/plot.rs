@@ -0,0 +1,260 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Plots of arbitrary waves as text, for a quick look in a terminal.
+
+The wave is drawn with Unicode braille characters, which have 2 × 4
+dots in each character, or with half block characters, which have
+1 × 2, for terminals and fonts without braille. The vertical axis is
+always the device's whole range of 0 - 4095, so the size and offset
+of the wave can be seen as well as its shape, and the rows which hold
+the wave's minimum, maximum, and mean are labelled:
+```text
+4095 ┤  ⢀⡤⠖⠋⠉⠙⠲⢤⡀                 ◂ max 4095
+     │⢀⡴⠋       ⠙⢦⡀
+2048 ┤⠋           ⠙⣆           ⣠  ◂ mean 2048
+     │             ⠈⠳⣄       ⣠⠞⠁
+   0 ┤               ⠈⠓⠦⣄⣀⣠⠴⠚⠁    ◂ min 1
+     └──────────────────────────
+      0                     2047
+```
+
+A second wave can be drawn over the first to compare them. Where it
+doesn't overlap the first one, it's drawn in yellow if colour is
+turned on.
+*/
+
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+
+/** Which characters to draw with. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlotStyle {
+	/// Braille, with 2 × 4 dots in each character.
+	Braille,
+
+	/// Half blocks, with 1 × 2 dots in each character.
+	Block,
+}
+
+impl PlotStyle {
+	/// Parse `braille` or `block`.
+	pub fn parse(style: &str) -> Result<PlotStyle, clap::Error> {
+		match style {
+			"braille" => Ok(PlotStyle::Braille),
+			"block" => Ok(PlotStyle::Block),
+			_ => Err(Error::with_description(
+				&format!("unsupported plot style (must be braille or block): {}", style),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+
+	/// How many dots there are across and down each character.
+	fn dots(&self) -> (usize, usize) {
+		match self {
+			PlotStyle::Braille => (2, 4),
+			PlotStyle::Block => (1, 2),
+		}
+	}
+
+	/// The character with some of its dots set. Bit `x * rows + y` is
+	/// the dot in column `x` and row `y`.
+	fn cell(&self, mask: u8) -> char {
+		match self {
+			PlotStyle::Braille => {
+				// The braille dots are numbered down the first column,
+				// then down the second, with the bottom row last.
+				const BITS: [u8; 8] = [0x01, 0x02, 0x04, 0x40, 0x08, 0x10, 0x20, 0x80];
+
+				let bits = (0..8).filter(|i| mask & (1 << i) != 0).fold(0u32, |bits, i| bits | BITS[i] as u32);
+
+				if bits == 0 {
+					' '
+				} else {
+					char::from_u32(0x2800 + bits).unwrap_or(' ')
+				}
+			}
+
+			PlotStyle::Block => match mask & 0b11 {
+				0b01 => '▀',
+				0b10 => '▄',
+				0b11 => '█',
+				_ => ' ',
+			},
+		}
+	}
+}
+
+/** How to draw a plot. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlotOptions {
+	/// How many characters wide the plot is.
+	pub width: usize,
+
+	/// How many lines high the plot is.
+	pub height: usize,
+
+	/// Which characters to draw with.
+	pub style: PlotStyle,
+
+	/// Whether to draw the second wave in colour, with ANSI escape codes.
+	pub color: bool,
+}
+
+impl Default for PlotOptions {
+	fn default() -> PlotOptions {
+		PlotOptions {
+			width: 64,
+			height: 12,
+			style: PlotStyle::Braille,
+			color: false,
+		}
+	}
+}
+
+impl PlotOptions {
+	/// Parse a size like `80x16`, in characters wide by lines high.
+	pub fn parse_size(size: &str) -> Result<(usize, usize), clap::Error> {
+		let err = || {
+			Error::with_description(
+				&format!("unsupported plot size (must be WIDTHxHEIGHT, at least 8x3): {}", size),
+				ErrorKind::InvalidValue,
+			)
+		};
+
+		let (width, height) = size.split_once('x').ok_or_else(err)?;
+		let width = width.trim().parse::<usize>().map_err(|_e| err())?;
+		let height = height.trim().parse::<usize>().map_err(|_e| err())?;
+
+		if width < 8 || height < 3 {
+			return Err(err());
+		}
+
+		Ok((width, height))
+	}
+}
+
+/** The smallest, largest, and mean sample of a wave. */
+fn summary(wave: &WaveData) -> (u16, u16, f64) {
+	let samples = wave.samples();
+	let min = samples.iter().copied().min().unwrap_or_default();
+	let max = samples.iter().copied().max().unwrap_or_default();
+	let mean = samples.iter().map(|s| *s as f64).sum::<f64>() / samples.len() as f64;
+
+	(min, max, mean)
+}
+
+/** Which row of dots a value is drawn in, counting from the top. */
+fn dot_row(value: f64, rows: usize) -> usize {
+	let y = (ARBITRARY_WAVE_MAX as f64 - value) / ARBITRARY_WAVE_MAX as f64 * (rows - 1) as f64;
+
+	(y.round().max(0.0) as usize).min(rows - 1)
+}
+
+/** Draw a wave's dots into a grid of character masks. Each column of
+dots covers a run of samples, and is filled in from the lowest to the
+highest of them, and the last sample before them, so the trace is
+joined up. */
+fn draw(wave: &WaveData, opts: &PlotOptions) -> Vec<Vec<u8>> {
+	let (dx, dy) = opts.style.dots();
+	let (cols, rows) = (opts.width * dx, opts.height * dy);
+	let samples = wave.samples();
+	let mut grid = vec![vec![0u8; opts.width]; opts.height];
+
+	for x in 0..cols {
+		let start = x * samples.len() / cols;
+		let end = ((x + 1) * samples.len() / cols).max(start + 1);
+		let run = &samples[start.saturating_sub(1)..end];
+
+		let top = dot_row(*run.iter().max().unwrap_or(&0) as f64, rows);
+		let bottom = dot_row(*run.iter().min().unwrap_or(&0) as f64, rows);
+
+		for y in top..=bottom {
+			grid[y / dy][x / dx] |= 1 << ((x % dx) * dy + y % dy);
+		}
+	}
+
+	grid
+}
+
+/** Plot a wave, and optionally a second one over it to compare them,
+as lines of text. Returns the plot, ending with a newline. */
+pub fn plot(wave: &WaveData, compare: Option<&WaveData>, opts: &PlotOptions) -> String {
+	let (_dx, dy) = opts.style.dots();
+	let rows = opts.height * dy;
+
+	let first = draw(wave, opts);
+	let second = compare.map(|compare| draw(compare, opts));
+
+	let (min, max, mean) = summary(wave);
+
+	// Label the lines which hold the minimum, maximum, and mean.
+	let mut labels = vec![Vec::new(); opts.height];
+
+	labels[dot_row(max as f64, rows) / dy].push(format!("max {}", max));
+	labels[dot_row(mean, rows) / dy].push(format!("mean {:.0}", mean));
+	labels[dot_row(min as f64, rows) / dy].push(format!("min {}", min));
+
+	let middle = dot_row((ARBITRARY_WAVE_MAX / 2 + 1) as f64, rows) / dy;
+	let mut out = String::new();
+
+	for (i, line) in first.iter().enumerate() {
+		let axis = match i {
+			0 => format!("{:>4} ┤", ARBITRARY_WAVE_MAX),
+			_ if i == opts.height - 1 => format!("{:>4} ┤", 0),
+			_ if i == middle => format!("{:>4} ┤", ARBITRARY_WAVE_MAX / 2 + 1),
+			_ => "     │".to_string(),
+		};
+
+		out.push_str(&axis);
+
+		for (j, mask) in line.iter().enumerate() {
+			let other = second.as_ref().map_or(0, |second| second[i][j]);
+			let c = opts.style.cell(mask | other);
+
+			if opts.color && other & !mask != 0 {
+				out.push_str(&format!("\x1b[33m{}\x1b[0m", c));
+			} else {
+				out.push(c);
+			}
+		}
+
+		if !labels[i].is_empty() {
+			out.push_str(&format!("  ◂ {}", labels[i].join(", ")));
+		}
+
+		out.push('\n');
+	}
+
+	out.push_str(&format!("     └{}\n", "─".repeat(opts.width)));
+	out.push_str(&format!(
+		"      0{:>width$}\n",
+		ARBITRARY_WAVE_LEN - 1,
+		width = opts.width.saturating_sub(1)
+	));
+
+	if let Some(compare) = compare {
+		let (min2, max2, mean2) = summary(compare);
+
+		out.push_str(&format!("wave:    min {}, max {}, mean {:.1}\n", min, max, mean));
+		out.push_str(&format!(
+			"{}compare: min {}, max {}, mean {:.1}{}\n",
+			if opts.color { "\x1b[33m" } else { "" },
+			min2,
+			max2,
+			mean2,
+			if opts.color { "\x1b[0m" } else { "" }
+		));
+	}
+
+	out
+}
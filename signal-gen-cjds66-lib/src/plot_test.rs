@@ -0,0 +1,88 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::plot::*;
+use super::wave::*;
+
+fn ramp() -> WaveData {
+	WaveData::new(&(0..2048).map(|i| (i * 2) as u16).collect::<Vec<u16>>()).unwrap()
+}
+
+#[test]
+pub fn plot_ok() {
+	let opts = PlotOptions {
+		width: 8,
+		height: 3,
+		..PlotOptions::default()
+	};
+
+	// A flat line in the middle, with all its labels on that line.
+	let res = plot(&WaveData::default(), None, &opts);
+	let lines: Vec<&str> = res.lines().collect();
+
+	assert_eq!(lines.len(), 5);
+	assert_eq!(lines[0], "4095 ┤        ");
+	assert_eq!(lines[1], "2048 ┤⠒⠒⠒⠒⠒⠒⠒⠒  ◂ max 2048, mean 2048, min 2048");
+	assert_eq!(lines[2], "   0 ┤        ");
+	assert_eq!(lines[3], "     └────────");
+	assert_eq!(lines[4], "      0   2047");
+
+	// A ramp, from the bottom corner to the top one.
+	let res = plot(&ramp(), None, &PlotOptions { style: PlotStyle::Block, ..opts });
+	let lines: Vec<&str> = res.lines().collect();
+
+	assert_eq!(lines[0], "4095 ┤     ▄▄█  ◂ max 4094");
+	assert_eq!(lines[1], "2048 ┤  ▄▄█▀    ◂ mean 2047");
+	assert_eq!(lines[2], "   0 ┤█▀▀       ◂ min 0");
+}
+
+#[test]
+pub fn plot_compare_ok() {
+	let opts = PlotOptions {
+		width: 8,
+		height: 3,
+		..PlotOptions::default()
+	};
+
+	let res = plot(&WaveData::default(), Some(&ramp()), &opts);
+
+	assert!(!res.contains('\x1b'));
+	assert!(res.contains("wave:    min 2048, max 2048, mean 2048.0\n"));
+	assert!(res.contains("compare: min 0, max 4094, mean 2047.0\n"));
+
+	// Only the parts of the second wave which don't overlap the first
+	// are coloured.
+	let res = plot(&WaveData::default(), Some(&ramp()), &PlotOptions { color: true, ..opts });
+
+	assert!(res.lines().next().unwrap().contains("\x1b[33m"));
+	assert!(res.contains("\x1b[33mcompare: min 0"));
+
+	let res = plot(&WaveData::default(), Some(&WaveData::default()), &PlotOptions { color: true, ..opts });
+
+	assert!(!res.lines().next().unwrap().contains('\x1b'));
+}
+
+#[test]
+pub fn plot_options_ok() {
+	assert_eq!(PlotStyle::parse("braille").unwrap(), PlotStyle::Braille);
+	assert_eq!(PlotStyle::parse("block").unwrap(), PlotStyle::Block);
+	assert_eq!(PlotOptions::parse_size("80x16").unwrap(), (80, 16));
+	assert_eq!(PlotOptions::parse_size("8x3").unwrap(), (8, 3));
+}
+
+#[test]
+pub fn plot_options_err() {
+	PlotStyle::parse("ascii").unwrap_err();
+	PlotOptions::parse_size("80").unwrap_err();
+	PlotOptions::parse_size("80x").unwrap_err();
+	PlotOptions::parse_size("7x16").unwrap_err();
+	PlotOptions::parse_size("80x2").unwrap_err();
+}
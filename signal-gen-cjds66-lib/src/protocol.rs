@@ -485,38 +485,6 @@ macro_rules! SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ {
 pub const SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ: &'static str =
 	SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ!();
 
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MIN!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX {
-	() => {
-			80000000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			2
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES: usize =
-	SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER {
-	() => {
-			100.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ_ARG_MULTIPLIER!();
-
 macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
 	() => {
 			"3"
@@ -525,38 +493,6 @@ macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ {
 pub const SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ: &'static str =
 	SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ!();
 
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MIN!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX {
-	() => {
-			80000000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			2
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES: usize =
-	SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER {
-	() => {
-			100.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ_ARG_MULTIPLIER!();
-
 macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ {
 	() => {
 			"0"
@@ -564,38 +500,6 @@ macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ {
 }
 pub const SET_FREQUENCY_COMMAND_UNIT_HERTZ: &'static str = SET_FREQUENCY_COMMAND_UNIT_HERTZ!();
 
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MIN!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX {
-	() => {
-			60000000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			2
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES: usize =
-	SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER {
-	() => {
-			100.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_HERTZ_ARG_MULTIPLIER!();
-
 macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
 	() => {
 			"1"
@@ -604,38 +508,6 @@ macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ {
 pub const SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ: &'static str =
 	SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ!();
 
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MIN!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX {
-	() => {
-			60000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			5
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES: usize =
-	SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER {
-	() => {
-			100000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ_ARG_MULTIPLIER!();
-
 macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
 	() => {
 			"2"
@@ -644,38 +516,6 @@ macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ {
 pub const SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ: &'static str =
 	SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ!();
 
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MIN!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX {
-	() => {
-			60.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			8
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES: usize =
-	SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER {
-	() => {
-			100000000.0
-	};
-}
-pub const SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER: f64 =
-	SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ_ARG_MULTIPLIER!();
-
 macro_rules! SET_FREQUENCY_RES_LEN {
 	() => {
 			6
@@ -751,34 +591,6 @@ macro_rules! SET_AMPLITUDE_COMMAND_CH2 {
 }
 pub const SET_AMPLITUDE_COMMAND_CH2: &'static str = SET_AMPLITUDE_COMMAND_CH2!();
 
-macro_rules! SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN {
-	() => {
-		0.0
-	};
-}
-pub const SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN: f64 = SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MIN!();
-
-macro_rules! SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX {
-	() => {
-		20.0
-	};
-}
-pub const SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX: f64 = SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX!();
-
-macro_rules! SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES {
-	() => {
-		3
-	};
-}
-pub const SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES: usize = SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER {
-	() => {
-		1000.0
-	};
-}
-pub const SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER: f64 = SET_AMPLITUDE_COMMAND_UNIT_VOLTS_ARG_MULTIPLIER!();
-
 macro_rules! SET_AMPLITUDE_RES_LEN {
 	() => {
 			6
@@ -863,34 +675,6 @@ macro_rules! SET_DUTY_CYCLE_COMMAND_CH2 {
 }
 pub const SET_DUTY_CYCLE_COMMAND_CH2: &'static str = SET_DUTY_CYCLE_COMMAND_CH2!();
 
-macro_rules! SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN: f64 = SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MIN!();
-
-macro_rules! SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX {
-	() => {
-			99.9
-	};
-}
-pub const SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX: f64 = SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX!();
-
-macro_rules! SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES {
-	() => {
-			1
-	};
-}
-pub const SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES: usize = SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MAX_DECIMAL_PLACES!();
-
-macro_rules! SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER {
-	() => {
-			10.0
-	};
-}
-pub const SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER: f64 = SET_DUTY_CYCLE_COMMAND_UNIT_PERCENT_ARG_MULTIPLIER!();
-
 macro_rules! SET_DUTY_CYCLE_RES_LEN {
 	() => {
 			6
@@ -2561,20 +2345,6 @@ macro_rules! SET_PULSE_WIDTH_ARG_NANOSECONDS {
 }
 pub const SET_PULSE_WIDTH_ARG_NANOSECONDS: &'static str = SET_PULSE_WIDTH_ARG_NANOSECONDS!();
 
-macro_rules! SET_PULSE_WIDTH_ARG_NANOSECONDS_MIN {
-	() => {
-		25.0
-	};
-}
-pub const SET_PULSE_WIDTH_ARG_NANOSECONDS_MIN: f64 = SET_PULSE_WIDTH_ARG_NANOSECONDS_MIN!();
-
-macro_rules! SET_PULSE_WIDTH_ARG_NANOSECONDS_MAX {
-	() => {
-		4000000000.0
-	};
-}
-pub const SET_PULSE_WIDTH_ARG_NANOSECONDS_MAX: f64 = SET_PULSE_WIDTH_ARG_NANOSECONDS_MAX!();
-
 // command example:
 // 1000 microseconds:
 //   ":w45=1000,1.\r\n"
@@ -2585,20 +2355,6 @@ macro_rules! SET_PULSE_WIDTH_ARG_MICROSECONDS {
 }
 pub const SET_PULSE_WIDTH_ARG_MICROSECONDS: &'static str = SET_PULSE_WIDTH_ARG_MICROSECONDS!();
 
-macro_rules! SET_PULSE_WIDTH_ARG_MICROSECONDS_MIN {
-	() => {
-			1.0
-	};
-}
-pub const SET_PULSE_WIDTH_ARG_MICROSECONDS_MIN: f64 = SET_PULSE_WIDTH_ARG_MICROSECONDS_MIN!();
-
-macro_rules! SET_PULSE_WIDTH_ARG_MICROSECONDS_MAX {
-	() => {
-		4000000000.0
-	};
-}
-pub const SET_PULSE_WIDTH_ARG_MICROSECONDS_MAX: f64 = SET_PULSE_WIDTH_ARG_MICROSECONDS_MAX!();
-
 macro_rules! SET_PULSE_WIDTH_RES_LEN {
 	() => {
 			6
@@ -2626,20 +2382,6 @@ macro_rules! SET_PULSE_PERIOD_ARG_NANOSECONDS {
 }
 pub const SET_PULSE_PERIOD_ARG_NANOSECONDS: &'static str = SET_PULSE_PERIOD_ARG_NANOSECONDS!();
 
-macro_rules! SET_PULSE_PERIOD_ARG_NANOSECONDS_MIN {
-	() => {
-		25.0
-	};
-}
-pub const SET_PULSE_PERIOD_ARG_NANOSECONDS_MIN: f64 = SET_PULSE_PERIOD_ARG_NANOSECONDS_MIN!();
-
-macro_rules! SET_PULSE_PERIOD_ARG_NANOSECONDS_MAX {
-	() => {
-		4000000000.0
-	};
-}
-pub const SET_PULSE_PERIOD_ARG_NANOSECONDS_MAX: f64 = SET_PULSE_PERIOD_ARG_NANOSECONDS_MAX!();
-
 // command example:
 // 1000 microseconds:
 //   ":w45=1000,1.\r\n"
@@ -2651,20 +2393,6 @@ macro_rules! SET_PULSE_PERIOD_ARG_MICROSECONDS {
 pub const SET_PULSE_PERIOD_ARG_MICROSECONDS: &'static str =
 	SET_PULSE_PERIOD_ARG_MICROSECONDS!();
 
-macro_rules! SET_PULSE_PERIOD_ARG_MICROSECONDS_MIN {
-	() => {
-			1.0
-	};
-}
-pub const SET_PULSE_PERIOD_ARG_MICROSECONDS_MIN: f64 = SET_PULSE_PERIOD_ARG_MICROSECONDS_MIN!();
-
-macro_rules! SET_PULSE_PERIOD_ARG_MICROSECONDS_MAX {
-	() => {
-		4000000000.0
-	};
-}
-pub const SET_PULSE_PERIOD_ARG_MICROSECONDS_MAX: f64 = SET_PULSE_PERIOD_ARG_MICROSECONDS_MAX!();
-
 macro_rules! SET_PULSE_PERIOD_RES_LEN {
 	() => {
 			6
@@ -2685,20 +2413,6 @@ pub const SET_PULSE_OFFSET_COMMAND: &'static str = SET_PULSE_OFFSET_COMMAND!();
 // command example:
 // 100 percent:
 //   ":w47=100.\r\n"
-macro_rules! SET_PULSE_OFFSET_ARG_PERCENT_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_PULSE_OFFSET_ARG_PERCENT_MIN: f64 = SET_PULSE_OFFSET_ARG_PERCENT_MIN!();
-
-macro_rules! SET_PULSE_OFFSET_ARG_PERCENT_MAX {
-	() => {
-		100.0
-	};
-}
-pub const SET_PULSE_OFFSET_ARG_PERCENT_MAX: f64 = SET_PULSE_OFFSET_ARG_PERCENT_MAX!();
-
 macro_rules! SET_PULSE_OFFSET_RES_LEN {
 	() => {
 			6
@@ -2719,20 +2433,6 @@ pub const SET_PULSE_AMPLITUDE_COMMAND: &'static str = SET_PULSE_AMPLITUDE_COMMAN
 // command example:
 // 5 volts:
 //   ":w48=500.\r\n"
-macro_rules! SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN {
-	() => {
-			0.0
-	};
-}
-pub const SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN: f64 = SET_PULSE_AMPLITUDE_ARG_VOLTS_MIN!();
-
-macro_rules! SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX {
-	() => {
-		10.0
-	};
-}
-pub const SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX: f64 = SET_PULSE_AMPLITUDE_ARG_VOLTS_MAX!();
-
 macro_rules! SET_PULSE_AMPLITUDE_RES_LEN {
 	() => {
 			6
@@ -2877,6 +2577,31 @@ macro_rules! SET_ARBITRARY_WAVE_RES_LEN {
 	};
 }
 pub const SET_ARBITRARY_WAVE_RES_LEN: u8 = SET_ARBITRARY_WAVE_RES_LEN!();
+
+// How many times to upload an arbitrary wave again, by default, if it
+// doesn't read back the same when verifying.
+macro_rules! SET_ARBITRARY_WAVE_RETRIES {
+	() => {
+			2
+	};
+}
+pub const SET_ARBITRARY_WAVE_RETRIES: u32 = SET_ARBITRARY_WAVE_RETRIES!();
+
+// The number of points in an arbitrary wave.
+macro_rules! ARBITRARY_WAVE_LEN {
+	() => {
+			2048
+	};
+}
+pub const ARBITRARY_WAVE_LEN: usize = ARBITRARY_WAVE_LEN!();
+
+// The largest value of a point in an arbitrary wave. The smallest is 0.
+macro_rules! ARBITRARY_WAVE_MAX {
+	() => {
+			4095
+	};
+}
+pub const ARBITRARY_WAVE_MAX: u16 = ARBITRARY_WAVE_MAX!();
 // -----
 
 // -----
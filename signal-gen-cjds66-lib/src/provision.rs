@@ -0,0 +1,416 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! A manifest of what should be in each of the device's arbitrary
+wave slots, so the device can be made to match it in one step.
+
+A manifest is a YAML or TOML file with a `slots` table, from slot
+numbers to where each slot's wave comes from. A wave can be a file in
+the device's text format or a WaveCAD or audio .wav file, a common
+shape as in [synth](../synth/index.html), a formula as in
+[expr](../expr/index.html), or a list of harmonics as in
+[fourier](../fourier/index.html). A slot can be just the name of a
+file, or a table with one of the keys `file`, `shape`, `expr`, or
+`harmonics`, and any of the options below. In YAML:
+```yaml
+slots:
+  1: sine.txt
+  2:
+    file: voice.wav
+    channel: 1
+    window: 10ms:30ms
+    resample: sinc
+    normalize: 0.9
+  3:
+    shape: gauss sigma=0.05
+  4:
+    expr: sin(2*pi*t)^3
+    auto_scale: false
+  5:
+    harmonics: "1:1.0, 3:0.1@30"
+```
+
+Or the same in TOML:
+```toml
+[slots]
+1 = "sine.txt"
+2 = { file = "voice.wav", channel = 1, window = "10ms:30ms", resample = "sinc", normalize = 0.9 }
+
+[slots.3]
+shape = "gauss sigma=0.05"
+```
+
+The options are the same as the command line flags with the same
+names, with `_` instead of `-`:
+
+* For files only: `channel`, `resample`, `window`, and `single_period`.
+* For generated waves and .wav files: `normalize`, `remove_dc`,
+  `gain` in dB, `dither`, and `fail_on_clip`.
+* For formulas only: `auto_scale`, which is true if it's left out.
+
+Files are found relative to the manifest's directory. A manifest
+only needs tables, strings, numbers, and true or false, so lists and
+dates are errors.
+*/
+
+extern crate toml;
+extern crate yaml_rust;
+
+use crate::convert::*;
+use crate::expr::*;
+use crate::fourier::*;
+use crate::protocol::*;
+use crate::resample::*;
+use crate::synth::*;
+
+use clap::{Error, ErrorKind};
+use std::fs;
+use std::path::Path;
+use yaml_rust::{Yaml, YamlLoader};
+
+/** Where a slot's wave comes from. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum WaveSource {
+	/// A file in the device's text format, or a WaveCAD or audio .wav
+	/// file, read as with the `--analyze` flag.
+	File {
+		path: String,
+		channel: u16,
+		resample: ResampleOptions,
+		convert: ConvertOptions,
+	},
+
+	/// A common shape.
+	Shape { spec: WaveSpec, convert: ConvertOptions },
+
+	/// A formula over `t`.
+	Expr {
+		expr: Expr,
+		auto_scale: bool,
+		convert: ConvertOptions,
+	},
+
+	/// A list of harmonics.
+	Fourier {
+		series: FourierSeries,
+		convert: ConvertOptions,
+	},
+}
+
+/** One slot in a manifest. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotProvision {
+	/// The slot number, from 1 to 60.
+	pub slot: u8,
+
+	/// Where the wave comes from, as written in the manifest, such as
+	/// `"shape gauss sigma=0.05"`, for showing progress.
+	pub description: String,
+
+	/// Where the wave comes from.
+	pub source: WaveSource,
+}
+
+/** What should be in each of the device's arbitrary wave slots. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionManifest {
+	/// The slots, in order of their numbers.
+	pub slots: Vec<SlotProvision>,
+}
+
+/** A value in a manifest, from either YAML or TOML. */
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+	Str(String),
+	Int(i64),
+	Float(f64),
+	Bool(bool),
+	Table(Vec<(String, Value)>),
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid provisioning manifest: {}", msg), ErrorKind::InvalidValue)
+}
+
+/** Say which part of the manifest an error is about. */
+fn in_context(context: &str, e: clap::Error) -> clap::Error {
+	let msg = e.message.trim_start_matches("error: ").trim_start_matches("invalid provisioning manifest: ");
+
+	invalid(&format!("{}: {}", context, msg))
+}
+
+impl Value {
+	fn str(&self, key: &str) -> Result<&str, clap::Error> {
+		match self {
+			Value::Str(s) => Ok(s),
+			_ => Err(invalid(&format!("{}: must be a string", key))),
+		}
+	}
+
+	fn num(&self, key: &str) -> Result<f64, clap::Error> {
+		match self {
+			Value::Int(i) => Ok(*i as f64),
+			Value::Float(f) => Ok(*f),
+			_ => Err(invalid(&format!("{}: must be a number", key))),
+		}
+	}
+
+	fn bool(&self, key: &str) -> Result<bool, clap::Error> {
+		match self {
+			Value::Bool(b) => Ok(*b),
+			_ => Err(invalid(&format!("{}: must be true or false", key))),
+		}
+	}
+}
+
+fn parse_slot(key: &str, value: &Value) -> Result<SlotProvision, clap::Error> {
+	let slot = key
+		.trim()
+		.parse::<u8>()
+		.ok()
+		.filter(|slot| (SET_ARBITRARY_WAVE_ARG_NUM_MIN..=SET_ARBITRARY_WAVE_ARG_NUM_MAX).contains(&(*slot as f64)))
+		.ok_or_else(|| {
+			invalid(&format!(
+				"unsupported slot number (must be {}-{}): {}",
+				SET_ARBITRARY_WAVE_ARG_NUM_MIN, SET_ARBITRARY_WAVE_ARG_NUM_MAX, key
+			))
+		})?;
+
+	let entries = match value {
+		Value::Str(path) => vec![("file".to_string(), Value::Str(path.clone()))],
+		Value::Table(entries) => entries.clone(),
+		_ => return Err(invalid(&format!("slot {}: must be a file name, or a table", slot))),
+	};
+
+	let slot_name = format!("slot {}", slot);
+	let context = |e: clap::Error| in_context(&slot_name, e);
+
+	let mut kind: Option<(String, String)> = None;
+	let mut channel = None;
+	let mut resample = ResampleOptions::default();
+	let mut convert = ConvertOptions::default();
+	let mut auto_scale = None;
+
+	for (key, value) in entries.iter() {
+		match key.as_str() {
+			"file" | "shape" | "expr" | "harmonics" => {
+				if let Some((other, _)) = &kind {
+					return Err(context(invalid(&format!("has both {} and {}, but can only have one", other, key))));
+				}
+
+				kind = Some((key.clone(), value.str(key).map_err(context)?.to_string()));
+			}
+
+			"channel" => {
+				let num = value.num(key).map_err(context)?;
+
+				if num.fract() != 0.0 || !(0.0..=u16::MAX as f64).contains(&num) {
+					return Err(context(invalid(&format!("channel: must be a channel number, or 0 to mix them all: {}", num))));
+				}
+
+				channel = Some(num as u16);
+			}
+
+			"resample" => resample.interpolation = Some(Interpolation::parse(value.str(key).map_err(context)?).map_err(context)?),
+			"window" => resample.window = Some(TimeWindow::parse(value.str(key).map_err(context)?).map_err(context)?),
+			"single_period" => resample.single_period = value.bool(key).map_err(context)?,
+			"normalize" => convert.normalize = Some(value.num(key).map_err(context)?),
+			"remove_dc" => convert.remove_dc = value.bool(key).map_err(context)?,
+			"gain" => convert.gain_db = value.num(key).map_err(context)?,
+			"dither" => convert.dither = value.bool(key).map_err(context)?,
+			"fail_on_clip" => convert.fail_on_clip = value.bool(key).map_err(context)?,
+			"auto_scale" => auto_scale = Some(value.bool(key).map_err(context)?),
+
+			_ => return Err(context(invalid(&format!("unknown setting: {}", key)))),
+		}
+	}
+
+	convert.validate().map_err(context)?;
+
+	let (kind, text) = kind.ok_or_else(|| context(invalid("needs one of file, shape, expr, or harmonics")))?;
+
+	if kind != "file" && (channel.is_some() || resample != ResampleOptions::default()) {
+		return Err(context(invalid("channel, resample, window, and single_period are only for files")));
+	}
+
+	if kind != "expr" && auto_scale.is_some() {
+		return Err(context(invalid("auto_scale is only for formulas")));
+	}
+
+	let source = match kind.as_str() {
+		"file" => WaveSource::File {
+			path: text.clone(),
+			channel: channel.unwrap_or(1),
+			resample,
+			convert,
+		},
+		"shape" => WaveSource::Shape {
+			spec: WaveSpec::parse(&text).map_err(context)?,
+			convert,
+		},
+		"expr" => WaveSource::Expr {
+			expr: Expr::parse(&text).map_err(context)?,
+			auto_scale: auto_scale.unwrap_or(true),
+			convert,
+		},
+		_ => WaveSource::Fourier {
+			series: FourierSeries::parse(&text).map_err(context)?,
+			convert,
+		},
+	};
+
+	Ok(SlotProvision {
+		slot,
+		description: format!("{} {}", kind, text),
+		source,
+	})
+}
+
+impl ProvisionManifest {
+	fn from_value(root: &Value) -> Result<ProvisionManifest, clap::Error> {
+		let entries = match root {
+			Value::Table(entries) => entries,
+			_ => return Err(invalid("must be a table with a slots table in it")),
+		};
+
+		let mut slots = Vec::new();
+		let mut found = false;
+
+		for (key, value) in entries.iter() {
+			match (key.as_str(), value) {
+				("slots", Value::Table(table)) => {
+					found = true;
+
+					for (key, value) in table.iter() {
+						slots.push(parse_slot(key, value)?);
+					}
+				}
+				("slots", _) => return Err(invalid("slots: must be a table from slot numbers to waves")),
+				_ => return Err(invalid(&format!("unknown setting: {}", key))),
+			}
+		}
+
+		if !found || slots.is_empty() {
+			return Err(invalid("there are no slots listed"));
+		}
+
+		slots.sort_by_key(|slot| slot.slot);
+
+		if let Some(pair) = slots.windows(2).find(|pair| pair[0].slot == pair[1].slot) {
+			return Err(invalid(&format!("slot listed more than once: {}", pair[0].slot)));
+		}
+
+		Ok(ProvisionManifest { slots })
+	}
+
+	/// Parse a manifest in YAML.
+	pub fn from_yaml(text: &str) -> Result<ProvisionManifest, clap::Error> {
+		let docs = YamlLoader::load_from_str(text).map_err(|e| invalid(&e.to_string()))?;
+		let doc = docs.first().ok_or_else(|| invalid("it's empty"))?;
+
+		ProvisionManifest::from_value(&yaml_value(doc)?)
+	}
+
+	/// Parse a manifest in TOML.
+	pub fn from_toml(text: &str) -> Result<ProvisionManifest, clap::Error> {
+		let root = text.parse::<toml::Value>().map_err(|e| invalid(&e.to_string()))?;
+
+		ProvisionManifest::from_value(&toml_value(&root)?)
+	}
+
+	/// Read a manifest file, in YAML if its name ends in .yaml or .yml,
+	/// or TOML if it ends in .toml. Files named in it are found
+	/// relative to its directory.
+	pub fn load(path: &str) -> Result<ProvisionManifest, clap::Error> {
+		let text = fs::read_to_string(path).map_err(|e| {
+			Error::with_description(&format!("failed reading file: {}: {}", path, e), ErrorKind::Io)
+		})?;
+
+		let lower = path.to_lowercase();
+
+		let mut manifest = if lower.ends_with(".toml") {
+			ProvisionManifest::from_toml(&text)?
+		} else if lower.ends_with(".yaml") || lower.ends_with(".yml") {
+			ProvisionManifest::from_yaml(&text)?
+		} else {
+			return Err(invalid(&format!("the file name must end in .toml, .yaml, or .yml: {}", path)));
+		};
+
+		let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+
+		for slot in manifest.slots.iter_mut() {
+			if let WaveSource::File { path, .. } = &mut slot.source {
+				if Path::new(path.as_str()).is_relative() {
+					*path = dir.join(path.as_str()).to_string_lossy().to_string();
+				}
+			}
+		}
+
+		Ok(manifest)
+	}
+}
+
+fn yaml_value(yaml: &Yaml) -> Result<Value, clap::Error> {
+	match yaml {
+		Yaml::String(s) => Ok(Value::Str(s.clone())),
+		Yaml::Integer(i) => Ok(Value::Int(*i)),
+		Yaml::Boolean(b) => Ok(Value::Bool(*b)),
+
+		Yaml::Real(s) => s
+			.parse::<f64>()
+			.ok()
+			.filter(|f| f.is_finite())
+			.map(Value::Float)
+			.ok_or_else(|| invalid(&format!("not a finite number: {}", s))),
+
+		Yaml::Hash(hash) => {
+			let mut entries = Vec::new();
+
+			for (key, value) in hash.iter() {
+				let key = match key {
+					Yaml::String(s) | Yaml::Real(s) => s.clone(),
+					Yaml::Integer(i) => i.to_string(),
+					_ => return Err(invalid(&format!("unsupported key: {:?}", key))),
+				};
+
+				entries.push((key.clone(), yaml_value(value).map_err(|e| in_context(&key, e))?));
+			}
+
+			Ok(Value::Table(entries))
+		}
+
+		Yaml::Array(_) => Err(invalid("lists aren't supported")),
+		Yaml::Null => Err(invalid("missing value")),
+		_ => Err(invalid("unsupported value")),
+	}
+}
+
+fn toml_value(toml: &toml::Value) -> Result<Value, clap::Error> {
+	match toml {
+		toml::Value::String(s) => Ok(Value::Str(s.clone())),
+		toml::Value::Integer(i) => Ok(Value::Int(*i)),
+		toml::Value::Boolean(b) => Ok(Value::Bool(*b)),
+
+		toml::Value::Float(f) if f.is_finite() => Ok(Value::Float(*f)),
+		toml::Value::Float(f) => Err(invalid(&format!("not a finite number: {}", f))),
+
+		toml::Value::Table(table) => {
+			let mut entries = Vec::new();
+
+			for (key, value) in table.iter() {
+				entries.push((key.clone(), toml_value(value).map_err(|e| in_context(key, e))?));
+			}
+
+			Ok(Value::Table(entries))
+		}
+
+		toml::Value::Array(_) => Err(invalid("lists aren't supported")),
+		toml::Value::Datetime(_) => Err(invalid("unsupported value")),
+	}
+}
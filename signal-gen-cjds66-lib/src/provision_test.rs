@@ -0,0 +1,193 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::convert::*;
+use super::expr::*;
+use super::fourier::*;
+use super::provision::*;
+use super::resample::*;
+use super::synth::*;
+
+const YAML: &str = "
+slots:
+  12:
+    shape: gauss sigma=0.05
+    gain: -6
+  1: sine.txt
+  2:
+    file: voice.wav
+    channel: 2
+    window: 1000:3048
+    resample: sinc
+    normalize: 0.9
+    dither: true
+  4:
+    expr: sin(2*pi*t)^3
+    auto_scale: false
+  '5':
+    harmonics: '1:1.0, 3:0.1@30'
+";
+
+const TOML: &str = r#"
+# The same as the YAML.
+[slots]
+1 = "sine.txt"
+2 = { file = "voice.wav", channel = 2, window = "1000:3048", resample = "sinc", normalize = 0.9, dither = true }
+4.expr = 'sin(2*pi*t)^3'
+4.auto_scale = false
+
+[slots.12]
+shape = "gauss sigma=0.05"  # A pulse.
+gain = -6
+
+[slots."5"]
+harmonics = "1:1.0, 3:0.1@30"
+"#;
+
+#[test]
+pub fn from_yaml_ok() {
+	let manifest = ProvisionManifest::from_yaml(YAML).unwrap();
+	let slots: Vec<u8> = manifest.slots.iter().map(|slot| slot.slot).collect();
+
+	assert_eq!(slots, vec![1, 2, 4, 5, 12]);
+
+	assert_eq!(
+		manifest.slots[0].source,
+		WaveSource::File {
+			path: "sine.txt".to_string(),
+			channel: 1,
+			resample: ResampleOptions::default(),
+			convert: ConvertOptions::default(),
+		}
+	);
+
+	assert_eq!(
+		manifest.slots[1].source,
+		WaveSource::File {
+			path: "voice.wav".to_string(),
+			channel: 2,
+			resample: ResampleOptions {
+				interpolation: Some(Interpolation::Sinc),
+				window: Some(TimeWindow::parse("1000:3048").unwrap()),
+				single_period: false,
+			},
+			convert: ConvertOptions {
+				normalize: Some(0.9),
+				dither: true,
+				..ConvertOptions::default()
+			},
+		}
+	);
+
+	assert_eq!(
+		manifest.slots[2].source,
+		WaveSource::Expr {
+			expr: Expr::parse("sin(2*pi*t)^3").unwrap(),
+			auto_scale: false,
+			convert: ConvertOptions::default(),
+		}
+	);
+
+	assert_eq!(
+		manifest.slots[3].source,
+		WaveSource::Fourier {
+			series: FourierSeries::parse("1:1.0, 3:0.1@30").unwrap(),
+			convert: ConvertOptions::default(),
+		}
+	);
+
+	assert_eq!(
+		manifest.slots[4].source,
+		WaveSource::Shape {
+			spec: WaveSpec::parse("gauss sigma=0.05").unwrap(),
+			convert: ConvertOptions {
+				gain_db: -6.0,
+				..ConvertOptions::default()
+			},
+		}
+	);
+
+	assert_eq!(manifest.slots[4].description, "shape gauss sigma=0.05");
+}
+
+#[test]
+pub fn from_toml_ok() {
+	assert_eq!(ProvisionManifest::from_toml(TOML).unwrap(), ProvisionManifest::from_yaml(YAML).unwrap());
+
+	let manifest = ProvisionManifest::from_toml("slots.7 = \"a \\\"b\\\"\\u0021.txt\"").unwrap();
+
+	assert_eq!(manifest.slots[0].description, "file a \"b\"!.txt");
+
+	let manifest = ProvisionManifest::from_toml("[slots]\n2 = \"\"\"a.txt\"\"\"\n1 = 'b.txt'").unwrap();
+
+	assert_eq!(manifest.slots[0].description, "file b.txt");
+	assert_eq!(manifest.slots[1].description, "file a.txt");
+}
+
+#[test]
+pub fn from_yaml_err() {
+	ProvisionManifest::from_yaml("").unwrap_err();
+	ProvisionManifest::from_yaml("slots:").unwrap_err();
+	ProvisionManifest::from_yaml("slots: [a.txt]").unwrap_err();
+	ProvisionManifest::from_yaml("slot:\n  1: a.txt").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  0: a.txt").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  61: a.txt").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1: 5").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1: a.txt\n  '1': b.txt").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    gain: 3").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    file: a.txt\n    shape: sine").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    file: a.txt\n    colour: red").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    file: a.txt\n    normalize: 2").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    file: a.txt\n    dither: yes please").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    file: a.txt\n    auto_scale: false").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    shape: sine\n    channel: 2").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    shape: triangle").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    expr: sin(").unwrap_err();
+	ProvisionManifest::from_yaml("slots:\n  1:\n    harmonics: '0:1'").unwrap_err();
+
+	let err = ProvisionManifest::from_yaml("slots:\n  3:\n    file: a.txt\n    resample: fast").unwrap_err();
+	assert!(err.message.contains("slot 3: unsupported interpolation method"), "{}", err.message);
+}
+
+#[test]
+pub fn from_toml_err() {
+	ProvisionManifest::from_toml("").unwrap_err();
+	ProvisionManifest::from_toml("[slots]").unwrap_err();
+	ProvisionManifest::from_toml("[[slots]]\n1 = \"a.txt\"").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = [\"a.txt\"]").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = \"a.txt").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = \"a.txt\" b").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = \"a.txt\"\n1 = \"b.txt\"").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = \"a.txt\"\n[slots]").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = { file = \"a.txt\", gain = 1e }").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = { file = \"a.txt\" gain = 1 }").unwrap_err();
+	ProvisionManifest::from_toml("[slots]\n1 = \"\\q\"").unwrap_err();
+	ProvisionManifest::from_toml("slots = \"a.txt\"").unwrap_err();
+
+	let err = ProvisionManifest::from_toml("[slots]\n\n1 = nope").unwrap_err();
+	assert!(err.message.contains("line 3"), "{}", err.message);
+
+	let err = ProvisionManifest::from_toml("[slots]\n1 = { file = \"a.txt\", gain = nan }").unwrap_err();
+	assert!(err.message.contains("not a finite number"), "{}", err.message);
+}
+
+#[test]
+pub fn load_err() {
+	ProvisionManifest::load("/nonexistent/slots.yaml").unwrap_err();
+	ProvisionManifest::load("/nonexistent/slots.json").unwrap_err();
+}
+
+#[test]
+pub fn load_example_ok() {
+	let manifest = ProvisionManifest::load("../examples/slots.yaml").unwrap();
+
+	assert_eq!(manifest.slots.len(), 6);
+}
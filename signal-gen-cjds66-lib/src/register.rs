@@ -0,0 +1,1073 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! A declarative table of the device's numeric settings registers.
+
+Each entry describes one register completely: its command codes, the
+range and precision of values it accepts, how a value is scaled into
+the number sent over the wire, the response lengths, and the
+command-line flags which set and get it. The generic setter and getter
+in [`command`](../command/index.html), the range validation, the
+command-line arguments, and the `--list-registers` documentation are
+all generated from this table, so adding or fixing a register only
+needs a change here.
+
+The waveform presets are registers too, with names which can be
+given instead of their numbers, and so is the amplitude, which is
+converted for the channel's load before it's sent. Saved presets,
+tracking, arbitrary waveform uploads, and the other commands which
+aren't a single number are still written out by hand in `command.rs`.
+
+The set flags for the registers are run in the order they're given on
+the command line.
+*/
+
+use crate::protocol::*;
+use crate::units::*;
+
+use clap::{Error, ErrorKind};
+
+/** A command-line flag generated for a register. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegisterFlag {
+	/// The clap argument name, which is also used as its key in the matches.
+	pub name: &'static str,
+
+	/// The long flag, without the leading dashes.
+	pub long: &'static str,
+
+	/// The short flag, if there is one, without the leading dash.
+	pub short: Option<&'static str>,
+
+	/// The name shown for the flag's value in the help text.
+	pub value_name: &'static str,
+}
+
+/** One numeric register on the device.
+
+A value given by the user is turned into the number sent to the device
+like this:
+```ignore
+raw = zero + value * multiplier
+```
+*/
+#[derive(Debug, PartialEq)]
+pub struct Register {
+	/// A short name used in messages, for example "duty cycle".
+	pub name: &'static str,
+
+	/// What the register sets, used in the help text. A `{ch}` is replaced
+	/// with the channel number.
+	pub description: &'static str,
+
+	/// The unit the value is given in, for the docs.
+	pub unit: &'static str,
+
+	/// The kind of quantity, if the value may be written with a unit after it.
+	pub quantity: Option<Quantity>,
+
+	/// How many of the quantity's base units are in one of this register's units.
+	pub unit_base: f64,
+
+	/// Whether the value is an amplitude seen by the channel's load, which
+	/// may also be given in Vrms or dBm. It's converted into the unterminated
+	/// volts the device expects by `command::set_register()`.
+	pub amplitude: bool,
+
+	/// The set command code for each channel, or a single code if the
+	/// register isn't per-channel.
+	pub set_codes: &'static [&'static str],
+
+	/// The get command code for each channel, or empty if the register
+	/// can't be read back.
+	pub get_codes: &'static [&'static str],
+
+	/// The argument sent with the get command.
+	pub get_arg: u8,
+
+	/// An extra argument sent after the value when setting, or "" for none.
+	pub set_suffix: &'static str,
+
+	/// The smallest value accepted.
+	pub min: f64,
+
+	/// The largest value accepted.
+	pub max: f64,
+
+	/// The most decimal places accepted.
+	pub decimal_places: usize,
+
+	/// If not zero, the value must be a multiple of this.
+	pub step: f64,
+
+	/// Names which may be given instead of a number, for each value
+	/// starting at `min`.
+	pub names: &'static [&'static [&'static str]],
+
+	/// How much to multiply the value by before sending it.
+	pub multiplier: f64,
+
+	/// What to add to the multiplied value before sending it.
+	pub zero: f64,
+
+	/// The length of the device's response to the set command.
+	pub set_res_len: u8,
+
+	/// The length of the device's response to the get command.
+	pub get_res_len: u8,
+
+	/// The flags which set the register, one per channel.
+	pub set_flags: &'static [RegisterFlag],
+
+	/// The flags which get the register, one per channel.
+	pub get_flags: &'static [RegisterFlag],
+
+	/// Extra information appended to the range in the help text.
+	pub note: &'static str,
+
+	/// An example value for the help text.
+	pub example: &'static str,
+}
+
+impl Register {
+	/// Whether the register has a separate code for each channel.
+	pub fn per_channel(&self) -> bool {
+		self.set_codes.len() > 1
+	}
+
+	/// Whether the register can be read back from the device.
+	pub fn readable(&self) -> bool {
+		!self.get_codes.is_empty()
+	}
+
+	fn code(&self, codes: &'static [&'static str], chan: u64) -> Result<&'static str, clap::Error> {
+		if codes.len() == 1 {
+			return Ok(codes[0]);
+		}
+
+		if chan == 1 || chan == 2 {
+			if let Some(code) = codes.get(chan as usize - 1) {
+				return Ok(code);
+			}
+		}
+
+		Err(Error::with_description(
+			"Unsupported channel number. Must be 1 or 2.",
+			ErrorKind::InvalidValue,
+		))
+	}
+
+	fn range_error(&self, amount: &str, detail: &str) -> clap::Error {
+		let names = if self.names.is_empty() { "" } else { " or a name" };
+
+		Error::with_description(
+			&format!(
+				"unsupported value passed to \"set {}\" argument (must be {}-{}{}): {}{}",
+				self.name, self.min, self.max, names, amount, detail
+			),
+			ErrorKind::InvalidValue,
+		)
+	}
+
+	/// Check a value given by the user, and return the raw number to send
+	/// to the device for it. A unit can be written after the value if the
+	/// register has a `quantity`, and one of its `names` can be given
+	/// instead of a number.
+	pub fn validate(&self, amount: &str) -> Result<f64, clap::Error> {
+		if let Some(i) = self.names.iter().position(|names| names.contains(&amount)) {
+			return Ok(self.encode(self.min + i as f64));
+		}
+
+		let amount = match self.quantity {
+			Some(quantity) => amount_in_unit(amount, quantity, self.unit_base)?,
+			None => amount.to_string(),
+		};
+
+		let amount_parts: Vec<&str> = amount.split('.').collect();
+
+		if amount_parts.len() > 1 && amount_parts[1].len() > self.decimal_places {
+			return Err(self.range_error(&amount, &format!(": too many decimal places ({} max)", self.decimal_places)));
+		}
+
+		let value = amount
+			.parse::<f64>()
+			.map_err(|e| self.range_error(&amount, &format!(": {}", e)))?;
+
+		if value < self.min || value > self.max {
+			return Err(self.range_error(&amount, ""));
+		}
+
+		if self.step > 0.0 && value % self.step != 0.0 {
+			return Err(self.range_error(&amount, &format!(": it must be a multiple of {}", self.step)));
+		}
+
+		Ok(self.encode(value))
+	}
+
+	/// Convert a value into the raw number sent to the device.
+	pub fn encode(&self, value: f64) -> f64 {
+		(self.zero + value * self.multiplier).round()
+	}
+
+	/// Convert a raw number from the device back into a value.
+	pub fn decode(&self, raw: f64) -> f64 {
+		(raw - self.zero) / self.multiplier
+	}
+
+	/// The command which sets the register to a raw number.
+	pub fn set_command(&self, chan: u64, raw: f64) -> Result<String, clap::Error> {
+		let code = self.code(self.set_codes, chan)?;
+
+		let suffix = if self.set_suffix.is_empty() {
+			String::new()
+		} else {
+			format!("{}{}", COMMAND_ARG_SEPARATOR, self.set_suffix)
+		};
+
+		Ok(format!(
+			"{}{}{}{}{}{}{}",
+			COMMAND_BEGIN, COMMAND_SET, code, COMMAND_SEPARATOR, raw, suffix, COMMAND_END,
+		))
+	}
+
+	/// The command which reads the register back.
+	pub fn get_command(&self, chan: u64) -> Result<String, clap::Error> {
+		if !self.readable() {
+			return Err(Error::with_description(
+				&format!("the {} can't be read back from the device", self.name),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		let code = self.code(self.get_codes, chan)?;
+
+		Ok(format!(
+			"{}{}{}{}{}{}",
+			COMMAND_BEGIN, COMMAND_GET, code, COMMAND_SEPARATOR, self.get_arg, COMMAND_END,
+		))
+	}
+
+	/// The numbers the device should report after being set to a raw number.
+	pub fn expected(&self, raw: f64) -> Vec<f64> {
+		let mut expected = vec![raw];
+
+		if let Ok(suffix) = self.set_suffix.parse::<f64>() {
+			expected.push(suffix);
+		}
+
+		expected
+	}
+
+	/// The help text for one of the register's flags.
+	pub fn help(&self, chan: u64) -> String {
+		let flags = if self.set_flags.is_empty() { self.get_flags } else { self.set_flags };
+		let description = self.description.replace("{ch}", &chan.to_string());
+
+		let names = if self.names.is_empty() { "" } else { " or a name from below" };
+		let mut help = format!("Set {}. The value must be a number {}-{}{}", description, self.min, self.max, names);
+
+		if self.step > 0.0 {
+			help.push_str(&format!(", in multiples of {}", self.step));
+		}
+
+		help.push_str(self.note);
+		help.push('.');
+
+		if let Some(quantity) = self.quantity {
+			let mut units: Vec<&str> = units_for(quantity).iter().map(|(name, _)| *name).collect();
+
+			if self.amplitude {
+				units.extend(&["mVrms", "Vrms", "dBm"]);
+			}
+
+			help.push_str(&format!(" A unit ({}) can also be given.", units.join(", ")));
+		}
+
+		if self.amplitude {
+			help.push_str(&format!(" The amplitude is the one seen by the load set with --load-ch{}.", chan));
+		}
+
+		if !self.readable() {
+			help.push_str(" The device can't read it back, so it can't be used with --verify.");
+		}
+
+		if let Some(flag) = flags.get(chan.max(1) as usize - 1) {
+			let flag_str = match flag.short {
+				Some(short) => format!("-{}", short),
+				None => format!("--{}", flag.long),
+			};
+
+			help.push_str(&format!(" For example: {} {}", flag_str, self.example));
+		}
+
+		if !self.names.is_empty() {
+			help.push_str("\n\nAccepted names:");
+
+			for (i, names) in self.names.iter().enumerate() {
+				help.push_str(&format!("\n{}: {}", self.min + i as f64, names.join(" || ")));
+			}
+		}
+
+		help
+	}
+
+	/// The help text for one of the register's get flags.
+	pub fn get_help(&self, chan: u64) -> String {
+		format!("Get {}.", self.description.replace("{ch}", &chan.to_string()))
+	}
+}
+
+/** Set the waveform preset, by number or by name. */
+pub static WAVEFORM_PRESET: Register = Register {
+	name: "waveform preset",
+	description: "the waveform preset for channel {ch}",
+	unit: "",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_WAVEFORM_PRESET_COMMAND_CH1, SET_WAVEFORM_PRESET_COMMAND_CH2],
+	get_codes: &[GET_WAVEFORM_PRESET_COMMAND_CH1, GET_WAVEFORM_PRESET_COMMAND_CH2],
+	get_arg: GET_WAVEFORM_PRESET_ARG,
+	set_suffix: "",
+	min: 0.0,
+	max: 16.0,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[
+		&["sine", "sin"],
+		&["square", "sq"],
+		&["pulse", "pul"],
+		&["triangle", "tri"],
+		&["partialsine", "partial-sine", "parsine", "par-sine", "parsin", "par-sin", "psine", "p-sine", "psin", "p-sin"],
+		&["cmos", "cm"],
+		&["dc"],
+		&["halfwave", "half-wave", "hw", "h-w"],
+		&["fullwave", "full-wave", "fw", "f-w"],
+		&["pos-ladder", "posladder", "pos-lad", "poslad", "positive-ladder", "positiveladder", "pl"],
+		&["neg-ladder", "negladder", "neg-lad", "neglad", "negative-ladder", "negativeladder", "nl"],
+		&["noise", "nois", "noi", "no", "n"],
+		&["exp-rise", "exprise", "e-r", "er", "e-rise", "erise", "e-ris", "eris"],
+		&["exp-decay", "expdecay", "e-d", "ed", "e-decay", "edecay", "e-dec", "edec"],
+		&["multi-tone", "multitone", "m-t", "mt", "m-tone", "mtone"],
+		&["sinc", "sc"],
+		&["lorenz", "loren", "lor", "lz"],
+	],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_WAVEFORM_PRESET_RES_LEN,
+	get_res_len: GET_WAVEFORM_PRESET_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_waveform_channel1", long: "wave-preset-ch1", short: Some("w"), value_name: "ch1_preset" },
+		RegisterFlag { name: "set_waveform_channel2", long: "wave-preset-ch2", short: Some("x"), value_name: "ch2_preset" },
+	],
+	get_flags: &[
+		RegisterFlag { name: "get_waveform_channel1", long: "gw", short: None, value_name: "" },
+		RegisterFlag { name: "get_waveform_channel2", long: "gx", short: None, value_name: "" },
+	],
+	note: "",
+	example: "sine",
+};
+
+/** Set the waveform to one of the arbitrary waveforms, by its slot
+number. The device reports these as waveform presets 101-160. */
+pub static ARBITRARY_WAVEFORM: Register = Register {
+	name: "arbitrary waveform preset",
+	description: "the arbitrary waveform preset for channel {ch}",
+	unit: "",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_WAVEFORM_PRESET_COMMAND_CH1, SET_WAVEFORM_PRESET_COMMAND_CH2],
+	get_codes: &[GET_WAVEFORM_PRESET_COMMAND_CH1, GET_WAVEFORM_PRESET_COMMAND_CH2],
+	get_arg: GET_WAVEFORM_PRESET_ARG,
+	set_suffix: "",
+	min: SET_ARBITRARY_WAVE_ARG_NUM_MIN,
+	max: SET_ARBITRARY_WAVE_ARG_NUM_MAX,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 100.0,
+	set_res_len: SET_WAVEFORM_PRESET_RES_LEN,
+	get_res_len: GET_WAVEFORM_PRESET_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_arbitrary_waveform_channel1", long: "wave-arb-ch1", short: Some("a"), value_name: "ch1_arb_preset" },
+		RegisterFlag { name: "set_arbitrary_waveform_channel2", long: "wave-arb-ch2", short: Some("b"), value_name: "ch2_arb_preset" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "1",
+};
+
+/** Set the output frequency in microhertz (µHz). */
+pub static FREQUENCY_MICROHERTZ: Register = Register {
+	name: "frequency uHz",
+	description: "the waveform frequency for channel {ch} in µHz",
+	unit: "µHz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: FrequencyUnit::Microhertz.hertz(),
+	amplitude: false,
+	set_codes: &[SET_FREQUENCY_COMMAND_CH1, SET_FREQUENCY_COMMAND_CH2],
+	get_codes: &[GET_FREQUENCY_COMMAND_CH1, GET_FREQUENCY_COMMAND_CH2],
+	get_arg: 0,
+	set_suffix: SET_FREQUENCY_COMMAND_UNIT_MICROHERTZ,
+	min: 0.0,
+	max: 80000000.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_FREQUENCY_RES_LEN,
+	get_res_len: GET_FREQUENCY_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_frequency_uhz_channel1", long: "freq-micro-ch1", short: Some("u"), value_name: "ch1_freq_uHz" },
+		RegisterFlag { name: "set_frequency_uhz_channel2", long: "freq-micro-ch2", short: Some("2"), value_name: "ch2_freq_uHz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.01",
+};
+
+/** Set the output frequency in millihertz (mHz). */
+pub static FREQUENCY_MILLIHERTZ: Register = Register {
+	name: "frequency mHz",
+	description: "the waveform frequency for channel {ch} in mHz",
+	unit: "mHz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: FrequencyUnit::Millihertz.hertz(),
+	amplitude: false,
+	set_codes: &[SET_FREQUENCY_COMMAND_CH1, SET_FREQUENCY_COMMAND_CH2],
+	get_codes: &[GET_FREQUENCY_COMMAND_CH1, GET_FREQUENCY_COMMAND_CH2],
+	get_arg: 0,
+	set_suffix: SET_FREQUENCY_COMMAND_UNIT_MILLIHERTZ,
+	min: 0.0,
+	max: 80000000.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_FREQUENCY_RES_LEN,
+	get_res_len: GET_FREQUENCY_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_frequency_millihz_channel1", long: "freq-milli-ch1", short: Some("i"), value_name: "ch1_freq_mHz" },
+		RegisterFlag { name: "set_frequency_millihz_channel2", long: "freq-milli-ch2", short: Some("j"), value_name: "ch2_freq_mHz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.01",
+};
+
+/** Set the output frequency in hertz (Hz). */
+pub static FREQUENCY_HERTZ: Register = Register {
+	name: "frequency Hz",
+	description: "the waveform frequency for channel {ch} in Hz",
+	unit: "Hz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: FrequencyUnit::Hertz.hertz(),
+	amplitude: false,
+	set_codes: &[SET_FREQUENCY_COMMAND_CH1, SET_FREQUENCY_COMMAND_CH2],
+	get_codes: &[GET_FREQUENCY_COMMAND_CH1, GET_FREQUENCY_COMMAND_CH2],
+	get_arg: 0,
+	set_suffix: SET_FREQUENCY_COMMAND_UNIT_HERTZ,
+	min: 0.0,
+	max: 60000000.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_FREQUENCY_RES_LEN,
+	get_res_len: GET_FREQUENCY_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_frequency_hz_channel1", long: "freq-hz-ch1", short: Some("e"), value_name: "ch1_freq_Hz" },
+		RegisterFlag { name: "set_frequency_hz_channel2", long: "freq-hz-ch2", short: Some("f"), value_name: "ch2_freq_Hz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.01",
+};
+
+/** Set the output frequency in kilohertz (kHz). */
+pub static FREQUENCY_KILOHERTZ: Register = Register {
+	name: "frequency kHz",
+	description: "the waveform frequency for channel {ch} in kHz",
+	unit: "kHz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: FrequencyUnit::Kilohertz.hertz(),
+	amplitude: false,
+	set_codes: &[SET_FREQUENCY_COMMAND_CH1, SET_FREQUENCY_COMMAND_CH2],
+	get_codes: &[GET_FREQUENCY_COMMAND_CH1, GET_FREQUENCY_COMMAND_CH2],
+	get_arg: 0,
+	set_suffix: SET_FREQUENCY_COMMAND_UNIT_KILOHERTZ,
+	min: 0.0,
+	max: 60000.0,
+	decimal_places: 5,
+	step: 0.0,
+	names: &[],
+	multiplier: 100000.0,
+	zero: 0.0,
+	set_res_len: SET_FREQUENCY_RES_LEN,
+	get_res_len: GET_FREQUENCY_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_frequency_khz_channel1", long: "freq-khz-ch1", short: Some("k"), value_name: "ch1_freq_kHz" },
+		RegisterFlag { name: "set_frequency_khz_channel2", long: "freq-khz-ch2", short: Some("l"), value_name: "ch2_freq_kHz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.00001",
+};
+
+/** Set the output frequency in megahertz (MHz). */
+pub static FREQUENCY_MEGAHERTZ: Register = Register {
+	name: "frequency MHz",
+	description: "the waveform frequency for channel {ch} in MHz",
+	unit: "MHz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: FrequencyUnit::Megahertz.hertz(),
+	amplitude: false,
+	set_codes: &[SET_FREQUENCY_COMMAND_CH1, SET_FREQUENCY_COMMAND_CH2],
+	get_codes: &[GET_FREQUENCY_COMMAND_CH1, GET_FREQUENCY_COMMAND_CH2],
+	get_arg: 0,
+	set_suffix: SET_FREQUENCY_COMMAND_UNIT_MEGAHERTZ,
+	min: 0.0,
+	max: 60.0,
+	decimal_places: 8,
+	step: 0.0,
+	names: &[],
+	multiplier: 100000000.0,
+	zero: 0.0,
+	set_res_len: SET_FREQUENCY_RES_LEN,
+	get_res_len: GET_FREQUENCY_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_frequency_mega_channel1", long: "freq-mega-ch1", short: Some("y"), value_name: "ch1_freq_MHz" },
+		RegisterFlag { name: "set_frequency_mega_channel2", long: "freq-mega-ch2", short: Some("z"), value_name: "ch2_freq_MHz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.00000001",
+};
+
+/** Set the output amplitude in volts (V), as seen by the channel's load. */
+pub static AMPLITUDE_VOLTS: Register = Register {
+	name: "amplitude volts",
+	description: "the signal amplitude for channel {ch} in volts",
+	unit: "V",
+	quantity: Some(Quantity::Voltage),
+	unit_base: 1.0,
+	amplitude: true,
+	set_codes: &[SET_AMPLITUDE_COMMAND_CH1, SET_AMPLITUDE_COMMAND_CH2],
+	get_codes: &[GET_AMPLITUDE_COMMAND_CH1, GET_AMPLITUDE_COMMAND_CH2],
+	get_arg: GET_AMPLITUDE_ARG,
+	set_suffix: "",
+	min: 0.0,
+	max: 20.0,
+	decimal_places: 3,
+	step: 0.0,
+	names: &[],
+	multiplier: 1000.0,
+	zero: 0.0,
+	set_res_len: SET_AMPLITUDE_RES_LEN,
+	get_res_len: GET_AMPLITUDE_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_amplitude_volts_channel1", long: "ampli-ch1", short: Some("p"), value_name: "ch1_ampli_v" },
+		RegisterFlag { name: "set_amplitude_volts_channel2", long: "ampli-ch2", short: Some("q"), value_name: "ch2_ampli_v" },
+	],
+	get_flags: &[
+		RegisterFlag { name: "get_amplitude_volts_channel1", long: "gp", short: None, value_name: "" },
+		RegisterFlag { name: "get_amplitude_volts_channel2", long: "gq", short: None, value_name: "" },
+	],
+	note: ", and is further limited by any voltage offset which may be currently set",
+	example: "5V",
+};
+
+/** Set the duty cycle in percent (%). */
+pub static DUTY_CYCLE: Register = Register {
+	name: "duty cycle",
+	description: "the duty cycle for channel {ch} in percent",
+	unit: "%",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_DUTY_CYCLE_COMMAND_CH1, SET_DUTY_CYCLE_COMMAND_CH2],
+	get_codes: &[GET_DUTY_CYCLE_COMMAND_CH1, GET_DUTY_CYCLE_COMMAND_CH2],
+	get_arg: GET_DUTY_CYCLE_ARG,
+	set_suffix: "",
+	min: 0.0,
+	max: 99.9,
+	decimal_places: 1,
+	step: 0.0,
+	names: &[],
+	multiplier: 10.0,
+	zero: 0.0,
+	set_res_len: SET_DUTY_CYCLE_RES_LEN,
+	get_res_len: GET_DUTY_CYCLE_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_duty_cycle_channel1", long: "duty-ch1", short: Some("t"), value_name: "ch1_duty_cycle" },
+		RegisterFlag { name: "set_duty_cycle_channel2", long: "duty-ch2", short: Some("c"), value_name: "ch2_duty_cycle" },
+	],
+	get_flags: &[
+		RegisterFlag { name: "get_duty_cycle_channel1", long: "gty", short: None, value_name: "" },
+		RegisterFlag { name: "get_duty_cycle_channel2", long: "gcy", short: None, value_name: "" },
+	],
+	note: "",
+	example: "40.1",
+};
+
+/** Set the voltage offset in volts (V). */
+pub static VOLTAGE_OFFSET: Register = Register {
+	name: "voltage offset",
+	description: "the voltage offset for channel {ch} in volts",
+	unit: "V",
+	quantity: Some(Quantity::Voltage),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_VOLTAGE_OFFSET_COMMAND_CH1, SET_VOLTAGE_OFFSET_COMMAND_CH2],
+	get_codes: &[GET_VOLTAGE_OFFSET_COMMAND_CH1, GET_VOLTAGE_OFFSET_COMMAND_CH2],
+	get_arg: GET_VOLTAGE_OFFSET_ARG,
+	set_suffix: "",
+	min: -9.99,
+	max: 9.99,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 1000.0,
+	set_res_len: SET_VOLTAGE_OFFSET_RES_LEN,
+	get_res_len: GET_VOLTAGE_OFFSET_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_voltage_offset_channel1", long: "offset-ch1", short: Some("g"), value_name: "ch1_volt_offset" },
+		RegisterFlag { name: "set_voltage_offset_channel2", long: "offset-ch2", short: Some("n"), value_name: "ch2_volt_offset" },
+	],
+	get_flags: &[
+		RegisterFlag { name: "get_voltage_offset_channel1", long: "gg", short: None, value_name: "" },
+		RegisterFlag { name: "get_voltage_offset_channel2", long: "gn", short: None, value_name: "" },
+	],
+	note: "",
+	example: "-1.23",
+};
+
+/** Set the phase in degrees (°). */
+pub static PHASE: Register = Register {
+	name: "phase",
+	description: "the phase in ° (degrees)",
+	unit: "°",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_PHASE_COMMAND],
+	get_codes: &[GET_PHASE_COMMAND],
+	get_arg: GET_PHASE_ARG,
+	set_suffix: "",
+	min: 0.0,
+	max: 360.0,
+	decimal_places: 1,
+	step: 0.0,
+	names: &[],
+	multiplier: 10.0,
+	zero: 0.0,
+	set_res_len: SET_PHASE_RES_LEN,
+	get_res_len: GET_PHASE_RES_LEN,
+	set_flags: &[
+		RegisterFlag { name: "set_phase", long: "phase", short: Some("r"), value_name: "phase_deg" },
+	],
+	get_flags: &[
+		RegisterFlag { name: "get_phase", long: "gr", short: None, value_name: "" },
+	],
+	note: ", and 360.0 wraps around to 0.0",
+	example: "180.7",
+};
+
+/** Set the measurement gate time in seconds (s). */
+pub static MEASUREMENT_GATE_TIME: Register = Register {
+	name: "measurement gate time",
+	description: "the measurement gate time in seconds",
+	unit: "s",
+	quantity: Some(Quantity::Time),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_MEASUREMENT_GATE_TIME_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.01,
+	max: 10.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_MEASUREMENT_GATE_TIME_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_measurement_gate_time", long: "gt", short: None, value_name: "gate_time" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "0.01",
+};
+
+/** Set the number of burst pulses. */
+pub static BURST_PULSE_NUMBER: Register = Register {
+	name: "burst pulse number",
+	description: "the number of burst pulses",
+	unit: "",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_BURST_PULSE_NUMBER_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 1.0,
+	max: 1048575.0,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_BURST_PULSE_NUMBER_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_burst_pulse_number", long: "bn", short: None, value_name: "num_pulses" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "5",
+};
+
+/** Set the sweep starting frequency in hertz (Hz). */
+pub static SWEEP_STARTING_FREQUENCY: Register = Register {
+	name: "sweep starting frequency",
+	description: "the sweep starting frequency in Hz",
+	unit: "Hz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_SWEEP_STARTING_FREQUENCY_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.01,
+	max: 60000000.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_SWEEP_STARTING_FREQUENCY_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_sweep_start_freq", long: "ss", short: None, value_name: "start_freq_hz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "1.5kHz",
+};
+
+/** Set the sweep end frequency in hertz (Hz). */
+pub static SWEEP_END_FREQUENCY: Register = Register {
+	name: "sweep end frequency",
+	description: "the sweep end frequency in Hz",
+	unit: "Hz",
+	quantity: Some(Quantity::Frequency),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_SWEEP_END_FREQUENCY_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.01,
+	max: 60000000.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_SWEEP_END_FREQUENCY_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_sweep_end_freq", long: "se", short: None, value_name: "end_freq_hz" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "2MHz",
+};
+
+/** Set the sweep time in seconds (s). */
+pub static SWEEP_TIME: Register = Register {
+	name: "sweep time",
+	description: "the sweep time in seconds",
+	unit: "s",
+	quantity: Some(Quantity::Time),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_SWEEP_TIME_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.1,
+	max: 999.9,
+	decimal_places: 1,
+	step: 0.0,
+	names: &[],
+	multiplier: 10.0,
+	zero: 0.0,
+	set_res_len: SET_SWEEP_TIME_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_sweep_time", long: "st", short: None, value_name: "sweep_time_seconds" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "500ms",
+};
+
+/** Set the pulse width in nanoseconds (ns). */
+pub static PULSE_WIDTH_NANOSECONDS: Register = Register {
+	name: "pulse width (ns)",
+	description: "the pulse width for the pulse function in nanoseconds",
+	unit: "ns",
+	quantity: Some(Quantity::Time),
+	unit_base: 0.000000001,
+	amplitude: false,
+	set_codes: &[SET_PULSE_WIDTH_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: SET_PULSE_WIDTH_ARG_NANOSECONDS,
+	min: 25.0,
+	max: 4000000000.0,
+	decimal_places: 0,
+	step: 5.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_WIDTH_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_width_nanoseconds", long: "pwn", short: None, value_name: "pulse_width_nanoseconds" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "1000",
+};
+
+/** Set the pulse width in microseconds (µs). */
+pub static PULSE_WIDTH_MICROSECONDS: Register = Register {
+	name: "pulse width (us)",
+	description: "the pulse width for the pulse function in microseconds",
+	unit: "µs",
+	quantity: Some(Quantity::Time),
+	unit_base: 0.000001,
+	amplitude: false,
+	set_codes: &[SET_PULSE_WIDTH_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: SET_PULSE_WIDTH_ARG_MICROSECONDS,
+	min: 1.0,
+	max: 4000000000.0,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_WIDTH_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_width_microseconds", long: "pwu", short: None, value_name: "pulse_width_microseconds" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "1000",
+};
+
+/** Set the pulse period in nanoseconds (ns). */
+pub static PULSE_PERIOD_NANOSECONDS: Register = Register {
+	name: "pulse period (ns)",
+	description: "the period for the pulse function in nanoseconds",
+	unit: "ns",
+	quantity: Some(Quantity::Time),
+	unit_base: 0.000000001,
+	amplitude: false,
+	set_codes: &[SET_PULSE_PERIOD_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: SET_PULSE_PERIOD_ARG_NANOSECONDS,
+	min: 25.0,
+	max: 4000000000.0,
+	decimal_places: 0,
+	step: 5.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_PERIOD_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_period_nanoseconds", long: "pdn", short: None, value_name: "pulse_period_nanoseconds" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "2000",
+};
+
+/** Set the pulse period in microseconds (µs). */
+pub static PULSE_PERIOD_MICROSECONDS: Register = Register {
+	name: "pulse period (us)",
+	description: "the period for the pulse function in microseconds",
+	unit: "µs",
+	quantity: Some(Quantity::Time),
+	unit_base: 0.000001,
+	amplitude: false,
+	set_codes: &[SET_PULSE_PERIOD_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: SET_PULSE_PERIOD_ARG_MICROSECONDS,
+	min: 1.0,
+	max: 4000000000.0,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_PERIOD_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_period_microseconds", long: "pdu", short: None, value_name: "pulse_period_microseconds" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "2000",
+};
+
+/** Set the pulse offset in percent (%). */
+pub static PULSE_OFFSET: Register = Register {
+	name: "pulse offset",
+	description: "the offset for the pulse function in percent",
+	unit: "%",
+	quantity: None,
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_PULSE_OFFSET_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.0,
+	max: 100.0,
+	decimal_places: 0,
+	step: 0.0,
+	names: &[],
+	multiplier: 1.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_OFFSET_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_offset", long: "po", short: None, value_name: "pulse_offset_percent" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "50",
+};
+
+/** Set the pulse amplitude in volts (V). */
+pub static PULSE_AMPLITUDE: Register = Register {
+	name: "pulse amplitude",
+	description: "the amplitude for the pulse function in volts",
+	unit: "V",
+	quantity: Some(Quantity::Voltage),
+	unit_base: 1.0,
+	amplitude: false,
+	set_codes: &[SET_PULSE_AMPLITUDE_COMMAND],
+	get_codes: &[],
+	get_arg: 0,
+	set_suffix: "",
+	min: 0.0,
+	max: 10.0,
+	decimal_places: 2,
+	step: 0.0,
+	names: &[],
+	multiplier: 100.0,
+	zero: 0.0,
+	set_res_len: SET_PULSE_AMPLITUDE_RES_LEN,
+	get_res_len: 0,
+	set_flags: &[
+		RegisterFlag { name: "set_pulse_amplitude", long: "pa", short: None, value_name: "pulse_amplitude_volts" },
+	],
+	get_flags: &[],
+	note: "",
+	example: "250mV",
+};
+
+/** Every register, in the order their command-line flags are listed. */
+pub static REGISTERS: [&Register; 22] = [
+	&WAVEFORM_PRESET,
+	&ARBITRARY_WAVEFORM,
+	&FREQUENCY_MICROHERTZ,
+	&FREQUENCY_MILLIHERTZ,
+	&FREQUENCY_HERTZ,
+	&FREQUENCY_KILOHERTZ,
+	&FREQUENCY_MEGAHERTZ,
+	&AMPLITUDE_VOLTS,
+	&DUTY_CYCLE,
+	&VOLTAGE_OFFSET,
+	&PHASE,
+	&MEASUREMENT_GATE_TIME,
+	&BURST_PULSE_NUMBER,
+	&SWEEP_STARTING_FREQUENCY,
+	&SWEEP_END_FREQUENCY,
+	&SWEEP_TIME,
+	&PULSE_WIDTH_NANOSECONDS,
+	&PULSE_WIDTH_MICROSECONDS,
+	&PULSE_PERIOD_NANOSECONDS,
+	&PULSE_PERIOD_MICROSECONDS,
+	&PULSE_OFFSET,
+	&PULSE_AMPLITUDE,
+];
+
+/** Find a register by its name. */
+pub fn find_register(name: &str) -> Option<&'static Register> {
+	REGISTERS.iter().find(|r| r.name == name).copied()
+}
+
+/** Document every register as a markdown table. */
+pub fn registers_markdown() -> String {
+	let mut out = String::from("| Register | Set flags | Get flags | Range | Unit | Decimal places | Set command | Readable |\n");
+	out.push_str("|---|---|---|---|---|---|---|---|\n");
+
+	let flag_list = |flags: &[RegisterFlag]| -> String {
+		flags
+			.iter()
+			.map(|f| match f.short {
+				Some(short) => format!("`-{}` `--{}`", short, f.long),
+				None => format!("`--{}`", f.long),
+			})
+			.collect::<Vec<String>>()
+			.join(", ")
+	};
+
+	for reg in REGISTERS.iter() {
+		let step = if reg.step > 0.0 { format!(" (×{})", reg.step) } else { String::new() };
+
+		out.push_str(&format!(
+			"| {} | {} | {} | {} - {}{} | {} | {} | `{}` | {} |\n",
+			reg.name,
+			flag_list(reg.set_flags),
+			flag_list(reg.get_flags),
+			reg.min,
+			reg.max,
+			step,
+			reg.unit,
+			reg.decimal_places,
+			reg.set_codes.join("`, `"),
+			if reg.readable() { "yes" } else { "no" },
+		));
+	}
+
+	out
+}
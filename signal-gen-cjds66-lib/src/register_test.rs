@@ -0,0 +1,99 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::register::*;
+
+#[test]
+pub fn validate_ok() {
+	let args = [
+		(&DUTY_CYCLE, "40.1", 401.0),
+		(&VOLTAGE_OFFSET, "-1.23", 877.0),
+		(&VOLTAGE_OFFSET, "-250mV", 975.0),
+		(&PHASE, "180.7", 1807.0),
+		(&FREQUENCY_HERTZ, "12.34", 1234.0),
+		(&FREQUENCY_HERTZ, "1.5kHz", 150000.0),
+		(&SWEEP_TIME, "500ms", 5.0),
+		(&PULSE_WIDTH_NANOSECONDS, "1us", 1000.0),
+		(&BURST_PULSE_NUMBER, "5", 5.0),
+		(&WAVEFORM_PRESET, "sine", 0.0),
+		(&WAVEFORM_PRESET, "lz", 16.0),
+		(&WAVEFORM_PRESET, "3", 3.0),
+		(&ARBITRARY_WAVEFORM, "12", 112.0),
+		(&AMPLITUDE_VOLTS, "250mV", 250.0),
+	];
+
+	for (reg, amount, raw) in args.iter() {
+		assert_eq!(reg.validate(amount).unwrap(), *raw, "{} {}", reg.name, amount);
+	}
+}
+
+#[test]
+pub fn validate_err() {
+	let args = [
+		(&DUTY_CYCLE, "100"),
+		(&DUTY_CYCLE, "40.12"),
+		(&DUTY_CYCLE, "abc"),
+		(&VOLTAGE_OFFSET, "10"),
+		(&PHASE, "-1"),
+		(&PULSE_WIDTH_NANOSECONDS, "1001"),
+		(&BURST_PULSE_NUMBER, "1.5"),
+		(&SWEEP_TIME, "2kHz"),
+		(&WAVEFORM_PRESET, "17"),
+		(&WAVEFORM_PRESET, "sinewave"),
+		(&ARBITRARY_WAVEFORM, "0"),
+		(&ARBITRARY_WAVEFORM, "61"),
+		(&AMPLITUDE_VOLTS, "20.5"),
+	];
+
+	for (reg, amount) in args.iter() {
+		reg.validate(amount).unwrap_err();
+	}
+}
+
+#[test]
+pub fn commands_ok() {
+	assert_eq!(DUTY_CYCLE.set_command(2, 401.0).unwrap(), format!(":w{}=401.\r\n", DUTY_CYCLE.set_codes[1]));
+	assert_eq!(FREQUENCY_KILOHERTZ.set_command(1, 5.0).unwrap(), format!(":w{}=5,{}.\r\n", FREQUENCY_KILOHERTZ.set_codes[0], FREQUENCY_KILOHERTZ.set_suffix));
+	assert_eq!(PHASE.get_command(2).unwrap(), format!(":r{}=0.\r\n", PHASE.get_codes[0]));
+	assert_eq!(FREQUENCY_KILOHERTZ.expected(5.0), vec![5.0, 1.0]);
+	assert_eq!(VOLTAGE_OFFSET.decode(877.0), -1.23);
+}
+
+#[test]
+pub fn commands_err() {
+	DUTY_CYCLE.set_command(3, 401.0).unwrap_err();
+	SWEEP_TIME.get_command(1).unwrap_err();
+}
+
+#[test]
+pub fn table_ok() {
+	let mut names: Vec<&str> = Vec::new();
+
+	for reg in REGISTERS.iter() {
+		assert!(reg.min <= reg.max, "{}", reg.name);
+		assert!(reg.validate(reg.example).is_ok(), "{}: {}", reg.name, reg.example);
+		assert_eq!(find_register(reg.name), Some(*reg));
+
+		if !reg.set_flags.is_empty() {
+			assert_eq!(reg.set_flags.len(), reg.set_codes.len(), "{}", reg.name);
+		}
+
+		for flag in reg.set_flags.iter().chain(reg.get_flags.iter()) {
+			assert!(!names.contains(&flag.name), "{}", flag.name);
+			names.push(flag.name);
+		}
+	}
+
+	assert!(registers_markdown().contains("| duty cycle |"));
+	assert!(WAVEFORM_PRESET.help(1).contains("\n16: lorenz || loren || lor || lz"));
+	assert!(AMPLITUDE_VOLTS.help(2).contains("Vrms"));
+	assert!(AMPLITUDE_VOLTS.help(2).contains("--load-ch2"));
+}
@@ -0,0 +1,714 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Images of arbitrary waves, as SVG or PNG files, for test reports.
+
+The wave is drawn over one period, with a grid and labelled axes. The
+vertical axis is in volts if the output's amplitude (peak-to-peak) and
+offset are given, or in device values of 0 - 4095 otherwise, and the
+horizontal axis is in time if the frequency the wave is played at is
+given, or in samples otherwise. Below it there can be a second plot
+with the level of each harmonic, from the same analysis as
+[analyze](../analyze/index.html), in dBV if the amplitude is given, or
+in dB relative to full scale otherwise.
+
+Both formats are drawn from the same shapes, so they look the same.
+PNG files are drawn here, with a small built-in font for the labels,
+and encoded with the `png` crate.
+*/
+
+use crate::analyze::*;
+use crate::convert::HALF_SCALE;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::fmt::Write;
+
+/** Which kind of image file to write. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+	/// Scalable vector graphics.
+	Svg,
+
+	/// Portable network graphics.
+	Png,
+}
+
+impl RenderFormat {
+	/// The format for a file name ending in .svg or .png.
+	pub fn from_path(path: &str) -> Result<RenderFormat, clap::Error> {
+		let lower = path.to_lowercase();
+
+		if lower.ends_with(".svg") {
+			Ok(RenderFormat::Svg)
+		} else if lower.ends_with(".png") {
+			Ok(RenderFormat::Png)
+		} else {
+			Err(Error::with_description(
+				&format!("unsupported image file name (must end in .svg or .png): {}", path),
+				ErrorKind::InvalidValue,
+			))
+		}
+	}
+}
+
+/** What to draw, and how big. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+	/// The width of the image in pixels.
+	pub width: u32,
+
+	/// The height of the image in pixels.
+	pub height: u32,
+
+	/// The output's peak-to-peak amplitude in volts, to label the
+	/// vertical axis in volts, or `None` for device values.
+	pub amplitude: Option<f64>,
+
+	/// The output's offset in volts, used with `amplitude`.
+	pub offset: f64,
+
+	/// The frequency the wave is played at in hertz, to label the
+	/// horizontal axes in time and frequency, or `None` for samples
+	/// and harmonic numbers.
+	pub frequency: Option<f64>,
+
+	/// Whether to draw the levels of the harmonics below the wave.
+	pub spectrum: bool,
+
+	/// How many harmonics to draw, starting with the fundamental.
+	pub harmonics: usize,
+
+	/// A title to write above the wave.
+	pub title: Option<String>,
+}
+
+impl Default for RenderOptions {
+	fn default() -> RenderOptions {
+		RenderOptions {
+			width: 800,
+			height: 400,
+			amplitude: None,
+			offset: 0.0,
+			frequency: None,
+			spectrum: false,
+			harmonics: 20,
+			title: None,
+		}
+	}
+}
+
+impl RenderOptions {
+	/// Parse an image size like `800x400`, in pixels.
+	pub fn parse_size(size: &str) -> Result<(u32, u32), clap::Error> {
+		let err = || {
+			Error::with_description(
+				&format!("unsupported image size (must be WIDTHxHEIGHT, from 200x150 to 8000x8000): {}", size),
+				ErrorKind::InvalidValue,
+			)
+		};
+
+		let (width, height) = size.split_once('x').ok_or_else(err)?;
+		let width = width.trim().parse::<u32>().map_err(|_e| err())?;
+		let height = height.trim().parse::<u32>().map_err(|_e| err())?;
+
+		if !(200..=8000).contains(&width) || !(150..=8000).contains(&height) {
+			return Err(err());
+		}
+
+		Ok((width, height))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Color(u8, u8, u8);
+
+const BACKGROUND: Color = Color(255, 255, 255);
+const FOREGROUND: Color = Color(34, 34, 34);
+const GRID: Color = Color(221, 221, 221);
+const TRACE: Color = Color(31, 119, 180);
+const BARS: Color = Color(255, 127, 14);
+
+/** The size of the built-in font's characters, before scaling. */
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/** How much the built-in font is scaled up by. */
+const TEXT_SCALE: usize = 2;
+
+/** How far apart characters are, in pixels. */
+const TEXT_ADVANCE: f64 = ((GLYPH_WIDTH + 1) * TEXT_SCALE) as f64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Anchor {
+	Start,
+	Middle,
+	End,
+}
+
+/** The shapes an image is made of. Text is placed by the middle of its
+line, and its start, middle, or end. */
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+	Line { from: (f64, f64), to: (f64, f64), color: Color },
+	Polyline { points: Vec<(f64, f64)>, color: Color },
+	Rect { x: f64, y: f64, w: f64, h: f64, color: Color },
+	Text { x: f64, y: f64, text: String, anchor: Anchor, color: Color },
+}
+
+/** An image, as a list of shapes to draw in order. */
+#[derive(Debug, Clone, PartialEq)]
+struct Figure {
+	width: u32,
+	height: u32,
+	shapes: Vec<Shape>,
+}
+
+/** One axis of a plot: its range, where its ticks go, and how to
+label them. */
+#[derive(Debug, Clone, PartialEq)]
+struct Axis {
+	min: f64,
+	max: f64,
+	ticks: Vec<f64>,
+	step: f64,
+	scale: f64,
+	title: String,
+}
+
+impl Axis {
+	/// An axis from "min" to "max", with about "count" ticks at round
+	/// numbers, which are multiples of 1, 2, or 5 times a power of ten.
+	fn new(min: f64, max: f64, count: usize, title: &str) -> Axis {
+		let rough = (max - min).abs().max(f64::MIN_POSITIVE) / count.max(1) as f64;
+		let power = 10f64.powf(rough.log10().floor());
+
+		let step = [1.0, 2.0, 5.0, 10.0]
+			.iter()
+			.map(|m| m * power)
+			.find(|step| *step >= rough)
+			.unwrap_or(10.0 * power);
+
+		let first = (min / step).ceil() as i64;
+		let last = (max / step + 1e-9).floor() as i64;
+
+		Axis {
+			min,
+			max,
+			ticks: (first..=last).map(|i| i as f64 * step).collect(),
+			step,
+			scale: 1.0,
+			title: title.to_string(),
+		}
+	}
+
+	/// The label for a tick, multiplied by the axis's scale, with as
+	/// many decimal places as the ticks need to be told apart.
+	fn label(&self, value: f64) -> String {
+		let decimals = (-(self.step * self.scale).abs().log10().floor()).clamp(0.0, 9.0) as usize;
+		let label = format!("{:.*}", decimals, value * self.scale);
+
+		if label.trim_start_matches('-').chars().all(|c| c == '0' || c == '.') {
+			label.trim_start_matches('-').to_string()
+		} else {
+			label
+		}
+	}
+
+	/// Where a value is along the axis, from 0 to 1.
+	fn fraction(&self, value: f64) -> f64 {
+		(value - self.min) / (self.max - self.min)
+	}
+}
+
+/** A time or frequency unit to label an axis in, so its numbers are
+small: the unit's name, and how many of the base unit are in it. */
+fn scaled_unit(value: f64, units: &[(&'static str, f64)]) -> (&'static str, f64) {
+	units
+		.iter()
+		.rev()
+		.find(|(_name, size)| value.abs() >= *size)
+		.or_else(|| units.first())
+		.copied()
+		.unwrap_or(("", 1.0))
+}
+
+const TIME_SCALES: [(&str, f64); 4] = [("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)];
+const FREQUENCY_SCALES: [(&str, f64); 3] = [("Hz", 1.0), ("kHz", 1e3), ("MHz", 1e6)];
+
+impl Figure {
+	fn line(&mut self, from: (f64, f64), to: (f64, f64), color: Color) {
+		self.shapes.push(Shape::Line { from, to, color });
+	}
+
+	fn text(&mut self, x: f64, y: f64, text: &str, anchor: Anchor) {
+		self.shapes.push(Shape::Text {
+			x,
+			y,
+			text: text.to_string(),
+			anchor,
+			color: FOREGROUND,
+		});
+	}
+
+	/// Draw the frame, grid, tick labels, and titles of a plot in the
+	/// box at "x", "y", with the size "w" by "h". Returns a function
+	/// which turns values into pixels inside the box.
+	fn axes(&mut self, (x, y, w, h): (f64, f64, f64, f64), xaxis: &Axis, yaxis: &Axis) -> impl Fn(f64, f64) -> (f64, f64) {
+		for tick in xaxis.ticks.iter() {
+			let px = x + xaxis.fraction(*tick) * w;
+
+			self.line((px, y), (px, y + h), GRID);
+			self.line((px, y + h), (px, y + h + 5.0), FOREGROUND);
+			self.text(px, y + h + 16.0, &xaxis.label(*tick), Anchor::Middle);
+		}
+
+		for tick in yaxis.ticks.iter() {
+			let py = y + h - yaxis.fraction(*tick) * h;
+
+			self.line((x, py), (x + w, py), GRID);
+			self.line((x - 5.0, py), (x, py), FOREGROUND);
+			self.text(x - 9.0, py, &yaxis.label(*tick), Anchor::End);
+		}
+
+		self.shapes.push(Shape::Polyline {
+			points: vec![(x, y), (x + w, y), (x + w, y + h), (x, y + h), (x, y)],
+			color: FOREGROUND,
+		});
+
+		self.text(x + w, y + h + 36.0, &xaxis.title, Anchor::End);
+		self.text(x, y - 12.0, &yaxis.title, Anchor::Start);
+
+		let (xaxis, yaxis) = (xaxis.clone(), yaxis.clone());
+
+		move |vx, vy| (x + xaxis.fraction(vx) * w, y + h - yaxis.fraction(vy) * h)
+	}
+}
+
+/** Lay out the plots of a wave as shapes. */
+fn figure(wave: &WaveData, opts: &RenderOptions) -> Figure {
+	let mut fig = Figure {
+		width: opts.width,
+		height: opts.height,
+		shapes: vec![Shape::Rect {
+			x: 0.0,
+			y: 0.0,
+			w: opts.width as f64,
+			h: opts.height as f64,
+			color: BACKGROUND,
+		}],
+	};
+
+	let (width, height) = (opts.width as f64, opts.height as f64);
+	let (left, right) = (80.0, 24.0);
+	let top = if opts.title.is_some() { 56.0 } else { 36.0 };
+
+	if let Some(title) = &opts.title {
+		fig.text(width / 2.0, 18.0, title, Anchor::Middle);
+	}
+
+	// The space for each plot, including its labels below it.
+	let panels = if opts.spectrum { 2.0 } else { 1.0 };
+	let panel = (height - top) / panels;
+	let plot_h = (panel - 64.0).max(10.0);
+	let plot_w = (width - left - right).max(10.0);
+
+	// The wave.
+	let samples = wave.samples();
+	let len = samples.len() as f64;
+
+	let (yaxis, to_y): (Axis, Box<dyn Fn(u16) -> f64>) = match opts.amplitude {
+		Some(amplitude) => {
+			let volts = move |s: u16| opts.offset + (s as f64 - HALF_SCALE) / HALF_SCALE * amplitude / 2.0;
+			let axis = Axis::new(volts(0), volts(ARBITRARY_WAVE_MAX), 6, "V");
+
+			(axis, Box::new(volts))
+		}
+
+		None => (Axis::new(0.0, ARBITRARY_WAVE_MAX as f64, 6, "value"), Box::new(|s: u16| s as f64)),
+	};
+
+	let (xaxis, to_x): (Axis, Box<dyn Fn(f64) -> f64>) = match opts.frequency {
+		Some(frequency) => {
+			let period = 1.0 / frequency;
+			let (unit, size) = scaled_unit(period, &TIME_SCALES);
+
+			(Axis::new(0.0, period / size, 8, &format!("time ({})", unit)), Box::new(move |i| i / len * period / size))
+		}
+
+		None => (Axis::new(0.0, len, 8, "sample"), Box::new(|i| i)),
+	};
+
+	let to_px = fig.axes((left, top, plot_w, plot_h), &xaxis, &yaxis);
+
+	let points = samples.iter().enumerate().map(|(i, s)| to_px(to_x(i as f64), to_y(*s))).collect();
+
+	fig.shapes.push(Shape::Polyline { points, color: TRACE });
+
+	// The levels of the harmonics.
+	if opts.spectrum {
+		let harmonics = analyze_wave(wave, opts.harmonics.max(1)).harmonics;
+		let count = harmonics.len().max(1) as f64;
+
+		let (unit, to_db) = match opts.amplitude {
+			Some(amplitude) => ("dBV", Box::new(move |m: f64| 20.0 * (m * amplitude / 2.0 / 2f64.sqrt()).log10()) as Box<dyn Fn(f64) -> f64>),
+			None => ("dBFS", Box::new(|m: f64| 20.0 * m.log10()) as Box<dyn Fn(f64) -> f64>),
+		};
+
+		let levels: Vec<f64> = harmonics.iter().map(|h| to_db(h.magnitude)).collect();
+		let loudest = levels.iter().copied().filter(|l| l.is_finite()).fold(f64::MIN, f64::max);
+		let ceiling = if loudest == f64::MIN { 0.0 } else { (loudest / 10.0).ceil() * 10.0 };
+		let yaxis = Axis::new(ceiling - 100.0, ceiling, 5, unit);
+
+		// A tick for each harmonic, or every few if there are a lot.
+		let mut xaxis = Axis::new(0.5, count + 0.5, 10, "harmonic");
+		xaxis.step = xaxis.step.max(1.0);
+		xaxis.ticks = (1..=harmonics.len()).map(|n| n as f64).filter(|n| n % xaxis.step == 0.0).collect();
+
+		if let Some(frequency) = opts.frequency {
+			let (unit, size) = scaled_unit(frequency * count, &FREQUENCY_SCALES);
+
+			xaxis.title = format!("frequency ({})", unit);
+			xaxis.scale = frequency / size;
+		}
+
+		let to_px = fig.axes((left, top + panel, plot_w, plot_h), &xaxis, &yaxis);
+
+		draw_bars(&mut fig, &to_px, &levels, &yaxis, plot_w / count);
+	}
+
+	fig
+}
+
+/** Draw a bar for each harmonic's level, from the bottom of the plot. */
+fn draw_bars(fig: &mut Figure, to_px: &dyn Fn(f64, f64) -> (f64, f64), levels: &[f64], yaxis: &Axis, spacing: f64) {
+	let bar_w = (spacing * 0.6).max(1.0);
+
+	for (i, level) in levels.iter().enumerate() {
+		if !level.is_finite() || *level <= yaxis.min {
+			continue;
+		}
+
+		let (px, top) = to_px((i + 1) as f64, level.min(yaxis.max));
+		let (_px, bottom) = to_px((i + 1) as f64, yaxis.min);
+
+		fig.shapes.push(Shape::Rect {
+			x: px - bar_w / 2.0,
+			y: top,
+			w: bar_w,
+			h: bottom - top,
+			color: BARS,
+		});
+	}
+}
+
+fn escape_xml(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn svg_color(color: Color) -> String {
+	format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+impl Figure {
+	fn to_svg(&self) -> String {
+		let mut out = String::new();
+
+		let _ = writeln!(
+			out,
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+			w = self.width,
+			h = self.height
+		);
+
+		for shape in self.shapes.iter() {
+			let _ = match shape {
+				Shape::Line { from, to, color } => writeln!(
+					out,
+					"<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\"/>",
+					from.0,
+					from.1,
+					to.0,
+					to.1,
+					svg_color(*color)
+				),
+
+				Shape::Polyline { points, color } => {
+					let points: Vec<String> = points.iter().map(|(x, y)| format!("{:.1},{:.1}", x, y)).collect();
+
+					writeln!(
+						out,
+						"<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>",
+						svg_color(*color),
+						points.join(" ")
+					)
+				}
+
+				Shape::Rect { x, y, w, h, color } => writeln!(
+					out,
+					"<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+					x,
+					y,
+					w,
+					h,
+					svg_color(*color)
+				),
+
+				Shape::Text { x, y, text, anchor, color } => writeln!(
+					out,
+					"<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"monospace\" font-size=\"14\" dominant-baseline=\"middle\" text-anchor=\"{}\" fill=\"{}\">{}</text>",
+					x,
+					y,
+					match anchor {
+						Anchor::Start => "start",
+						Anchor::Middle => "middle",
+						Anchor::End => "end",
+					},
+					svg_color(*color),
+					escape_xml(text)
+				),
+			};
+		}
+
+		out.push_str("</svg>\n");
+
+		out
+	}
+
+	/// Draw the shapes into rows of RGB pixels.
+	fn to_pixels(&self) -> Vec<u8> {
+		let (width, height) = (self.width as i64, self.height as i64);
+		let mut pixels = vec![255u8; (width * height * 3) as usize];
+
+		let mut plot = |x: i64, y: i64, color: Color| {
+			if x >= 0 && y >= 0 && x < width && y < height {
+				let i = ((y * width + x) * 3) as usize;
+
+				pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+			}
+		};
+
+		for shape in self.shapes.iter() {
+			match shape {
+				Shape::Line { from, to, color } => draw_line(*from, *to, *color, 1, &mut plot),
+
+				Shape::Polyline { points, color } => {
+					for pair in points.windows(2) {
+						draw_line(pair[0], pair[1], *color, if *color == TRACE { 2 } else { 1 }, &mut plot);
+					}
+				}
+
+				Shape::Rect { x, y, w, h, color } => {
+					for py in y.round() as i64..(y + h).round() as i64 {
+						for px in x.round() as i64..(x + w).round() as i64 {
+							plot(px, py, *color);
+						}
+					}
+				}
+
+				Shape::Text { x, y, text, anchor, color } => {
+					let text_w = text.chars().count() as f64 * TEXT_ADVANCE - TEXT_SCALE as f64;
+
+					let left = match anchor {
+						Anchor::Start => *x,
+						Anchor::Middle => x - text_w / 2.0,
+						Anchor::End => x - text_w,
+					}
+					.round() as i64;
+
+					let top = (y - (GLYPH_HEIGHT * TEXT_SCALE) as f64 / 2.0).round() as i64;
+
+					for (n, c) in text.chars().enumerate() {
+						let rows = glyph(c);
+						let gx = left + n as i64 * TEXT_ADVANCE as i64;
+
+						for (row, bits) in rows.iter().enumerate() {
+							for col in 0..GLYPH_WIDTH {
+								if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+									continue;
+								}
+
+								for dy in 0..TEXT_SCALE {
+									for dx in 0..TEXT_SCALE {
+										plot(
+											gx + (col * TEXT_SCALE + dx) as i64,
+											top + (row * TEXT_SCALE + dy) as i64,
+											*color,
+										);
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		pixels
+	}
+}
+
+/** Draw a line "thickness" pixels wide, one step at a time along its
+longer direction. */
+fn draw_line(from: (f64, f64), to: (f64, f64), color: Color, thickness: i64, plot: &mut dyn FnMut(i64, i64, Color)) {
+	let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+	let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as i64;
+
+	for i in 0..=steps {
+		let t = i as f64 / steps as f64;
+		let (x, y) = ((from.0 + dx * t).round() as i64, (from.1 + dy * t).round() as i64);
+
+		for oy in 0..thickness {
+			for ox in 0..thickness {
+				plot(x + ox, y + oy, color);
+			}
+		}
+	}
+}
+
+/** The rows of a character in the built-in font, or a box for
+characters it doesn't have. */
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+	FONT.iter()
+		.find(|(fc, _rows)| *fc == c)
+		.map_or([0x1f, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1f], |(_fc, rows)| *rows)
+}
+
+/** Draw a wave as an SVG image. */
+pub fn render_svg(wave: &WaveData, opts: &RenderOptions) -> String {
+	figure(wave, opts).to_svg()
+}
+
+/** Draw a wave as a PNG image. */
+pub fn render_png(wave: &WaveData, opts: &RenderOptions) -> Result<Vec<u8>, clap::Error> {
+	let fig = figure(wave, opts);
+	let mut out = Vec::new();
+
+	let mut encoder = png::Encoder::new(&mut out, fig.width, fig.height);
+	encoder.set_color(png::ColorType::RGB);
+	encoder.set_depth(png::BitDepth::Eight);
+
+	encoder
+		.write_header()
+		.and_then(|mut writer| writer.write_image_data(&fig.to_pixels()))
+		.map_err(|e| Error::with_description(&format!("failed encoding PNG image: {}", e), ErrorKind::Io))?;
+
+	Ok(out)
+}
+
+/** Draw a wave as an image in either format. */
+pub fn render(wave: &WaveData, opts: &RenderOptions, format: RenderFormat) -> Result<Vec<u8>, clap::Error> {
+	match format {
+		RenderFormat::Svg => Ok(render_svg(wave, opts).into_bytes()),
+		RenderFormat::Png => render_png(wave, opts),
+	}
+}
+
+/** The built-in font, with 5 × 7 pixels for each character, one byte
+for each row, with the leftmost pixel in the 5th bit. */
+const FONT: [(char, [u8; GLYPH_HEIGHT]); 97] = [
+	(' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+	('0', [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e]),
+	('1', [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+	('2', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f]),
+	('3', [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e]),
+	('4', [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02]),
+	('5', [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e]),
+	('6', [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e]),
+	('7', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+	('8', [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e]),
+	('9', [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c]),
+	('A', [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+	('B', [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e]),
+	('C', [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e]),
+	('D', [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c]),
+	('E', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f]),
+	('F', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10]),
+	('G', [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f]),
+	('H', [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+	('I', [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+	('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c]),
+	('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+	('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f]),
+	('M', [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11]),
+	('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+	('O', [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+	('P', [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10]),
+	('Q', [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d]),
+	('R', [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11]),
+	('S', [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e]),
+	('T', [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+	('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+	('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04]),
+	('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a]),
+	('X', [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11]),
+	('Y', [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04]),
+	('Z', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f]),
+	('a', [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f]),
+	('b', [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e]),
+	('c', [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e]),
+	('d', [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f]),
+	('e', [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e]),
+	('f', [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08]),
+	('g', [0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e]),
+	('h', [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11]),
+	('i', [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e]),
+	('j', [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0c]),
+	('k', [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12]),
+	('l', [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+	('m', [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11]),
+	('n', [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11]),
+	('o', [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e]),
+	('p', [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10]),
+	('q', [0x00, 0x00, 0x0d, 0x13, 0x0f, 0x01, 0x01]),
+	('r', [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10]),
+	('s', [0x00, 0x00, 0x0e, 0x10, 0x0e, 0x01, 0x1e]),
+	('t', [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06]),
+	('u', [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d]),
+	('v', [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04]),
+	('w', [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a]),
+	('x', [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11]),
+	('y', [0x00, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e]),
+	('z', [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f]),
+	('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c]),
+	(',', [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08]),
+	('-', [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00]),
+	('+', [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00]),
+	('(', [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02]),
+	(')', [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08]),
+	('/', [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00]),
+	(':', [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00]),
+	('%', [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03]),
+	('_', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f]),
+	('=', [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00]),
+	('\'', [0x04, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00]),
+	('µ', [0x00, 0x00, 0x11, 0x11, 0x13, 0x1d, 0x10]),
+	('#', [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a]),
+	('?', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04]),
+	('!', [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04]),
+	('[', [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e]),
+	(']', [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e]),
+	('<', [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02]),
+	('>', [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08]),
+	('*', [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00]),
+	('"', [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00]),
+	('&', [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d]),
+	('~', [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00]),
+	('^', [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00]),
+	(';', [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08]),
+	('|', [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+	('\\', [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00]),
+	('{', [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02]),
+	('}', [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08]),
+	('@', [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e]),
+	('$', [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04]),
+	('`', [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00]),
+	('°', [0x0c, 0x12, 0x12, 0x0c, 0x00, 0x00, 0x00]),
+];
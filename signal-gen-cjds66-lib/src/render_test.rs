@@ -0,0 +1,102 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::render::*;
+use super::wave::*;
+
+use std::f64::consts::PI;
+
+fn sine() -> WaveData {
+	WaveData::new(
+		&(0..2048)
+			.map(|i| (2048.0 + 2000.0 * (2.0 * PI * i as f64 / 2048.0).sin()).round() as u16)
+			.collect::<Vec<u16>>(),
+	)
+	.unwrap()
+}
+
+#[test]
+pub fn render_format_ok() {
+	assert_eq!(RenderFormat::from_path("wave.svg").unwrap(), RenderFormat::Svg);
+	assert_eq!(RenderFormat::from_path("out/WAVE.PNG").unwrap(), RenderFormat::Png);
+}
+
+#[test]
+pub fn render_format_err() {
+	assert!(RenderFormat::from_path("wave.jpg").is_err());
+	assert!(RenderFormat::from_path("svg").is_err());
+}
+
+#[test]
+pub fn render_size_ok() {
+	assert_eq!(RenderOptions::parse_size("800x400").unwrap(), (800, 400));
+	assert_eq!(RenderOptions::parse_size("200x150").unwrap(), (200, 150));
+}
+
+#[test]
+pub fn render_size_err() {
+	assert!(RenderOptions::parse_size("800").is_err());
+	assert!(RenderOptions::parse_size("199x400").is_err());
+	assert!(RenderOptions::parse_size("800x9000").is_err());
+	assert!(RenderOptions::parse_size("wide x tall").is_err());
+}
+
+#[test]
+pub fn render_svg_ok() {
+	let res = render_svg(&sine(), &RenderOptions::default());
+
+	assert!(res.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"800\" height=\"400\""));
+	assert!(res.ends_with("</svg>\n"));
+	assert!(res.contains(">value</text>"));
+	assert!(res.contains(">sample</text>"));
+	assert!(res.contains(">4000</text>"));
+	assert!(!res.contains("harmonic"));
+
+	// In volts and time, with the spectrum.
+	let opts = RenderOptions {
+		amplitude: Some(5.0),
+		frequency: Some(1000.0),
+		spectrum: true,
+		title: Some("sine <1 kHz>".to_string()),
+		..RenderOptions::default()
+	};
+
+	let res = render_svg(&sine(), &opts);
+
+	assert!(res.contains(">sine &lt;1 kHz&gt;</text>"));
+	assert!(res.contains(">V</text>"));
+	assert!(res.contains(">-2</text>"));
+	assert!(res.contains(">time (ms)</text>"));
+	assert!(res.contains(">dBV</text>"));
+	assert!(res.contains(">frequency (kHz)</text>"));
+
+	// Only the fundamental is loud enough to have a bar.
+	assert_eq!(res.matches("fill=\"#ff7f0e\"").count(), 1);
+}
+
+#[test]
+pub fn render_png_ok() {
+	let opts = RenderOptions {
+		width: 300,
+		height: 200,
+		spectrum: true,
+		..RenderOptions::default()
+	};
+
+	let res = render_png(&sine(), &opts).unwrap();
+
+	assert_eq!(&res[..8], &[137, 80, 78, 71, 13, 10, 26, 10]);
+	assert_eq!(&res[16..24], &[0, 0, 1, 44, 0, 0, 0, 200]);
+	assert!(res.ends_with(&[0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130]));
+
+	assert_eq!(render(&sine(), &opts, RenderFormat::Png).unwrap(), res);
+	assert!(render(&sine(), &opts, RenderFormat::Svg).unwrap().starts_with(b"<svg"));
+}
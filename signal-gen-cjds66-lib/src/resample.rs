@@ -0,0 +1,354 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Resampling waveforms of any length to fit the device.
+
+The device stores exactly 2048 points per arbitrary wave slot. The
+functions here can cut a time window, or one detected period, out of
+a longer recording, and then stretch or squeeze it to 2048 points
+with linear, cubic spline, or band-limited sinc interpolation.
+
+The samples are treated as one cycle of a repeating wave, because
+that's how the device plays them back, so interpolation near the end
+of the wave blends towards its start.
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::units::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::f64::consts::PI;
+
+/** The number of zero crossings on each side of the sinc kernel. */
+pub const SINC_ZERO_CROSSINGS: usize = 16;
+
+/** The most samples looked at when detecting the period of a wave. */
+pub const PERIOD_DETECT_MAX_SAMPLES: usize = 16384;
+
+/** How to work out the values between samples when resampling. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+	/// A straight line between each pair of samples.
+	Linear,
+
+	/// A smooth Catmull-Rom cubic spline through the samples.
+	Cubic,
+
+	/// Band-limited Blackman-windowed sinc interpolation, which also
+	/// filters out frequencies that are too high to fit when shrinking.
+	Sinc,
+}
+
+impl Interpolation {
+	/// Parse an interpolation method by name: "linear", "cubic", or "sinc".
+	pub fn parse(name: &str) -> Result<Interpolation, clap::Error> {
+		match name.trim().to_lowercase().as_str() {
+			"linear" => Ok(Interpolation::Linear),
+			"cubic" | "spline" => Ok(Interpolation::Cubic),
+			"sinc" => Ok(Interpolation::Sinc),
+			_ => Err(Error::with_description(
+				&format!("unsupported interpolation method (must be linear, cubic, or sinc): {}", name),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+}
+
+/** A part of a longer recording to use, given as a start and an
+optional end, each either a time with a unit such as `"10ms"`, which
+needs the sample rate to be known, or a plain sample number. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWindow {
+	/// Where the window starts.
+	pub start: ParsedQuantity,
+
+	/// Where the window ends, or `None` for the end of the recording.
+	pub end: Option<ParsedQuantity>,
+}
+
+impl TimeWindow {
+	/// Parse a time window, such as "10ms:30ms", "0.5s:", or "1000:3048".
+	pub fn parse(window: &str) -> Result<TimeWindow, clap::Error> {
+		let parts: Vec<&str> = window.split(':').collect();
+
+		if parts.len() != 2 || parts[0].trim().is_empty() {
+			return Err(Error::with_description(
+				&format!("unsupported time window (must be <start>:<end> or <start>:, as times such as 10ms or sample numbers): {}", window),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		let start = parse_quantity(parts[0], Quantity::Time)?;
+
+		let end = if parts[1].trim().is_empty() {
+			None
+		} else {
+			Some(parse_quantity(parts[1], Quantity::Time)?)
+		};
+
+		Ok(TimeWindow { start, end })
+	}
+
+	/// The range of sample numbers this window covers in a
+	/// recording of `len` samples.
+	pub fn range(&self, len: usize, sample_rate: Option<f64>) -> Result<(usize, usize), clap::Error> {
+		let index = |point: &ParsedQuantity| -> Result<f64, clap::Error> {
+			match (point.unit, sample_rate) {
+				(None, _) => Ok(point.number),
+				(Some(_), Some(rate)) => Ok((point.base * rate).round()),
+				(Some(unit), None) => Err(Error::with_description(
+					&format!("unsupported time window: a time in {} needs a known sample rate, use sample numbers instead", unit),
+					ErrorKind::InvalidValue,
+				)),
+			}
+		};
+
+		let start = index(&self.start)?;
+		let end = match &self.end {
+			Some(end) => index(end)?,
+			None => len as f64,
+		};
+
+		if start < 0.0 || end > len as f64 || end - start < 2.0 || start.fract() != 0.0 || end.fract() != 0.0 {
+			return Err(Error::with_description(
+				&format!(
+					"unsupported time window: samples {} to {} don't fit in a recording of {} samples (must be whole numbers, at least 2 samples apart)",
+					start, end, len
+				),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		Ok((start as usize, end as usize))
+	}
+}
+
+/** Options for fitting a recording to the device. The defaults
+leave the recording as it is. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResampleOptions {
+	/// How to resample to 2048 points, or `None` to not resample.
+	pub interpolation: Option<Interpolation>,
+
+	/// Which part of the recording to use, or `None` for all of it.
+	pub window: Option<TimeWindow>,
+
+	/// Whether to use just one period of the wave, detected automatically.
+	pub single_period: bool,
+}
+
+impl ResampleOptions {
+	/// Returns true if these options would change a recording.
+	pub fn is_active(&self) -> bool {
+		self.interpolation.is_some() || self.window.is_some() || self.single_period
+	}
+}
+
+/** Cut the window and period chosen in "opts" out of the samples,
+and resample them to "len" points if an interpolation method was
+chosen. Picking a single period resamples linearly if no method
+was chosen, because a period is hardly ever exactly the right length.
+*/
+pub fn prepare_wave(
+	samples: &[f64],
+	sample_rate: Option<f64>,
+	opts: &ResampleOptions,
+	len: usize,
+) -> Result<Vec<f64>, clap::Error> {
+	let mut samples = samples;
+
+	if let Some(window) = &opts.window {
+		let (start, end) = window.range(samples.len(), sample_rate)?;
+		samples = &samples[start..end];
+	}
+
+	let mut interpolation = opts.interpolation;
+
+	if opts.single_period {
+		let (start, period) = detect_period(samples).ok_or_else(|| {
+			Error::with_description(
+				"failed detecting a period in the wave: it doesn't seem to repeat",
+				ErrorKind::InvalidValue,
+			)
+		})?;
+
+		samples = &samples[start..start + period];
+		interpolation = interpolation.or(Some(Interpolation::Linear));
+	}
+
+	match interpolation {
+		Some(interpolation) => Ok(resample(samples, len, interpolation)),
+		None => Ok(samples.to_vec()),
+	}
+}
+
+/** Resample one cycle of a repeating wave to "len" points. */
+pub fn resample(samples: &[f64], len: usize, interpolation: Interpolation) -> Vec<f64> {
+	let n = samples.len();
+
+	if n == 0 {
+		return vec![0.0; len];
+	}
+
+	if n == len {
+		return samples.to_vec();
+	}
+
+	let at = |i: isize| samples[i.rem_euclid(n as isize) as usize];
+	let step = n as f64 / len as f64;
+
+	// How much to lower the sinc kernel's cutoff frequency
+	// by, to avoid aliasing when shrinking.
+	let cutoff = (len as f64 / n as f64).min(1.0);
+	let half_width = (SINC_ZERO_CROSSINGS as f64 / cutoff).ceil() as isize;
+
+	(0..len)
+		.map(|i| {
+			let pos = i as f64 * step;
+			let x0 = pos.floor() as isize;
+			let t = pos - x0 as f64;
+
+			match interpolation {
+				Interpolation::Linear => at(x0) * (1.0 - t) + at(x0 + 1) * t,
+
+				Interpolation::Cubic => {
+					let (p0, p1, p2, p3) = (at(x0 - 1), at(x0), at(x0 + 1), at(x0 + 2));
+
+					p1 + 0.5
+						* t * (p2 - p0 + t * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3 + t * (3.0 * (p1 - p2) + p3 - p0)))
+				}
+
+				Interpolation::Sinc => {
+					let mut sum = 0.0;
+					let mut weights = 0.0;
+
+					for k in (x0 - half_width + 1)..=(x0 + half_width) {
+						let x = (pos - k as f64) * cutoff;
+						let w = sinc(x) * blackman(x / SINC_ZERO_CROSSINGS as f64);
+
+						sum += at(k) * w;
+						weights += w;
+					}
+
+					if weights == 0.0 {
+						at(x0)
+					} else {
+						sum / weights
+					}
+				}
+			}
+		})
+		.collect()
+}
+
+fn sinc(x: f64) -> f64 {
+	if x == 0.0 {
+		1.0
+	} else {
+		(PI * x).sin() / (PI * x)
+	}
+}
+
+/// A Blackman window which is 1 at x = 0 and falls to 0 at x = -1 and 1.
+fn blackman(x: f64) -> f64 {
+	if x.abs() >= 1.0 {
+		0.0
+	} else {
+		0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos()
+	}
+}
+
+/** Detect one period of a repeating wave, using autocorrelation.
+Returns where the period starts, at the first point where the
+wave rises through its average, and how many samples long it is,
+or `None` if the wave doesn't seem to repeat.
+*/
+pub fn detect_period(samples: &[f64]) -> Option<(usize, usize)> {
+	let samples = &samples[..samples.len().min(PERIOD_DETECT_MAX_SAMPLES)];
+	let n = samples.len();
+
+	if n < 8 {
+		return None;
+	}
+
+	let mean = samples.iter().sum::<f64>() / n as f64;
+	let centered: Vec<f64> = samples.iter().map(|s| s - mean).collect();
+
+	// The average product of the wave with itself shifted by "lag"
+	// samples, so shifts with fewer overlapping samples aren't penalized.
+	let correlation = |lag: usize| -> f64 {
+		let sum: f64 = centered.iter().zip(centered[lag..].iter()).map(|(a, b)| a * b).sum();
+		sum / (n - lag) as f64
+	};
+
+	let energy = correlation(0);
+
+	if energy <= 0.0 {
+		return None;
+	}
+
+	let max_lag = n / 2;
+	let corr: Vec<f64> = (0..=max_lag).map(correlation).collect();
+
+	// Skip the peak at zero lag, then take the first peak that's
+	// nearly as strong as the strongest one, so a wave isn't
+	// mistaken for several periods of itself.
+	let first_dip = corr.iter().position(|c| *c < 0.0)?;
+
+	let peaks: Vec<usize> = (first_dip.max(1)..max_lag)
+		.filter(|&lag| corr[lag] > corr[lag - 1] && corr[lag] >= corr[lag + 1] && corr[lag] > 0.0)
+		.collect();
+
+	let strongest = peaks.iter().map(|&lag| corr[lag]).fold(0.0, f64::max);
+
+	if strongest < energy * 0.5 {
+		return None;
+	}
+
+	let period = *peaks.iter().find(|&&lag| corr[lag] >= strongest * 0.9)?;
+
+	let start = (1..period)
+		.find(|&i| centered[i - 1] < 0.0 && centered[i] >= 0.0)
+		.unwrap_or(0);
+
+	if start + period > n {
+		return None;
+	}
+
+	Some((start, period))
+}
+
+/** Resample the device's arbitrary wave text format as chosen in
+"opts", so that any number of values can be uploaded. The text can
+be in any form that [parse_txt](../wave/fn.parse_txt.html) accepts.
+It fails if "opts" doesn't resample the values to exactly 2048.
+
+Cubic and sinc interpolation can overshoot the device's range, so
+values outside it are clipped, or it fails if "fail_on_clip" is set.
+Returns the wave, and a report of its levels and how many values
+had to be clipped to fit. */
+pub fn resample_wave_data(
+	text: &str,
+	opts: &ResampleOptions,
+	fail_on_clip: bool,
+) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let samples = prepare_wave(&parse_txt(text)?, None, opts, ARBITRARY_WAVE_LEN)?;
+	let levels: Vec<f64> = samples.iter().map(|s| (s - HALF_SCALE) / HALF_SCALE).collect();
+
+	let convert = ConvertOptions {
+		fail_on_clip,
+		..ConvertOptions::default()
+	};
+
+	let (values, report) = convert_to_device(&levels, &convert)?;
+
+	Ok((WaveData::new(&values)?, report))
+}
@@ -0,0 +1,183 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::resample::*;
+
+use std::f64::consts::PI;
+
+fn sine(len: usize, period: f64) -> Vec<f64> {
+	(0..len).map(|i| (2.0 * PI * i as f64 / period).sin()).collect()
+}
+
+#[test]
+pub fn parse_ok() {
+	assert_eq!(Interpolation::parse("linear").unwrap(), Interpolation::Linear);
+	assert_eq!(Interpolation::parse("Cubic").unwrap(), Interpolation::Cubic);
+	assert_eq!(Interpolation::parse("sinc").unwrap(), Interpolation::Sinc);
+
+	let window = TimeWindow::parse("10ms:20ms").unwrap();
+	assert_eq!(window.range(100, Some(1000.0)).unwrap(), (10, 20));
+
+	let window = TimeWindow::parse("5:").unwrap();
+	assert_eq!(window.range(100, None).unwrap(), (5, 100));
+}
+
+#[test]
+pub fn parse_err() {
+	Interpolation::parse("nearest").unwrap_err();
+	TimeWindow::parse("10ms").unwrap_err();
+	TimeWindow::parse(":20ms").unwrap_err();
+	TimeWindow::parse("1x:2x").unwrap_err();
+
+	TimeWindow::parse("10ms:20ms").unwrap().range(100, None).unwrap_err();
+	TimeWindow::parse("50:150").unwrap().range(100, None).unwrap_err();
+	TimeWindow::parse("50:51").unwrap().range(100, None).unwrap_err();
+	TimeWindow::parse("1.5:20").unwrap().range(100, None).unwrap_err();
+}
+
+#[test]
+pub fn resample_ok() {
+	assert_eq!(resample(&[0.0, 1.0], 4, Interpolation::Linear), vec![0.0, 0.5, 1.0, 0.5]);
+	assert_eq!(resample(&[3.0; 10], 25, Interpolation::Cubic), vec![3.0; 25]);
+	assert_eq!(resample(&[], 3, Interpolation::Sinc), vec![0.0; 3]);
+
+	// One cycle of a sine wave should stay a sine wave, whether
+	// it's being stretched or squeezed.
+	let expected = sine(2048, 2048.0);
+
+	for len in [100, 8192].iter() {
+		for interpolation in [Interpolation::Linear, Interpolation::Cubic, Interpolation::Sinc].iter() {
+			let out = resample(&sine(*len, *len as f64), 2048, *interpolation);
+
+			assert_eq!(out.len(), 2048);
+
+			let err = out.iter().zip(expected.iter()).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+			assert!(err < 0.01, "{} {:?}: {}", len, interpolation, err);
+		}
+	}
+
+	// High frequencies which don't fit after squeezing are filtered out by sinc.
+	let noise: Vec<f64> = (0..8192).map(|i| if i % 2 == 0 { 1.0 } else { -1.0 }).collect();
+	let out = resample(&noise, 2048, Interpolation::Sinc);
+	assert!(out.iter().all(|s| s.abs() < 0.01));
+}
+
+#[test]
+pub fn detect_period_ok() {
+	let (start, period) = detect_period(&sine(1000, 100.0)).unwrap();
+	assert_eq!((start, period), (0, 100));
+
+	let shifted: Vec<f64> = sine(1000, 80.0).iter().skip(30).copied().collect();
+	let (start, period) = detect_period(&shifted).unwrap();
+	assert_eq!(period, 80);
+	assert!((50..=51).contains(&start), "{}", start);
+
+	// A wave with two peaks per period is still one period.
+	let double: Vec<f64> = (0..1000)
+		.map(|i| (2.0 * PI * i as f64 / 120.0).sin() + 0.8 * (4.0 * PI * i as f64 / 120.0).sin())
+		.collect();
+	assert_eq!(detect_period(&double).unwrap().1, 120);
+}
+
+#[test]
+pub fn detect_period_err() {
+	assert_eq!(detect_period(&[1.0; 500]), None);
+	assert_eq!(detect_period(&[0.0, 1.0, 0.0]), None);
+
+	let ramp: Vec<f64> = (0..500).map(|i| i as f64).collect();
+	assert_eq!(detect_period(&ramp), None);
+}
+
+#[test]
+pub fn prepare_wave_ok() {
+	let samples = sine(1000, 100.0);
+
+	let opts = ResampleOptions::default();
+	assert!(!opts.is_active());
+	assert_eq!(prepare_wave(&samples, None, &opts, 2048).unwrap(), samples);
+
+	let opts = ResampleOptions {
+		window: Some(TimeWindow::parse("0.1s:0.2s").unwrap()),
+		..ResampleOptions::default()
+	};
+	assert_eq!(prepare_wave(&samples, Some(1000.0), &opts, 2048).unwrap(), &samples[100..200]);
+
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+	let out = prepare_wave(&samples, None, &opts, 2048).unwrap();
+	assert_eq!(out.len(), 2048);
+	assert!(out.iter().zip(sine(2048, 2048.0).iter()).all(|(a, b)| (a - b).abs() < 0.1));
+
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Cubic),
+		..ResampleOptions::default()
+	};
+	assert_eq!(prepare_wave(&samples, None, &opts, 2048).unwrap().len(), 2048);
+}
+
+#[test]
+pub fn resample_wave_data_ok() {
+	let data: String = (0..100).map(|i| format!("{}\n", i * 40)).collect::<String>() + "\n";
+
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Linear),
+		..ResampleOptions::default()
+	};
+
+	let (out, report) = resample_wave_data(&data, &opts, true).unwrap();
+
+	assert_eq!(out.samples()[0], 0);
+	assert_eq!(out.samples()[20], 39);
+	assert_eq!(report.clipped(), 0);
+
+	// A square wave overshoots with sinc interpolation, and is clipped.
+	let square: String = (0..64).map(|i| if i % 32 < 16 { "0\n" } else { "4095\n" }).collect();
+
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Sinc),
+		..ResampleOptions::default()
+	};
+
+	let (out, report) = resample_wave_data(&square, &opts, false).unwrap();
+
+	assert!(report.clipped() > 0);
+	assert!(out.samples().contains(&4095));
+}
+
+#[test]
+pub fn resample_wave_data_err() {
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Linear),
+		..ResampleOptions::default()
+	};
+
+	resample_wave_data("1\nx\n", &opts, false).unwrap_err();
+
+	let opts = ResampleOptions {
+		window: Some(TimeWindow::parse("0:10").unwrap()),
+		..ResampleOptions::default()
+	};
+
+	let data: String = (0..100).map(|i| format!("{}\n", i)).collect();
+	resample_wave_data(&data, &opts, false).unwrap_err();
+
+	// Overshoot from resampling, when clipping isn't allowed.
+	let square: String = (0..64).map(|i| if i % 32 < 16 { "0\n" } else { "4095\n" }).collect();
+
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Sinc),
+		..ResampleOptions::default()
+	};
+
+	resample_wave_data(&square, &opts, true).unwrap_err();
+}
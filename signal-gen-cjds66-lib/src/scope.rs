@@ -0,0 +1,546 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Oscilloscope captures saved as CSV files, so real signals can be
+played back by the device.
+
+Rigol, Siglent, and Keysight scopes each lay their CSV files out
+differently. Rigol scopes write a sequence number and the time of the
+first sample and between samples, or a time column on older models:
+```text
+X,CH1,CH2,Start,Increment,
+Sequence,Volt,Volt,-6.000000e-03,2.000000e-06
+0,2.40e-01,1.20e-01,
+```
+```text
+Time(s),CH1(V)
+-6.000000e-03,2.40e-01
+```
+
+Siglent scopes write a list of settings before the samples:
+```text
+Record Length,Analog:1400,
+Sample Interval,1.00E-06,
+Source,CH1,
+Vertical Units,V,
+Horizontal Units,S,
+Second,Value,
+-7.000000E-04,2.32E-01,
+```
+
+Keysight scopes write the channel numbers, and then the units:
+```text
+x-axis,1,2
+second,Volt,Volt
+-5.0000000E-04,+1.23E-01,+5.00E-02
+```
+
+The layout is detected from the lines before the samples, and times
+and voltages are converted to seconds and volts from any of the usual
+units, such as ms or mV. The time is measured from the trigger, so a
+single period can be picked out starting at the trigger, and it lines
+up with what was seen on the scope.
+*/
+
+use crate::convert::MAX_PEAK;
+use crate::resample::*;
+
+use clap::{Error, ErrorKind};
+use std::fmt;
+
+/** Whose layout a CSV capture is in. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeVendor {
+	/// Rigol, with a sequence number and start time and increment,
+	/// or with a time column.
+	Rigol,
+
+	/// Siglent, with a list of settings before the samples.
+	Siglent,
+
+	/// Keysight (and Agilent), with an x-axis line and a units line.
+	Keysight,
+}
+
+impl fmt::Display for ScopeVendor {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ScopeVendor::Rigol => write!(f, "Rigol"),
+			ScopeVendor::Siglent => write!(f, "Siglent"),
+			ScopeVendor::Keysight => write!(f, "Keysight"),
+		}
+	}
+}
+
+impl ScopeVendor {
+	/// Parse `rigol`, `siglent`, or `keysight`.
+	pub fn parse(name: &str) -> Result<ScopeVendor, clap::Error> {
+		match name.trim().to_lowercase().as_str() {
+			"rigol" => Ok(ScopeVendor::Rigol),
+			"siglent" => Ok(ScopeVendor::Siglent),
+			"keysight" | "agilent" => Ok(ScopeVendor::Keysight),
+			_ => Err(Error::with_description(
+				&format!("unsupported oscilloscope vendor (must be rigol, siglent, or keysight): {}", name),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+
+	/// Detect whose layout a CSV capture is in, from its first few
+	/// lines, or `None` if it isn't a capture from one of them.
+	pub fn detect(text: &str) -> Option<ScopeVendor> {
+		let (header, _data) = split_header(text);
+		let first = header.first()?;
+
+		match first.first()?.to_lowercase().as_str() {
+			"x-axis" => Some(ScopeVendor::Keysight),
+			"x" if first.iter().any(|cell| cell.eq_ignore_ascii_case("increment")) => Some(ScopeVendor::Rigol),
+			_ if header.iter().any(|row| row[0].eq_ignore_ascii_case("sample interval")) => Some(ScopeVendor::Siglent),
+			_ if header.len() == 1 && first.len() >= 2 && first[1..].iter().all(|cell| is_rigol_channel(cell)) => {
+				Some(ScopeVendor::Rigol)
+			}
+			_ => None,
+		}
+	}
+}
+
+/** A capture from an oscilloscope. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeCapture {
+	/// Whose layout the capture was in.
+	pub vendor: ScopeVendor,
+
+	/// The name of each channel, such as `CH1`.
+	pub channels: Vec<String>,
+
+	/// The time of each sample in seconds, from the trigger.
+	pub time: Vec<f64>,
+
+	/// The samples of each channel, in volts (or amps for a current
+	/// probe).
+	pub volts: Vec<Vec<f64>>,
+}
+
+impl ScopeCapture {
+	/// The number of samples in each channel.
+	pub fn len(&self) -> usize {
+		self.time.len()
+	}
+
+	/// Whether there are no samples.
+	pub fn is_empty(&self) -> bool {
+		self.time.is_empty()
+	}
+
+	/// The number of samples per second, or `None` if the times
+	/// don't go forwards.
+	pub fn sample_rate(&self) -> Option<f64> {
+		let span = self.time.last()? - self.time.first()?;
+
+		if span > 0.0 {
+			Some((self.len() - 1) as f64 / span)
+		} else {
+			None
+		}
+	}
+
+	/// The sample closest to the trigger, at time 0, or `None` if the
+	/// trigger isn't in the capture.
+	pub fn trigger_index(&self) -> Option<usize> {
+		let (first, last) = (*self.time.first()?, *self.time.last()?);
+
+		if !(first..=last).contains(&0.0) {
+			return None;
+		}
+
+		let after = self.time.iter().position(|t| *t >= 0.0)?;
+
+		if after > 0 && -self.time[after - 1] < self.time[after] {
+			Some(after - 1)
+		} else {
+			Some(after)
+		}
+	}
+
+	/// The samples of one channel, counting from 1. Channel 0 gives
+	/// the average of all the channels.
+	pub fn channel(&self, channel: u16) -> Result<Vec<f64>, clap::Error> {
+		if channel as usize > self.volts.len() {
+			return Err(Error::with_description(
+				&format!(
+					"unsupported channel number for oscilloscope capture (must be 0-{}, where 0 mixes all channels together): {}",
+					self.volts.len(),
+					channel
+				),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		if channel == 0 {
+			Ok((0..self.len())
+				.map(|i| self.volts.iter().map(|column| column[i]).sum::<f64>() / self.volts.len() as f64)
+				.collect())
+		} else {
+			Ok(self.volts[channel as usize - 1].clone())
+		}
+	}
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid oscilloscope capture: {}", msg), ErrorKind::InvalidValue)
+}
+
+/** The cells of a CSV line, without the empty ones some scopes leave
+at the end. */
+fn cells(line: &str) -> Vec<&str> {
+	let mut cells: Vec<&str> = line.split(',').map(|cell| cell.trim()).collect();
+
+	while cells.last().is_some_and(|cell| cell.is_empty()) {
+		cells.pop();
+	}
+
+	cells
+}
+
+fn is_number(cell: &str) -> bool {
+	cell.parse::<f64>().is_ok()
+}
+
+/** Lines of a CSV file split into cells, with their line numbers. */
+type NumberedRows<'a> = Vec<(usize, Vec<&'a str>)>;
+
+/** Split a CSV file into the lines before the samples, and the
+lines of samples with their line numbers. The samples start at the
+first line which is all numbers. */
+fn split_header(text: &str) -> (Vec<Vec<&str>>, NumberedRows<'_>) {
+	let mut header = Vec::new();
+	let mut data = Vec::new();
+
+	for (i, line) in text.lines().enumerate() {
+		let row = cells(line);
+
+		if row.is_empty() {
+			continue;
+		}
+
+		if data.is_empty() && !row.iter().all(|cell| is_number(cell)) {
+			header.push(row);
+		} else {
+			data.push((i + 1, row));
+		}
+	}
+
+	(header, data)
+}
+
+/** A Rigol column heading, such as `CH1(V)`. */
+fn is_rigol_channel(cell: &str) -> bool {
+	let (name, unit) = split_unit(cell);
+
+	unit.is_some() && (name.to_uppercase().starts_with("CH") || name.to_uppercase().starts_with("MATH"))
+}
+
+/** Split a column heading like `Time(s)` or `CH1 (mV)` into its name
+and unit. */
+fn split_unit(cell: &str) -> (&str, Option<&str>) {
+	match cell.split_once('(') {
+		Some((name, unit)) => (name.trim(), Some(unit.trim_end_matches(')').trim())),
+		None => (cell.trim(), None),
+	}
+}
+
+/** How many seconds, volts, or amps are in a unit, such as `ms`,
+`mV`, or `Volt`. */
+pub fn unit_scale(unit: &str) -> Result<f64, clap::Error> {
+	let base = |name: &str| {
+		matches!(
+			name.to_lowercase().as_str(),
+			"s" | "sec" | "second" | "seconds" | "v" | "volt" | "volts" | "a" | "amp" | "amps" | "ampere" | "amperes"
+		)
+	};
+
+	let unit = unit.trim();
+
+	if base(unit) {
+		return Ok(1.0);
+	}
+
+	for (prefix, scale) in [("n", 1e-9), ("u", 1e-6), ("µ", 1e-6), ("μ", 1e-6), ("m", 1e-3), ("k", 1e3)] {
+		if unit.strip_prefix(prefix).is_some_and(base) {
+			return Ok(scale);
+		}
+	}
+
+	Err(invalid(&format!("unsupported unit (must be in seconds, volts, or amps): {}", unit)))
+}
+
+/** Read a number from a line of samples. */
+fn number(line: usize, row: &[&str], column: usize) -> Result<f64, clap::Error> {
+	row.get(column)
+		.and_then(|cell| cell.parse::<f64>().ok())
+		.ok_or_else(|| invalid(&format!("line {}: column {} is missing", line, column + 1)))
+}
+
+/** The setting named "key" in a Siglent file's list of settings. */
+fn setting<'a>(header: &'a [Vec<&'a str>], key: &str) -> Option<&'a [&'a str]> {
+	header.iter().find(|row| row[0].eq_ignore_ascii_case(key)).map(|row| &row[1..])
+}
+
+/** How to get the time of each sample. */
+enum TimeColumn {
+	/// A sequence number, with the time of the first sample, and
+	/// between samples.
+	Sequence { start: f64, increment: f64 },
+
+	/// A time, with how many seconds are in its unit.
+	Time(f64),
+}
+
+impl TimeColumn {
+	fn time(&self, line: usize, row: &[&str]) -> Result<f64, clap::Error> {
+		match self {
+			TimeColumn::Sequence { start, increment } => Ok(start + number(line, row, 0)? * increment),
+			TimeColumn::Time(scale) => Ok(number(line, row, 0)? * scale),
+		}
+	}
+}
+
+/** Parse an oscilloscope's CSV capture, in the layout of "vendor", or
+in a detected layout if it's `None`. */
+pub fn parse_scope_csv(text: &str, vendor: Option<ScopeVendor>) -> Result<ScopeCapture, clap::Error> {
+	let vendor = match vendor.or_else(|| ScopeVendor::detect(text)) {
+		Some(vendor) => vendor,
+		None => {
+			return Err(invalid("the layout isn't one of Rigol's, Siglent's, or Keysight's"));
+		}
+	};
+
+	let (header, data) = split_header(text);
+
+	let width = data.first().map_or(0, |(_line, row)| row.len());
+
+	if data.len() < 2 || width < 2 {
+		return Err(invalid("it needs at least 2 samples, with a time and at least one channel"));
+	}
+
+	let last_header = header.last().cloned().unwrap_or_default();
+
+	// The name of each channel's column, how many volts are in its
+	// unit, and how to get the time of each sample.
+	let (channels, scales, time): (Vec<String>, Vec<f64>, TimeColumn) = match vendor {
+		ScopeVendor::Rigol if header.len() >= 2 => {
+			// A sequence number, with the start time and increment
+			// under their headings.
+			let names = &header[0];
+			let units = &header[1];
+
+			let position = |name: &str| names.iter().position(|cell| cell.eq_ignore_ascii_case(name));
+			let value = |column: Option<usize>| column.and_then(|column| units.get(column)).and_then(|cell| cell.parse::<f64>().ok());
+
+			let start = value(position("start")).ok_or_else(|| invalid("the start time is missing"))?;
+			let increment = value(position("increment")).ok_or_else(|| invalid("the time increment is missing"))?;
+
+			let count = width - 1;
+			let channels = names.iter().skip(1).take(count).map(|name| name.to_string()).collect();
+			let scales = (1..=count)
+				.map(|column| units.get(column).map_or(Ok(1.0), |unit| unit_scale(unit)))
+				.collect::<Result<Vec<f64>, clap::Error>>()?;
+
+			(channels, scales, TimeColumn::Sequence { start, increment })
+		}
+
+		ScopeVendor::Rigol => {
+			// Headings with units, such as Time(s) and CH1(V).
+			let time_scale = split_unit(last_header.first().copied().unwrap_or_default()).1.map_or(Ok(1.0), unit_scale)?;
+
+			let mut channels = Vec::new();
+			let mut scales = Vec::new();
+
+			for cell in last_header.iter().skip(1).take(width - 1) {
+				let (name, unit) = split_unit(cell);
+
+				channels.push(name.to_string());
+				scales.push(unit.map_or(Ok(1.0), unit_scale)?);
+			}
+
+			(channels, scales, TimeColumn::Time(time_scale))
+		}
+
+		ScopeVendor::Siglent => {
+			let time_scale = setting(&header, "horizontal units")
+				.and_then(|units| units.first())
+				.map_or(Ok(1.0), |unit| unit_scale(unit))?;
+
+			let volt_scale = setting(&header, "vertical units")
+				.and_then(|units| units.first())
+				.map_or(Ok(1.0), |unit| unit_scale(unit))?;
+
+			let sources: Vec<String> = setting(&header, "source").unwrap_or_default().iter().map(|name| name.to_string()).collect();
+
+			let channels = if sources.len() == width - 1 {
+				sources
+			} else {
+				(1..width).map(|n| format!("CH{}", n)).collect()
+			};
+
+			(channels, vec![volt_scale; width - 1], TimeColumn::Time(time_scale))
+		}
+
+		ScopeVendor::Keysight => {
+			let names = header.first().cloned().unwrap_or_default();
+			let units = header.get(1).cloned().unwrap_or_default();
+
+			let time_scale = units.first().map_or(Ok(1.0), |unit| unit_scale(unit))?;
+
+			let channels = (1..width)
+				.map(|column| match names.get(column) {
+					Some(name) if is_number(name) => format!("CH{}", name),
+					Some(name) => name.to_string(),
+					None => format!("CH{}", column),
+				})
+				.collect();
+
+			let scales = (1..width)
+				.map(|column| units.get(column).map_or(Ok(1.0), |unit| unit_scale(unit)))
+				.collect::<Result<Vec<f64>, clap::Error>>()?;
+
+			(channels, scales, TimeColumn::Time(time_scale))
+		}
+	};
+
+	let mut capture = ScopeCapture {
+		vendor,
+		channels,
+		time: Vec::with_capacity(data.len()),
+		volts: vec![Vec::with_capacity(data.len()); width - 1],
+	};
+
+	for (line, row) in data.iter() {
+		if row.len() != width {
+			return Err(invalid(&format!("line {}: it has {} columns, instead of {}", line, row.len(), width)));
+		}
+
+		capture.time.push(time.time(*line, row)?);
+
+		for (column, samples) in capture.volts.iter_mut().enumerate() {
+			samples.push(number(*line, row, column + 1)? * scales[column]);
+		}
+	}
+
+	Ok(capture)
+}
+
+/** Detect one period of a repeating wave, as with
+[detect_period](../resample/fn.detect_period.html), but starting at
+the trigger, if there is one, so it lines up with what was seen on the
+scope. If a period doesn't fit after the trigger, it starts a whole
+number of periods before it. The period is measured to where the wave
+next crosses the level it starts at, in the same direction, so there's
+no step where it repeats. Long captures are thinned out to find the
+period roughly first.
+
+Returns where the period starts, and how many samples long it is.
+*/
+pub fn trigger_period(samples: &[f64], trigger: Option<usize>) -> Option<(usize, usize)> {
+	let step = samples.len().div_ceil(PERIOD_DETECT_MAX_SAMPLES).max(1);
+	let thinned: Vec<f64> = samples.iter().step_by(step).copied().collect();
+
+	let (start, period) = detect_period(&thinned)?;
+	let (start, period) = (start * step, period * step);
+	let period = refine_period(samples, start, period, (period / 20).max(2 * step));
+
+	let aligned = trigger.and_then(|trigger| {
+		let back = (trigger + period).saturating_sub(samples.len()).div_ceil(period);
+
+		trigger.checked_sub(back * period)
+	});
+
+	Some((aligned.unwrap_or(start), period))
+}
+
+/** The sample within "slack" of "start" + "period" where the wave
+crosses the level it has at "start" again, in the same direction, or
+"period" if it doesn't. Returns the length of the period to there. */
+fn refine_period(samples: &[f64], start: usize, period: usize, slack: usize) -> usize {
+	let level = samples[start];
+	let rising = samples.get(start + 1).is_none_or(|next| *next >= level);
+	let end = start + period;
+
+	let crosses = |i: usize| {
+		let (before, after) = (samples[i - 1] - level, samples[i] - level);
+
+		if rising {
+			before < 0.0 && after >= 0.0
+		} else {
+			before > 0.0 && after <= 0.0
+		}
+	};
+
+	(end.saturating_sub(slack).max(start + 2)..=(end + slack).min(samples.len() - 1))
+		.filter(|i| crosses(*i))
+		.min_by_key(|i| i.abs_diff(end))
+		.map(|i| if (samples[i - 1] - level).abs() < (samples[i] - level).abs() { i - 1 } else { i })
+		.map_or(period, |i| i - start)
+}
+
+/** Cut the window and period chosen in "opts" out of one channel of a
+capture, and resample it to "len" points. Captures are always
+resampled, since they're hardly ever the right length, linearly unless
+"opts" chooses another method. See [ScopeCapture::channel](struct.ScopeCapture.html#method.channel)
+for the "channel" parameter. */
+pub fn capture_samples(
+	capture: &ScopeCapture,
+	channel: u16,
+	opts: &ResampleOptions,
+	len: usize,
+) -> Result<Vec<f64>, clap::Error> {
+	let samples = capture.channel(channel)?;
+
+	let (start, end) = match &opts.window {
+		Some(window) => window.range(samples.len(), capture.sample_rate())?,
+		None => (0, samples.len()),
+	};
+
+	let mut samples = &samples[start..end];
+
+	if opts.single_period {
+		let trigger = capture.trigger_index().filter(|trigger| (start..end).contains(trigger)).map(|trigger| trigger - start);
+
+		let (start, period) = trigger_period(samples, trigger).ok_or_else(|| {
+			Error::with_description(
+				"failed detecting a period in the wave: it doesn't seem to repeat",
+				ErrorKind::InvalidValue,
+			)
+		})?;
+
+		samples = &samples[start..start + period];
+	}
+
+	Ok(resample(samples, len, opts.interpolation.unwrap_or(Interpolation::Linear)))
+}
+
+/** Scale samples in volts so the lowest and highest reach the bottom
+and top of the device's range, as fractions of full scale. Returns the
+scaled samples, and the lowest and highest voltages, which are what
+the output's amplitude and offset should be set from to play them back
+at the same levels. */
+pub fn fit_volts(samples: &[f64]) -> (Vec<f64>, f64, f64) {
+	let low = samples.iter().copied().fold(f64::INFINITY, f64::min);
+	let high = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+	let middle = (low + high) / 2.0;
+	let half = (high - low) / 2.0;
+
+	let scaled = samples
+		.iter()
+		.map(|v| if half > 0.0 { (v - middle) / half * MAX_PEAK } else { 0.0 })
+		.collect();
+
+	(scaled, low, high)
+}
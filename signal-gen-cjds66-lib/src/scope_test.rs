@@ -0,0 +1,265 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::command::*;
+use super::convert::*;
+use super::resample::*;
+use super::scope::*;
+
+use std::f64::consts::PI;
+use std::fs;
+
+/// 4 ms of a 1 kHz sine, 250 samples per period, with the trigger at
+/// sample 500, where it rises through 0 V.
+fn sine(i: usize) -> f64 {
+	(2.0 * PI * (i as f64 - 500.0) / 250.0).sin()
+}
+
+fn rigol() -> String {
+	let mut csv = "X,CH1,CH2,Start,Increment,\nSequence,Volt,mV,-2.000000e-03,4.000000e-06\n".to_string();
+
+	for i in 0..1000 {
+		csv.push_str(&format!("{},{:.6e},{:.6e},\n", i, sine(i), 500.0));
+	}
+
+	csv
+}
+
+#[test]
+pub fn scope_vendor_ok() {
+	assert_eq!(ScopeVendor::parse("Rigol").unwrap(), ScopeVendor::Rigol);
+	assert_eq!(ScopeVendor::parse("agilent").unwrap(), ScopeVendor::Keysight);
+
+	assert_eq!(ScopeVendor::detect(&rigol()), Some(ScopeVendor::Rigol));
+	assert_eq!(ScopeVendor::detect("Time(s),CH1(V)\n0,1\n"), Some(ScopeVendor::Rigol));
+	assert_eq!(ScopeVendor::detect("Sample Interval,1e-6,\nSecond,Value,\n0,1,\n"), Some(ScopeVendor::Siglent));
+	assert_eq!(ScopeVendor::detect("x-axis,1\nsecond,Volt\n0,1\n"), Some(ScopeVendor::Keysight));
+}
+
+#[test]
+pub fn scope_vendor_err() {
+	assert!(ScopeVendor::parse("tektronix").is_err());
+
+	assert_eq!(ScopeVendor::detect("harmonic,amplitude\n1,1.0\n"), None);
+	assert_eq!(ScopeVendor::detect("2048,2048,2048"), None);
+	assert_eq!(ScopeVendor::detect(""), None);
+}
+
+#[test]
+pub fn unit_scale_ok() {
+	assert_eq!(unit_scale("Volt").unwrap(), 1.0);
+	assert_eq!(unit_scale("mV").unwrap(), 1e-3);
+	assert_eq!(unit_scale("second").unwrap(), 1.0);
+	assert_eq!(unit_scale("S").unwrap(), 1.0);
+	assert_eq!(unit_scale("µs").unwrap(), 1e-6);
+	assert_eq!(unit_scale("mA").unwrap(), 1e-3);
+}
+
+#[test]
+pub fn unit_scale_err() {
+	assert!(unit_scale("dB").is_err());
+	assert!(unit_scale("m").is_err());
+	assert!(unit_scale("").is_err());
+}
+
+#[test]
+pub fn parse_scope_csv_ok() {
+	// Rigol, with a sequence number.
+	let res = parse_scope_csv(&rigol(), None).unwrap();
+
+	assert_eq!(res.vendor, ScopeVendor::Rigol);
+	assert_eq!(res.channels, vec!["CH1", "CH2"]);
+	assert_eq!(res.len(), 1000);
+	assert!((res.time[1] + 0.001996).abs() < 1e-12);
+	assert!((res.sample_rate().unwrap() - 250_000.0).abs() < 1e-6);
+	assert_eq!(res.trigger_index(), Some(500));
+	assert!((res.volts[1][0] - 0.5).abs() < 1e-12);
+	assert!((res.channel(0).unwrap()[500] - 0.25).abs() < 1e-9);
+
+	// Rigol, with a time column.
+	let res = parse_scope_csv("Time(ms),CH1(mV)\n-0.5,100\n0.25,-200\n", None).unwrap();
+
+	assert_eq!(res.channels, vec!["CH1"]);
+	assert_eq!(res.time, vec![-0.0005, 0.00025]);
+	assert_eq!(res.volts, vec![vec![0.1, -0.2]]);
+	assert_eq!(res.trigger_index(), Some(1));
+
+	// Siglent.
+	let csv = "Record Length,Analog:3,\nSample Interval,1.00E-06,\nSource,CH2,\nVertical Units,mV,\nHorizontal Units,uS,\nSecond,Value,\n-1,10,\n0,20,\n1,30,\n";
+	let res = parse_scope_csv(csv, None).unwrap();
+
+	assert_eq!(res.vendor, ScopeVendor::Siglent);
+	assert_eq!(res.channels, vec!["CH2"]);
+	assert_eq!(res.time, vec![-1e-6, 0.0, 1e-6]);
+	assert_eq!(res.volts, vec![vec![0.01, 0.02, 0.03]]);
+
+	// Keysight.
+	let res = parse_scope_csv("x-axis,1,2\nsecond,Volt,mV\n-1.0E-03,+1.5E-01,+5.0E+01\n+3.0E-03,-1.5E-01,-5.0E+01\n", None).unwrap();
+
+	assert_eq!(res.vendor, ScopeVendor::Keysight);
+	assert_eq!(res.channels, vec!["CH1", "CH2"]);
+	assert_eq!(res.volts, vec![vec![0.15, -0.15], vec![0.05, -0.05]]);
+	assert_eq!(res.trigger_index(), Some(0));
+
+	// A layout given, instead of detected.
+	let res = parse_scope_csv("Time,Volts\n0,1\n1,2\n", Some(ScopeVendor::Siglent)).unwrap();
+
+	assert_eq!(res.channels, vec!["CH1"]);
+	assert_eq!(res.time, vec![0.0, 1.0]);
+}
+
+#[test]
+pub fn parse_scope_csv_err() {
+	// Not a scope's layout.
+	parse_scope_csv("a,b\n1,2\n3,4\n", None).unwrap_err();
+
+	// Not enough samples.
+	parse_scope_csv("x-axis,1\nsecond,Volt\n0,1\n", None).unwrap_err();
+
+	// A missing column.
+	parse_scope_csv("x-axis,1,2\nsecond,Volt,Volt\n0,1,2\n1,2\n", None).unwrap_err();
+
+	// An unknown unit.
+	parse_scope_csv("x-axis,1\nsecond,dBm\n0,1\n1,2\n", None).unwrap_err();
+
+	// Rigol without the increment.
+	parse_scope_csv("X,CH1,Start,Increment,\nSequence,Volt,0\n0,1\n1,2\n", None).unwrap_err();
+
+	// A channel which isn't there.
+	parse_scope_csv(&rigol(), None).unwrap().channel(3).unwrap_err();
+}
+
+#[test]
+pub fn trigger_period_ok() {
+	let samples: Vec<f64> = (0..1000).map(sine).collect();
+
+	// Starting at the trigger.
+	assert_eq!(trigger_period(&samples, Some(500)), Some((500, 250)));
+
+	// A whole period before the trigger, if one doesn't fit after it.
+	assert_eq!(trigger_period(&samples, Some(900)), Some((650, 250)));
+
+	// Where the wave rises through its average, without a trigger.
+	assert_eq!(trigger_period(&samples, None), Some((250, 250)));
+
+	// A long capture, which is thinned out to find the period.
+	let samples: Vec<f64> = (0..100_000).map(|i| (2.0 * PI * i as f64 / 25_000.0).sin()).collect();
+	let (_start, period) = trigger_period(&samples, Some(50_000)).unwrap();
+
+	assert!((period as f64 - 25_000.0).abs() <= 7.0);
+}
+
+#[test]
+pub fn trigger_period_err() {
+	let samples: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+
+	assert_eq!(trigger_period(&samples, Some(500)), None);
+	assert_eq!(trigger_period(&[], None), None);
+}
+
+#[test]
+pub fn capture_samples_ok() {
+	let capture = parse_scope_csv(&rigol(), None).unwrap();
+
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	// One period from the trigger, rising through 0 V.
+	let res = capture_samples(&capture, 1, &opts, 2048).unwrap();
+
+	assert_eq!(res.len(), 2048);
+	assert!(res[0].abs() < 1e-9);
+	assert!((res[512] - 1.0).abs() < 1e-3);
+	assert!((res[1536] + 1.0).abs() < 1e-3);
+
+	// The whole capture.
+	let res = capture_samples(&capture, 1, &ResampleOptions::default(), 2048).unwrap();
+
+	assert_eq!(res.len(), 2048);
+	assert!((res[0] - sine(0)).abs() < 1e-9);
+
+	// Scaled to the device's range.
+	let (res, low, high) = fit_volts(&[0.5, 3.3, 1.9]);
+
+	assert_eq!((low, high), (0.5, 3.3));
+	assert!((res[0] + 2047.0 / 2048.0).abs() < 1e-12);
+	assert!((res[1] - 2047.0 / 2048.0).abs() < 1e-12);
+	assert!(res[2].abs() < 1e-12);
+}
+
+#[test]
+pub fn capture_samples_err() {
+	let capture = parse_scope_csv(&rigol(), None).unwrap();
+
+	// A flat channel doesn't repeat.
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	capture_samples(&capture, 2, &opts, 2048).unwrap_err();
+	capture_samples(&capture, 3, &ResampleOptions::default(), 2048).unwrap_err();
+
+	// A window past the end of the capture.
+	let opts = ResampleOptions {
+		window: Some(TimeWindow::parse("0ms:5ms").unwrap()),
+		..ResampleOptions::default()
+	};
+
+	capture_samples(&capture, 1, &opts, 2048).unwrap_err();
+}
+
+#[test]
+pub fn load_scope_capture_ok() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-scope-test-{}.csv", std::process::id()));
+
+	fs::write(&path, rigol()).unwrap();
+
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	// One period from the trigger, filling the device's range.
+	let res = load_wave_file(path.to_str().unwrap(), 1, &opts, &ConvertOptions::default(), 0).unwrap();
+	let samples = res.samples();
+
+	assert_eq!(samples[0], 2048);
+	assert_eq!(samples[512], 4095);
+	assert_eq!(samples[1536], 1);
+
+	// The whole capture, as uploaded from a file.
+	let (res, report) = wav_to_wave_data(&fs::read(&path).unwrap(), 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap();
+
+	assert_eq!(res.samples().len(), 2048);
+	assert_eq!(report.clipped(), 0);
+
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+pub fn load_scope_capture_err() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-scope-test-err-{}.csv", std::process::id()));
+
+	// A channel which isn't there.
+	fs::write(&path, rigol()).unwrap();
+
+	load_wave_file(path.to_str().unwrap(), 3, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	// A layout which isn't a scope's.
+	fs::write(&path, "time,value\n0,1\n1,2\n").unwrap();
+
+	load_wave_file(path.to_str().unwrap(), 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	fs::remove_file(&path).unwrap();
+}
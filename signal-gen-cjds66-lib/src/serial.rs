@@ -15,8 +15,14 @@ device.
 extern crate serial;
 
 use crate::protocol::*;
+use crate::stats::*;
+use crate::transfer::ProgressCallback;
+use crate::units::Load;
+use crate::wave::WaveData;
+use std::collections::HashMap;
 use std::io;
-use std::time::Duration;
+use std::io::prelude::*;
+use std::time::{Duration, Instant};
 
 use serial::prelude::*;
 
@@ -39,6 +45,35 @@ pub struct SerialPortType {
 	/** Mock a specific numbered condition.
 	*/
 	pub mock_num: u64,
+
+	/// Read back every setting after it's written, and return an error
+	/// if the device reports a different value than the one requested.
+	pub verify: bool,
+
+	/// How many times to upload an arbitrary wave again if it doesn't
+	/// read back the same when verifying.
+	pub wave_retries: u32,
+
+	/// The load connected to each channel's output, which the amplitude
+	/// setters and getters take into account. Defaults to High-Z.
+	pub load: [Load; 2],
+
+	/// The latency, byte counts, and outcome of every command sent
+	/// through this port, for diagnosing a slow or unreliable link.
+	pub stats: Stats,
+
+	/// The arbitrary waves written to each slot when mocking, so they
+	/// can be read back. Slots which haven't been written read back as
+	/// a flat line in the middle of the range.
+	pub mock_waves: HashMap<u8, WaveData>,
+
+	/// How many times each arbitrary wave slot has been written to when
+	/// mocking, for the conditions which only fail the first upload.
+	pub mock_uploads: HashMap<u8, u32>,
+
+	/// Called with the progress of each arbitrary wave read or write,
+	/// as the samples are sent or decoded.
+	pub progress: Option<ProgressCallback>,
 }
 
 impl SerialPortType {
@@ -58,6 +93,13 @@ impl SerialPortType {
 					port: None,
 					mock,
 					mock_num,
+					verify: false,
+					wave_retries: SET_ARBITRARY_WAVE_RETRIES,
+					load: [Load::HighZ, Load::HighZ],
+					stats: Stats::default(),
+					mock_waves: HashMap::new(),
+					mock_uploads: HashMap::new(),
+					progress: None,
 				}
 			)
 
@@ -69,10 +111,43 @@ impl SerialPortType {
 					port: Some(port),
 					mock,
 					mock_num,
+					verify: false,
+					wave_retries: SET_ARBITRARY_WAVE_RETRIES,
+					load: [Load::HighZ, Load::HighZ],
+					stats: Stats::default(),
+					mock_waves: HashMap::new(),
+					mock_uploads: HashMap::new(),
+					progress: None,
 				}
 			)
 		}
 	}
+
+	/// Send a command to the device and read its response into `outbuf`,
+	/// returning the number of bytes read. The transaction is recorded in
+	/// `stats`, under the command's code.
+	///
+	/// When mocking, nothing is sent, and the transaction is recorded as if
+	/// `outbuf` was filled in right away.
+	pub fn transact(&mut self, inbuf: &[u8], outbuf: &mut [u8]) -> io::Result<usize> {
+		let start = Instant::now();
+
+		let (res, outcome) = match self.port.as_mut() {
+			Some(port) if !self.mock => {
+				let res = port.write_all(inbuf).and_then(|_| port.read(outbuf));
+				let outcome = Outcome::from_result(&res, outbuf);
+
+				(res, outcome)
+			},
+			_ => (Ok(outbuf.len()), Outcome::Ok),
+		};
+
+		let received = *res.as_ref().unwrap_or(&0);
+
+		self.stats.record(&command_key(inbuf), start.elapsed(), inbuf.len(), received, outcome);
+
+		res
+	}
 }
 
 /** Open a serial communication link with the device,
@@ -0,0 +1,436 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Waveforms from circuit simulators, so simulated signals can be
+played by the device.
+
+ngspice raw files, written with its `write` command or the `-r` option,
+list the variables, and then the values at each point, as text after a
+`Values:` line, with the point's number before its first value:
+```text
+Title: * rc filter
+Date: Sat Oct 17 12:00:00  2026
+Plotname: Transient Analysis
+Flags: real
+No. Variables: 3
+No. Points: 1001
+Variables:
+    0    time    time
+    1    v(out)    voltage
+    2    i(v1)    current
+Values:
+ 0    0.000000000000000e+00
+    0.000000000000000e+00
+    -1.000000000000000e-03
+```
+or as 64-bit floats, least significant byte first, after a `Binary:`
+line instead.
+
+LTspice's text exports, from File → Export data as text in its
+waveform viewer, have a column for each trace, separated by tabs:
+```text
+time    V(out)    I(V1)
+0.000000000000000e+000    0.000000e+000    -1.000000e-003
+```
+
+Only transient analyses are read, since they're the ones over time. If
+a raw file has more than one plot, the first transient analysis in it
+is used, and if an LTspice export has stepped runs, the first run.
+
+Simulators take time steps of different lengths, so the points are
+interpolated to evenly spaced times, as close together as the shortest
+step, before a time window or a single period is picked out.
+*/
+
+use crate::resample::*;
+use crate::scope::trigger_period;
+
+use byteorder::{ByteOrder, LittleEndian};
+use clap::{Error, ErrorKind};
+use std::fmt;
+use std::str;
+
+/** The most evenly spaced samples a simulation is interpolated to. */
+pub const EVEN_SAMPLES_MAX: usize = 1 << 20;
+
+/** Which simulator's format the output is in. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpiceFormat {
+	/// An ngspice raw file, with the values as text or binary.
+	NgspiceRaw,
+
+	/// An LTspice text export.
+	LtspiceText,
+}
+
+impl fmt::Display for SpiceFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SpiceFormat::NgspiceRaw => write!(f, "ngspice raw"),
+			SpiceFormat::LtspiceText => write!(f, "LTspice text"),
+		}
+	}
+}
+
+impl SpiceFormat {
+	/// Which format a file is in, or `None` if it isn't a simulator's
+	/// output.
+	pub fn detect(data: &[u8]) -> Option<SpiceFormat> {
+		if data.starts_with(b"Title:") {
+			return Some(SpiceFormat::NgspiceRaw);
+		}
+
+		let first = data.split(|b| *b == b'\n').next()?;
+		let cells: Vec<&str> = str::from_utf8(first).ok()?.split('\t').map(str::trim).collect();
+
+		if cells.len() >= 2 && cells[0].eq_ignore_ascii_case("time") {
+			Some(SpiceFormat::LtspiceText)
+		} else {
+			None
+		}
+	}
+}
+
+/** A transient analysis from a circuit simulator. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpiceOutput {
+	/// Which format it was in.
+	pub format: SpiceFormat,
+
+	/// The name of each trace, such as `v(out)`.
+	pub traces: Vec<String>,
+
+	/// The time of each point, in seconds.
+	pub time: Vec<f64>,
+
+	/// The values of each trace at each point, in volts or amps.
+	pub values: Vec<Vec<f64>>,
+}
+
+impl SpiceOutput {
+	/// The number of points in each trace.
+	pub fn len(&self) -> usize {
+		self.time.len()
+	}
+
+	/// Whether there are no points.
+	pub fn is_empty(&self) -> bool {
+		self.time.is_empty()
+	}
+
+	/// How long the simulation ran, in seconds.
+	pub fn duration(&self) -> f64 {
+		match (self.time.first(), self.time.last()) {
+			(Some(first), Some(last)) => last - first,
+			_ => 0.0,
+		}
+	}
+
+	/// The shortest time step, in seconds, or `None` if the times
+	/// don't go forwards.
+	pub fn shortest_step(&self) -> Option<f64> {
+		self.time
+			.windows(2)
+			.map(|pair| pair[1] - pair[0])
+			.filter(|step| *step > 0.0)
+			.fold(None, |shortest: Option<f64>, step| Some(shortest.map_or(step, |shortest| shortest.min(step))))
+	}
+
+	/// The values of one trace, counting from 1. Trace 0 gives the
+	/// average of all the traces.
+	pub fn trace(&self, trace: u16) -> Result<Vec<f64>, clap::Error> {
+		if trace as usize > self.values.len() {
+			return Err(Error::with_description(
+				&format!(
+					"unsupported trace number for circuit simulator output (must be 0-{}, where 0 mixes all traces together): {}",
+					self.values.len(),
+					trace
+				),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		if trace == 0 {
+			Ok((0..self.len())
+				.map(|i| self.values.iter().map(|column| column[i]).sum::<f64>() / self.values.len() as f64)
+				.collect())
+		} else {
+			Ok(self.values[trace as usize - 1].clone())
+		}
+	}
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid circuit simulator output: {}", msg), ErrorKind::InvalidValue)
+}
+
+fn number(cell: &str) -> Result<f64, clap::Error> {
+	cell.trim()
+		.parse::<f64>()
+		.map_err(|_e| invalid(&format!("not a number (only transient analyses can be read): {}", cell)))
+}
+
+/** The next line of a raw file from "pos", without its line ending,
+and moves "pos" past it. */
+fn next_line(data: &[u8], pos: &mut usize) -> Option<String> {
+	let rest = data.get(*pos..).filter(|rest| !rest.is_empty())?;
+	let len = rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+
+	*pos += (len + 1).min(rest.len());
+
+	Some(String::from_utf8_lossy(&rest[..len]).trim_end_matches('\r').to_string())
+}
+
+/** Read one plot of a raw file, starting at "pos", and move "pos" past
+it. Gives `None` if it isn't a transient analysis. */
+fn raw_plot(data: &[u8], pos: &mut usize) -> Result<Option<SpiceOutput>, clap::Error> {
+	let mut complex = false;
+	let mut count = None;
+	let mut points = None;
+	let mut variables: Vec<(String, String)> = Vec::new();
+
+	let binary = loop {
+		let line = next_line(data, pos).ok_or_else(|| invalid("the header ended before the values"))?;
+		let (key, value) = line.split_once(':').unwrap_or((&line, ""));
+
+		let size = || {
+			value
+				.trim()
+				.parse::<usize>()
+				.map_err(|_e| invalid(&format!("not a number in the header: {}", line)))
+		};
+
+		match key.trim().to_lowercase().as_str() {
+			"flags" => complex = value.to_lowercase().contains("complex"),
+			"no. variables" => count = Some(size()?),
+			"no. points" => points = Some(size()?),
+
+			"variables" => {
+				let count = count.ok_or_else(|| invalid("the number of variables is missing"))?;
+
+				for _ in 0..count {
+					let line = next_line(data, pos).ok_or_else(|| invalid("the header ended before the variables"))?;
+					let cells: Vec<&str> = line.split_whitespace().collect();
+
+					if cells.len() < 3 {
+						return Err(invalid(&format!("a variable needs a number, name, and type: {}", line)));
+					}
+
+					variables.push((cells[1].to_string(), cells[2].to_lowercase()));
+				}
+			}
+
+			"values" => break false,
+			"binary" => break true,
+			_ => {}
+		}
+	};
+
+	let count = variables.len();
+	let points = points.ok_or_else(|| invalid("the number of points is missing"))?;
+	let width = if complex { 2 } else { 1 };
+
+	// The header can't be trusted to fit, so the values it claims are
+	// checked against the rest of the file before being read.
+	let too_many = || invalid(&format!("there are more points in the header than there could be in the file: {}", points));
+	let values = points.checked_mul(count).and_then(|values| values.checked_mul(width)).ok_or_else(too_many)?;
+
+	let numbers: Vec<f64> = if binary {
+		let size = values.checked_mul(8).ok_or_else(too_many)?;
+
+		if size > data.len() - *pos {
+			return Err(invalid(&format!("it ended before all {} points", points)));
+		}
+
+		let bytes = &data[*pos..*pos + size];
+
+		*pos += size;
+
+		bytes.chunks_exact(8).map(LittleEndian::read_f64).collect()
+	} else {
+		let total = points.checked_mul(count + 1).ok_or_else(too_many)?;
+		let mut numbers = Vec::new();
+		let mut cells = 0;
+
+		// Each point has its number, and then a value for each variable.
+		while cells < total {
+			let line = next_line(data, pos).ok_or_else(|| invalid(&format!("it ended before all {} points", points)))?;
+
+			for cell in line.split_whitespace() {
+				if cells % (count + 1) != 0 {
+					for part in cell.split(',') {
+						numbers.push(number(part)?);
+					}
+				}
+
+				cells += 1;
+			}
+		}
+
+		numbers
+	};
+
+	let transient = variables.first().is_some_and(|(_name, kind)| kind == "time");
+
+	if complex || !transient || count < 2 {
+		return Ok(None);
+	}
+
+	if numbers.len() != values {
+		return Err(invalid(&format!("there are {} values, instead of {}", numbers.len(), values)));
+	}
+
+	Ok(Some(SpiceOutput {
+		format: SpiceFormat::NgspiceRaw,
+		traces: variables[1..].iter().map(|(name, _kind)| name.clone()).collect(),
+		time: numbers.iter().step_by(count).copied().collect(),
+		values: (1..count).map(|v| numbers.iter().skip(v).step_by(count).copied().collect()).collect(),
+	}))
+}
+
+/** Read the first transient analysis in an ngspice raw file. */
+fn parse_raw(data: &[u8]) -> Result<SpiceOutput, clap::Error> {
+	let mut pos = 0;
+
+	while data[pos..].iter().any(|b| !b.is_ascii_whitespace()) {
+		if let Some(output) = raw_plot(data, &mut pos)? {
+			return Ok(output);
+		}
+	}
+
+	Err(invalid("there's no transient analysis in it"))
+}
+
+/** Read the first run in an LTspice text export. */
+fn parse_ltspice(text: &str) -> Result<SpiceOutput, clap::Error> {
+	let mut lines = text.lines().enumerate();
+	let header = lines.next().map_or("", |(_i, line)| line);
+
+	let traces: Vec<String> = header.split('\t').skip(1).map(|cell| cell.trim().to_string()).collect();
+	let mut time = Vec::new();
+	let mut values = vec![Vec::new(); traces.len()];
+
+	for (i, line) in lines {
+		if line.trim().is_empty() {
+			continue;
+		}
+
+		if line.starts_with("Step Information") {
+			if time.is_empty() {
+				continue;
+			}
+
+			break;
+		}
+
+		let cells: Vec<&str> = line.split('\t').collect();
+
+		if cells.len() != traces.len() + 1 {
+			return Err(invalid(&format!("line {}: there are {} columns, instead of {}", i + 1, cells.len(), traces.len() + 1)));
+		}
+
+		time.push(number(cells[0])?);
+
+		for (column, cell) in values.iter_mut().zip(&cells[1..]) {
+			column.push(number(cell)?);
+		}
+	}
+
+	Ok(SpiceOutput {
+		format: SpiceFormat::LtspiceText,
+		traces,
+		time,
+		values,
+	})
+}
+
+/** Read the output of a circuit simulator, in any of the formats. */
+pub fn parse_spice(data: &[u8]) -> Result<SpiceOutput, clap::Error> {
+	let output = match SpiceFormat::detect(data) {
+		Some(SpiceFormat::NgspiceRaw) => parse_raw(data)?,
+		Some(SpiceFormat::LtspiceText) => parse_ltspice(&String::from_utf8_lossy(data))?,
+		None => return Err(invalid("it isn't an ngspice raw file or an LTspice text export")),
+	};
+
+	if output.len() < 2 {
+		return Err(invalid(&format!("there are {} points, and at least 2 are needed", output.len())));
+	}
+
+	if output.time.iter().any(|t| !t.is_finite()) || output.time.windows(2).any(|pair| pair[1] < pair[0]) || output.duration() <= 0.0 {
+		return Err(invalid("the times don't go forwards"));
+	}
+
+	Ok(output)
+}
+
+/** Interpolate "samples", taken at "time", which can be unevenly
+spaced, to "len" evenly spaced times from the first time to the last. */
+pub fn even_samples(time: &[f64], samples: &[f64], len: usize) -> Vec<f64> {
+	if time.len() < 2 || len < 2 {
+		return samples.iter().copied().cycle().take(len).collect();
+	}
+
+	let (first, last) = (time[0], time[time.len() - 1]);
+	let mut j = 0;
+
+	(0..len)
+		.map(|i| {
+			let t = first + (last - first) * i as f64 / (len - 1) as f64;
+
+			while j + 2 < time.len() && time[j + 1] < t {
+				j += 1;
+			}
+
+			let (t0, t1) = (time[j], time[j + 1]);
+
+			if t1 > t0 {
+				samples[j] + (samples[j + 1] - samples[j]) * ((t - t0) / (t1 - t0)).clamp(0.0, 1.0)
+			} else {
+				samples[j + 1]
+			}
+		})
+		.collect()
+}
+
+/** The samples of one trace, counting from 1, or 0 to mix all the
+traces together, interpolated to even times, and resampled to "len"
+samples. The trace is first interpolated to as many evenly spaced
+samples as fit the shortest time step, up to
+[EVEN_SAMPLES_MAX](constant.EVEN_SAMPLES_MAX.html).
+
+The whole simulation is used, unless a time window or a single period
+is chosen in "opts", and the interpolation is linear unless another
+one is chosen.
+*/
+pub fn spice_samples(output: &SpiceOutput, trace: u16, opts: &ResampleOptions, len: usize) -> Result<Vec<f64>, clap::Error> {
+	let steps = output.shortest_step().map_or(0.0, |step| (output.duration() / step).round());
+	let count = (steps as usize + 1).max(output.len()).max(len).min(EVEN_SAMPLES_MAX);
+	let samples = even_samples(&output.time, &output.trace(trace)?, count);
+	let sample_rate = (count - 1) as f64 / output.duration();
+
+	let (start, end) = match &opts.window {
+		Some(window) => window.range(samples.len(), Some(sample_rate))?,
+		None => (0, samples.len()),
+	};
+
+	let mut samples = &samples[start..end];
+
+	if opts.single_period {
+		let (start, period) = trigger_period(samples, None).ok_or_else(|| {
+			Error::with_description(
+				"failed detecting a period in the wave: it doesn't seem to repeat",
+				ErrorKind::InvalidValue,
+			)
+		})?;
+
+		samples = &samples[start..start + period];
+	}
+
+	Ok(resample(samples, len, opts.interpolation.unwrap_or(Interpolation::Linear)))
+}
@@ -0,0 +1,257 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::command::*;
+use super::convert::*;
+use super::resample::*;
+use super::spice::*;
+
+use std::f64::consts::PI;
+use std::fs;
+
+/// 4 ms of a 1 kHz sine, with uneven time steps, as a simulator takes
+/// them: shorter ones near the peaks.
+fn times() -> Vec<f64> {
+	let mut time = vec![0.0];
+
+	while *time.last().unwrap() < 0.004 {
+		let t = *time.last().unwrap();
+		let step = if (2.0 * PI * t / 0.001).sin().abs() > 0.9 { 1e-6 } else { 4e-6 };
+
+		time.push(t + step);
+	}
+
+	time
+}
+
+fn ascii_raw() -> String {
+	let time = times();
+
+	// An operating point analysis first, which isn't over time.
+	let mut raw = "Title: * rc filter\nDate: Sat Oct 17 12:00:00  2026\nPlotname: Operating Point\nFlags: real\nNo. Variables: 1\nNo. Points: 1\nVariables:\n\t0\tv(out)\tvoltage\nValues:\n 0\t0.000000000000000e+00\n\n".to_string();
+
+	raw.push_str(&format!(
+		"Title: * rc filter\nDate: Sat Oct 17 12:00:00  2026\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 3\nNo. Points: {}\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\n\t2\ti(v1)\tcurrent\nValues:\n",
+		time.len()
+	));
+
+	for (i, t) in time.iter().enumerate() {
+		raw.push_str(&format!(" {}\t{:.15e}\n\t{:.15e}\n\t{:.15e}\n\n", i, t, (2.0 * PI * t / 0.001).sin(), -0.001));
+	}
+
+	raw
+}
+
+fn binary_raw() -> Vec<u8> {
+	let mut raw = b"Title: * rc filter\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 2\nNo. Points: 3\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\nBinary:\n".to_vec();
+
+	for value in &[0.0f64, 1.0, 1e-3, 2.0, 2e-3, -1.0] {
+		raw.extend_from_slice(&value.to_le_bytes());
+	}
+
+	raw
+}
+
+fn ltspice() -> String {
+	let mut text = "time\tV(out)\tI(R1)\n".to_string();
+
+	for t in times() {
+		text.push_str(&format!("{:.15e}\t{:.6e}\t{:.6e}\n", t, 2.5 + (2.0 * PI * t / 0.001).sin(), 0.0));
+	}
+
+	text
+}
+
+#[test]
+pub fn spice_format_ok() {
+	assert_eq!(SpiceFormat::detect(ascii_raw().as_bytes()), Some(SpiceFormat::NgspiceRaw));
+	assert_eq!(SpiceFormat::detect(&binary_raw()), Some(SpiceFormat::NgspiceRaw));
+	assert_eq!(SpiceFormat::detect(ltspice().as_bytes()), Some(SpiceFormat::LtspiceText));
+	assert_eq!(SpiceFormat::detect(b"Time\tV(out)\r\n0\t1\r\n"), Some(SpiceFormat::LtspiceText));
+}
+
+#[test]
+pub fn spice_format_err() {
+	assert_eq!(SpiceFormat::detect(b"Freq.\tV(out)\n1\t(0dB,0\xc2\xb0)\n"), None);
+	assert_eq!(SpiceFormat::detect(b"Time(s),CH1(V)\n0,1\n"), None);
+	assert_eq!(SpiceFormat::detect(b"2048,2048,2048"), None);
+	assert_eq!(SpiceFormat::detect(b""), None);
+}
+
+#[test]
+pub fn parse_spice_ok() {
+	// The transient analysis after the operating point.
+	let res = parse_spice(ascii_raw().as_bytes()).unwrap();
+
+	assert_eq!(res.format, SpiceFormat::NgspiceRaw);
+	assert_eq!(res.traces, vec!["v(out)", "i(v1)"]);
+	assert_eq!(res.len(), times().len());
+	assert!((res.duration() - 0.004).abs() < 1e-5);
+	assert!((res.shortest_step().unwrap() - 1e-6).abs() < 1e-12);
+	assert_eq!(res.values[1][0], -0.001);
+	assert!((res.trace(0).unwrap()[0] + 0.0005).abs() < 1e-12);
+
+	// Binary values.
+	let res = parse_spice(&binary_raw()).unwrap();
+
+	assert_eq!(res.traces, vec!["v(out)"]);
+	assert_eq!(res.time, vec![0.0, 1e-3, 2e-3]);
+	assert_eq!(res.values, vec![vec![1.0, 2.0, -1.0]]);
+
+	// LTspice, with only the first of its stepped runs.
+	let text = "time\tV(out)\nStep Information: R=1K  (Run: 1/2)\n0\t1\n1e-3\t2\nStep Information: R=2K  (Run: 2/2)\n0\t3\n1e-3\t4\n";
+	let res = parse_spice(text.as_bytes()).unwrap();
+
+	assert_eq!(res.format, SpiceFormat::LtspiceText);
+	assert_eq!(res.traces, vec!["V(out)"]);
+	assert_eq!(res.time, vec![0.0, 1e-3]);
+	assert_eq!(res.values, vec![vec![1.0, 2.0]]);
+}
+
+#[test]
+pub fn parse_spice_err() {
+	// Not a simulator's output.
+	parse_spice(b"a,b\n1,2\n").unwrap_err();
+
+	// Only an AC analysis.
+	parse_spice(b"Title: ac\nPlotname: AC Analysis\nFlags: complex\nNo. Variables: 2\nNo. Points: 1\nVariables:\n\t0\tfrequency\tfrequency\n\t1\tv(out)\tvoltage\nValues:\n 0\t1.0,0.0\n\t0.5,0.1\n").unwrap_err();
+
+	// Fewer points than the header says.
+	let raw = binary_raw();
+	parse_spice(&raw[..raw.len() - 8]).unwrap_err();
+
+	// More points than could fit in the file, or in memory.
+	parse_spice(b"Title: t\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 2\nNo. Points: 18446744073709551615\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\nBinary:\n\0\0\0\0\0\0\0\0").unwrap_err();
+	parse_spice(b"Title: t\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 2\nNo. Points: 1000000000000\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\nBinary:\n\0\0\0\0\0\0\0\0").unwrap_err();
+	parse_spice(b"Title: t\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 2\nNo. Points: 18446744073709551615\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\nValues:\n 0\t0\n\t1\n").unwrap_err();
+	parse_spice(b"Title: t\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 2\nNo. Points: 99999999999999\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\nValues:\n 0\t0\n\t1\n").unwrap_err();
+
+	// Not a number.
+	parse_spice(b"time\tV(out)\n0\t1\n1e-3\tx\n").unwrap_err();
+
+	// A missing column.
+	parse_spice(b"time\tV(out)\n0\t1\n1e-3\n").unwrap_err();
+
+	// Times going backwards, and only one point.
+	parse_spice(b"time\tV(out)\n1e-3\t1\n0\t2\n").unwrap_err();
+	parse_spice(b"time\tV(out)\n0\t1\n").unwrap_err();
+
+	// A trace which isn't there.
+	parse_spice(&binary_raw()).unwrap().trace(2).unwrap_err();
+}
+
+#[test]
+pub fn even_samples_ok() {
+	let res = even_samples(&[0.0, 1.0, 1.5, 4.0], &[0.0, 1.0, 2.0, 8.0], 5);
+
+	assert_eq!(res, vec![0.0, 1.0, 3.2, 5.6, 8.0]);
+}
+
+#[test]
+pub fn spice_samples_ok() {
+	let output = parse_spice(ltspice().as_bytes()).unwrap();
+
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	// One period, rising through the middle.
+	let res = spice_samples(&output, 1, &opts, 2048).unwrap();
+
+	assert_eq!(res.len(), 2048);
+	assert!((res[0] - 2.5).abs() < 0.01);
+	assert!((res[512] - 3.5).abs() < 0.01);
+	assert!((res[1536] - 1.5).abs() < 0.01);
+
+	// The first millisecond.
+	let opts = ResampleOptions {
+		window: Some(TimeWindow::parse("0ms:1ms").unwrap()),
+		..ResampleOptions::default()
+	};
+
+	let res = spice_samples(&output, 1, &opts, 2048).unwrap();
+
+	assert!((res[0] - 2.5).abs() < 0.01);
+	assert!((res[512] - 3.5).abs() < 0.01);
+}
+
+#[test]
+pub fn spice_samples_err() {
+	let output = parse_spice(ltspice().as_bytes()).unwrap();
+
+	// A flat trace doesn't repeat.
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	spice_samples(&output, 2, &opts, 2048).unwrap_err();
+	spice_samples(&output, 3, &ResampleOptions::default(), 2048).unwrap_err();
+
+	// A window past the end of the simulation.
+	let opts = ResampleOptions {
+		window: Some(TimeWindow::parse("0ms:5ms").unwrap()),
+		..ResampleOptions::default()
+	};
+
+	spice_samples(&output, 1, &opts, 2048).unwrap_err();
+}
+
+#[test]
+pub fn load_spice_output_ok() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-spice-test-{}.txt", std::process::id()));
+
+	let opts = ResampleOptions {
+		single_period: true,
+		..ResampleOptions::default()
+	};
+
+	// An LTspice text export, which ends in .txt like the device's files.
+	fs::write(&path, ltspice()).unwrap();
+
+	let res = load_wave_file(path.to_str().unwrap(), 1, &opts, &ConvertOptions::default(), 0).unwrap();
+	let samples = res.samples();
+
+	assert!((samples[0] as i32 - 2048).abs() <= 16);
+	assert!(samples[512] >= 4090);
+	assert!(samples[1536] <= 5);
+
+	// An ngspice raw file.
+	let path = path.with_extension("raw");
+
+	fs::write(&path, ascii_raw()).unwrap();
+
+	let (res, report) = wav_to_wave_data(&fs::read(&path).unwrap(), 1, &opts, &ConvertOptions::default(), 0).unwrap();
+
+	assert_eq!(res, load_wave_file(path.to_str().unwrap(), 1, &opts, &ConvertOptions::default(), 0).unwrap());
+	assert_eq!(report.clipped(), 0);
+
+	fs::remove_file(&path).unwrap();
+	fs::remove_file(path.with_extension("txt")).unwrap();
+}
+
+#[test]
+pub fn load_spice_output_err() {
+	let path = std::env::temp_dir().join(format!("signal-gen-cjds66-spice-test-err-{}.raw", std::process::id()));
+
+	// A trace which isn't there.
+	fs::write(&path, ascii_raw()).unwrap();
+
+	load_wave_file(path.to_str().unwrap(), 3, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	// A raw file which isn't ngspice's.
+	fs::write(&path, [0u8; 64]).unwrap();
+
+	load_wave_file(path.to_str().unwrap(), 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,317 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Telemetry for the commands sent to the device.
+
+Every transaction with the device is recorded in the `stats` field of
+the `SerialPortType` it was sent through, keyed by the command's code
+(for example `"w23"` for setting channel 1's frequency, or `"r23"` for
+reading it). For each command the number of transactions, their
+outcomes, the bytes sent and received, any retries, and a histogram
+of latencies are kept, so slow or failing commands can be found.
+*/
+
+use crate::protocol::*;
+use crate::register::*;
+
+use std::collections::BTreeMap;
+use std::io;
+use std::time::Duration;
+
+/** The upper bounds of the latency histogram's buckets, in milliseconds.
+Anything slower goes in one extra bucket at the end. */
+pub const LATENCY_BUCKETS_MS: [u64; 12] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000, 2000, 5000];
+
+/** How a transaction with the device ended. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+	/// A full response was received.
+	Ok,
+
+	/// A response was received, but it was shorter than expected, or
+	/// its end was never found.
+	ShortRead,
+
+	/// The device didn't respond before the serial timeout.
+	Timeout,
+
+	/// Writing or reading failed for some other reason.
+	Error,
+}
+
+impl Outcome {
+	/// Classify the result of reading a response into `outbuf`. Every
+	/// response ends with `COMMAND_LINEBREAK`, so one without it was cut off.
+	pub fn from_result(res: &io::Result<usize>, outbuf: &[u8]) -> Outcome {
+		match res {
+			Ok(len) if !outbuf[..(*len).min(outbuf.len())].ends_with(COMMAND_LINEBREAK.as_bytes()) => Outcome::ShortRead,
+			Ok(_) => Outcome::Ok,
+			Err(e) => Outcome::from_error(e),
+		}
+	}
+
+	/// Classify an error from writing or reading.
+	pub fn from_error(e: &io::Error) -> Outcome {
+		if e.kind() == io::ErrorKind::TimedOut {
+			Outcome::Timeout
+		} else {
+			Outcome::Error
+		}
+	}
+}
+
+/** The figures recorded for one command. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandStats {
+	/// How many transactions were sent.
+	pub count: u64,
+
+	/// How many got a full response.
+	pub ok: u64,
+
+	/// How many got a shorter response than expected.
+	pub short_reads: u64,
+
+	/// How many timed out.
+	pub timeouts: u64,
+
+	/// How many failed some other way.
+	pub errors: u64,
+
+	/// How many times the command was retried.
+	pub retries: u64,
+
+	/// The total number of bytes written to the device.
+	pub bytes_sent: u64,
+
+	/// The total number of bytes read from the device.
+	pub bytes_received: u64,
+
+	/// The sum of all the latencies.
+	pub total_latency: Duration,
+
+	/// The fastest transaction.
+	pub min_latency: Option<Duration>,
+
+	/// The slowest transaction.
+	pub max_latency: Duration,
+
+	/// The number of transactions in each bucket of `LATENCY_BUCKETS_MS`,
+	/// plus one bucket for anything slower.
+	pub histogram: [u64; 13],
+}
+
+impl CommandStats {
+	/// Add one transaction.
+	pub fn record(&mut self, latency: Duration, sent: usize, received: usize, outcome: Outcome) {
+		self.count += 1;
+		self.bytes_sent += sent as u64;
+		self.bytes_received += received as u64;
+
+		match outcome {
+			Outcome::Ok => self.ok += 1,
+			Outcome::ShortRead => self.short_reads += 1,
+			Outcome::Timeout => self.timeouts += 1,
+			Outcome::Error => self.errors += 1,
+		}
+
+		self.total_latency += latency;
+		self.max_latency = self.max_latency.max(latency);
+		self.min_latency = Some(self.min_latency.map_or(latency, |min| min.min(latency)));
+
+		let ms = latency.as_secs_f64() * 1000.0;
+		let bucket = LATENCY_BUCKETS_MS
+			.iter()
+			.position(|&bound| ms <= bound as f64)
+			.unwrap_or(LATENCY_BUCKETS_MS.len());
+
+		self.histogram[bucket] += 1;
+	}
+
+	/// Add another command's figures to these ones.
+	pub fn merge(&mut self, other: &CommandStats) {
+		self.count += other.count;
+		self.ok += other.ok;
+		self.short_reads += other.short_reads;
+		self.timeouts += other.timeouts;
+		self.errors += other.errors;
+		self.retries += other.retries;
+		self.bytes_sent += other.bytes_sent;
+		self.bytes_received += other.bytes_received;
+		self.total_latency += other.total_latency;
+		self.max_latency = self.max_latency.max(other.max_latency);
+
+		self.min_latency = match (self.min_latency, other.min_latency) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		};
+
+		for (a, b) in self.histogram.iter_mut().zip(other.histogram.iter()) {
+			*a += b;
+		}
+	}
+
+	/// The mean latency, or zero if nothing was recorded.
+	pub fn mean_latency(&self) -> Duration {
+		if self.count == 0 {
+			return Duration::from_secs(0);
+		}
+
+		self.total_latency / self.count as u32
+	}
+
+	/// An upper bound for the given percentile (0-100) of the latencies,
+	/// taken from the histogram. `None` means it's slower than the last
+	/// bucket, or that nothing was recorded.
+	pub fn percentile_ms(&self, percentile: f64) -> Option<u64> {
+		if self.count == 0 {
+			return None;
+		}
+
+		let wanted = (self.count as f64 * percentile / 100.0).ceil().max(1.0) as u64;
+		let mut seen = 0;
+
+		for (i, n) in self.histogram.iter().enumerate() {
+			seen += n;
+
+			if seen >= wanted {
+				return LATENCY_BUCKETS_MS.get(i).copied();
+			}
+		}
+
+		None
+	}
+}
+
+/** All the figures recorded for a device, by command. */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+	/// The figures for each command, keyed by its code.
+	pub commands: BTreeMap<String, CommandStats>,
+}
+
+impl Stats {
+	/// Record one transaction for a command.
+	pub fn record(&mut self, command: &str, latency: Duration, sent: usize, received: usize, outcome: Outcome) {
+		self.commands
+			.entry(command.to_string())
+			.or_default()
+			.record(latency, sent, received, outcome);
+	}
+
+	/// Record that a command is being retried.
+	pub fn record_retry(&mut self, command: &str) {
+		self.commands.entry(command.to_string()).or_default().retries += 1;
+	}
+
+	/// The figures for every command added together.
+	pub fn totals(&self) -> CommandStats {
+		let mut totals = CommandStats::default();
+
+		for stats in self.commands.values() {
+			totals.merge(stats);
+		}
+
+		totals
+	}
+
+	/// A text table of the figures for each command, followed by the totals.
+	pub fn report(&self) -> String {
+		let mut out = format!(
+			"{:<8} {:<26} {:>6} {:>6} {:>6} {:>6} {:>6} {:>7} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}\n",
+			"command", "name", "count", "ok", "short", "tmout", "error", "retries", "sent", "received", "mean ms", "p95 ms", "max ms", "min ms"
+		);
+
+		let row = |key: &str, name: &str, s: &CommandStats| -> String {
+			let ms = |latency: Duration| format!("{:.1}", latency.as_secs_f64() * 1000.0);
+
+			// A command which was only retried has no latencies.
+			let (mean, p95, max, min) = if s.count == 0 {
+				("-".to_string(), "-".to_string(), "-".to_string(), "-".to_string())
+			} else {
+				(
+					ms(s.mean_latency()),
+					s.percentile_ms(95.0).map_or_else(|| format!(">{}", LATENCY_BUCKETS_MS[LATENCY_BUCKETS_MS.len() - 1]), |ms| format!("<={}", ms)),
+					ms(s.max_latency),
+					ms(s.min_latency.unwrap_or_default()),
+				)
+			};
+
+			format!(
+				"{:<8} {:<26} {:>6} {:>6} {:>6} {:>6} {:>6} {:>7} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}\n",
+				key,
+				name,
+				s.count,
+				s.ok,
+				s.short_reads,
+				s.timeouts,
+				s.errors,
+				s.retries,
+				s.bytes_sent,
+				s.bytes_received,
+				mean,
+				p95,
+				max,
+				min,
+			)
+		};
+
+		for (key, stats) in self.commands.iter() {
+			out.push_str(&row(key, &command_name(key), stats));
+		}
+
+		out.push_str(&row("total", "", &self.totals()));
+
+		out
+	}
+}
+
+/** The key a command is recorded under: its code, which is everything
+between the start of the command and the `=`, such as `"w23"`. */
+pub fn command_key(command: &[u8]) -> String {
+	let command = String::from_utf8_lossy(command);
+	let command = command.trim_start_matches(COMMAND_BEGIN);
+
+	command
+		.split(['=', '\r', '\n'])
+		.next()
+		.unwrap_or_default()
+		.chars()
+		.take(8)
+		.collect()
+}
+
+/** A readable name for a command key, if it's a register in the
+register table. If several registers share the code, such as the
+frequency in each unit, the words their names have in common are used. */
+pub fn command_name(key: &str) -> String {
+	let names: Vec<&str> = REGISTERS
+		.iter()
+		.filter(|reg| {
+			let set = key.strip_prefix(COMMAND_SET).is_some_and(|code| reg.set_codes.contains(&code));
+			let get = key.strip_prefix(COMMAND_GET).is_some_and(|code| reg.get_codes.contains(&code));
+
+			set || get
+		})
+		.map(|reg| reg.name)
+		.collect();
+
+	let first = match names.first() {
+		Some(first) => first,
+		None => return String::new(),
+	};
+
+	first
+		.split(' ')
+		.enumerate()
+		.take_while(|(i, word)| names.iter().all(|name| name.split(' ').nth(*i) == Some(word)))
+		.map(|(_, word)| word)
+		.collect::<Vec<&str>>()
+		.join(" ")
+}
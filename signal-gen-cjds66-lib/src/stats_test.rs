@@ -0,0 +1,125 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::serial::*;
+use super::stats::*;
+
+use std::io;
+use std::time::Duration;
+
+#[test]
+pub fn record_ok() {
+	let mut stats = Stats::default();
+
+	stats.record("w23", Duration::from_millis(3), 12, 6, Outcome::Ok);
+	stats.record("w23", Duration::from_millis(40), 12, 2, Outcome::ShortRead);
+	stats.record("r23", Duration::from_millis(7000), 9, 0, Outcome::Timeout);
+	stats.record_retry("r23");
+
+	let w23 = &stats.commands["w23"];
+
+	assert_eq!(w23.count, 2);
+	assert_eq!(w23.ok, 1);
+	assert_eq!(w23.short_reads, 1);
+	assert_eq!(w23.bytes_sent, 24);
+	assert_eq!(w23.bytes_received, 8);
+	assert_eq!(w23.min_latency, Some(Duration::from_millis(3)));
+	assert_eq!(w23.max_latency, Duration::from_millis(40));
+	assert_eq!(w23.mean_latency(), Duration::from_micros(21500));
+	assert_eq!(w23.histogram[2], 1);
+	assert_eq!(w23.histogram[5], 1);
+	assert_eq!(w23.percentile_ms(50.0), Some(5));
+	assert_eq!(w23.percentile_ms(95.0), Some(50));
+
+	let r23 = &stats.commands["r23"];
+
+	assert_eq!(r23.timeouts, 1);
+	assert_eq!(r23.retries, 1);
+	assert_eq!(r23.percentile_ms(95.0), None);
+
+	let totals = stats.totals();
+
+	assert_eq!(totals.count, 3);
+	assert_eq!(totals.retries, 1);
+	assert_eq!(totals.bytes_sent, 33);
+	assert_eq!(totals.min_latency, Some(Duration::from_millis(3)));
+	assert_eq!(totals.max_latency, Duration::from_millis(7000));
+
+	let report = stats.report();
+
+	assert!(report.contains("w23"));
+	assert!(report.contains("total"));
+}
+
+#[test]
+pub fn record_retry_only_ok() {
+	let mut stats = Stats::default();
+
+	stats.record_retry("a01");
+
+	let a01 = &stats.commands["a01"];
+
+	assert_eq!(a01.count, 0);
+	assert_eq!(a01.retries, 1);
+	assert_eq!(a01.percentile_ms(95.0), None);
+
+	// The latency columns are blank, instead of looking slow.
+	let report = stats.report();
+	let line = report.lines().find(|line| line.starts_with("a01")).unwrap();
+
+	assert!(line.trim_end().ends_with("-         -         -         -"), "{}", line);
+	assert!(!report.contains(">5000"), "{}", report);
+}
+
+#[test]
+pub fn outcome_ok() {
+	let mut outbuf = [0u8; 32];
+
+	outbuf[..9].copy_from_slice(b":ok.\r\n\0\0\0");
+	assert_eq!(Outcome::from_result(&Ok(6), &outbuf), Outcome::Ok);
+
+	// Shorter than the buffer, but complete.
+	outbuf[..12].copy_from_slice(b":r23=500.\r\n\0");
+	assert_eq!(Outcome::from_result(&Ok(11), &outbuf), Outcome::Ok);
+
+	// Cut off before the line break.
+	assert_eq!(Outcome::from_result(&Ok(5), &outbuf), Outcome::ShortRead);
+	assert_eq!(Outcome::from_result(&Ok(0), &outbuf), Outcome::ShortRead);
+	assert_eq!(Outcome::from_result(&Err(io::Error::from(io::ErrorKind::TimedOut)), &outbuf), Outcome::Timeout);
+	assert_eq!(Outcome::from_result(&Err(io::Error::from(io::ErrorKind::BrokenPipe)), &outbuf), Outcome::Error);
+}
+
+#[test]
+pub fn command_key_ok() {
+	assert_eq!(command_key(b":w23=1000,0.\r\n"), "w23");
+	assert_eq!(command_key(b":r31=0.\r\n"), "r31");
+	assert_eq!(command_key(b":a01=1,2,3.\r\n"), "a01");
+	assert_eq!(command_name("w29"), "duty cycle");
+	assert_eq!(command_name("w23"), "frequency");
+	assert_eq!(command_name("w45"), "pulse width");
+	assert_eq!(command_name("x99"), "");
+}
+
+#[test]
+pub fn transact_ok() {
+	let mut port = SerialPortType::new("", true, 0).unwrap();
+	let mut outbuf = [0u8; 6];
+
+	assert_eq!(port.transact(b":w29=401.\r\n", &mut outbuf).unwrap(), 6);
+	assert_eq!(port.transact(b":w29=402.\r\n", &mut outbuf).unwrap(), 6);
+
+	let w29 = &port.stats.commands["w29"];
+
+	assert_eq!(w29.count, 2);
+	assert_eq!(w29.ok, 2);
+	assert_eq!(w29.bytes_sent, 22);
+	assert_eq!(w29.bytes_received, 12);
+}
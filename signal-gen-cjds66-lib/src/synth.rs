@@ -0,0 +1,414 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Making common arbitrary waves from a few parameters.
+
+A [WaveSpec](struct.WaveSpec.html) is parsed from a shape name followed
+by any of its parameters as `name=value` pairs, separated by spaces:
+```ignore
+let spec = WaveSpec::parse("gauss sigma=0.05")?;
+let (wave, report) = generate_wave(&spec, &ConvertOptions::default())?;
+```
+
+Times are fractions of one period of the wave, which is the whole
+arbitrary wave slot, so `center=0.5` is the middle of it, and
+frequencies are in cycles per period. The shapes are:
+
+* `sine harmonics=0.5,0.25`: A sine wave, with the relative
+  amplitudes of any harmonics from the 2nd up. It's scaled down if
+  the harmonics make its peak larger than 1.
+* `square duty=0.5 rise=0`: A square wave, high for the "duty"
+  fraction of the period, including the rising edge, with linear
+  edges which each take "rise" of the period.
+* `trapezoid rise=0.25 high=0.25 fall=0.25`: Rising, high, falling,
+  then low for the rest of the period.
+* `sawtooth width=1`: Rising for the "width" fraction of the period,
+  then falling. A width of 0.5 is a triangle wave.
+* `gauss sigma=0.1 center=0.5`: A Gaussian pulse, rising from 0 to 1.
+* `sinc zeros=8 center=0.5`: A sinc pulse, with this many zero
+  crossings on each side of its peak of 1.
+* `damped cycles=8 decay=5`: A sine wave which decays exponentially,
+  to `e^-decay` of its amplitude by the end of the period.
+* `chirp start=1 end=16 sweep=linear`: A sine wave sweeping from the
+  "start" to the "end" frequency, with a `linear` or `log` sweep.
+* `stairs steps=8`: A staircase rising from -1 to 1 in this many steps.
+
+Every shape also takes `amp=1`, `offset=0`, and `phase=0`, which is
+in degrees. The values above are the defaults. Each sample is
+`offset + amp * shape`, where -1 to 1 is the full range of the device,
+scaled by 2047/2048 so that an amplitude of 1 fits without clipping.
+*/
+
+use crate::convert::*;
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+
+/** How a chirp's frequency changes. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sweep {
+	/// The frequency changes by the same amount each sample.
+	Linear,
+
+	/// The frequency changes by the same ratio each sample.
+	Log,
+}
+
+/** The shape of a generated wave, and the parameters for it. */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+	/// A sine wave, with the relative amplitudes of harmonics from the 2nd up.
+	Sine { harmonics: Vec<f64> },
+
+	/// A square wave with linear edges.
+	Square { duty: f64, rise: f64 },
+
+	/// A trapezoid, low for the rest of the period.
+	Trapezoid { rise: f64, high: f64, fall: f64 },
+
+	/// A sawtooth, rising for "width" of the period.
+	Sawtooth { width: f64 },
+
+	/// A Gaussian pulse.
+	Gauss { sigma: f64, center: f64 },
+
+	/// A sinc pulse.
+	Sinc { zeros: f64, center: f64 },
+
+	/// An exponentially decaying sine wave.
+	Damped { cycles: f64, decay: f64 },
+
+	/// A sine wave with a changing frequency.
+	Chirp { start: f64, end: f64, sweep: Sweep },
+
+	/// A rising staircase.
+	Stairs { steps: usize },
+}
+
+/** A shape, and how to scale and shift it. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveSpec {
+	/// The shape of the wave.
+	pub shape: Shape,
+
+	/// The amplitude, where 1 is the full range of the device.
+	pub amp: f64,
+
+	/// Added to every sample, where 1 is the top of the device's range.
+	pub offset: f64,
+
+	/// Where in the period the wave starts, in degrees.
+	pub phase: f64,
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("unsupported wave spec: {}", msg), ErrorKind::InvalidValue)
+}
+
+/** The parameters of a wave spec, which are removed as they're used,
+so any unknown ones can be reported. */
+struct Params {
+	values: BTreeMap<String, String>,
+}
+
+impl Params {
+	fn take_f64(&mut self, key: &str, default: f64) -> Result<f64, clap::Error> {
+		match self.values.remove(key) {
+			Some(value) => {
+				let num = value
+					.parse::<f64>()
+					.map_err(|e| invalid(&format!("{}={}: {}", key, value, e)))?;
+
+				if !num.is_finite() {
+					return Err(invalid(&format!("{}={}: must be a finite number", key, value)));
+				}
+
+				Ok(num)
+			}
+			None => Ok(default),
+		}
+	}
+
+	fn take_fraction(&mut self, key: &str, default: f64) -> Result<f64, clap::Error> {
+		let num = self.take_f64(key, default)?;
+
+		if !(0.0..=1.0).contains(&num) {
+			return Err(invalid(&format!("{}={}: must be 0 - 1", key, num)));
+		}
+
+		Ok(num)
+	}
+
+	fn take_positive(&mut self, key: &str, default: f64) -> Result<f64, clap::Error> {
+		let num = self.take_f64(key, default)?;
+
+		if num <= 0.0 {
+			return Err(invalid(&format!("{}={}: must be more than 0", key, num)));
+		}
+
+		Ok(num)
+	}
+
+	fn take_str(&mut self, key: &str) -> Option<String> {
+		self.values.remove(key)
+	}
+}
+
+impl WaveSpec {
+	/// Parse a spec like `"chirp start=1 end=20 sweep=log amp=0.8"`.
+	pub fn parse(spec: &str) -> Result<WaveSpec, clap::Error> {
+		let mut words = spec.split_whitespace();
+
+		let name = words
+			.next()
+			.ok_or_else(|| invalid("no shape given (must be sine, square, trapezoid, sawtooth, gauss, sinc, damped, chirp, or stairs)"))?
+			.to_lowercase();
+
+		let mut params = Params {
+			values: BTreeMap::new(),
+		};
+
+		for word in words {
+			let mut parts = word.splitn(2, '=');
+			let key = parts.next().unwrap_or_default().to_lowercase();
+
+			let value = parts
+				.next()
+				.ok_or_else(|| invalid(&format!("{}: parameters must be name=value", word)))?;
+
+			if params.values.insert(key.clone(), value.to_string()).is_some() {
+				return Err(invalid(&format!("{}: given more than once", key)));
+			}
+		}
+
+		let shape = match name.as_str() {
+			"sine" | "sin" => {
+				let harmonics = match params.take_str("harmonics") {
+					Some(list) => list
+						.split(',')
+						.map(|h| {
+							h.trim()
+								.parse::<f64>()
+								.ok()
+								.filter(|h| h.is_finite())
+								.ok_or_else(|| invalid(&format!("harmonics={}: invalid amplitude: {}", list, h)))
+						})
+						.collect::<Result<Vec<f64>, clap::Error>>()?,
+					None => Vec::new(),
+				};
+
+				Shape::Sine { harmonics }
+			}
+
+			"square" => {
+				let duty = params.take_fraction("duty", 0.5)?;
+				let rise = params.take_fraction("rise", 0.0)?;
+
+				if rise > duty || rise > 1.0 - duty {
+					return Err(invalid(&format!("rise={}: each edge must fit in the high and low parts of the period", rise)));
+				}
+
+				Shape::Square { duty, rise }
+			}
+
+			"trapezoid" | "trap" => {
+				let rise = params.take_fraction("rise", 0.25)?;
+				let high = params.take_fraction("high", 0.25)?;
+				let fall = params.take_fraction("fall", 0.25)?;
+
+				if rise + high + fall > 1.0 {
+					return Err(invalid(&format!("rise + high + fall = {}: must be at most 1", rise + high + fall)));
+				}
+
+				Shape::Trapezoid { rise, high, fall }
+			}
+
+			"sawtooth" | "saw" => Shape::Sawtooth {
+				width: params.take_fraction("width", 1.0)?,
+			},
+
+			"gauss" | "gaussian" => Shape::Gauss {
+				sigma: params.take_positive("sigma", 0.1)?,
+				center: params.take_fraction("center", 0.5)?,
+			},
+
+			"sinc" => Shape::Sinc {
+				zeros: params.take_positive("zeros", 8.0)?,
+				center: params.take_fraction("center", 0.5)?,
+			},
+
+			"damped" => Shape::Damped {
+				cycles: params.take_f64("cycles", 8.0)?,
+				decay: params.take_f64("decay", 5.0)?,
+			},
+
+			"chirp" => {
+				let sweep = match params.take_str("sweep").map(|s| s.to_lowercase()).as_deref() {
+					None | Some("linear") | Some("lin") => Sweep::Linear,
+					Some("log") => Sweep::Log,
+					Some(other) => return Err(invalid(&format!("sweep={}: must be linear or log", other))),
+				};
+
+				let (start, end) = if sweep == Sweep::Log {
+					(params.take_positive("start", 1.0)?, params.take_positive("end", 16.0)?)
+				} else {
+					(params.take_f64("start", 1.0)?, params.take_f64("end", 16.0)?)
+				};
+
+				Shape::Chirp { start, end, sweep }
+			}
+
+			"stairs" | "staircase" => {
+				let steps = params.take_f64("steps", 8.0)?;
+
+				if steps < 2.0 || steps > ARBITRARY_WAVE_LEN as f64 || steps.fract() != 0.0 {
+					return Err(invalid(&format!("steps={}: must be a whole number from 2 - {}", steps, ARBITRARY_WAVE_LEN)));
+				}
+
+				Shape::Stairs { steps: steps as usize }
+			}
+
+			_ => {
+				return Err(invalid(&format!(
+					"unknown shape: {} (must be sine, square, trapezoid, sawtooth, gauss, sinc, damped, chirp, or stairs)",
+					name
+				)))
+			}
+		};
+
+		let amp = params.take_f64("amp", 1.0)?;
+		let offset = params.take_f64("offset", 0.0)?;
+		let phase = params.take_f64("phase", 0.0)?;
+
+		if let Some(key) = params.values.keys().next() {
+			return Err(invalid(&format!("unknown parameter for {}: {}", name, key)));
+		}
+
+		Ok(WaveSpec {
+			shape,
+			amp,
+			offset,
+			phase,
+		})
+	}
+
+	/// The value of the shape at time "t", from 0 up to 1.
+	fn shape_at(&self, t: f64) -> f64 {
+		match &self.shape {
+			Shape::Sine { harmonics } => {
+				(2.0 * PI * t).sin()
+					+ harmonics
+						.iter()
+						.enumerate()
+						.map(|(i, a)| a * (2.0 * PI * (i + 2) as f64 * t).sin())
+						.sum::<f64>()
+			}
+
+			Shape::Square { duty, rise } => {
+				if t < *rise {
+					-1.0 + 2.0 * t / rise
+				} else if t < *duty {
+					1.0
+				} else if t < duty + rise {
+					1.0 - 2.0 * (t - duty) / rise
+				} else {
+					-1.0
+				}
+			}
+
+			Shape::Trapezoid { rise, high, fall } => {
+				if t < *rise {
+					-1.0 + 2.0 * t / rise
+				} else if t < rise + high {
+					1.0
+				} else if t < rise + high + fall {
+					1.0 - 2.0 * (t - rise - high) / fall
+				} else {
+					-1.0
+				}
+			}
+
+			Shape::Sawtooth { width } => {
+				if t < *width {
+					-1.0 + 2.0 * t / width
+				} else {
+					1.0 - 2.0 * (t - width) / (1.0 - width)
+				}
+			}
+
+			Shape::Gauss { sigma, center } => (-(t - center).powi(2) / (2.0 * sigma * sigma)).exp(),
+
+			Shape::Sinc { zeros, center } => {
+				let x = PI * (t - center) * 2.0 * zeros;
+
+				if x == 0.0 {
+					1.0
+				} else {
+					x.sin() / x
+				}
+			}
+
+			Shape::Damped { cycles, decay } => (-decay * t).exp() * (2.0 * PI * cycles * t).sin(),
+
+			Shape::Chirp { start, end, sweep } => {
+				let cycles = match sweep {
+					Sweep::Linear => start * t + (end - start) * t * t / 2.0,
+					Sweep::Log if (end - start).abs() < f64::EPSILON => start * t,
+					Sweep::Log => {
+						let k = (end / start).ln();
+
+						start * ((k * t).exp() - 1.0) / k
+					}
+				};
+
+				(2.0 * PI * cycles).sin()
+			}
+
+			Shape::Stairs { steps } => {
+				let step = ((t * *steps as f64).floor() as usize).min(steps - 1);
+
+				-1.0 + 2.0 * step as f64 / (steps - 1) as f64
+			}
+		}
+	}
+
+	/// Generate "len" samples of one period of the wave, where -1 to 1
+	/// is the full range of the device.
+	pub fn samples(&self, len: usize) -> Vec<f64> {
+		let shift = self.phase / 360.0;
+
+		let mut shape: Vec<f64> = (0..len)
+			.map(|i| self.shape_at((i as f64 / len as f64 + shift).rem_euclid(1.0)))
+			.collect();
+
+		if let Shape::Sine { harmonics } = &self.shape {
+			let peak = shape.iter().fold(0.0, |peak: f64, s| peak.max(s.abs()));
+
+			if !harmonics.is_empty() && peak > 1.0 {
+				shape.iter_mut().for_each(|s| *s /= peak);
+			}
+		}
+
+		shape.iter().map(|s| self.offset + self.amp * s).collect()
+	}
+}
+
+/** Generate a wave for an arbitrary wave slot, converted to the
+device's range as chosen in "convert". It's scaled by
+[MAX_PEAK](../convert/constant.MAX_PEAK.html) first, so that a shape
+with an amplitude of 1 fits without clipping. */
+pub fn generate_wave(spec: &WaveSpec, convert: &ConvertOptions) -> Result<(WaveData, ConversionReport), clap::Error> {
+	let samples: Vec<f64> = spec.samples(ARBITRARY_WAVE_LEN).iter().map(|s| s * MAX_PEAK).collect();
+	let (samples, report) = convert_to_device(&samples, convert)?;
+
+	Ok((WaveData::new(&samples)?, report))
+}
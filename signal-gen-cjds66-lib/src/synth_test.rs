@@ -0,0 +1,141 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::convert::*;
+use super::synth::*;
+
+fn close(a: f64, b: f64) -> bool {
+	(a - b).abs() < 1e-9
+}
+
+#[test]
+pub fn parse_ok() {
+	let spec = WaveSpec::parse("gauss sigma=0.05").unwrap();
+
+	assert_eq!(spec.shape, Shape::Gauss { sigma: 0.05, center: 0.5 });
+	assert_eq!((spec.amp, spec.offset, spec.phase), (1.0, 0.0, 0.0));
+
+	let spec = WaveSpec::parse("  Chirp start=2 end=20 sweep=LOG amp=0.5 phase=90 ").unwrap();
+
+	assert_eq!(spec.shape, Shape::Chirp { start: 2.0, end: 20.0, sweep: Sweep::Log });
+	assert_eq!((spec.amp, spec.phase), (0.5, 90.0));
+
+	assert_eq!(
+		WaveSpec::parse("sine harmonics=0.5,0.25").unwrap().shape,
+		Shape::Sine { harmonics: vec![0.5, 0.25] }
+	);
+	assert_eq!(WaveSpec::parse("stairs steps=4").unwrap().shape, Shape::Stairs { steps: 4 });
+	assert_eq!(WaveSpec::parse("saw").unwrap().shape, Shape::Sawtooth { width: 1.0 });
+}
+
+#[test]
+pub fn parse_err() {
+	WaveSpec::parse("").unwrap_err();
+	WaveSpec::parse("triangle").unwrap_err();
+	WaveSpec::parse("gauss sigma").unwrap_err();
+	WaveSpec::parse("gauss sigma=0").unwrap_err();
+	WaveSpec::parse("gauss sigma=x").unwrap_err();
+	WaveSpec::parse("gauss sigma=0.1 sigma=0.2").unwrap_err();
+	WaveSpec::parse("gauss width=0.1").unwrap_err();
+	WaveSpec::parse("square duty=0.2 rise=0.3").unwrap_err();
+	WaveSpec::parse("trapezoid rise=0.5 high=0.5 fall=0.5").unwrap_err();
+	WaveSpec::parse("chirp start=0 sweep=log").unwrap_err();
+	WaveSpec::parse("chirp sweep=cubic").unwrap_err();
+	WaveSpec::parse("stairs steps=1").unwrap_err();
+	WaveSpec::parse("stairs steps=2.5").unwrap_err();
+	WaveSpec::parse("sine harmonics=0.5,x").unwrap_err();
+	WaveSpec::parse("sine amp=inf").unwrap_err();
+}
+
+#[test]
+pub fn samples_ok() {
+	let sine = WaveSpec::parse("sine").unwrap().samples(8);
+	assert!(close(sine[0], 0.0) && close(sine[2], 1.0) && close(sine[6], -1.0));
+
+	let shifted = WaveSpec::parse("sine phase=90").unwrap().samples(8);
+	assert!(close(shifted[0], 1.0));
+
+	// Harmonics which make the peak too large are scaled back to 1.
+	let rich = WaveSpec::parse("sine harmonics=1,1").unwrap().samples(2048);
+	assert!(close(rich.iter().fold(0.0, |p: f64, s| p.max(s.abs())), 1.0));
+
+	let square = WaveSpec::parse("square duty=0.25 rise=0.125").unwrap().samples(16);
+	assert_eq!(square, vec![-1.0, 0.0, 1.0, 1.0, 1.0, 0.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0, -1.0]);
+
+	let trapezoid = WaveSpec::parse("trapezoid").unwrap().samples(8);
+	assert_eq!(trapezoid, vec![-1.0, 0.0, 1.0, 1.0, 1.0, 0.0, -1.0, -1.0]);
+
+	let triangle = WaveSpec::parse("sawtooth width=0.5").unwrap().samples(4);
+	assert_eq!(triangle, vec![-1.0, 0.0, 1.0, 0.0]);
+
+	let gauss = WaveSpec::parse("gauss sigma=0.05 amp=2 offset=-1").unwrap().samples(2048);
+	assert!(close(gauss[1024], 1.0));
+	assert!(gauss[0] < -0.999);
+
+	let sinc = WaveSpec::parse("sinc zeros=4").unwrap().samples(16);
+	assert!(close(sinc[8], 1.0) && close(sinc[10], 0.0) && close(sinc[0], 0.0));
+
+	let damped = WaveSpec::parse("damped cycles=1 decay=1").unwrap().samples(4);
+	assert!(close(damped[1], (-0.25f64).exp()));
+
+	let stairs = WaveSpec::parse("stairs steps=3").unwrap().samples(6);
+	assert_eq!(stairs, vec![-1.0, -1.0, 0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+pub fn chirp_ok() {
+	// Count the rising zero crossings to see how many cycles there are.
+	let cycles = |spec: &str| {
+		let s = WaveSpec::parse(spec).unwrap().samples(2048);
+		s.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count()
+	};
+
+	assert_eq!(cycles("chirp start=4 end=4"), 3);
+	assert_eq!(cycles("chirp start=2 end=18"), 9);
+	assert_eq!(cycles("chirp start=2 end=2 sweep=log"), 1);
+
+	// A log sweep from 1 to 16 has (16 - 1) / ln(16) cycles.
+	assert_eq!(cycles("chirp start=1 end=16 sweep=log"), 5);
+}
+
+#[test]
+pub fn generate_wave_ok() {
+	let (wave, report) = generate_wave(&WaveSpec::parse("stairs steps=2").unwrap(), &ConvertOptions::default()).unwrap();
+
+	assert_eq!(wave.samples()[0], 1);
+	assert_eq!(wave.samples()[2047], 4095);
+	assert_eq!(report.clipped(), 0);
+
+	let (wave, report) = generate_wave(&WaveSpec::parse("stairs steps=2 amp=1.1").unwrap(), &ConvertOptions::default()).unwrap();
+
+	assert_eq!(wave.samples()[0], 0);
+	assert_eq!(report.clipped(), 2048);
+
+	let opts = ConvertOptions {
+		normalize: Some(0.5),
+		..ConvertOptions::default()
+	};
+
+	let (wave, _) = generate_wave(&WaveSpec::parse("stairs steps=2 amp=1.1").unwrap(), &opts).unwrap();
+
+	assert_eq!(wave.samples()[2047], 3072);
+}
+
+#[test]
+pub fn generate_wave_err() {
+	let opts = ConvertOptions {
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	generate_wave(&WaveSpec::parse("square amp=1.01").unwrap(), &opts).unwrap_err();
+	generate_wave(&WaveSpec::parse("square").unwrap(), &opts).unwrap();
+}
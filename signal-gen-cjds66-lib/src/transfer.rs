@@ -0,0 +1,281 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Moving arbitrary waves to and from the device a piece at a time.
+
+An arbitrary wave is about 10 KB of text, which takes a while to send
+over the serial link, so it's decoded as it arrives with a
+[WaveParser](struct.WaveParser.html), and both directions report their
+[Progress](struct.Progress.html) as they go.
+
+The device's response to a read looks like this, with 2048 values,
+each followed by a comma, and then the command's terminator:
+```text
+:b01=2048,2048,...,2048,.\r\n
+```
+*/
+
+use crate::protocol::*;
+use crate::wave::*;
+
+use clap::{Error, ErrorKind};
+use std::fmt;
+
+/** Which way a wave is moving. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+	/// Writing a wave to a slot.
+	Upload,
+
+	/// Reading a wave from a slot.
+	Download,
+}
+
+impl fmt::Display for Direction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Direction::Upload => write!(f, "writing"),
+			Direction::Download => write!(f, "reading"),
+		}
+	}
+}
+
+/** How far along a wave transfer is, in samples. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+	/// Which way the wave is moving.
+	pub direction: Direction,
+
+	/// The slot number, from 1 to 60.
+	pub slot: u8,
+
+	/// How many samples have been sent or decoded so far.
+	pub done: usize,
+
+	/// How many samples there are in total.
+	pub total: usize,
+}
+
+/** Something to call with the progress of each wave transfer, such
+as to draw a progress bar. */
+pub type ProgressCallback = Box<dyn FnMut(&Progress)>;
+
+impl Progress {
+	/// Whether the transfer is finished.
+	pub fn is_done(&self) -> bool {
+		self.done >= self.total
+	}
+
+	/// A one-line text progress bar, "width" characters wide between
+	/// the brackets, such as:
+	/// `slot 12: reading [##########----------]  1024/2048`
+	pub fn bar(&self, width: usize) -> String {
+		let filled = (self.done.min(self.total) * width).checked_div(self.total).unwrap_or(width);
+
+		format!(
+			"slot {}: {} [{}{}] {:>5}/{}",
+			self.slot,
+			self.direction,
+			"#".repeat(filled),
+			"-".repeat(width - filled),
+			self.done,
+			self.total
+		)
+	}
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(&format!("invalid arbitrary wave response from device: {}", msg), ErrorKind::Io)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+	Header,
+	Samples,
+
+	/// After the last sample, with this many bytes of the terminator
+	/// received.
+	Terminator(usize),
+
+	Done,
+}
+
+/** Decodes the device's response to an arbitrary wave read as it
+arrives, in pieces of any size. Each sample is decoded once, as soon
+as its comma arrives, and the response is complete once the terminator
+after the 2048th sample has arrived, so none of it is left to be read
+with the next command. Anything else after the last sample is an
+error. */
+#[derive(Debug, Clone)]
+pub struct WaveParser {
+	state: State,
+	samples: Vec<u16>,
+	value: Option<u32>,
+	received: usize,
+}
+
+impl Default for WaveParser {
+	fn default() -> WaveParser {
+		WaveParser::new()
+	}
+}
+
+impl WaveParser {
+	/// A parser waiting for the start of a response.
+	pub fn new() -> WaveParser {
+		WaveParser {
+			state: State::Header,
+			samples: Vec::with_capacity(ARBITRARY_WAVE_LEN),
+			value: None,
+			received: 0,
+		}
+	}
+
+	/// Decode the next piece of the response. Returns true once all
+	/// the samples, and the terminator after them, have arrived.
+	pub fn feed(&mut self, bytes: &[u8]) -> Result<bool, clap::Error> {
+		for &b in bytes {
+			let offset = self.received;
+			self.received += 1;
+
+			match self.state {
+				State::Header => {
+					if b == COMMAND_SEPARATOR.as_bytes()[0] {
+						self.state = State::Samples;
+					} else if !b.is_ascii() {
+						return Err(invalid(&format!("unexpected byte 0x{:02x} at offset {}", b, offset)));
+					}
+				}
+
+				State::Samples => match b {
+					b'0'..=b'9' => {
+						let value = self.value.unwrap_or(0) * 10 + (b - b'0') as u32;
+
+						if value > ARBITRARY_WAVE_MAX as u32 {
+							return Err(invalid(&format!(
+								"sample {} is out of range (0 - {}) at offset {}",
+								self.samples.len() + 1,
+								ARBITRARY_WAVE_MAX,
+								offset
+							)));
+						}
+
+						self.value = Some(value);
+					}
+
+					b',' | b'.' => {
+						match self.value.take() {
+							Some(value) => self.samples.push(value as u16),
+
+							None => {
+								return Err(invalid(&format!("sample {} is missing at offset {}", self.samples.len() + 1, offset)));
+							}
+						}
+
+						if self.samples.len() == ARBITRARY_WAVE_LEN {
+							// The stop can also end the last sample, without a comma.
+							self.state = State::Terminator(if b == b'.' { 1 } else { 0 });
+						} else if b == b'.' {
+							return Err(invalid(&format!(
+								"it ended after {} samples, instead of {}",
+								self.samples.len(),
+								ARBITRARY_WAVE_LEN
+							)));
+						}
+					}
+
+					b' ' | b'\r' | b'\n' | b'\t' => {}
+
+					_ => {
+						return Err(invalid(&format!("unexpected byte 0x{:02x} at offset {}", b, offset)));
+					}
+				},
+
+				State::Terminator(matched) => {
+					if b != COMMAND_END.as_bytes()[matched] {
+						return Err(invalid(&format!(
+							"unexpected byte 0x{:02x} at offset {}, instead of the terminator after the last sample",
+							b, offset
+						)));
+					}
+
+					self.state = if matched + 1 == COMMAND_END.len() {
+						State::Done
+					} else {
+						State::Terminator(matched + 1)
+					};
+				}
+
+				State::Done => {
+					return Err(invalid(&format!("unexpected byte 0x{:02x} at offset {}, after the terminator", b, offset)));
+				}
+			}
+		}
+
+		Ok(self.is_done())
+	}
+
+	/// Whether all the samples, and the terminator after them, have
+	/// arrived.
+	pub fn is_done(&self) -> bool {
+		self.state == State::Done
+	}
+
+	/// How many samples have been decoded so far.
+	pub fn samples_done(&self) -> usize {
+		self.samples.len()
+	}
+
+	/// How many bytes have been fed in so far.
+	pub fn bytes_received(&self) -> usize {
+		self.received
+	}
+
+	/// The wave, if all the samples and the terminator have arrived.
+	pub fn finish(self) -> Result<WaveData, clap::Error> {
+		if let State::Terminator(_matched) = self.state {
+			return Err(invalid("the terminator after the last sample is missing"));
+		}
+
+		if !self.is_done() {
+			return Err(invalid(&format!(
+				"it ended after {} samples, instead of {}",
+				self.samples.len(),
+				ARBITRARY_WAVE_LEN
+			)));
+		}
+
+		WaveData::new(&self.samples)
+	}
+}
+
+/** The byte offsets in an upload command, "command", after every
+"step" samples, and the number of samples sent by then, ending with
+the whole command. The samples are counted by their commas, after the
+command's separator. */
+pub fn upload_chunks(command: &[u8], step: usize) -> Vec<(usize, usize)> {
+	let mut chunks = Vec::new();
+	let mut samples = 0;
+	let start = command.iter().position(|&b| b == COMMAND_SEPARATOR.as_bytes()[0]).map_or(command.len(), |i| i + 1);
+
+	for (i, &b) in command.iter().enumerate().skip(start) {
+		if b == b',' {
+			samples += 1;
+
+			if samples % step == 0 {
+				chunks.push((i + 1, samples));
+			}
+		}
+	}
+
+	// The last sample has no comma after it.
+	chunks.push((command.len(), samples + 1));
+
+	chunks
+}
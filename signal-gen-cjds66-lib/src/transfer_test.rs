@@ -0,0 +1,144 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::transfer::*;
+use super::wave::*;
+
+fn response(wave: &WaveData) -> Vec<u8> {
+	format!(":b01={},.\r\n", wave.to_command_arg()).into_bytes()
+}
+
+fn ramp() -> WaveData {
+	WaveData::new(&(0..2048).map(|i| (i * 2) as u16).collect::<Vec<u16>>()).unwrap()
+}
+
+#[test]
+pub fn wave_parser_ok() {
+	let wave = ramp();
+	let res = response(&wave);
+
+	// Any size of pieces.
+	for size in [1, 7, 1024, res.len()] {
+		let mut parser = WaveParser::new();
+		let mut done = false;
+
+		for piece in res.chunks(size) {
+			done = parser.feed(piece).unwrap();
+		}
+
+		assert!(done);
+		assert_eq!(parser.samples_done(), 2048);
+		assert_eq!(parser.bytes_received(), res.len());
+		assert_eq!(parser.finish().unwrap(), wave);
+	}
+
+	// Not done until the terminator arrives, even if it's split up.
+	let mut parser = WaveParser::new();
+	let text = format!(":b01={},", wave.to_command_arg());
+
+	assert!(!parser.feed(text.as_bytes()).unwrap());
+	assert_eq!(parser.samples_done(), 2048);
+	assert!(!parser.feed(b".").unwrap());
+	assert!(!parser.feed(b"\r").unwrap());
+	assert!(parser.feed(b"\n").unwrap());
+	assert_eq!(parser.finish().unwrap(), wave);
+
+	// Or with its stop ending the last sample, without a comma.
+	let mut parser = WaveParser::new();
+	let text = format!(":b01={}.\r", wave.to_command_arg());
+
+	assert!(!parser.feed(text.as_bytes()).unwrap());
+	assert!(parser.feed(b"\n").unwrap());
+	assert_eq!(parser.finish().unwrap(), wave);
+}
+
+#[test]
+pub fn wave_parser_err() {
+	// Not finished yet.
+	let mut parser = WaveParser::new();
+
+	assert!(!parser.feed(b":b01=1,2,3,").unwrap());
+	assert_eq!(parser.samples_done(), 3);
+	parser.finish().unwrap_err();
+
+	// Ended too soon.
+	WaveParser::new().feed(b":b01=1,2,3,.\r\n").unwrap_err();
+
+	// Out of range.
+	WaveParser::new().feed(b":b01=1,4096,").unwrap_err();
+
+	// Missing a sample.
+	WaveParser::new().feed(b":b01=1,,2,").unwrap_err();
+
+	// Not a number.
+	WaveParser::new().feed(b":b01=1,x,").unwrap_err();
+
+	// Something other than the terminator after the last sample.
+	let text = format!(":b01={},", ramp().to_command_arg());
+	let mut parser = WaveParser::new();
+
+	parser.feed(text.as_bytes()).unwrap();
+	parser.feed(b"1.\r\n").unwrap_err();
+
+	// Something after the terminator.
+	let mut parser = WaveParser::new();
+
+	parser.feed(text.as_bytes()).unwrap();
+	parser.feed(b".\r\n:").unwrap_err();
+
+	// The terminator is missing.
+	let mut parser = WaveParser::new();
+
+	parser.feed(text.as_bytes()).unwrap();
+	parser.feed(b".\r").unwrap();
+	parser.finish().unwrap_err();
+
+	// Not UTF-8.
+	WaveParser::new().feed(b":b01=1,\xff,").unwrap_err();
+	WaveParser::new().feed(b"\xff:b01=").unwrap_err();
+}
+
+#[test]
+pub fn progress_ok() {
+	let progress = Progress {
+		direction: Direction::Download,
+		slot: 12,
+		done: 1024,
+		total: 2048,
+	};
+
+	assert_eq!(progress.bar(20), "slot 12: reading [##########----------]  1024/2048");
+	assert!(!progress.is_done());
+
+	let progress = Progress {
+		direction: Direction::Upload,
+		done: 2048,
+		..progress
+	};
+
+	assert_eq!(progress.bar(4), "slot 12: writing [####]  2048/2048");
+	assert!(progress.is_done());
+}
+
+#[test]
+pub fn upload_chunks_ok() {
+	let command = b":a01=1,22,333,4444.\r\n";
+
+	assert_eq!(upload_chunks(command, 2), vec![(10, 2), (command.len(), 4)]);
+	assert_eq!(upload_chunks(command, 10), vec![(command.len(), 4)]);
+
+	let command = format!(":a01={}.\r\n", ramp().to_command_arg());
+	let chunks = upload_chunks(command.as_bytes(), 128);
+
+	assert_eq!(chunks.len(), 16);
+	assert_eq!(chunks[0].1, 128);
+	assert_eq!(chunks[15], (command.len(), 2048));
+}
@@ -0,0 +1,565 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Parse numeric values which have a unit written after them,
+such as `"1.5kHz"`, `"250mV"`, `"3.3Vpp"`, `"800ns"`, or `"2.5ms"`,
+and convert them into the units the device's protocol expects.
+
+Values without a unit are left for the caller to interpret in
+its own default unit, so all the existing plain-number arguments
+keep working the same way they always have.
+*/
+
+use crate::register::*;
+
+use clap::{Error, ErrorKind};
+
+/** The kinds of physical quantity that the device accepts. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantity {
+	/// Frequency, with hertz (Hz) as the base unit.
+	Frequency,
+
+	/// Voltage, with peak-to-peak volts (V or Vpp) as the base unit.
+	Voltage,
+
+	/// Time, with seconds (s) as the base unit.
+	Time,
+}
+
+/** Units accepted for frequency values, and how many hertz are in each one. */
+pub const FREQUENCY_UNITS: [(&str, f64); 7] = [
+	("uHz", 0.000001),
+	("µHz", 0.000001),
+	("mHz", 0.001),
+	("Hz", 1.0),
+	("kHz", 1000.0),
+	("KHz", 1000.0),
+	("MHz", 1000000.0),
+];
+
+/** Units accepted for voltage values, and how many volts are in each one.
+The device always works in peak-to-peak volts, so "Vpp" is the same as "V".
+*/
+pub const VOLTAGE_UNITS: [(&str, f64); 6] = [
+	("uV", 0.000001),
+	("µV", 0.000001),
+	("mVpp", 0.001),
+	("mV", 0.001),
+	("Vpp", 1.0),
+	("V", 1.0),
+];
+
+/** Units accepted for time values, and how many seconds are in each one. */
+pub const TIME_UNITS: [(&str, f64); 5] = [
+	("ns", 0.000000001),
+	("us", 0.000001),
+	("µs", 0.000001),
+	("ms", 0.001),
+	("s", 1.0),
+];
+
+/** A value which was parsed by `parse_quantity()`. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedQuantity {
+	/// The number exactly as it was written, without its unit.
+	pub number: f64,
+
+	/// The unit it was written with (spelled as in the unit tables above), or `None`.
+	pub unit: Option<&'static str>,
+
+	/// The value in the base unit of its quantity (Hz, V, or s), or `number` if there was no unit.
+	pub base: f64,
+}
+
+/** Get the table of accepted units for a kind of quantity. */
+pub fn units_for(quantity: Quantity) -> &'static [(&'static str, f64)] {
+	match quantity {
+		Quantity::Frequency => &FREQUENCY_UNITS,
+		Quantity::Voltage => &VOLTAGE_UNITS,
+		Quantity::Time => &TIME_UNITS,
+	}
+}
+
+/** Parse a number which may have a unit written after it.
+
+"amount" parameter, for example:
+```ignore
+"1.5kHz" | "1.5 kHz" | "250mV" | "3.3Vpp" | "-1.25V" | "800ns" | "2.5ms" | "1000"
+```
+*/
+pub fn parse_quantity(amount: &str, quantity: Quantity) -> Result<ParsedQuantity, clap::Error> {
+	let amount_trimmed = amount.trim();
+
+	let number_len = amount_trimmed
+		.char_indices()
+		.take_while(|(i, c)| {
+			c.is_ascii_digit() || *c == '.' || ((*c == '-' || *c == '+') && *i == 0)
+		})
+		.count();
+
+	let (number_str, unit_str) = amount_trimmed.split_at(number_len);
+	let unit_str = unit_str.trim();
+
+	let number = number_str.parse::<f64>().map_err(|e| {
+		Error::with_description(
+			&format!("unsupported value: {}: {}", amount, e),
+			ErrorKind::InvalidValue,
+		)
+	})?;
+
+	if unit_str.is_empty() {
+		return Ok(ParsedQuantity {
+			number,
+			unit: None,
+			base: number,
+		});
+	}
+
+	let units = units_for(quantity);
+
+	match units.iter().find(|(name, _)| *name == unit_str) {
+		Some((name, multiplier)) => Ok(ParsedQuantity {
+			number,
+			unit: Some(name),
+			base: number * multiplier,
+		}),
+
+		None => Err(Error::with_description(
+			&format!(
+				"unsupported value: {}: unknown unit \"{}\" (must be one of: {})",
+				amount,
+				unit_str,
+				units.iter().map(|(name, _)| *name).collect::<Vec<&str>>().join(", ")
+			),
+			ErrorKind::InvalidValue,
+		)),
+	}
+}
+
+/** Format a number without any floating point noise or trailing zeros,
+so it can be passed on to one of the functions in the `command` module
+which check how many decimal places a value has.
+*/
+pub fn format_amount(amount: f64) -> String {
+	let formatted = format!("{:.9}", amount);
+	let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+
+	if trimmed == "-0" {
+		"0".to_string()
+	} else {
+		trimmed.to_string()
+	}
+}
+
+/** Convert an argument which may have a unit written after it into a plain
+number in the unit named by `multiplier` (how many base units are in the
+wanted unit). If the argument has no unit, it's returned unchanged.
+
+For example, to get a value in volts:
+```ignore
+amount_in_unit("250mV", Quantity::Voltage, 1.0) == Ok("0.25")
+amount_in_unit("0.25", Quantity::Voltage, 1.0) == Ok("0.25")
+```
+*/
+pub fn amount_in_unit(amount: &str, quantity: Quantity, multiplier: f64) -> Result<String, clap::Error> {
+	let parsed = parse_quantity(amount, quantity)?;
+
+	match parsed.unit {
+		Some(_) => Ok(format_amount(parsed.base / multiplier)),
+		None => Ok(amount.to_string()),
+	}
+}
+
+/** Count the decimal places of a number formatted with `format_amount()`. */
+fn decimal_places(amount: &str) -> usize {
+	match amount.split('.').nth(1) {
+		Some(decimals) => decimals.len(),
+		None => 0,
+	}
+}
+
+/** One of the frequency units the device's protocol supports. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrequencyUnit {
+	/// Microhertz (µHz).
+	Microhertz,
+
+	/// Millihertz (mHz).
+	Millihertz,
+
+	/// Hertz (Hz).
+	Hertz,
+
+	/// Kilohertz (kHz).
+	Kilohertz,
+
+	/// Megahertz (MHz).
+	Megahertz,
+}
+
+impl FrequencyUnit {
+	/// How many hertz are in one of this unit.
+	pub const fn hertz(&self) -> f64 {
+		match self {
+			FrequencyUnit::Microhertz => 0.000001,
+			FrequencyUnit::Millihertz => 0.001,
+			FrequencyUnit::Hertz => 1.0,
+			FrequencyUnit::Kilohertz => 1000.0,
+			FrequencyUnit::Megahertz => 1000000.0,
+		}
+	}
+
+	/// The largest value the device accepts in this unit.
+	pub fn max(&self) -> f64 {
+		match self {
+			FrequencyUnit::Microhertz => FREQUENCY_MICROHERTZ.max,
+			FrequencyUnit::Millihertz => FREQUENCY_MILLIHERTZ.max,
+			FrequencyUnit::Hertz => FREQUENCY_HERTZ.max,
+			FrequencyUnit::Kilohertz => FREQUENCY_KILOHERTZ.max,
+			FrequencyUnit::Megahertz => FREQUENCY_MEGAHERTZ.max,
+		}
+	}
+
+	/// The most decimal places the device accepts in this unit.
+	pub fn max_decimal_places(&self) -> usize {
+		match self {
+			FrequencyUnit::Microhertz => FREQUENCY_MICROHERTZ.decimal_places,
+			FrequencyUnit::Millihertz => FREQUENCY_MILLIHERTZ.decimal_places,
+			FrequencyUnit::Hertz => FREQUENCY_HERTZ.decimal_places,
+			FrequencyUnit::Kilohertz => FREQUENCY_KILOHERTZ.decimal_places,
+			FrequencyUnit::Megahertz => FREQUENCY_MEGAHERTZ.decimal_places,
+		}
+	}
+
+	/// Get the protocol unit matching a unit name from `FREQUENCY_UNITS`.
+	pub fn from_name(name: &str) -> Option<FrequencyUnit> {
+		match name {
+			"uHz" | "µHz" => Some(FrequencyUnit::Microhertz),
+			"mHz" => Some(FrequencyUnit::Millihertz),
+			"Hz" => Some(FrequencyUnit::Hertz),
+			"kHz" | "KHz" => Some(FrequencyUnit::Kilohertz),
+			"MHz" => Some(FrequencyUnit::Megahertz),
+			_ => None,
+		}
+	}
+}
+
+/** Choose which protocol unit to send a frequency in, and return it along
+with the amount in that unit.
+
+The unit the value was written in is used if the device can represent it
+exactly in that unit. Otherwise the next finer unit which can represent it
+exactly is used, going down as far as microhertz. Values without a unit
+are in hertz.
+
+"amount" parameter, for example:
+```ignore
+"1.5kHz" => (FrequencyUnit::Kilohertz, "1.5")
+"1000" => (FrequencyUnit::Hertz, "1000")
+"1.234567kHz" => (FrequencyUnit::Millihertz, "1234567")
+```
+*/
+pub fn frequency_to_protocol(amount: &str) -> Result<(FrequencyUnit, String), clap::Error> {
+	let parsed = parse_quantity(amount, Quantity::Frequency)?;
+
+	let preferred = match parsed.unit {
+		Some(name) => FrequencyUnit::from_name(name).unwrap_or(FrequencyUnit::Hertz),
+		None => FrequencyUnit::Hertz,
+	};
+
+	let order = [
+		FrequencyUnit::Megahertz,
+		FrequencyUnit::Kilohertz,
+		FrequencyUnit::Hertz,
+		FrequencyUnit::Millihertz,
+		FrequencyUnit::Microhertz,
+	];
+
+	if parsed.base < 0.0 || parsed.base > FREQUENCY_HERTZ.max {
+		return Err(Error::with_description(
+			&format!(
+				"unsupported value passed to \"set frequency\" argument (must be 0Hz-{}MHz): {}",
+				FREQUENCY_MEGAHERTZ.max, amount
+			),
+			ErrorKind::InvalidValue,
+		));
+	}
+
+	for unit in order.iter().skip_while(|u| **u != preferred) {
+		let in_unit = format_amount(parsed.base / unit.hertz());
+
+		if decimal_places(&in_unit) <= unit.max_decimal_places()
+			&& parsed.base / unit.hertz() <= unit.max()
+		{
+			return Ok((*unit, in_unit));
+		}
+	}
+
+	Err(Error::with_description(
+		&format!(
+			"unsupported value passed to \"set frequency\" argument: {}: too many decimal places for the device to represent exactly (the resolution is {}uHz at best)",
+			amount,
+			1.0 / FREQUENCY_MICROHERTZ.multiplier
+		),
+		ErrorKind::InvalidValue,
+	))
+}
+
+/** Convert a pulse width or period which may have a unit written after it
+into the amount and unit the device expects, returning the amount and
+whether it's in microseconds (`true`) or nanoseconds (`false`).
+
+Values without a unit are in nanoseconds, unless `microseconds` is true.
+Nanoseconds are used whenever the value is a whole multiple of 5ns that
+the device accepts, and microseconds are used otherwise.
+
+"amount" parameter, for example:
+```ignore
+"800ns" => ("800", false)
+"2.5ms" => ("2500", true)
+```
+*/
+pub fn pulse_time_to_protocol(amount: &str, microseconds: bool, ns_max: f64) -> Result<(String, bool), clap::Error> {
+	let parsed = parse_quantity(amount, Quantity::Time)?;
+
+	if parsed.unit.is_none() {
+		return Ok((amount.to_string(), microseconds));
+	}
+
+	let ns = format_amount(parsed.base * 1000000000.0);
+
+	if decimal_places(&ns) == 0 {
+		if let Ok(ns_val) = ns.parse::<f64>() {
+			if ns_val <= ns_max && ns_val as i64 % 5 == 0 {
+				return Ok((ns, false));
+			}
+		}
+	}
+
+	let us = format_amount(parsed.base * 1000000.0);
+
+	if decimal_places(&us) == 0 {
+		return Ok((us, true));
+	}
+
+	Err(Error::with_description(
+		&format!(
+			"unsupported value passed to \"set pulse time\" argument: {}: must be a whole number of microseconds, or a multiple of 5 nanoseconds up to {}ns",
+			amount, ns_max
+		),
+		ErrorKind::InvalidValue,
+	))
+}
+
+/** The device's output impedance in ohms (Ω). */
+pub const OUTPUT_IMPEDANCE_OHMS: f64 = 50.0;
+
+/** The load connected to one of the device's outputs. The device always
+sets and reports its amplitude as if its output is unterminated (High-Z),
+so a 50Ω load only sees half of it.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Load {
+	/// A high impedance load, such as an oscilloscope input. This is the default.
+	HighZ,
+
+	/// A 50Ω load, such as a terminated coaxial cable.
+	Ohms50,
+}
+
+impl Load {
+	/// Parse a load setting.
+	///
+	/// "load" parameter:
+	/// ```ignore
+	/// High-Z: "high-z" | "highz" | "hi-z" | "hiz" | "z"
+	/// 50Ω: "50" | "50ohm" | "50ohms" | "50Ω"
+	/// ```
+	pub fn parse(load: &str) -> Result<Load, clap::Error> {
+		match load.trim().to_lowercase().as_str() {
+			"high-z" | "highz" | "hi-z" | "hiz" | "z" => Ok(Load::HighZ),
+			"50" | "50ohm" | "50ohms" | "50ω" => Ok(Load::Ohms50),
+			_ => Err(Error::with_description(
+				&format!("unsupported value passed to \"load\" argument (must be high-z or 50): {}", load),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+
+	/// The resistance of the load in ohms (Ω), or `None` for High-Z.
+	pub fn ohms(&self) -> Option<f64> {
+		match self {
+			Load::HighZ => None,
+			Load::Ohms50 => Some(50.0),
+		}
+	}
+
+	/// How much of the device's unterminated output voltage reaches the load.
+	pub fn divider(&self) -> f64 {
+		match self.ohms() {
+			Some(ohms) => ohms / (ohms + OUTPUT_IMPEDANCE_OHMS),
+			None => 1.0,
+		}
+	}
+}
+
+/** The units an amplitude can be given in. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AmplitudeUnit {
+	/// Volts peak-to-peak.
+	Vpp,
+
+	/// Volts RMS, which depends on the waveform's crest factor.
+	Vrms,
+
+	/// Decibels relative to 1mW, which needs a load resistance.
+	Dbm,
+}
+
+impl AmplitudeUnit {
+	/// Parse an amplitude unit name.
+	///
+	/// "unit" parameter:
+	/// ```ignore
+	/// "vpp" | "vrms" | "dbm"
+	/// ```
+	pub fn parse(unit: &str) -> Result<AmplitudeUnit, clap::Error> {
+		match unit.trim().to_lowercase().as_str() {
+			"vpp" | "v" | "pp" => Ok(AmplitudeUnit::Vpp),
+			"vrms" | "rms" => Ok(AmplitudeUnit::Vrms),
+			"dbm" => Ok(AmplitudeUnit::Dbm),
+			_ => Err(Error::with_description(
+				&format!("unsupported amplitude unit (must be vpp, vrms, or dbm): {}", unit),
+				ErrorKind::InvalidValue,
+			)),
+		}
+	}
+}
+
+/** Get the crest factor (peak divided by RMS) of one of the device's
+waveform presets, as returned by `command::get_waveform_preset()`.
+The RMS doesn't include the waveform's DC level, which is how a meter
+measures it on AC.
+
+"duty" is the channel's duty cycle in percent, which changes the
+crest factor of the square and pulse waveforms. It's ignored for the
+other waveforms.
+
+Returns `None` for waveforms where it depends on other settings,
+such as noise or the arbitrary waveforms, and for a square or pulse
+waveform with a duty cycle of 0% or 100%, which is flat.
+*/
+pub fn crest_factor_for_preset(preset: u64, duty: f64) -> Option<f64> {
+	match preset {
+		// sine
+		0 => Some(2.0_f64.sqrt()),
+		// square, pulse
+		1 | 2 => {
+			let high = duty / 100.0;
+
+			if high > 0.0 && high < 1.0 {
+				Some(1.0 / (2.0 * (high * (1.0 - high)).sqrt()))
+			} else {
+				None
+			}
+		},
+		// triangle
+		3 => Some(3.0_f64.sqrt()),
+		_ => None,
+	}
+}
+
+/** Parse an amplitude which may be written in Vpp, Vrms, or dBm.
+Values without a unit are in volts peak-to-peak.
+
+"amount" parameter, for example:
+```ignore
+"3.3" | "3.3Vpp" | "250mV" | "1Vrms" | "707mVrms" | "10dBm" | "-3dBm"
+```
+*/
+pub fn parse_amplitude(amount: &str) -> Result<(f64, AmplitudeUnit), clap::Error> {
+	let amount_trimmed = amount.trim();
+
+	if let Some(number) = amount_trimmed.strip_suffix("dBm") {
+		let parsed = parse_quantity(number, Quantity::Voltage)?;
+
+		if parsed.unit.is_some() {
+			return Err(Error::with_description(
+				&format!("unsupported value passed to \"amplitude\" argument: {}", amount),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		return Ok((parsed.number, AmplitudeUnit::Dbm));
+	}
+
+	if let Some(number) = amount_trimmed.strip_suffix("rms") {
+		let parsed = parse_quantity(number, Quantity::Voltage)?;
+
+		if parsed.unit.is_none() || parsed.unit.unwrap().ends_with("pp") {
+			return Err(Error::with_description(
+				&format!("unsupported value passed to \"amplitude\" argument (use Vrms or mVrms): {}", amount),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		return Ok((parsed.base, AmplitudeUnit::Vrms));
+	}
+
+	Ok((parse_quantity(amount_trimmed, Quantity::Voltage)?.base, AmplitudeUnit::Vpp))
+}
+
+/** Convert an amplitude at the load into volts peak-to-peak at the load.
+
+"crest_factor" is only used for Vrms and dBm, and "load" is only used for dBm,
+which needs a load resistance, so it isn't supported with a High-Z load.
+*/
+pub fn amplitude_to_vpp(amount: f64, unit: AmplitudeUnit, crest_factor: f64, load: Load) -> Result<f64, clap::Error> {
+	match unit {
+		AmplitudeUnit::Vpp => Ok(amount),
+
+		AmplitudeUnit::Vrms => Ok(amount * crest_factor * 2.0),
+
+		AmplitudeUnit::Dbm => match load.ohms() {
+			Some(ohms) => {
+				let vrms = (10.0_f64.powf(amount / 10.0) / 1000.0 * ohms).sqrt();
+				Ok(vrms * crest_factor * 2.0)
+			}
+
+			None => Err(Error::with_description(
+				"dBm needs a load resistance: set the channel's load to 50 ohms first",
+				ErrorKind::ArgumentConflict,
+			)),
+		},
+	}
+}
+
+/** Convert volts peak-to-peak at the load into another amplitude unit.
+This is the reverse of `amplitude_to_vpp()`.
+*/
+pub fn vpp_to_amplitude(vpp: f64, unit: AmplitudeUnit, crest_factor: f64, load: Load) -> Result<f64, clap::Error> {
+	match unit {
+		AmplitudeUnit::Vpp => Ok(vpp),
+
+		AmplitudeUnit::Vrms => Ok(vpp / 2.0 / crest_factor),
+
+		AmplitudeUnit::Dbm => match load.ohms() {
+			Some(ohms) => {
+				let vrms = vpp / 2.0 / crest_factor;
+				Ok(10.0 * (vrms * vrms / ohms * 1000.0).log10())
+			}
+
+			None => Err(Error::with_description(
+				"dBm needs a load resistance: set the channel's load to 50 ohms first",
+				ErrorKind::ArgumentConflict,
+			)),
+		},
+	}
+}
@@ -0,0 +1,174 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS 
+outlined in the file titled LICENSE.md contained in the 
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::units::*;
+
+#[test]
+pub fn parse_quantity_ok() {
+	let args = [
+		("1.5kHz", Quantity::Frequency, 1500.0, Some("kHz")),
+		("1.5 KHz", Quantity::Frequency, 1500.0, Some("KHz")),
+		("10MHz", Quantity::Frequency, 10000000.0, Some("MHz")),
+		("1000", Quantity::Frequency, 1000.0, None),
+		("250mV", Quantity::Voltage, 0.25, Some("mV")),
+		("3.3Vpp", Quantity::Voltage, 3.3, Some("Vpp")),
+		("-1.25V", Quantity::Voltage, -1.25, Some("V")),
+		("800ns", Quantity::Time, 0.0000008, Some("ns")),
+		("2.5ms", Quantity::Time, 0.0025, Some("ms")),
+	];
+
+	for (amount, quantity, base, unit) in args.iter() {
+		let parsed = parse_quantity(amount, *quantity).unwrap();
+
+		assert!((parsed.base - base).abs() < 1e-12, "{}: {}", amount, parsed.base);
+		assert_eq!(parsed.unit, *unit);
+	}
+}
+
+#[test]
+pub fn parse_quantity_err() {
+	let args = [
+		("1.5kV", Quantity::Voltage),
+		("250mV", Quantity::Frequency),
+		("kHz", Quantity::Frequency),
+		("1.5.5Hz", Quantity::Frequency),
+		("", Quantity::Time),
+	];
+
+	for (amount, quantity) in args.iter() {
+		parse_quantity(amount, *quantity).unwrap_err();
+	}
+}
+
+#[test]
+pub fn amount_in_unit_ok() {
+	assert_eq!(amount_in_unit("250mV", Quantity::Voltage, 1.0).unwrap(), "0.25");
+	assert_eq!(amount_in_unit("1.1mV", Quantity::Voltage, 1.0).unwrap(), "0.0011");
+	assert_eq!(amount_in_unit("0.25", Quantity::Voltage, 1.0).unwrap(), "0.25");
+	assert_eq!(amount_in_unit("1.5kHz", Quantity::Frequency, 1.0).unwrap(), "1500");
+	assert_eq!(amount_in_unit("500ms", Quantity::Time, 1.0).unwrap(), "0.5");
+}
+
+#[test]
+pub fn frequency_to_protocol_ok() {
+	let args = [
+		("1.5kHz", FrequencyUnit::Kilohertz, "1.5"),
+		("1000", FrequencyUnit::Hertz, "1000"),
+		("1000.25", FrequencyUnit::Hertz, "1000.25"),
+		("10MHz", FrequencyUnit::Megahertz, "10"),
+		("250mHz", FrequencyUnit::Millihertz, "250"),
+		("1.234567kHz", FrequencyUnit::Millihertz, "1234567"),
+		("0.5uHz", FrequencyUnit::Microhertz, "0.5"),
+	];
+
+	for (amount, unit, in_unit) in args.iter() {
+		assert_eq!(frequency_to_protocol(amount).unwrap(), (*unit, in_unit.to_string()));
+	}
+}
+
+#[test]
+pub fn frequency_to_protocol_err() {
+	let args = ["61MHz", "-1Hz", "1.001uHz", "1.5mV"];
+
+	for amount in args.iter() {
+		frequency_to_protocol(amount).unwrap_err();
+	}
+}
+
+#[test]
+pub fn pulse_time_to_protocol_ok() {
+	assert_eq!(pulse_time_to_protocol("800ns", false, 4000000000.0).unwrap(), ("800".to_string(), false));
+	assert_eq!(pulse_time_to_protocol("2.5ms", false, 4000000000.0).unwrap(), ("2500000".to_string(), false));
+	assert_eq!(pulse_time_to_protocol("2.5ms", false, 1000000.0).unwrap(), ("2500".to_string(), true));
+	assert_eq!(pulse_time_to_protocol("1000", true, 4000000000.0).unwrap(), ("1000".to_string(), true));
+}
+
+#[test]
+pub fn pulse_time_to_protocol_err() {
+	pulse_time_to_protocol("802ns", false, 4000000000.0).unwrap_err();
+	pulse_time_to_protocol("1.5V", false, 4000000000.0).unwrap_err();
+}
+
+#[test]
+pub fn load_parse_ok() {
+	assert_eq!(Load::parse("high-z").unwrap(), Load::HighZ);
+	assert_eq!(Load::parse("HiZ").unwrap(), Load::HighZ);
+	assert_eq!(Load::parse("50").unwrap(), Load::Ohms50);
+	assert_eq!(Load::parse("50ohms").unwrap(), Load::Ohms50);
+	assert_eq!(Load::HighZ.divider(), 1.0);
+	assert_eq!(Load::Ohms50.divider(), 0.5);
+}
+
+#[test]
+pub fn load_parse_err() {
+	Load::parse("75").unwrap_err();
+	Load::parse("").unwrap_err();
+}
+
+#[test]
+pub fn parse_amplitude_ok() {
+	let args = [
+		("3.3", 3.3, AmplitudeUnit::Vpp),
+		("3.3Vpp", 3.3, AmplitudeUnit::Vpp),
+		("250mV", 0.25, AmplitudeUnit::Vpp),
+		("1Vrms", 1.0, AmplitudeUnit::Vrms),
+		("707mVrms", 0.707, AmplitudeUnit::Vrms),
+		("10dBm", 10.0, AmplitudeUnit::Dbm),
+		("-3dBm", -3.0, AmplitudeUnit::Dbm),
+	];
+
+	for (amount, value, unit) in args.iter() {
+		let (parsed, parsed_unit) = parse_amplitude(amount).unwrap();
+
+		assert!((parsed - value).abs() < 1e-12, "{}: {}", amount, parsed);
+		assert_eq!(parsed_unit, *unit);
+	}
+}
+
+#[test]
+pub fn parse_amplitude_err() {
+	let args = ["1rms", "1Vpprms", "1VdBm", "1Hz", "dBm"];
+
+	for amount in args.iter() {
+		parse_amplitude(amount).unwrap_err();
+	}
+}
+
+#[test]
+pub fn amplitude_conversion_ok() {
+	let sine = crest_factor_for_preset(0, 0.0).unwrap();
+
+	let vpp = amplitude_to_vpp(1.0, AmplitudeUnit::Vrms, sine, Load::HighZ).unwrap();
+	assert!((vpp - 2.0 * 2.0_f64.sqrt()).abs() < 1e-9);
+
+	let vpp = amplitude_to_vpp(10.0, AmplitudeUnit::Dbm, sine, Load::Ohms50).unwrap();
+	assert!((vpp - 2.0).abs() < 1e-9);
+
+	let dbm = vpp_to_amplitude(2.0, AmplitudeUnit::Dbm, sine, Load::Ohms50).unwrap();
+	assert!((dbm - 10.0).abs() < 1e-9);
+
+	let vrms = vpp_to_amplitude(2.0, AmplitudeUnit::Vrms, crest_factor_for_preset(1, 50.0).unwrap(), Load::HighZ).unwrap();
+	assert!((vrms - 1.0).abs() < 1e-9);
+
+	// A 2 Vpp pulse which is high for a quarter of the time.
+	let vrms = vpp_to_amplitude(2.0, AmplitudeUnit::Vrms, crest_factor_for_preset(2, 25.0).unwrap(), Load::HighZ).unwrap();
+	assert!((vrms - 0.75_f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+pub fn amplitude_conversion_err() {
+	amplitude_to_vpp(10.0, AmplitudeUnit::Dbm, 1.0, Load::HighZ).unwrap_err();
+	vpp_to_amplitude(1.0, AmplitudeUnit::Dbm, 1.0, Load::HighZ).unwrap_err();
+	assert_eq!(crest_factor_for_preset(11, 50.0), None);
+	assert_eq!(crest_factor_for_preset(101, 50.0), None);
+	assert_eq!(crest_factor_for_preset(2, 0.0), None);
+	assert_eq!(crest_factor_for_preset(1, 100.0), None);
+}
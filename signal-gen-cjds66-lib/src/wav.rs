@@ -0,0 +1,206 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! A reader for RIFF/WAVE (.wav) audio files.
+
+Supports 8, 16, 24, and 32-bit integer PCM, and 32 and 64-bit
+floating point samples, with any number of channels and any
+sample rate, including files using the `WAVE_FORMAT_EXTENSIBLE`
+header. Samples are returned as floating point values in the
+range `-1.0` to `1.0`.
+
+WaveCAD files as used by the device have no header at all, they're
+just 2048 little-endian signed 16-bit numbers. Use
+[is_riff_wave](fn.is_riff_wave.html) to tell the two apart.
+*/
+
+use byteorder::{ByteOrder, LittleEndian};
+use clap::{Error, ErrorKind};
+
+/** The format code for integer PCM samples. */
+pub const WAVE_FORMAT_PCM: u16 = 0x0001;
+
+/** The format code for floating point samples. */
+pub const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+
+/** The format code for a header with an extra sub-format field,
+which holds one of the other format codes. */
+pub const WAVE_FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/** How the samples in a .wav file are encoded. */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleFormat {
+	/// Signed integers, or unsigned for 8-bit samples.
+	Pcm,
+
+	/// IEEE floating point numbers.
+	Float,
+}
+
+/** The decoded contents of a .wav file. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct WavFile {
+	/// How the samples were encoded in the file.
+	pub format: SampleFormat,
+
+	/// The number of interleaved channels.
+	pub channels: u16,
+
+	/// The number of frames per second.
+	pub sample_rate: u32,
+
+	/// The size of each sample in the file.
+	pub bits_per_sample: u16,
+
+	/// The samples of every channel, interleaved, in the range -1.0 to 1.0.
+	pub samples: Vec<f64>,
+}
+
+impl WavFile {
+	/// The number of samples in each channel.
+	pub fn frames(&self) -> usize {
+		self.samples.len() / self.channels as usize
+	}
+
+	/// The samples of one channel, counting from 1. Channel 0 gives
+	/// the average of all the channels mixed together.
+	pub fn channel(&self, channel: u16) -> Result<Vec<f64>, clap::Error> {
+		if channel > self.channels {
+			return Err(Error::with_description(
+				&format!(
+					"unsupported channel number for .wav file (must be 0-{}, where 0 mixes all channels together): {}",
+					self.channels, channel
+				),
+				ErrorKind::InvalidValue,
+			));
+		}
+
+		let frames = self.samples.chunks(self.channels as usize);
+
+		if channel == 0 {
+			Ok(frames
+				.map(|frame| frame.iter().sum::<f64>() / frame.len() as f64)
+				.collect())
+		} else {
+			Ok(frames.map(|frame| frame[channel as usize - 1]).collect())
+		}
+	}
+}
+
+/** Returns true if the data starts with a RIFF/WAVE header. */
+pub fn is_riff_wave(data: &[u8]) -> bool {
+	data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE"
+}
+
+fn wav_error(msg: &str) -> clap::Error {
+	Error::with_description(&format!("failed parsing .wav file: {}", msg), ErrorKind::InvalidValue)
+}
+
+/** Parse a RIFF/WAVE file's `fmt ` and `data` chunks, and decode its samples. */
+pub fn parse_wav(data: &[u8]) -> Result<WavFile, clap::Error> {
+	if !is_riff_wave(data) {
+		return Err(wav_error("missing RIFF/WAVE header"));
+	}
+
+	let mut fmt: Option<&[u8]> = None;
+	let mut body: Option<&[u8]> = None;
+	let mut pos = 12;
+
+	while pos + 8 <= data.len() {
+		let id = &data[pos..pos + 4];
+		let size = LittleEndian::read_u32(&data[pos + 4..pos + 8]) as usize;
+		let start = pos + 8;
+
+		// Some programs which write .wav files as a stream never
+		// go back and fill in the size of the data chunk.
+		let end = start.saturating_add(size).min(data.len());
+
+		match id {
+			b"fmt " => fmt = Some(&data[start..end]),
+			b"data" => body = Some(&data[start..end]),
+			_ => {}
+		}
+
+		// Chunks are padded to an even number of bytes.
+		pos = start.saturating_add(size).saturating_add(size % 2);
+	}
+
+	let fmt = fmt.ok_or_else(|| wav_error("missing \"fmt \" chunk"))?;
+	let body = body.ok_or_else(|| wav_error("missing \"data\" chunk"))?;
+
+	if fmt.len() < 16 {
+		return Err(wav_error(&format!("\"fmt \" chunk is too short: {} bytes", fmt.len())));
+	}
+
+	let mut format_code = LittleEndian::read_u16(&fmt[0..2]);
+	let channels = LittleEndian::read_u16(&fmt[2..4]);
+	let sample_rate = LittleEndian::read_u32(&fmt[4..8]);
+	let block_align = LittleEndian::read_u16(&fmt[12..14]) as usize;
+	let bits_per_sample = LittleEndian::read_u16(&fmt[14..16]);
+
+	if format_code == WAVE_FORMAT_EXTENSIBLE {
+		if fmt.len() < 26 {
+			return Err(wav_error("extensible \"fmt \" chunk is missing its sub-format"));
+		}
+
+		format_code = LittleEndian::read_u16(&fmt[24..26]);
+	}
+
+	let format = match (format_code, bits_per_sample) {
+		(WAVE_FORMAT_PCM, 8) | (WAVE_FORMAT_PCM, 16) | (WAVE_FORMAT_PCM, 24) | (WAVE_FORMAT_PCM, 32) => SampleFormat::Pcm,
+		(WAVE_FORMAT_IEEE_FLOAT, 32) | (WAVE_FORMAT_IEEE_FLOAT, 64) => SampleFormat::Float,
+		_ => {
+			return Err(wav_error(&format!(
+				"unsupported sample format (must be 8, 16, 24, or 32-bit PCM, or 32 or 64-bit float): format code: {:#06x}: bits per sample: {}",
+				format_code, bits_per_sample
+			)));
+		}
+	};
+
+	if channels == 0 {
+		return Err(wav_error("the file has no channels"));
+	}
+
+	let sample_len = bits_per_sample as usize / 8;
+
+	if block_align < sample_len * channels as usize {
+		return Err(wav_error(&format!(
+			"block alignment is too small for {} channels of {}-bit samples: {}",
+			channels, bits_per_sample, block_align
+		)));
+	}
+
+	let mut samples = Vec::with_capacity(body.len() / sample_len);
+
+	for frame in body.chunks_exact(block_align) {
+		for sample in frame.chunks_exact(sample_len).take(channels as usize) {
+			samples.push(decode_sample(format, sample));
+		}
+	}
+
+	Ok(WavFile {
+		format,
+		channels,
+		sample_rate,
+		bits_per_sample,
+		samples,
+	})
+}
+
+/** Decode one sample to the range -1.0 to 1.0. */
+fn decode_sample(format: SampleFormat, sample: &[u8]) -> f64 {
+	match (format, sample.len()) {
+		(SampleFormat::Pcm, 1) => (sample[0] as f64 - 128.0) / 128.0,
+		(SampleFormat::Pcm, 2) => LittleEndian::read_i16(sample) as f64 / 32768.0,
+		(SampleFormat::Pcm, 3) => LittleEndian::read_i24(sample) as f64 / 8388608.0,
+		(SampleFormat::Pcm, _) => LittleEndian::read_i32(sample) as f64 / 2147483648.0,
+		(SampleFormat::Float, 4) => LittleEndian::read_f32(sample) as f64,
+		(SampleFormat::Float, _) => LittleEndian::read_f64(sample),
+	}
+}
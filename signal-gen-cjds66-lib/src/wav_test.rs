@@ -0,0 +1,201 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::command::*;
+use super::convert::*;
+use super::resample::*;
+use super::wav::*;
+
+use std::fs;
+
+/// Build a .wav file with the given header fields around the sample bytes.
+fn wav_bytes(format: u16, channels: u16, bits: u16, data: &[u8], extensible: bool) -> Vec<u8> {
+	let block_align = channels * bits / 8;
+
+	let mut fmt = Vec::new();
+	fmt.extend_from_slice(&(if extensible { WAVE_FORMAT_EXTENSIBLE } else { format }).to_le_bytes());
+	fmt.extend_from_slice(&channels.to_le_bytes());
+	fmt.extend_from_slice(&44100u32.to_le_bytes());
+	fmt.extend_from_slice(&(44100 * block_align as u32).to_le_bytes());
+	fmt.extend_from_slice(&block_align.to_le_bytes());
+	fmt.extend_from_slice(&bits.to_le_bytes());
+
+	if extensible {
+		fmt.extend_from_slice(&22u16.to_le_bytes());
+		fmt.extend_from_slice(&bits.to_le_bytes());
+		fmt.extend_from_slice(&0u32.to_le_bytes());
+		fmt.extend_from_slice(&format.to_le_bytes());
+		fmt.extend_from_slice(&[0u8; 14]);
+	}
+
+	let mut out = Vec::new();
+	out.extend_from_slice(b"RIFF");
+	out.extend_from_slice(&0u32.to_le_bytes());
+	out.extend_from_slice(b"WAVE");
+
+	// An unknown chunk with an odd size, which must be skipped along with its padding.
+	out.extend_from_slice(b"LIST");
+	out.extend_from_slice(&3u32.to_le_bytes());
+	out.extend_from_slice(&[1, 2, 3, 0]);
+
+	out.extend_from_slice(b"fmt ");
+	out.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+	out.extend_from_slice(&fmt);
+	out.extend_from_slice(b"data");
+	out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+	out.extend_from_slice(data);
+
+	out
+}
+
+#[test]
+pub fn parse_wav_ok() {
+	let mut f32_data = Vec::new();
+	f32_data.extend_from_slice(&0.5f32.to_le_bytes());
+	f32_data.extend_from_slice(&(-0.25f32).to_le_bytes());
+
+	let mut f64_data = Vec::new();
+	f64_data.extend_from_slice(&0.5f64.to_le_bytes());
+	f64_data.extend_from_slice(&(-0.25f64).to_le_bytes());
+
+	let files = [
+		wav_bytes(WAVE_FORMAT_PCM, 1, 8, &[192, 96], false),
+		wav_bytes(WAVE_FORMAT_PCM, 1, 16, &[0x00, 0x40, 0x00, 0xe0], false),
+		wav_bytes(WAVE_FORMAT_PCM, 1, 24, &[0x00, 0x00, 0x40, 0x00, 0x00, 0xe0], false),
+		wav_bytes(WAVE_FORMAT_PCM, 1, 32, &[0, 0, 0, 0x40, 0, 0, 0, 0xe0], true),
+		wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 1, 32, &f32_data, false),
+		wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 1, 64, &f64_data, true),
+	];
+
+	for file in files.iter() {
+		let wav = parse_wav(file).unwrap();
+
+		assert_eq!(wav.channels, 1);
+		assert_eq!(wav.sample_rate, 44100);
+		assert_eq!(wav.samples, vec![0.5, -0.25], "{}-bit {:?}", wav.bits_per_sample, wav.format);
+	}
+}
+
+#[test]
+pub fn parse_wav_err() {
+	let mut no_data = wav_bytes(WAVE_FORMAT_PCM, 1, 16, &[], false);
+	let len = no_data.len();
+	no_data[len - 8..len - 4].copy_from_slice(b"junk");
+
+	let files = [
+		vec![0u8; 4096],
+		wav_bytes(WAVE_FORMAT_PCM, 1, 12, &[0, 0], false),
+		wav_bytes(WAVE_FORMAT_IEEE_FLOAT, 1, 16, &[0, 0], false),
+		wav_bytes(0x0055, 1, 16, &[0, 0], false),
+		wav_bytes(WAVE_FORMAT_PCM, 0, 16, &[0, 0], false),
+		no_data,
+	];
+
+	for file in files.iter() {
+		parse_wav(file).unwrap_err();
+	}
+}
+
+#[test]
+pub fn channel_ok() {
+	let data = [0x00, 0x40, 0x00, 0xc0, 0x00, 0x20, 0x00, 0x20];
+	let wav = parse_wav(&wav_bytes(WAVE_FORMAT_PCM, 2, 16, &data, false)).unwrap();
+
+	assert_eq!(wav.frames(), 2);
+	assert_eq!(wav.channel(1).unwrap(), vec![0.5, 0.25]);
+	assert_eq!(wav.channel(2).unwrap(), vec![-0.5, 0.25]);
+	assert_eq!(wav.channel(0).unwrap(), vec![0.0, 0.25]);
+
+	wav.channel(3).unwrap_err();
+}
+
+#[test]
+pub fn wav_to_txt_ok() {
+	let dir = std::env::temp_dir().join(format!("signal-gen-cjds66-wav-test-{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+
+	// A regular audio file, scaled to the device's full range.
+	let audio = dir.join("audio.wav");
+	let data = [0x00, 0x40, 0x00, 0x80, 0xff, 0x7f, 0x00, 0x00];
+	fs::write(&audio, wav_bytes(WAVE_FORMAT_PCM, 2, 16, &data, false)).unwrap();
+
+	let res = wav_to_txt(audio.to_str().unwrap(), None, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap().0.to_txt_string();
+	let lines: Vec<&str> = res.split('\n').collect();
+
+	assert_eq!(lines.len(), 2049);
+	assert_eq!(&lines[..3], &["3072", "4095", "2048"]);
+
+	let res = wav_to_txt(audio.to_str().unwrap(), None, 2, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap().0.to_txt_string();
+	assert_eq!(&res.split('\n').collect::<Vec<&str>>()[..2], &["0", "2048"]);
+
+	wav_to_txt(audio.to_str().unwrap(), None, 3, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap_err();
+
+	// A longer recording, resampled to fit.
+	let long = dir.join("long.wav");
+	let data: Vec<u8> = (0..4096).flat_map(|i| if i < 2048 { 16384i16 } else { -16384i16 }.to_le_bytes().to_vec()).collect();
+	fs::write(&long, wav_bytes(WAVE_FORMAT_PCM, 1, 16, &data, false)).unwrap();
+
+	let opts = ResampleOptions {
+		interpolation: Some(Interpolation::Linear),
+		..ResampleOptions::default()
+	};
+
+	let res = wav_to_txt(long.to_str().unwrap(), None, 1, &opts, &ConvertOptions::default(), 0).unwrap().0.to_txt_string();
+	let lines: Vec<&str> = res.split('\n').collect();
+
+	assert_eq!(lines.len(), 2049);
+	assert_eq!((lines[0], lines[1023], lines[1025], lines[2047]), ("3072", "3072", "1024", "1024"));
+
+	// The same recording, normalized, with a report.
+	let convert = ConvertOptions {
+		normalize: Some(1.0),
+		..ConvertOptions::default()
+	};
+
+	let (wave, report) = wav_to_txt(long.to_str().unwrap(), None, 1, &ResampleOptions::default(), &convert, 0).unwrap();
+	let res = wave.to_txt_string();
+
+	assert_eq!(&res.split('\n').collect::<Vec<&str>>()[..2], &["4095", "4095"]);
+	assert_eq!(report.clipped(), 0);
+	assert_eq!(report.peak, MAX_PEAK);
+
+	let convert = ConvertOptions {
+		normalize: Some(1.0),
+		gain_db: 1.0,
+		fail_on_clip: true,
+		..ConvertOptions::default()
+	};
+
+	wav_to_txt(long.to_str().unwrap(), None, 1, &ResampleOptions::default(), &convert, 0).unwrap_err();
+
+	// A headerless WaveCAD file, read as it always has been.
+	let wavecad = dir.join("wavecad.wav");
+	let mut data = vec![0u8; 4096];
+	data[0..2].copy_from_slice(&(-2048i16).to_le_bytes());
+	data[2..4].copy_from_slice(&2047i16.to_le_bytes());
+	fs::write(&wavecad, &data).unwrap();
+
+	let res = wav_to_txt(wavecad.to_str().unwrap(), None, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap().0.to_txt_string();
+	let lines: Vec<&str> = res.split('\n').collect();
+
+	assert_eq!(lines.len(), 2049);
+	assert_eq!(&lines[..3], &["0", "4095", "2048"]);
+
+	// Saved somewhere else, and read back from memory.
+	let txt = dir.join("other.txt");
+	wav_to_txt(wavecad.to_str().unwrap(), txt.to_str(), 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap();
+	assert_eq!(fs::read_to_string(&txt).unwrap(), res);
+
+	let (wave, _) = wav_to_wave_data(&data, 1, &ResampleOptions::default(), &ConvertOptions::default(), 0).unwrap();
+	assert_eq!(wave.to_txt_string(), res);
+
+	fs::remove_dir_all(&dir).unwrap();
+}
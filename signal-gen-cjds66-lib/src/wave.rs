@@ -0,0 +1,272 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! The contents of one of the device's arbitrary wave slots.
+
+A [WaveData](struct.WaveData.html) always holds exactly 2048 samples,
+each in the range of 0 - 4095, so once you have one, it can be sent
+to the device as it is. It can be read from and written to the
+device's text format, one number per line, and the WaveCAD format,
+2048 little-endian signed 16-bit numbers in the range of -2048 to
+2047, using any reader or writer. Byte slices are readers, and
+`Vec<u8>` is a writer, so no files are needed:
+```ignore
+let wave = WaveData::from_txt(&b"2048\n2048\n..."[..])?;
+
+let mut wavecad = Vec::new();
+wave.to_wavecad(&mut wavecad)?;
+```
+*/
+
+use crate::protocol::*;
+
+use byteorder::{ByteOrder, LittleEndian};
+use clap::{Error, ErrorKind};
+use std::io::prelude::*;
+
+/** The size of a WaveCAD file in bytes. */
+pub const WAVECAD_LEN: usize = ARBITRARY_WAVE_LEN * 2;
+
+/** The character which starts a comment in the text format. */
+pub const TXT_COMMENT: char = '#';
+
+/** Parse the values in the device's text format, without checking
+how many there are.
+
+The values are whole numbers from 0 - 4095, separated by commas,
+whitespace, or newlines, which can be `\n` or `\r\n`. Blank lines
+are skipped, and anything after a `#` on a line is a comment. If any
+value has a decimal point or an exponent, all of them are read as
+floating point numbers from -1.0 to 1.0 instead, which are scaled to
+the device's range, with -1.0 as 0, 0.0 as 2048, and 1.0 as 4095.
+
+Returns the values in the device's range, not rounded yet, so they
+can still be resampled accurately. Errors give the line and column
+of the value which couldn't be read, counting from 1.
+*/
+pub fn parse_txt(text: &str) -> Result<Vec<f64>, clap::Error> {
+	let text = text.trim_start_matches('\u{feff}');
+	let mut tokens: Vec<(usize, usize, &str)> = Vec::new();
+
+	for (line_num, line) in text.split('\n').enumerate() {
+		let line = line.split(TXT_COMMENT).next().unwrap_or_default();
+		let mut start: Option<(usize, usize)> = None;
+
+		for (col, (i, c)) in line.char_indices().chain(std::iter::once((line.len(), ' '))).enumerate() {
+			let separator = c == ',' || c.is_whitespace();
+
+			match (start, separator) {
+				(None, false) => start = Some((col, i)),
+				(Some((start_col, start_i)), true) => {
+					tokens.push((line_num + 1, start_col + 1, &line[start_i..i]));
+					start = None;
+				}
+				_ => {}
+			}
+		}
+	}
+
+	let floats = tokens
+		.iter()
+		.any(|(_, _, token)| token.contains(['.', 'e', 'E']));
+
+	let mut values = Vec::with_capacity(tokens.len());
+
+	for (line, col, token) in tokens {
+		let at = |msg: &str| {
+			Error::with_description(
+				&format!("Invalid arbitrary wave data: line {}, column {}: {}: {}", line, col, msg, token),
+				ErrorKind::InvalidValue,
+			)
+		};
+
+		if floats {
+			let val = token
+				.parse::<f64>()
+				.map_err(|e| at(&format!("invalid number: {}", e)))?;
+
+			if !(-1.0..=1.0).contains(&val) {
+				return Err(at("number out of range (decimal numbers must be -1.0 to 1.0)"));
+			}
+
+			values.push((val * 2048.0 + 2048.0).min(ARBITRARY_WAVE_MAX as f64));
+		} else {
+			let val = token
+				.parse::<i64>()
+				.map_err(|e| at(&format!("invalid number: {}", e)))?;
+
+			if val < 0 || val > ARBITRARY_WAVE_MAX as i64 {
+				return Err(at(&format!("number out of range (must be 0 - {})", ARBITRARY_WAVE_MAX)));
+			}
+
+			values.push(val as f64);
+		}
+	}
+
+	Ok(values)
+}
+
+/** 2048 samples for one of the device's arbitrary wave slots,
+each in the range of 0 - 4095. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveData {
+	samples: Vec<u16>,
+}
+
+impl Default for WaveData {
+	/// A flat line in the middle of the range.
+	fn default() -> WaveData {
+		WaveData {
+			samples: vec![ARBITRARY_WAVE_MAX / 2 + 1; ARBITRARY_WAVE_LEN],
+		}
+	}
+}
+
+fn invalid(msg: &str) -> clap::Error {
+	Error::with_description(
+		&format!("Invalid arbitrary wave data. Must be 2048 values in the range of 0 - 4095: {}", msg),
+		ErrorKind::InvalidValue,
+	)
+}
+
+fn io_error(action: &str, e: std::io::Error) -> clap::Error {
+	Error::with_description(&format!("failed {} wave data: {}", action, e), ErrorKind::Io)
+}
+
+impl WaveData {
+	/// Check that there are 2048 samples, all in the range of 0 - 4095.
+	pub fn new(samples: &[u16]) -> Result<WaveData, clap::Error> {
+		if samples.len() != ARBITRARY_WAVE_LEN {
+			return Err(invalid(&format!("Incorrect number of samples: {}", samples.len())));
+		}
+
+		if let Some(i) = samples.iter().position(|s| *s > ARBITRARY_WAVE_MAX) {
+			return Err(invalid(&format!("Number out of range: {}: at sample: {}", samples[i], i + 1)));
+		}
+
+		Ok(WaveData {
+			samples: samples.to_vec(),
+		})
+	}
+
+	/// The samples.
+	pub fn samples(&self) -> &[u16] {
+		&self.samples
+	}
+
+	/// The indices of the samples which differ from another wave's.
+	pub fn diff(&self, other: &WaveData) -> Vec<usize> {
+		self.samples
+			.iter()
+			.zip(other.samples.iter())
+			.enumerate()
+			.filter(|(_, (a, b))| a != b)
+			.map(|(i, _)| i)
+			.collect()
+	}
+
+	/// Parse the device's text format, which must have exactly 2048
+	/// values. See [parse_txt](fn.parse_txt.html) for what's accepted.
+	pub fn from_txt_str(text: &str) -> Result<WaveData, clap::Error> {
+		let values = parse_txt(text)?;
+
+		if values.len() != ARBITRARY_WAVE_LEN {
+			return Err(invalid(&format!(
+				"Incorrect number of values: {}: resample the wave if it isn't meant to be 2048 values long",
+				values.len()
+			)));
+		}
+
+		Ok(WaveData {
+			samples: values.iter().map(|v| v.round() as u16).collect(),
+		})
+	}
+
+	/// Parse lines of the device's text format, as with
+	/// [from_txt_str](#method.from_txt_str).
+	pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<WaveData, clap::Error> {
+		WaveData::from_txt_str(&lines.iter().map(|line| line.as_ref()).collect::<Vec<&str>>().join("\n"))
+	}
+
+	/// Read the device's text format, as with [from_txt_str](#method.from_txt_str).
+	pub fn from_txt<R: Read>(mut reader: R) -> Result<WaveData, clap::Error> {
+		let mut text = String::new();
+
+		reader.read_to_string(&mut text).map_err(|e| io_error("reading", e))?;
+
+		WaveData::from_txt_str(&text)
+	}
+
+	/// Write the device's text format, one number per line, each followed by a newline.
+	pub fn to_txt<W: Write>(&self, mut writer: W) -> Result<(), clap::Error> {
+		writer.write_all(self.to_txt_string().as_bytes()).map_err(|e| io_error("writing", e))
+	}
+
+	/// The device's text format, one number per line, each followed by a newline.
+	pub fn to_txt_string(&self) -> String {
+		self.samples.iter().map(|s| format!("{}\n", s)).collect()
+	}
+
+	/// Read a WaveCAD file, 2048 little-endian signed 16-bit numbers
+	/// in the range of -2048 to 2047. Anything after them is ignored.
+	pub fn from_wavecad<R: Read>(reader: R) -> Result<WaveData, clap::Error> {
+		let mut buf = Vec::with_capacity(WAVECAD_LEN);
+
+		reader
+			.take(WAVECAD_LEN as u64)
+			.read_to_end(&mut buf)
+			.map_err(|e| io_error("reading", e))?;
+
+		if buf.len() != WAVECAD_LEN {
+			return Err(invalid(&format!("WaveCAD data is too short: {} bytes (must be {})", buf.len(), WAVECAD_LEN)));
+		}
+
+		let mut values = [0i16; ARBITRARY_WAVE_LEN];
+
+		LittleEndian::read_i16_into(&buf, &mut values);
+
+		let mut samples = Vec::with_capacity(ARBITRARY_WAVE_LEN);
+
+		for (i, val) in values.iter().enumerate() {
+			let sample = *val as i32 + 2048;
+
+			if sample < 0 || sample > ARBITRARY_WAVE_MAX as i32 {
+				return Err(invalid(&format!("WaveCAD number out of range of -2048 to 2047: {}: at sample: {}", val, i + 1)));
+			}
+
+			samples.push(sample as u16);
+		}
+
+		Ok(WaveData { samples })
+	}
+
+	/// Write a WaveCAD file.
+	pub fn to_wavecad<W: Write>(&self, mut writer: W) -> Result<(), clap::Error> {
+		writer.write_all(&self.to_wavecad_bytes()).map_err(|e| io_error("writing", e))
+	}
+
+	/// The bytes of a WaveCAD file.
+	pub fn to_wavecad_bytes(&self) -> Vec<u8> {
+		let values: Vec<i16> = self.samples.iter().map(|s| *s as i16 - 2048).collect();
+		let mut buf = vec![0u8; WAVECAD_LEN];
+
+		LittleEndian::write_i16_into(&values, &mut buf);
+
+		buf
+	}
+
+	/// The samples separated with commas, as they're sent to and received from the device.
+	pub fn to_command_arg(&self) -> String {
+		self.samples
+			.iter()
+			.map(|s| s.to_string())
+			.collect::<Vec<String>>()
+			.join(COMMAND_ARG_SEPARATOR)
+	}
+}
@@ -0,0 +1,151 @@
+/* Copyright © 2020-2021 Jeremy Carter <jeremy@jeremycarter.ca>
+
+By using this software, you agree to the LICENSE TERMS
+outlined in the file titled LICENSE.md contained in the
+top-level directory of this project. If you don't agree
+to the LICENSE TERMS, you aren't allowed to use this
+software.
+*/
+
+/*! Unit Tests */
+
+use super::wave::*;
+
+fn ramp() -> Vec<u16> {
+	(0..2048).map(|i| i * 2).collect()
+}
+
+#[test]
+pub fn new_ok() {
+	let wave = WaveData::new(&ramp()).unwrap();
+
+	assert_eq!(wave.samples(), &ramp()[..]);
+	assert_eq!(WaveData::default().samples(), &[2048; 2048][..]);
+}
+
+#[test]
+pub fn new_err() {
+	WaveData::new(&ramp()[..2047]).unwrap_err();
+	WaveData::new(&[4096; 2048]).unwrap_err();
+}
+
+#[test]
+pub fn diff_ok() {
+	let mut samples = vec![2048; 2048];
+
+	samples[3] = 0;
+	samples[2047] = 4095;
+
+	assert_eq!(WaveData::default().diff(&WaveData::new(&samples).unwrap()), vec![3, 2047]);
+	assert!(WaveData::default().diff(&WaveData::default()).is_empty());
+}
+
+#[test]
+pub fn txt_ok() {
+	let wave = WaveData::new(&ramp()).unwrap();
+	let txt = wave.to_txt_string();
+
+	assert!(txt.starts_with("0\n2\n4\n"));
+	assert!(txt.ends_with("4094\n"));
+
+	assert_eq!(WaveData::from_txt(txt.as_bytes()).unwrap(), wave);
+
+	let mut out = Vec::new();
+	wave.to_txt(&mut out).unwrap();
+	assert_eq!(out, txt.as_bytes());
+
+	// Windows line endings and extra blank lines at the end are fine.
+	let crlf = txt.replace('\n', "\r\n") + "\r\n\r\n";
+	assert_eq!(WaveData::from_txt(crlf.as_bytes()).unwrap(), wave);
+
+	assert_eq!(WaveData::from_lines(&["2048"; 2048]).unwrap(), WaveData::default());
+}
+
+#[test]
+pub fn txt_err() {
+	let txt = WaveData::default().to_txt_string();
+
+	WaveData::from_txt(&txt.as_bytes()[..txt.len() - 5]).unwrap_err();
+	WaveData::from_txt((txt.clone() + "1\n").as_bytes()).unwrap_err();
+	WaveData::from_txt(txt.replacen("2048", "4096", 1).as_bytes()).unwrap_err();
+	WaveData::from_txt(txt.replacen("2048", "-1", 1).as_bytes()).unwrap_err();
+	WaveData::from_txt(txt.replacen("2048", "abc", 1).as_bytes()).unwrap_err();
+	WaveData::from_txt(&[0xff, 0xfe][..]).unwrap_err();
+}
+
+#[test]
+pub fn parse_txt_ok() {
+	let txt = "\u{feff}# A comment\r\n\r\n0, 1,2\t3 # the rest\r\n  4095,\n\n";
+	assert_eq!(parse_txt(txt).unwrap(), vec![0.0, 1.0, 2.0, 3.0, 4095.0]);
+
+	// A decimal point anywhere makes every value a normalized float.
+	assert_eq!(parse_txt("-1\n0.0\n0.5\n1\n").unwrap(), vec![0.0, 2048.0, 3072.0, 4095.0]);
+	assert_eq!(parse_txt("-5e-1").unwrap(), vec![1024.0]);
+
+	assert_eq!(parse_txt("").unwrap(), Vec::<f64>::new());
+	assert_eq!(parse_txt("# nothing\n").unwrap(), Vec::<f64>::new());
+
+	// Several values on a line, in the wave's text format.
+	let txt: String = (0..512).map(|_| "0.0, 0.0, 0.0, 0.0\n").collect();
+	assert_eq!(WaveData::from_txt_str(&txt).unwrap(), WaveData::default());
+}
+
+#[test]
+pub fn parse_txt_err() {
+	let err = parse_txt("1\n2\n  3, abc\n").unwrap_err();
+	assert!(err.message.contains("line 3, column 6"), "{}", err.message);
+	assert!(err.message.contains("abc"), "{}", err.message);
+
+	let err = parse_txt("1\r\n4096\r\n").unwrap_err();
+	assert!(err.message.contains("line 2, column 1"), "{}", err.message);
+
+	let err = parse_txt("0.5\n\n # x\n 1.5\n").unwrap_err();
+	assert!(err.message.contains("line 4, column 2"), "{}", err.message);
+
+	parse_txt("-1\n").unwrap_err();
+	parse_txt("1;2\n").unwrap_err();
+	parse_txt("0.5 NaN\n").unwrap_err();
+
+	let err = WaveData::from_txt_str("1,2,3").unwrap_err();
+	assert!(err.message.contains("Incorrect number of values: 3"), "{}", err.message);
+}
+
+#[test]
+pub fn wavecad_ok() {
+	let wave = WaveData::new(&ramp()).unwrap();
+	let bytes = wave.to_wavecad_bytes();
+
+	assert_eq!(bytes.len(), WAVECAD_LEN);
+	assert_eq!(&bytes[..4], &[0x00, 0xf8, 0x02, 0xf8]);
+
+	assert_eq!(WaveData::from_wavecad(&bytes[..]).unwrap(), wave);
+
+	let mut out = Vec::new();
+	wave.to_wavecad(&mut out).unwrap();
+	assert_eq!(out, bytes);
+
+	// Anything after the wave is ignored.
+	let mut longer = bytes.clone();
+	longer.extend_from_slice(&[1, 2, 3]);
+	assert_eq!(WaveData::from_wavecad(&longer[..]).unwrap(), wave);
+}
+
+#[test]
+pub fn wavecad_err() {
+	let bytes = WaveData::default().to_wavecad_bytes();
+
+	WaveData::from_wavecad(&bytes[..WAVECAD_LEN - 1]).unwrap_err();
+
+	let mut out_of_range = bytes;
+	out_of_range[0..2].copy_from_slice(&2048i16.to_le_bytes());
+	WaveData::from_wavecad(&out_of_range[..]).unwrap_err();
+}
+
+#[test]
+pub fn to_command_arg_ok() {
+	let arg = WaveData::new(&ramp()).unwrap().to_command_arg();
+
+	assert!(arg.starts_with("0,2,4,"));
+	assert!(arg.ends_with(",4092,4094"));
+	assert_eq!(arg.matches(',').count(), 2047);
+}
@@ -14,11 +14,24 @@ extern crate signal_gen_cjds66_lib;
 extern crate clap;
 
 use signal_gen_cjds66_lib::command::*;
+use signal_gen_cjds66_lib::analyze::*;
+use signal_gen_cjds66_lib::catalog::*;
+use signal_gen_cjds66_lib::convert::*;
+use signal_gen_cjds66_lib::export::*;
+use signal_gen_cjds66_lib::fourier::*;
+use signal_gen_cjds66_lib::ops::*;
+use signal_gen_cjds66_lib::plot::*;
 use signal_gen_cjds66_lib::error;
 use signal_gen_cjds66_lib::error::From;
+use signal_gen_cjds66_lib::register::*;
+use signal_gen_cjds66_lib::render::*;
+use signal_gen_cjds66_lib::resample::*;
 use signal_gen_cjds66_lib::serial::*;
+use signal_gen_cjds66_lib::transfer::*;
+use signal_gen_cjds66_lib::units::*;
 
-use clap::{values_t, App, ErrorKind};
+use clap::{value_t, values_t, App, Arg, ArgMatches, ErrorKind};
+use std::io::{self, IsTerminal};
 
 fn main() {
 	let res = real_main();
@@ -26,11 +39,111 @@ fn main() {
 	std::process::exit(error::handle_exit(res).map_or_else(|e| e.code, |code| code));
 }
 
+/** The flags which change settings that the device can't read back,
+so the --verify flag can't check them, along with their long names. */
+const UNVERIFIED_FLAGS: [(&str, &str); 14] = [
+	("set_tracking", "--track"),
+	("set_measurement_coupling_ac", "--ac"),
+	("set_measurement_coupling_dc", "--dc"),
+	("set_measurement_count_frequency", "--cf"),
+	("set_measurement_counting_period", "--cp"),
+	("set_burst_manual_trigger", "--bm"),
+	("set_burst_ch2", "--bc"),
+	("set_burst_external_ac", "--ba"),
+	("set_burst_external_dc", "--bd"),
+	("set_sweep_direction_rise", "--sdr"),
+	("set_sweep_direction_fall", "--sdf"),
+	("set_sweep_direction_rise_fall", "--sdrf"),
+	("set_sweep_linear", "--sml"),
+	("set_sweep_logarithm", "--smg"),
+];
+
+/** The error for the catalogue commands when there's nowhere to keep
+the catalogues. */
+fn no_catalog_dir() -> clap::Error {
+	clap::Error::with_description(
+		"there's no default place to keep the arbitrary wave catalogues, so please choose one with --catalog-dir",
+		ErrorKind::MissingRequiredArgument,
+	)
+}
+
+/** The help text for each command-line flag in the register table,
+along with whether the flag takes a value. */
+fn register_arg_helps() -> Vec<(&'static Register, &'static RegisterFlag, String, bool)> {
+	let mut helps = Vec::new();
+
+	for reg in REGISTERS.iter() {
+		for (i, flag) in reg.set_flags.iter().enumerate() {
+			helps.push((*reg, flag, reg.help(i as u64 + 1), true));
+		}
+
+		for (i, flag) in reg.get_flags.iter().enumerate() {
+			helps.push((*reg, flag, reg.get_help(i as u64 + 1), false));
+		}
+	}
+
+	helps
+}
+
+/** The total harmonic distortion of a Fourier series, for showing
+with the conversion report. */
+fn fourier_thd(series: &FourierSeries) -> String {
+	match series.thd() {
+		Some(thd) => format!("\nTHD of the harmonics: {:.4}%", thd * 100.0),
+		None => "\nTHD of the harmonics: no fundamental".to_string(),
+	}
+}
+
+/** Build the command-line arguments for the register table. */
+fn register_args<'a>(helps: &'a [(&'static Register, &'static RegisterFlag, String, bool)]) -> Vec<Arg<'a, 'a>> {
+	helps
+		.iter()
+		.map(|(reg, flag, help, takes_value)| {
+			let mut arg = Arg::with_name(flag.name).long(flag.long).help(help);
+
+			if let Some(short) = flag.short {
+				arg = arg.short(short);
+			}
+
+			if *takes_value {
+				arg = arg
+					.takes_value(true)
+					.value_name(flag.value_name)
+					.allow_hyphen_values(reg.min < 0.0);
+			}
+
+			arg
+		})
+		.collect()
+}
+
+/** The register flags which were given, either the set flags or the
+get flags, in the order they were given on the command line. Each one
+comes with its register and the channel it's for. */
+fn register_flags_given(matches: &ArgMatches, set: bool) -> Vec<(&'static Register, u64, &'static RegisterFlag)> {
+	let mut given = Vec::new();
+
+	for reg in REGISTERS.iter() {
+		let flags = if set { reg.set_flags } else { reg.get_flags };
+
+		for (i, flag) in flags.iter().enumerate() {
+			if let Some(index) = matches.index_of(flag.name) {
+				given.push((index, *reg, i as u64 + 1, flag));
+			}
+		}
+	}
+
+	given.sort_by_key(|(index, _, _, _)| *index);
+
+	given.into_iter().map(|(_, reg, chan, flag)| (reg, chan, flag)).collect()
+}
+
 fn real_main() -> Result<i32, error::Error> {
 	let mut err: Option<error::Error> = None;
 
 	let yaml = load_yaml!("../clap.yaml");
-	let app = App::from_yaml(yaml);
+	let register_helps = register_arg_helps();
+	let app = App::from_yaml(yaml).args(&register_args(&register_helps));
 
 	let matches = app
 		.clone()
@@ -73,12 +186,86 @@ fn real_main() -> Result<i32, error::Error> {
 
 	/* ----- Utility commands ----- */
 
+	// Which channel to read from multichannel .wav audio files.
+	let wav_channel = value_t!(matches, "wav_channel", u16).map_err(error::Error::from_clap_error)?;
+
+	// How to fit waveform files and stdin of any length to the device.
+	let mut resample = ResampleOptions {
+		single_period: matches.is_present("single_period"),
+		..ResampleOptions::default()
+	};
+
+	if let Some(method) = matches.value_of("resample") {
+		resample.interpolation = Some(Interpolation::parse(method).map_err(error::Error::from_clap_error)?);
+	}
+
+	if let Some(window) = matches.value_of("wave_window") {
+		resample.window = Some(TimeWindow::parse(window).map_err(error::Error::from_clap_error)?);
+	}
+
+	// How to scale waveform files to the device's 12-bit range.
+	let mut convert = ConvertOptions {
+		remove_dc: matches.is_present("remove_dc"),
+		dither: matches.is_present("dither"),
+		fail_on_clip: matches.is_present("fail_on_clip"),
+		..ConvertOptions::default()
+	};
+
+	if matches.is_present("normalize") {
+		convert.normalize = Some(value_t!(matches, "normalize", f64).map_err(error::Error::from_clap_error)?);
+	}
+
+	if matches.is_present("gain") {
+		convert.gain_db = value_t!(matches, "gain", f64).map_err(error::Error::from_clap_error)?;
+	}
+
+	convert.validate().map_err(error::Error::from_clap_error)?;
+
+	// How many times to upload an arbitrary wave again if it doesn't
+	// read back the same in write-verify mode.
+	let wave_retries = value_t!(matches, "wave_retries", u32).map_err(error::Error::from_clap_error)?;
+
+	// Where to keep the catalogues of the devices' arbitrary wave slots.
+	let catalog_dir = matches.value_of("catalog_dir").map(String::from).or_else(default_catalog_dir);
+
+	// How to plot a wave in the terminal.
+	let (plot_width, plot_height) = PlotOptions::parse_size(matches.value_of("plot_size").unwrap_or_default()).map_err(error::Error::from_clap_error)?;
+
+	let plot = PlotOptions {
+		width: plot_width,
+		height: plot_height,
+		style: PlotStyle::parse(matches.value_of("plot_style").unwrap_or_default()).map_err(error::Error::from_clap_error)?,
+		color: io::stdout().is_terminal(),
+	};
+
+	// A second wave to draw over the plot, to compare them.
+	let plot_compare = match matches.value_of("plot_compare") {
+		Some(source) => Some(Source::parse(source).map_err(error::Error::from_clap_error)?),
+		None => None,
+	};
+
+	let plot_compare_slot = matches!(plot_compare, Some(Source::Slot(_)));
+
 	// If wav to txt is requested.
 	if matches.is_present("wav_to_txt") {
 		let path = matches.value_of("wav_to_txt").unwrap_or_default();
 
-		match wav_to_txt(path, verbose) {
-			Ok(_res) => {
+		let res = wav_to_txt(path, matches.value_of("out_file"), wav_channel, &resample, &convert, verbose).and_then(|(res, report)| {
+			if matches.is_present("plot") {
+				plot_wave(None, &res, plot_compare.as_ref(), &plot, wav_channel, &resample, verbose)?;
+			}
+
+			Ok((res, report))
+		});
+
+		match res {
+			Ok((_res, report)) => {
+				if verbose > 0 {
+					println!("\nConversion report:\n{}", report);
+				} else if report.clipped() > 0 {
+					println!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+				}
+
 				return Ok(0);
 			}
 			Err(e) => {
@@ -97,7 +284,48 @@ fn real_main() -> Result<i32, error::Error> {
 
 		let output_binary = matches.is_present("output_binary");
 
-		match txt_to_wav(path, output_binary, verbose) {
+		match txt_to_wav(path, matches.value_of("out_file"), output_binary, verbose) {
+			Ok(_res) => {
+				return Ok(0);
+			}
+			Err(e) => {
+				if e.kind != ErrorKind::Io {
+					println!("{}", e);
+				}
+
+				err = Some(error::Error::from_clap_error(e));
+			}
+		}
+	}
+
+	// How to show the analysis of a wave.
+	let analysis = AnalysisOptions {
+		harmonics: value_t!(matches, "analyze_harmonics", usize).map_err(error::Error::from_clap_error)?,
+		format: AnalysisFormat::parse(matches.value_of("analysis_format").unwrap_or_default()).map_err(error::Error::from_clap_error)?,
+	};
+
+	// If an analysis of a wave file is requested.
+	if matches.is_present("analyze") {
+		let path = matches.value_of("analyze").unwrap_or_default();
+
+		match analyze_wave_file(path, wav_channel, &resample, &convert, &analysis, verbose) {
+			Ok(_res) => {
+				return Ok(0);
+			}
+			Err(e) => {
+				if e.kind != ErrorKind::Io {
+					println!("{}", e);
+				}
+
+				err = Some(error::Error::from_clap_error(e));
+			}
+		}
+	}
+
+	// If a plot of a wave file is requested, without a saved
+	// arbitrary wave to compare it with.
+	if let Some(path) = matches.value_of("plot").filter(|_path| !plot_compare_slot) {
+		match plot_wave_file(path, plot_compare.as_ref(), &plot, wav_channel, &resample, &convert, verbose) {
 			Ok(_res) => {
 				return Ok(0);
 			}
@@ -111,6 +339,169 @@ fn real_main() -> Result<i32, error::Error> {
 		}
 	}
 
+	// How to draw a wave as an image.
+	let (render_width, render_height) = RenderOptions::parse_size(matches.value_of("render_size").unwrap_or_default()).map_err(error::Error::from_clap_error)?;
+
+	let render = RenderOptions {
+		width: render_width,
+		height: render_height,
+		amplitude: match matches.value_of("render_amplitude") {
+			Some(amount) => Some(parse_quantity(amount, Quantity::Voltage).map_err(error::Error::from_clap_error)?.base),
+			None => None,
+		},
+		offset: parse_quantity(matches.value_of("render_offset").unwrap_or_default(), Quantity::Voltage).map_err(error::Error::from_clap_error)?.base,
+		frequency: match matches.value_of("render_frequency") {
+			Some(amount) => Some(parse_quantity(amount, Quantity::Frequency).map_err(error::Error::from_clap_error)?.base),
+			None => None,
+		},
+		spectrum: matches.is_present("render_spectrum"),
+		harmonics: analysis.harmonics,
+		title: matches.value_of("render_title").map(String::from),
+	};
+
+	// The wave to draw, and where to save the image.
+	let render_args: Vec<&str> = matches.values_of("render").map(|values| values.collect()).unwrap_or_default();
+
+	let render_source = match render_args.first() {
+		Some(source) => match Source::parse(source).map_err(error::Error::from_clap_error)? {
+			Source::Current => {
+				return Err(error::Error::with_description(&format!("unsupported waveform to render (must be a file or slot:N): {}", source), clap::ErrorKind::InvalidValue));
+			}
+			source => Some(source),
+		},
+		None => None,
+	};
+
+	let render_path = render_args.get(1).copied().unwrap_or_default();
+
+	// If an image of a wave file is requested.
+	if let Some(Source::File(path)) = &render_source {
+		match render_wave_file(path, render_path, &render, wav_channel, &resample, &convert, verbose) {
+			Ok(_res) => {
+				return Ok(0);
+			}
+			Err(e) => {
+				if e.kind != ErrorKind::Io {
+					println!("{}", e);
+				}
+
+				err = Some(error::Error::from_clap_error(e));
+			}
+		}
+	}
+
+	// How to save a wave in another generator's file format.
+	let export = ExportOptions {
+		format: match matches.value_of("export_format") {
+			Some(format) => Some(ExportFormat::parse(format).map_err(error::Error::from_clap_error)?),
+			None => None,
+		},
+		frequency: parse_quantity(matches.value_of("export_frequency").unwrap_or_default(), Quantity::Frequency).map_err(error::Error::from_clap_error)?.base,
+		amplitude: parse_quantity(matches.value_of("export_amplitude").unwrap_or_default(), Quantity::Voltage).map_err(error::Error::from_clap_error)?.base,
+		offset: parse_quantity(matches.value_of("export_offset").unwrap_or_default(), Quantity::Voltage).map_err(error::Error::from_clap_error)?.base,
+	};
+
+	export.validate().map_err(error::Error::from_clap_error)?;
+
+	// The wave to export, and where to save it.
+	let export_args: Vec<&str> = matches.values_of("export").map(|values| values.collect()).unwrap_or_default();
+
+	let export_source = match export_args.first() {
+		Some(source) => match Source::parse(source).map_err(error::Error::from_clap_error)? {
+			Source::Current => {
+				return Err(error::Error::with_description(&format!("unsupported waveform to export (must be a file or slot:N): {}", source), clap::ErrorKind::InvalidValue));
+			}
+			source => Some(source),
+		},
+		None => None,
+	};
+
+	let export_path = export_args.get(1).copied().unwrap_or_default();
+
+	// If a wave file is requested in another generator's format.
+	if let Some(Source::File(path)) = &export_source {
+		match export_wave_file(path, export_path, &export, wav_channel, &resample, &convert, verbose) {
+			Ok(_res) => {
+				return Ok(0);
+			}
+			Err(e) => {
+				if e.kind != ErrorKind::Io {
+					println!("{}", e);
+				}
+
+				err = Some(error::Error::from_clap_error(e));
+			}
+		}
+	}
+
+	// A recipe of harmonics for a generated wave.
+	let fourier = if let Some(list) = matches.value_of("fourier_wave") {
+		Some(FourierSeries::parse(list).map_err(error::Error::from_clap_error)?)
+	} else if let Some(path) = matches.value_of("fourier_csv") {
+		Some(FourierSeries::load_csv(path).map_err(error::Error::from_clap_error)?)
+	} else {
+		None
+	};
+
+	let auto_scale = !matches.is_present("no_auto_scale");
+
+	// A pipeline of operations on waves, for a generated wave.
+	let pipeline = match matches.value_of("wave_op") {
+		Some(pipeline) => Some(WavePipeline::parse(pipeline).map_err(error::Error::from_clap_error)?),
+		None => None,
+	};
+
+	// If a generated wave is requested, without uploading it.
+	if !matches.is_present("wave_slot") {
+		let out_file = matches.value_of("out_file");
+		let mut thd = None;
+
+		let res = if let Some(spec) = matches.value_of("gen_wave") {
+			Some(gen_wave(spec, out_file, &convert, verbose))
+		} else if let Some(formula) = matches.value_of("expr_wave") {
+			Some(expr_wave(formula, out_file, auto_scale, &convert, verbose))
+		} else if let Some(pipeline) = pipeline.as_ref().filter(|pipeline| !pipeline.uses_slots()) {
+			Some(wave_op(None, pipeline, out_file, wav_channel, &resample, &convert, verbose))
+		} else {
+			fourier.as_ref().map(|series| {
+				thd = Some(fourier_thd(series));
+
+				fourier_wave(series, out_file, &convert, verbose)
+			})
+		};
+
+		match res {
+			Some(Ok((_res, report))) => {
+				if verbose > 0 {
+					if let Some(thd) = thd {
+						eprintln!("{}", thd);
+					}
+
+					eprintln!("\nConversion report:\n{}", report);
+				} else if report.clipped() > 0 {
+					eprintln!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+				}
+
+				return Ok(0);
+			}
+			Some(Err(e)) => {
+				if e.kind != ErrorKind::Io {
+					println!("{}", e);
+				}
+
+				err = Some(error::Error::from_clap_error(e));
+			}
+			None => {}
+		}
+	}
+
+	// If a list of the registers is requested.
+	if matches.is_present("list_registers") {
+		print!("{}", registers_markdown());
+
+		return Ok(0);
+	}
+
 	/* ----- END Utility commands ----- */
 
 	/* ----- Command that selects the devices
@@ -152,6 +543,50 @@ fn real_main() -> Result<i32, error::Error> {
 			|mut port| {
 				let mut err: Option<error::Error> = None;
 
+				// If write-verify mode is requested.
+				port.verify = matches.is_present("verify");
+				port.wave_retries = wave_retries;
+
+				if port.verify {
+					let unverified: Vec<&str> = UNVERIFIED_FLAGS
+						.iter()
+						.filter(|(name, _long)| matches.is_present(name))
+						.map(|(_name, long)| *long)
+						.collect();
+
+					if !unverified.is_empty() {
+						println!("warning: the device can't read these settings back, so --verify won't check them: {}", unverified.join(", "));
+					}
+				}
+
+				// Show a progress bar for each arbitrary wave transfer.
+				if !matches.is_present("no_progress") && io::stderr().is_terminal() {
+					port.progress = Some(Box::new(|progress: &Progress| {
+						eprint!("\r{}", progress.bar(40));
+
+						if progress.is_done() {
+							eprintln!();
+						}
+					}));
+				}
+
+				// If a load is specified for either channel.
+				for (i, name) in ["load_channel1", "load_channel2"].iter().enumerate() {
+					if matches.is_present(name) {
+						let load = matches.value_of(name).unwrap_or_default();
+
+						match Load::parse(load) {
+							Ok(load) => {
+								port.load[i] = load;
+							},
+							Err(e) => {
+								err = Some(error::Error::from_clap_error(e));
+								println!("{}", err.as_ref().unwrap());
+							},
+						}
+					}
+				}
+
 				/* ----- Commands which retrieve values from
 						 the device.                         ----- */
 
@@ -172,11 +607,17 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If read arbitrary wave is requested.
+				// If read arbitrary wave is requested, or a plot of it.
 				if matches.is_present("get_arbitrary_wave") {
 					let arg = matches.value_of("get_arbitrary_wave").unwrap_or_default();
 
-					match get_arbitrary_wave(&mut port, arg, verbose) {
+					let res = if matches.is_present("plot") {
+						plot_arbitrary_wave(&mut port, arg, plot_compare.as_ref(), &plot, wav_channel, &resample, verbose).map(|_res| ())
+					} else {
+						get_arbitrary_wave(&mut port, arg, verbose).map(|_res| ())
+					};
+
+					match res {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -187,33 +628,15 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If get channel output is requested.
-				if matches.is_present("get_channel_output") {
-					match get_channel_output(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
+				// If a plot of a wave file is requested, compared with a
+				// saved arbitrary wave.
+				if let Some(path) = matches.value_of("plot").filter(|_path| plot_compare_slot) {
+					let res = load_wave_file(path, wav_channel, &resample, &convert, verbose)
+						.and_then(|wave| plot_wave(Some(&mut port), &wave, plot_compare.as_ref(), &plot, wav_channel, &resample, verbose));
 
-				// If get waveform for channel1 is requested.
-				if matches.is_present("get_waveform_channel1") {
-					match get_waveform_preset(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match res {
+						Ok(_res) => {
 						},
-					}
-				}
-
-				// If get waveform for channel2 is requested.
-				if matches.is_present("get_waveform_channel2") {
-					match get_waveform_preset(&mut port, 2, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -222,22 +645,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If get frequency for channel1 is requested.
-				if matches.is_present("get_frequency_channel1") {
-					match get_frequency(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+				// If an image of a saved arbitrary wave is requested.
+				if let Some(Source::Slot(amount)) = &render_source {
+					match render_arbitrary_wave(&mut port, &amount.to_string(), render_path, &render, verbose) {
+						Ok(_res) => {
 						},
-					}
-				}
-
-
-				// If get frequency for channel1 is requested.
-				if matches.is_present("get_frequency_channel2") {
-					match get_frequency(&mut port, 2, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -246,22 +658,12 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If get frequency for channel1 in hertz is requested.
-				if matches.is_present("get_frequency_hertz_channel1") {
-					match get_frequency_hertz(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+				// If a saved arbitrary wave is requested in another
+				// generator's format.
+				if let Some(Source::Slot(amount)) = &export_source {
+					match export_arbitrary_wave(&mut port, &amount.to_string(), export_path, &export, verbose) {
+						Ok(_res) => {
 						},
-					}
-				}
-
-
-				// If get frequency for channel1 in hertz is requested.
-				if matches.is_present("get_frequency_hertz_channel2") {
-					match get_frequency_hertz(&mut port, 2, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -270,33 +672,13 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If get amplitude for channel1 in volts is requested.
-				if matches.is_present("get_amplitude_volts_channel1") {
-					match get_amplitude(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
+				// If an analysis of a saved arbitrary wave is requested.
+				if matches.is_present("analyze_slot") {
+					let arg = matches.value_of("analyze_slot").unwrap_or_default();
 
-				// If get frequency for channel2 in MHz is requested.
-				if matches.is_present("get_amplitude_volts_channel2") {
-					match get_amplitude(&mut port, 2, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match analyze_arbitrary_wave(&mut port, arg, &analysis, verbose) {
+						Ok(_res) => {
 						},
-					}
-				}
-
-
-				// If get duty cycle for channel1 in percent is requested.
-				if matches.is_present("get_duty_cycle_channel1") {
-					match get_duty_cycle(&mut port, 1, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -304,33 +686,14 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If get duty cycle for channel2 in percent is requested.
-				if matches.is_present("get_duty_cycle_channel2") {
-					match get_duty_cycle(&mut port, 2, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
 
+				// If a backup of all the saved arbitrary waves is requested.
+				if matches.is_present("backup_arb") {
+					let arg = matches.value_of("backup_arb").unwrap_or_default();
 
-				// If get voltage offset for channel1 in volts is requested.
-				if matches.is_present("get_voltage_offset_channel1") {
-					match get_voltage_offset(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match backup_arbitrary_waves(&mut port, arg, verbose) {
+						Ok(_res) => {
 						},
-					}
-				}
-
-				// If get voltage offset for channel2 in volts is requested.
-				if matches.is_present("get_voltage_offset_channel2") {
-					match get_voltage_offset(&mut port, 2, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -339,44 +702,25 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If get phase in degrees is requested.
-				if matches.is_present("get_phase") {
-					match get_phase(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
+				// If naming a saved arbitrary wave in the catalogue is requested.
+				if matches.is_present("name_arb") {
+					let args: Vec<&str> = matches.values_of("name_arb").unwrap_or_default().collect();
 
+					let res = catalog_dir.as_deref().ok_or_else(no_catalog_dir).and_then(|dir| {
+						name_arbitrary_wave(
+							&mut port,
+							dir,
+							args[0],
+							args[1],
+							matches.value_of("arb_source"),
+							matches.value_of("arb_description"),
+							verbose,
+						)
+					});
 
-				// If get measurement count is requested.
-				if matches.is_present("get_measurement_count") {
-					match get_measurement_count(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-				// If get measurement frequency is requested.
-				if matches.is_present("get_measurement_frequency") {
-					match get_measurement_frequency(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match res {
+						Ok(_res) => {
 						},
-					}
-				}
-
-				// If get measurement frequency period is requested.
-				if matches.is_present("get_measurement_frequency_period") {
-					match get_measurement_frequency_period(&mut port, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -384,43 +728,14 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If get measurement pulse width positive is requested.
-				if matches.is_present("get_measurement_pulse_width_positive") {
-					match get_measurement_pulse_width_positive(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
 
-				// If get measurement pulse width negative is requested.
-				if matches.is_present("get_measurement_pulse_width_negative") {
-					match get_measurement_pulse_width_negative(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
+				// If checking the saved arbitrary waves against the catalogue is requested.
+				if matches.is_present("sync_arb_catalog") {
+					let res = catalog_dir.as_deref().ok_or_else(no_catalog_dir).and_then(|dir| sync_arbitrary_wave_catalog(&mut port, dir, verbose));
 
-				// If get measurement period is requested.
-				if matches.is_present("get_measurement_period") {
-					match get_measurement_period(&mut port, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match res {
+						Ok(_res) => {
 						},
-					}
-				}
-
-				// If get measurement duty cycle is requested.
-				if matches.is_present("get_measurement_duty_cycle") {
-					match get_measurement_duty_cycle(&mut port, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -429,31 +744,13 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				/* ----- END Commands which retrieve values from
-						 the device.                             ----- */
-
-
-
+				// If the catalogue of the saved arbitrary waves is requested.
+				if matches.is_present("list_arb") {
+					let res = catalog_dir.as_deref().ok_or_else(no_catalog_dir).and_then(|dir| list_arbitrary_waves(&mut port, dir, verbose));
 
-				/* ----- Commands which navigate to a different
-						 view on the device's display panel.    ----- */
-
-
-				// If set switch function panel main ch1 is requested.
-				if matches.is_present("switch_main_ch1") {
-					match switch_function_panel_main(&mut port, 1, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
+					match res {
+						Ok(_res) => {
 						},
-					}
-				}
-
-				// If set switch function panel main ch2 is requested.
-				if matches.is_present("switch_main_ch2") {
-					match switch_function_panel_main(&mut port, 2, verbose) {
-						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -462,10 +759,16 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel system settings is requested.
-				if matches.is_present("switch_sys") {
-					match switch_function_panel_sys(&mut port, verbose) {
-						Ok(_res) => {
+				// If wave operations which read from the saved arbitrary
+				// waves are requested, without uploading the result.
+				if let Some(pipeline) = pipeline.as_ref().filter(|pipeline| pipeline.uses_slots() && !matches.is_present("wave_slot")) {
+					match wave_op(Some(&mut port), pipeline, matches.value_of("out_file"), wav_channel, &resample, &convert, verbose) {
+						Ok((_res, report)) => {
+							if verbose > 0 {
+								eprintln!("\nConversion report:\n{}", report);
+							} else if report.clipped() > 0 {
+								eprintln!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+							}
 						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -475,11 +778,10 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel counting is requested.
-				if matches.is_present("switch_counting") {
-					match switch_function_panel_counting(&mut port, verbose) {
-						Ok(_res) => {
-						},
+				// If get channel output is requested.
+				if matches.is_present("get_channel_output") {
+					match get_channel_output(&mut port, verbose) {
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -488,9 +790,9 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel sweep ch1 is requested.
-				if matches.is_present("switch_sweep_ch1") {
-					match switch_function_panel_sweep(&mut port, 1, verbose) {
+				// If get frequency for channel1 is requested.
+				if matches.is_present("get_frequency_channel1") {
+					match get_frequency(&mut port, 1, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -499,9 +801,10 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set switch function panel sweep ch2 is requested.
-				if matches.is_present("switch_sweep_ch2") {
-					match switch_function_panel_sweep(&mut port, 2, verbose) {
+
+				// If get frequency for channel1 is requested.
+				if matches.is_present("get_frequency_channel2") {
+					match get_frequency(&mut port, 2, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -511,11 +814,10 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel pulse is requested.
-				if matches.is_present("switch_pulse") {
-					match switch_function_panel_pulse(&mut port, verbose) {
-						Ok(_res) => {
-						},
+				// If get frequency for channel1 in hertz is requested.
+				if matches.is_present("get_frequency_hertz_channel1") {
+					match get_frequency_hertz(&mut port, 1, verbose) {
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -524,11 +826,10 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel bursting is requested.
-				if matches.is_present("switch_burst") {
-					match switch_function_panel_bursting(&mut port, verbose) {
-						Ok(_res) => {
-						},
+				// If get frequency for channel1 in hertz is requested.
+				if matches.is_present("get_frequency_hertz_channel2") {
+					match get_frequency_hertz(&mut port, 2, verbose) {
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -537,11 +838,12 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set switch function panel measurement is requested.
-				if matches.is_present("switch_measurement") {
-					match switch_function_panel_measurement(&mut port, verbose) {
-						Ok(_res) => {
-						},
+				// If get amplitude for channel1 in a certain unit is requested.
+				if matches.is_present("get_amplitude_unit_channel1") {
+					let unit = matches.value_of("get_amplitude_unit_channel1").unwrap_or_default();
+
+					match AmplitudeUnit::parse(unit).and_then(|unit| get_amplitude_as(&mut port, 1, unit, verbose)) {
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -549,23 +851,11 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
+				// If get amplitude for channel2 in a certain unit is requested.
+				if matches.is_present("get_amplitude_unit_channel2") {
+					let unit = matches.value_of("get_amplitude_unit_channel2").unwrap_or_default();
 
-				/* ----- END Commands which navigate to a different
-						 view on the device's display panel.        ----- */
-
-
-
-
-				/* ----- Commands which change the device's
-						 settings or state, but don't
-						 activate the channels.             ----- */
-
-
-				// If set tracking mode is requested.
-				if matches.is_present("set_tracking") {
-					let track = matches.value_of("set_tracking").unwrap_or_default();
-
-					match set_tracking(&mut port, track, verbose) {
+					match AmplitudeUnit::parse(unit).and_then(|unit| get_amplitude_as(&mut port, 2, unit, verbose)) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -575,11 +865,10 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set waveform for channel1 is requested.
-				if matches.is_present("set_waveform_channel1") {
-					let preset = matches.value_of("set_waveform_channel1").unwrap_or_default();
-
-					match set_waveform_preset(&mut port, 1, preset, verbose) {
+				// If any of the settings in the register table are being read,
+				// in the order they were given.
+				for (reg, chan, _flag) in register_flags_given(&matches, false) {
+					match get_register(&mut port, reg, chan, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -588,11 +877,10 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set waveform for channel2 is requested.
-				if matches.is_present("set_waveform_channel2") {
-					let preset = matches.value_of("set_waveform_channel2").unwrap_or_default();
 
-					match set_waveform_preset(&mut port, 2, preset, verbose) {
+				// If get measurement count is requested.
+				if matches.is_present("get_measurement_count") {
+					match get_measurement_count(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -601,11 +889,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set arbitrary waveform for channel1 is requested.
-				if matches.is_present("set_arbitrary_waveform_channel1") {
-					let preset = matches.value_of("set_arbitrary_waveform_channel1").unwrap_or_default();
-
-					match set_waveform_preset_arbitrary(&mut port, 1, preset, verbose) {
+				// If get measurement frequency is requested.
+				if matches.is_present("get_measurement_frequency") {
+					match get_measurement_frequency(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -614,11 +900,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set arbitrary waveform for channel2 is requested.
-				if matches.is_present("set_arbitrary_waveform_channel2") {
-					let preset = matches.value_of("set_arbitrary_waveform_channel2").unwrap_or_default();
-
-					match set_waveform_preset_arbitrary(&mut port, 2, preset, verbose) {
+				// If get measurement frequency period is requested.
+				if matches.is_present("get_measurement_frequency_period") {
+					match get_measurement_frequency_period(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -627,12 +911,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-
-				// If set frequency for channel1 in uHz is requested.
-				if matches.is_present("set_frequency_uhz_channel1") {
-					let amount = matches.value_of("set_frequency_uhz_channel1").unwrap_or_default();
-
-					match set_frequency_microhertz(&mut port, 1, amount, verbose) {
+				// If get measurement pulse width positive is requested.
+				if matches.is_present("get_measurement_pulse_width_positive") {
+					match get_measurement_pulse_width_positive(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -641,11 +922,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in uHz is requested.
-				if matches.is_present("set_frequency_uhz_channel2") {
-					let amount = matches.value_of("set_frequency_uhz_channel2").unwrap_or_default();
-
-					match set_frequency_microhertz(&mut port, 2, amount, verbose) {
+				// If get measurement pulse width negative is requested.
+				if matches.is_present("get_measurement_pulse_width_negative") {
+					match get_measurement_pulse_width_negative(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -654,11 +933,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel1 in mHz is requested.
-				if matches.is_present("set_frequency_millihz_channel1") {
-					let amount = matches.value_of("set_frequency_millihz_channel1").unwrap_or_default();
-
-					match set_frequency_millihertz(&mut port, 1, amount, verbose) {
+				// If get measurement period is requested.
+				if matches.is_present("get_measurement_period") {
+					match get_measurement_period(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -667,11 +944,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in mHz is requested.
-				if matches.is_present("set_frequency_millihz_channel2") {
-					let amount = matches.value_of("set_frequency_millihz_channel2").unwrap_or_default();
-
-					match set_frequency_millihertz(&mut port, 2, amount, verbose) {
+				// If get measurement duty cycle is requested.
+				if matches.is_present("get_measurement_duty_cycle") {
+					match get_measurement_duty_cycle(&mut port, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -680,11 +955,20 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel1 in Hz is requested.
-				if matches.is_present("set_frequency_hz_channel1") {
-					let amount = matches.value_of("set_frequency_hz_channel1").unwrap_or_default();
 
-					match set_frequency_hertz(&mut port, 1, amount, verbose) {
+				/* ----- END Commands which retrieve values from
+						 the device.                             ----- */
+
+
+
+
+				/* ----- Commands which navigate to a different
+						 view on the device's display panel.    ----- */
+
+
+				// If set switch function panel main ch1 is requested.
+				if matches.is_present("switch_main_ch1") {
+					match switch_function_panel_main(&mut port, 1, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -693,11 +977,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in Hz is requested.
-				if matches.is_present("set_frequency_hz_channel2") {
-					let amount = matches.value_of("set_frequency_hz_channel2").unwrap_or_default();
-
-					match set_frequency_hertz(&mut port, 2, amount, verbose) {
+				// If set switch function panel main ch2 is requested.
+				if matches.is_present("switch_main_ch2") {
+					match switch_function_panel_main(&mut port, 2, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -706,12 +988,12 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel1 in kHz is requested.
-				if matches.is_present("set_frequency_khz_channel1") {
-					let amount = matches.value_of("set_frequency_khz_channel1").unwrap_or_default();
 
-					match set_frequency_kilohertz(&mut port, 1, amount, verbose) {
-						Ok(_res) => {},
+				// If set switch function panel system settings is requested.
+				if matches.is_present("switch_sys") {
+					match switch_function_panel_sys(&mut port, verbose) {
+						Ok(_res) => {
+						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -719,12 +1001,12 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in kHz is requested.
-				if matches.is_present("set_frequency_khz_channel2") {
-					let amount = matches.value_of("set_frequency_khz_channel2").unwrap_or_default();
 
-					match set_frequency_kilohertz(&mut port, 2, amount, verbose) {
-						Ok(_res) => {},
+				// If set switch function panel counting is requested.
+				if matches.is_present("switch_counting") {
+					match switch_function_panel_counting(&mut port, verbose) {
+						Ok(_res) => {
+						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -732,11 +1014,10 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel1 in MHz is requested.
-				if matches.is_present("set_frequency_mega_channel1") {
-					let amount = matches.value_of("set_frequency_mega_channel1").unwrap_or_default();
 
-					match set_frequency_megahertz(&mut port, 1, amount, verbose) {
+				// If set switch function panel sweep ch1 is requested.
+				if matches.is_present("switch_sweep_ch1") {
+					match switch_function_panel_sweep(&mut port, 1, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -745,11 +1026,9 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in MHz is requested.
-				if matches.is_present("set_frequency_mega_channel2") {
-					let amount = matches.value_of("set_frequency_mega_channel2").unwrap_or_default();
-
-					match set_frequency_megahertz(&mut port, 2, amount, verbose) {
+				// If set switch function panel sweep ch2 is requested.
+				if matches.is_present("switch_sweep_ch2") {
+					match switch_function_panel_sweep(&mut port, 2, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -759,12 +1038,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set amplitude for channel1 in volts is requested.
-				if matches.is_present("set_amplitude_volts_channel1") {
-					let amount = matches.value_of("set_amplitude_volts_channel1").unwrap_or_default();
-
-					match set_amplitude(&mut port, 1, amount, verbose) {
-						Ok(_res) => {},
+				// If set switch function panel pulse is requested.
+				if matches.is_present("switch_pulse") {
+					match switch_function_panel_pulse(&mut port, verbose) {
+						Ok(_res) => {
+						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -772,12 +1050,12 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set frequency for channel2 in MHz is requested.
-				if matches.is_present("set_amplitude_volts_channel2") {
-					let amount = matches.value_of("set_amplitude_volts_channel2").unwrap_or_default();
 
-					match set_amplitude(&mut port, 2, amount, verbose) {
-						Ok(_res) => {},
+				// If set switch function panel bursting is requested.
+				if matches.is_present("switch_burst") {
+					match switch_function_panel_bursting(&mut port, verbose) {
+						Ok(_res) => {
+						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -786,12 +1064,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set duty cycle for channel1 in percent is requested.
-				if matches.is_present("set_duty_cycle_channel1") {
-					let amount = matches.value_of("set_duty_cycle_channel1").unwrap_or_default();
-
-					match set_duty_cycle(&mut port, 1, amount, verbose) {
-						Ok(_res) => {},
+				// If set switch function panel measurement is requested.
+				if matches.is_present("switch_measurement") {
+					match switch_function_panel_measurement(&mut port, verbose) {
+						Ok(_res) => {
+						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -799,11 +1076,23 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set duty cycle for channel2 in percent is requested.
-				if matches.is_present("set_duty_cycle_channel2") {
-					let amount = matches.value_of("set_duty_cycle_channel2").unwrap_or_default();
 
-					match set_duty_cycle(&mut port, 2, amount, verbose) {
+				/* ----- END Commands which navigate to a different
+						 view on the device's display panel.        ----- */
+
+
+
+
+				/* ----- Commands which change the device's
+						 settings or state, but don't
+						 activate the channels.             ----- */
+
+
+				// If set tracking mode is requested.
+				if matches.is_present("set_tracking") {
+					let track = matches.value_of("set_tracking").unwrap_or_default();
+
+					match set_tracking(&mut port, track, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -813,11 +1102,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set voltage offset for channel1 in volts is requested.
-				if matches.is_present("set_voltage_offset_channel1") {
-					let amount = matches.value_of("set_voltage_offset_channel1").unwrap_or_default();
+				// If set frequency for channel1 with a unit is requested.
+				if matches.is_present("set_frequency_channel1") {
+					let amount = matches.value_of("set_frequency_channel1").unwrap_or_default();
 
-					match set_voltage_offset(&mut port, 1, amount, verbose) {
+					match set_frequency(&mut port, 1, amount, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -826,11 +1115,12 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set voltage offset for channel2 in volts is requested.
-				if matches.is_present("set_voltage_offset_channel2") {
-					let amount = matches.value_of("set_voltage_offset_channel2").unwrap_or_default();
 
-					match set_voltage_offset(&mut port, 2, amount, verbose) {
+				// If set frequency for channel2 with a unit is requested.
+				if matches.is_present("set_frequency_channel2") {
+					let amount = matches.value_of("set_frequency_channel2").unwrap_or_default();
+
+					match set_frequency(&mut port, 2, amount, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -840,11 +1130,13 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set phase in degrees is requested.
-				if matches.is_present("set_phase") {
-					let amount = matches.value_of("set_phase").unwrap_or_default();
+				// If any of the settings in the register table are being set,
+				// in the order they were given, so for example a waveform can
+				// be chosen before an amplitude in Vrms which depends on it.
+				for (reg, chan, flag) in register_flags_given(&matches, true) {
+					let amount = matches.value_of(flag.name).unwrap_or_default();
 
-					match set_phase(&mut port, amount, verbose) {
+					match set_register(&mut port, reg, chan, amount, verbose) {
 						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -879,20 +1171,6 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set measurement gate time in seconds is requested.
-				if matches.is_present("set_measurement_gate_time") {
-					let amount = matches.value_of("set_measurement_gate_time").unwrap_or_default();
-
-					match set_measurement_gate_time(&mut port, amount, verbose) {
-						Ok(_res) => {},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-
 				// If set measurement mode count frequency is requested.
 				if matches.is_present("set_measurement_count_frequency") {
 					match set_measurement_mode_count_frequency(&mut port, verbose) {
@@ -919,21 +1197,6 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set burst pulse number is requested.
-				if matches.is_present("set_burst_pulse_number") {
-					let amount = matches.value_of("set_burst_pulse_number").unwrap_or_default();
-
-					match set_burst_pulse_number(&mut port, amount, verbose) {
-						Ok(_res) => {
-						},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-
 				// If set burst mode manual trigger is requested.
 				if matches.is_present("set_burst_manual_trigger") {
 					match set_burst_mode_manual_trigger(&mut port, verbose) {
@@ -983,50 +1246,6 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set sweep starting frequency is requested.
-				if matches.is_present("set_sweep_start_freq") {
-					let amount = matches.value_of("set_sweep_start_freq").unwrap_or_default();
-
-					match set_sweep_starting_frequency(&mut port, amount, verbose) {
-						Ok(_res) => {
-						},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-				// If set sweep end frequency is requested.
-				if matches.is_present("set_sweep_end_freq") {
-					let amount = matches.value_of("set_sweep_end_freq").unwrap_or_default();
-
-					match set_sweep_end_frequency(&mut port, amount, verbose) {
-						Ok(_res) => {
-						},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-
-				// If set sweep time is requested.
-				if matches.is_present("set_sweep_time") {
-					let amount = matches.value_of("set_sweep_time").unwrap_or_default();
-
-					match set_sweep_time(&mut port, amount, verbose) {
-						Ok(_res) => {
-						},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-
 				// If set sweep direction rise is requested.
 				if matches.is_present("set_sweep_direction_rise") {
 					match set_sweep_direction_rise(&mut port, verbose) {
@@ -1089,27 +1308,12 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set pulse width nanoseconds is requested.
-				if matches.is_present("set_pulse_width_nanoseconds") {
-					let amount = matches.value_of("set_pulse_width_nanoseconds").unwrap_or_default();
+				// If set pulse width with a unit is requested.
+				if matches.is_present("set_pulse_width") {
+					let amount = matches.value_of("set_pulse_width").unwrap_or_default();
 
 					match set_pulse_width(&mut port, amount, false, verbose) {
-						Ok(_res) => {
-						},
-						Err(e) => {
-							err = Some(error::Error::from_clap_error(e));
-							println!("{}", err.as_ref().unwrap());
-						},
-					}
-				}
-
-				// If set pulse width microseconds is requested.
-				if matches.is_present("set_pulse_width_microseconds") {
-					let amount = matches.value_of("set_pulse_width_microseconds").unwrap_or_default();
-
-					match set_pulse_width(&mut port, amount, true, verbose) {
-						Ok(_res) => {
-						},
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -1118,13 +1322,12 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set pulse period nanoseconds is requested.
-				if matches.is_present("set_pulse_period_nanoseconds") {
-					let amount = matches.value_of("set_pulse_period_nanoseconds").unwrap_or_default();
+				// If set pulse period with a unit is requested.
+				if matches.is_present("set_pulse_period") {
+					let amount = matches.value_of("set_pulse_period").unwrap_or_default();
 
 					match set_pulse_period(&mut port, amount, false, verbose) {
-						Ok(_res) => {
-						},
+						Ok(_res) => {},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
 							println!("{}", err.as_ref().unwrap());
@@ -1132,11 +1335,10 @@ fn real_main() -> Result<i32, error::Error> {
 					}
 				}
 
-				// If set pulse period microseconds is requested.
-				if matches.is_present("set_pulse_period_microseconds") {
-					let amount = matches.value_of("set_pulse_period_microseconds").unwrap_or_default();
 
-					match set_pulse_period(&mut port, amount, true, verbose) {
+				// If set measurement count clear is requested.
+				if matches.is_present("clear_measurement_count") {
+					match set_measurement_count_clear(&mut port, verbose) {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -1147,11 +1349,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set pulse offset is requested.
-				if matches.is_present("set_pulse_offset") {
-					let amount = matches.value_of("set_pulse_offset").unwrap_or_default();
+				// If save preset is requested.
+				if matches.is_present("save_preset") {
+					let amount = matches.value_of("save_preset").unwrap_or_default();
 
-					match set_pulse_offset(&mut port, amount, verbose) {
+					match save_preset(&mut port, amount, verbose) {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -1162,11 +1364,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set pulse amplitude is requested.
-				if matches.is_present("set_pulse_amplitude") {
-					let amount = matches.value_of("set_pulse_amplitude").unwrap_or_default();
+				// If recall preset is requested.
+				if matches.is_present("load_preset") {
+					let amount = matches.value_of("load_preset").unwrap_or_default();
 
-					match set_pulse_amplitude(&mut port, amount, verbose) {
+					match load_preset(&mut port, amount, verbose) {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -1177,9 +1379,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If set measurement count clear is requested.
-				if matches.is_present("clear_measurement_count") {
-					match set_measurement_count_clear(&mut port, verbose) {
+				// If a restore of saved arbitrary waves from a backup is requested.
+				if matches.is_present("restore_arb") {
+					let arg = matches.value_of("restore_arb").unwrap_or_default();
+
+					match restore_arbitrary_waves(&mut port, arg, verbose) {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -1190,11 +1394,11 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If save preset is requested.
-				if matches.is_present("save_preset") {
-					let amount = matches.value_of("save_preset").unwrap_or_default();
+				// If provisioning the saved arbitrary waves from a manifest is requested.
+				if matches.is_present("provision_arb") {
+					let arg = matches.value_of("provision_arb").unwrap_or_default();
 
-					match save_preset(&mut port, amount, verbose) {
+					match provision_arbitrary_waves(&mut port, arg, catalog_dir.as_deref(), verbose) {
 						Ok(_res) => {
 						},
 						Err(e) => {
@@ -1205,12 +1409,17 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If recall preset is requested.
-				if matches.is_present("load_preset") {
-					let amount = matches.value_of("load_preset").unwrap_or_default();
+				// If write arbitrary wave stdin is requested.
+				if matches.is_present("set_arbitrary_wave_stdin") {
+					let arg = matches.value_of("set_arbitrary_wave_stdin").unwrap_or_default();
 
-					match load_preset(&mut port, amount, verbose) {
-						Ok(_res) => {
+					match set_arbitrary_wave_stdin(&mut port, arg, &resample, convert.fail_on_clip, verbose) {
+						Ok((_res, report)) => {
+							if verbose > 0 {
+								println!("\nConversion report:\n{}", report);
+							} else if report.clipped() > 0 {
+								println!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+							}
 						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -1220,12 +1429,36 @@ fn real_main() -> Result<i32, error::Error> {
 				}
 
 
-				// If write arbitrary wave stdin is requested.
-				if matches.is_present("set_arbitrary_wave_stdin") {
-					let arg = matches.value_of("set_arbitrary_wave_stdin").unwrap_or_default();
+				// If write generated arbitrary wave is requested.
+				if matches.is_present("wave_slot") {
+					let arg = matches.value_of("wave_slot").unwrap_or_default();
+					let mut thd = None;
 
-					match set_arbitrary_wave_stdin(&mut port, arg, verbose) {
-						Ok(_res) => {
+					let res = if let Some(formula) = matches.value_of("expr_wave") {
+						set_arbitrary_wave_expr(&mut port, arg, formula, auto_scale, &convert, verbose)
+					} else if let Some(series) = fourier.as_ref() {
+						thd = Some(fourier_thd(series));
+
+						set_arbitrary_wave_fourier(&mut port, arg, series, &convert, verbose)
+					} else if let Some(pipeline) = pipeline.as_ref() {
+						set_arbitrary_wave_op(&mut port, arg, pipeline, wav_channel, &resample, &convert, verbose)
+					} else {
+						let spec = matches.value_of("gen_wave").unwrap_or_default();
+
+						set_arbitrary_wave_gen(&mut port, arg, spec, &convert, verbose)
+					};
+
+					match res {
+						Ok((_res, report)) => {
+							if verbose > 0 {
+								if let Some(thd) = thd {
+									println!("{}", thd);
+								}
+
+								println!("\nConversion report:\n{}", report);
+							} else if report.clipped() > 0 {
+								println!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+							}
 						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -1239,8 +1472,13 @@ fn real_main() -> Result<i32, error::Error> {
 				if matches.is_present("set_arbitrary_wavecad") {
 					let arg = matches.value_of("set_arbitrary_wavecad").unwrap_or_default();
 
-					match set_arbitrary_wavecad(&mut port, arg, verbose) {
-						Ok(_res) => {
+					match set_arbitrary_wavecad(&mut port, arg, wav_channel, &resample, &convert, verbose) {
+						Ok((_res, report)) => {
+							if verbose > 0 {
+								println!("\nConversion report:\n{}", report);
+							} else if report.clipped() > 0 {
+								println!("warning: some of the wave data was clipped to fit the range of 0 - 4095: {}", report.clipped());
+							}
 						},
 						Err(e) => {
 							err = Some(error::Error::from_clap_error(e));
@@ -1371,6 +1609,11 @@ fn real_main() -> Result<i32, error::Error> {
 				/* ----- END Commands which set one or both of the
 						 device's channels ON.                     ----- */
 
+				// If command statistics are requested.
+				if matches.is_present("stats") {
+					println!("\nCommand statistics for device: {}\n{}", device, port.stats.report());
+				}
+
 				err.map_or_else(|| { Ok(0) }, |v| { Err(v) })
 			},
 		);